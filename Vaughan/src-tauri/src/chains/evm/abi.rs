@@ -0,0 +1,275 @@
+// ============================================================================
+// Vaughan Wallet - Contract ABI Encoding
+// ============================================================================
+//
+// Encodes calls and decodes results for arbitrary contracts from their JSON
+// ABI, with arguments given as strings (`"0x..."`, `"1000"`, `"true"`,
+// `"[1,2]"`, `"(0x...,5)"`) and coerced to the parameter types.
+//
+// ============================================================================
+
+use alloy::dyn_abi::{DynSolType, DynSolValue, FunctionExt, JsonAbiExt};
+use alloy::json_abi::{Function, JsonAbi, Param, StateMutability};
+use alloy::primitives::Bytes;
+use serde::Serialize;
+use specta::Type;
+
+use crate::error::WalletError;
+
+/// Function parameter
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+pub struct AbiParamInfo {
+    pub name: String,
+    /// Canonical type (tuples expanded, e.g. `(address,uint256)[]`)
+    pub ty: String,
+}
+
+/// Contract function from an ABI
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+pub struct AbiFunctionInfo {
+    pub name: String,
+    /// Full signature, unique within the ABI (e.g. `transfer(address,uint256)`)
+    pub signature: String,
+    /// 4-byte selector (hex)
+    pub selector: String,
+    pub inputs: Vec<AbiParamInfo>,
+    pub outputs: Vec<AbiParamInfo>,
+    /// `pure`, `view`, `nonpayable` or `payable`
+    pub state_mutability: String,
+    /// Read-only (`view`/`pure`): run with `eth_call`, no transaction
+    pub read_only: bool,
+    pub payable: bool,
+}
+
+/// Decoded return value
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+pub struct AbiValue {
+    pub name: String,
+    pub ty: String,
+    /// Display form (JSON for arrays and tuples)
+    pub value: String,
+}
+
+/// Parse a JSON ABI
+///
+/// Accepts a plain ABI array or a compiler artifact with an `abi` field
+/// (Hardhat, Foundry, Truffle).
+pub fn parse_abi(json: &str) -> Result<JsonAbi, WalletError> {
+    let value: serde_json::Value = serde_json::from_str(json.trim())
+        .map_err(|e| WalletError::ParseError(format!("Invalid ABI JSON: {}", e)))?;
+    let abi = match value {
+        serde_json::Value::Object(mut artifact) if artifact.contains_key("abi") => {
+            artifact.remove("abi").unwrap_or_default()
+        }
+        other => other,
+    };
+    serde_json::from_value(abi).map_err(|e| WalletError::ParseError(format!("Invalid ABI: {}", e)))
+}
+
+fn param_info(param: &Param) -> AbiParamInfo {
+    AbiParamInfo {
+        name: param.name.clone(),
+        ty: param.selector_type().into_owned(),
+    }
+}
+
+fn mutability_str(mutability: StateMutability) -> &'static str {
+    match mutability {
+        StateMutability::Pure => "pure",
+        StateMutability::View => "view",
+        StateMutability::NonPayable => "nonpayable",
+        StateMutability::Payable => "payable",
+    }
+}
+
+/// Whether a function only reads state
+pub fn is_read_only(function: &Function) -> bool {
+    matches!(function.state_mutability, StateMutability::Pure | StateMutability::View)
+}
+
+/// Functions of an ABI, sorted by signature
+pub fn list_functions(abi: &JsonAbi) -> Vec<AbiFunctionInfo> {
+    let mut functions: Vec<AbiFunctionInfo> = abi
+        .functions()
+        .map(|f| AbiFunctionInfo {
+            name: f.name.clone(),
+            signature: f.signature(),
+            selector: format!("{}", f.selector()),
+            inputs: f.inputs.iter().map(param_info).collect(),
+            outputs: f.outputs.iter().map(param_info).collect(),
+            state_mutability: mutability_str(f.state_mutability).to_string(),
+            read_only: is_read_only(f),
+            payable: f.state_mutability == StateMutability::Payable,
+        })
+        .collect();
+    functions.sort_by(|a, b| a.signature.cmp(&b.signature));
+    functions
+}
+
+/// Find a function by full signature, or by name if it is not overloaded
+pub fn find_function<'a>(abi: &'a JsonAbi, function: &str) -> Result<&'a Function, WalletError> {
+    let function = function.trim();
+    if function.contains('(') {
+        return abi
+            .functions()
+            .find(|f| f.signature() == function)
+            .ok_or_else(|| WalletError::Custom(format!("Function not in ABI: {}", function)));
+    }
+
+    match abi.function(function).map(Vec::as_slice) {
+        Some([single]) => Ok(single),
+        Some(overloads) if !overloads.is_empty() => Err(WalletError::Custom(format!(
+            "{} is overloaded; use the full signature ({})",
+            function,
+            overloads.iter().map(|f| f.signature()).collect::<Vec<_>>().join(", ")
+        ))),
+        _ => Err(WalletError::Custom(format!("Function not in ABI: {}", function))),
+    }
+}
+
+/// Encode calldata (selector + arguments) from string arguments
+pub fn encode_call(function: &Function, args: &[String]) -> Result<Bytes, WalletError> {
+    if args.len() != function.inputs.len() {
+        return Err(WalletError::InvalidTransaction(format!(
+            "{} expects {} arguments, got {}",
+            function.signature(),
+            function.inputs.len(),
+            args.len()
+        )));
+    }
+
+    let values = function
+        .inputs
+        .iter()
+        .zip(args)
+        .map(|(param, arg)| {
+            let ty = DynSolType::parse(&param.selector_type())
+                .map_err(|e| WalletError::InvalidTransaction(format!("Unsupported type {}: {}", param.ty, e)))?;
+            ty.coerce_str(arg.trim()).map_err(|e| {
+                let name = if param.name.is_empty() { param.ty.as_str() } else { param.name.as_str() };
+                WalletError::InvalidTransaction(format!("Invalid value for {}: {}", name, e))
+            })
+        })
+        .collect::<Result<Vec<DynSolValue>, WalletError>>()?;
+
+    function
+        .abi_encode_input(&values)
+        .map(Bytes::from)
+        .map_err(|e| WalletError::InvalidTransaction(format!("Encoding failed: {}", e)))
+}
+
+/// Decode a call result into display values
+pub fn decode_output(function: &Function, data: &[u8]) -> Result<Vec<AbiValue>, WalletError> {
+    let values = function
+        .abi_decode_output(data)
+        .map_err(|e| WalletError::ParseError(format!("Could not decode result: {}", e)))?;
+
+    Ok(function
+        .outputs
+        .iter()
+        .zip(values)
+        .map(|(param, value)| AbiValue {
+            name: param.name.clone(),
+            ty: param.selector_type().into_owned(),
+            value: match to_json(&value) {
+                serde_json::Value::String(s) => s,
+                other => other.to_string(),
+            },
+        })
+        .collect())
+}
+
+/// JSON form of a decoded value (numbers as decimal strings)
+fn to_json(value: &DynSolValue) -> serde_json::Value {
+    use serde_json::Value;
+    match value {
+        DynSolValue::Bool(b) => Value::Bool(*b),
+        DynSolValue::Int(i, _) => Value::String(i.to_string()),
+        DynSolValue::Uint(u, _) => Value::String(u.to_string()),
+        DynSolValue::FixedBytes(word, size) => {
+            Value::String(format!("0x{}", hex::encode(&word[..*size])))
+        }
+        DynSolValue::Address(a) => Value::String(a.to_checksum(None)),
+        DynSolValue::Function(f) => Value::String(format!("0x{}", hex::encode(f.as_slice()))),
+        DynSolValue::Bytes(b) => Value::String(format!("0x{}", hex::encode(b))),
+        DynSolValue::String(s) => Value::String(s.clone()),
+        DynSolValue::Array(items)
+        | DynSolValue::FixedArray(items)
+        | DynSolValue::Tuple(items)
+        | DynSolValue::CustomStruct { tuple: items, .. } => {
+            Value::Array(items.iter().map(to_json).collect())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ERC20_ABI: &str = r#"[
+        {"type":"function","name":"balanceOf","stateMutability":"view",
+         "inputs":[{"name":"owner","type":"address"}],
+         "outputs":[{"name":"","type":"uint256"}]},
+        {"type":"function","name":"transfer","stateMutability":"nonpayable",
+         "inputs":[{"name":"to","type":"address"},{"name":"amount","type":"uint256"}],
+         "outputs":[{"name":"","type":"bool"}]},
+        {"type":"function","name":"mint","stateMutability":"nonpayable",
+         "inputs":[{"name":"amount","type":"uint256"}],"outputs":[]},
+        {"type":"function","name":"mint","stateMutability":"nonpayable",
+         "inputs":[{"name":"to","type":"address"},{"name":"amount","type":"uint256"}],"outputs":[]}
+    ]"#;
+
+    #[test]
+    fn test_parse_artifact() {
+        let artifact = format!(r#"{{"contractName":"Token","abi":{}}}"#, ERC20_ABI);
+        let abi = parse_abi(&artifact).unwrap();
+        assert_eq!(list_functions(&abi).len(), 4);
+        assert!(parse_abi("not json").is_err());
+    }
+
+    #[test]
+    fn test_list_functions() {
+        let abi = parse_abi(ERC20_ABI).unwrap();
+        let functions = list_functions(&abi);
+        let balance_of = functions.iter().find(|f| f.name == "balanceOf").unwrap();
+        assert!(balance_of.read_only);
+        assert_eq!(balance_of.selector, "0x70a08231");
+        assert_eq!(balance_of.inputs[0].ty, "address");
+    }
+
+    #[test]
+    fn test_find_overloaded_function() {
+        let abi = parse_abi(ERC20_ABI).unwrap();
+        assert!(find_function(&abi, "transfer").is_ok());
+        assert!(find_function(&abi, "mint").is_err());
+        assert!(find_function(&abi, "mint(address,uint256)").is_ok());
+        assert!(find_function(&abi, "burn").is_err());
+    }
+
+    #[test]
+    fn test_encode_call() {
+        let abi = parse_abi(ERC20_ABI).unwrap();
+        let transfer = find_function(&abi, "transfer").unwrap();
+        let data = encode_call(
+            transfer,
+            &["0x1111111111111111111111111111111111111111".to_string(), "1000".to_string()],
+        )
+        .unwrap();
+        assert_eq!(&data[..4], &[0xa9, 0x05, 0x9c, 0xbb]);
+        assert_eq!(data.len(), 4 + 64);
+
+        assert!(encode_call(transfer, &["0x11".to_string(), "1".to_string()]).is_err());
+        assert!(encode_call(transfer, &["1000".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_decode_output() {
+        let abi = parse_abi(ERC20_ABI).unwrap();
+        let balance_of = find_function(&abi, "balanceOf").unwrap();
+        let mut data = [0u8; 32];
+        data[31] = 42;
+        let values = decode_output(balance_of, &data).unwrap();
+        assert_eq!(values[0].value, "42");
+        assert_eq!(values[0].ty, "uint256");
+    }
+}
@@ -2,8 +2,9 @@
 // Vaughan Wallet - EVM Chain Adapter
 // ============================================================================
 use alloy::{
+    eips::eip2930::AccessList,
     network::Ethereum,
-    primitives::{utils::format_units, Address, B256, TxKind, U256},
+    primitives::{utils::format_units, Address, Bytes, B256, TxKind, U256},
     rpc::types::eth::TransactionRequest,
     signers::local::PrivateKeySigner,
 };
@@ -16,7 +17,7 @@ use std::sync::Arc;
 
 pub type AlloyProvider = RootProvider<Ethereum>;
 
-use crate::chains::{evm::networks::get_network_by_chain_id, types::*, ChainAdapter};
+use crate::chains::{evm::l2, evm::networks::get_network_by_chain_id, types::*, ChainAdapter};
 use crate::error::WalletError;
 
 pub struct EvmAdapter {
@@ -73,6 +74,17 @@ impl EvmAdapter {
     pub fn rpc_url(&self) -> &str { &self.rpc_url }
     pub fn chain_id(&self) -> u64 { self.chain_id }
     pub fn provider(&self) -> Arc<AlloyProvider> { self.provider.clone() }
+    pub fn native_decimals(&self) -> u8 { self.native_token.decimals }
+}
+
+/// EIP-7702 authorizations of a transaction, in Alloy form
+fn authorization_list(
+    tx: &EvmTransaction,
+) -> Result<Option<Vec<alloy::eips::eip7702::SignedAuthorization>>, WalletError> {
+    tx.authorization_list
+        .as_ref()
+        .map(|list| list.iter().map(TryFrom::try_from).collect())
+        .transpose()
 }
 
 #[async_trait]
@@ -102,6 +114,8 @@ impl ChainAdapter for EvmAdapter {
         alloy_tx.to = Some(TxKind::Call(tx_addr)); // Fix: Use TxKind::Call for 'to' address
         alloy_tx.value = Some(U256::from_str_radix(&evm_tx.value, 10).map_err(|_| WalletError::InvalidParams)?);
 
+        alloy_tx.authorization_list = authorization_list(&evm_tx)?;
+
         if let Some(data) = evm_tx.data {
             let bytes = hex::decode(data.trim_start_matches("0x")).map_err(|_| WalletError::InvalidParams)?;
             alloy_tx.input.input = Some(bytes.into());
@@ -132,17 +146,47 @@ impl ChainAdapter for EvmAdapter {
             _ => return Err(WalletError::InvalidTransaction("Not an EVM transaction".into())),
         };
 
+        let from: Address = evm_tx.from.parse().map_err(|_| WalletError::InvalidAddress(evm_tx.from.clone()))?;
+        let to: Address = evm_tx.to.parse().map_err(|_| WalletError::InvalidAddress(evm_tx.to.clone()))?;
+        let value = U256::from_str_radix(&evm_tx.value, 10).map_err(|_| WalletError::InvalidParams)?;
+        let input: Bytes = match &evm_tx.data {
+            Some(data) => hex::decode(data.trim_start_matches("0x")).map_err(|_| WalletError::InvalidParams)?.into(),
+            None => Bytes::new(),
+        };
+
         let mut alloy_tx = TransactionRequest::default();
-        alloy_tx.from = Some(evm_tx.from.parse().map_err(|_| WalletError::InvalidAddress(evm_tx.from.clone()))?);
-        alloy_tx.to = Some(TxKind::Call(evm_tx.to.parse().map_err(|_| WalletError::InvalidAddress(evm_tx.to.clone()))?)); // Fix: Use TxKind::Call
-        alloy_tx.value = Some(U256::from_str_radix(&evm_tx.value, 10).map_err(|_| WalletError::InvalidParams)?);
+        alloy_tx.from = Some(from);
+        alloy_tx.to = Some(TxKind::Call(to)); // Fix: Use TxKind::Call
+        alloy_tx.value = Some(value);
+        alloy_tx.input.input = Some(input);
+        alloy_tx.authorization_list = authorization_list(evm_tx)?;
 
-        let gas_estimate = self.provider.estimate_gas(alloy_tx.clone()).await.map_err(|e| WalletError::GasEstimationFailed(e.to_string()))?;
+        let gas_estimate = match evm_tx.gas_limit {
+            Some(gas) => gas,
+            None => self.provider.estimate_gas(alloy_tx.clone()).await.map_err(|e| WalletError::GasEstimationFailed(e.to_string()))?,
+        };
         let gas_price = self.provider.get_gas_price().await.map_err(|e| WalletError::RpcError(e.to_string()))?;
-        let total_fee = U256::from(gas_estimate) * U256::from(gas_price);
+        let l2_fee = U256::from(gas_estimate) * U256::from(gas_price);
+
+        // Nonce only affects the encoded size by a byte or two
+        alloy_tx.nonce = Some(match evm_tx.nonce {
+            Some(n) => n,
+            None => self.provider.get_transaction_count(from).await.unwrap_or(0),
+        });
+        let l1_fee = self.l1_fee_estimate(&alloy_tx, gas_estimate, gas_price).await;
+
+        // OP Stack charges the L1 fee on top; Arbitrum already includes it in the gas limit
+        let total_fee = l2_fee + l2::l1_fee_surcharge(self.chain_id, l1_fee.amount());
 
-        Ok(Fee::new(total_fee.to_string(), format_units(total_fee, 18).unwrap_or_else(|_| "0.0".to_string()))
-            .with_gas(gas_estimate, gas_price.to_string()))
+        let decimals = self.native_token.decimals;
+        let mut fee = Fee::new(total_fee.to_string(), format_units(total_fee, decimals).unwrap_or_else(|_| "0.0".to_string()))
+            .with_gas(gas_estimate, gas_price.to_string());
+        match l1_fee {
+            l2::L1FeeEstimate::Known(l1) => fee = fee.with_l1_fee(l1.to_string()),
+            l2::L1FeeEstimate::Unavailable => fee = fee.with_l1_fee_unavailable(),
+            l2::L1FeeEstimate::NotApplicable => {},
+        }
+        Ok(fee)
     }
 
     fn validate_address(&self, address: &str) -> Result<(), WalletError> {
@@ -218,4 +262,164 @@ impl EvmAdapter {
     pub async fn get_block_number(&self) -> Result<u64, WalletError> { self.provider.get_block_number().await.map_err(|e| WalletError::RpcError(e.to_string())) }
     pub async fn get_gas_price(&self) -> Result<u128, WalletError> { self.provider.get_gas_price().await.map_err(|e| WalletError::RpcError(e.to_string())) }
     pub async fn get_transaction_count(&self, address: Address) -> Result<u64, WalletError> { self.provider.get_transaction_count(address).await.map_err(|e| WalletError::RpcError(e.to_string())) }
+    pub async fn get_pending_transaction_count(&self, address: Address) -> Result<u64, WalletError> { self.provider.get_transaction_count(address).pending().await.map_err(|e| WalletError::RpcError(e.to_string())) }
+
+    /// Generate an EIP-2930 access list and keep it only if it saves gas
+    ///
+    /// Calls `eth_createAccessList`, then estimates gas with and without the
+    /// list.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(Some((list, gas)))` - Non-empty list and the (lower) gas estimate with it
+    /// * `Ok(None)` - No list, or the list doesn't reduce gas
+    /// * `Err(WalletError)` - RPC error (e.g. method unsupported)
+    pub async fn optimize_access_list(
+        &self,
+        tx: &TransactionRequest,
+    ) -> Result<Option<(AccessList, u64)>, WalletError> {
+        let result = self.provider.create_access_list(tx).await.map_err(|e| WalletError::RpcError(e.to_string()))?;
+        if let Some(error) = result.error {
+            return Err(WalletError::GasEstimationFailed(error));
+        }
+        if result.access_list.0.is_empty() {
+            return Ok(None);
+        }
+
+        let gas_without = self.provider.estimate_gas(tx.clone()).await.map_err(|e| WalletError::GasEstimationFailed(e.to_string()))?;
+
+        let mut with_list = tx.clone();
+        with_list.access_list = Some(result.access_list.clone());
+        let gas_with = self.provider.estimate_gas(with_list).await.map_err(|e| WalletError::GasEstimationFailed(e.to_string()))?;
+
+        Ok((gas_with < gas_without).then_some((result.access_list, gas_with)))
+    }
+
+    /// Estimate the L1 data fee (in wei) for a transaction on a rollup
+    ///
+    /// Returns `Ok(None)` on chains that are not known rollups, or for contract
+    /// deployments. On OP Stack chains the fee is charged on top of
+    /// `gas * gas_price`; on Arbitrum it is already included in the estimated
+    /// gas limit (see `l2::RollupKind`).
+    pub async fn estimate_l1_fee(
+        &self,
+        tx: &TransactionRequest,
+        gas_limit: u64,
+        gas_price: u128,
+    ) -> Result<Option<U256>, WalletError> {
+        let Some(TxKind::Call(to)) = tx.to else {
+            return Ok(None);
+        };
+        let request = l2::L1FeeRequest {
+            chain_id: self.chain_id,
+            from: tx.from.unwrap_or_default(),
+            to,
+            value: tx.value.unwrap_or_default(),
+            input: tx.input.input().cloned().unwrap_or_default(),
+            nonce: tx.nonce.unwrap_or_default(),
+            gas_limit,
+            gas_price,
+        };
+        l2::estimate_l1_fee(&self.provider, &request).await
+    }
+
+    /// L1 data fee for a fee estimate
+    ///
+    /// Like `estimate_l1_fee`, but a failed oracle call doesn't fail the
+    /// estimate: it comes back as `L1FeeEstimate::Unavailable` so callers
+    /// can show the L2 fee and say the L1 part is missing.
+    pub async fn l1_fee_estimate(
+        &self,
+        tx: &TransactionRequest,
+        gas_limit: u64,
+        gas_price: u128,
+    ) -> l2::L1FeeEstimate {
+        let result = self.estimate_l1_fee(tx, gas_limit, gas_price).await;
+        if let Err(e) = &result {
+            eprintln!("[EvmAdapter] L1 fee lookup failed on chain {}: {}", self.chain_id, e);
+        }
+        l2::L1FeeEstimate::from_result(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::transports::mock::Asserter;
+    use serde_json::json;
+
+    fn mocked_adapter(asserter: Asserter) -> EvmAdapter {
+        EvmAdapter {
+            provider: Arc::new(RootProvider::<Ethereum>::new(RpcClient::mocked(asserter))),
+            signer: None,
+            rpc_url: "http://localhost:8545".to_string(),
+            chain_id: 1,
+            native_token: TokenInfo::native("ETH".into(), "Ether".into(), 18),
+        }
+    }
+
+    fn call() -> TransactionRequest {
+        let mut tx = TransactionRequest::default();
+        tx.from = Some(Address::repeat_byte(0x01));
+        tx.to = Some(TxKind::Call(Address::repeat_byte(0x02)));
+        tx
+    }
+
+    fn access_list_response() -> serde_json::Value {
+        json!({
+            "accessList": [{
+                "address": "0x2222222222222222222222222222222222222222",
+                "storageKeys": ["0x0000000000000000000000000000000000000000000000000000000000000001"]
+            }],
+            "gasUsed": "0x6000"
+        })
+    }
+
+    #[tokio::test]
+    async fn test_optimize_access_list_kept_when_cheaper() {
+        let asserter = Asserter::new();
+        asserter.push_success(&access_list_response());
+        asserter.push_success(&"0x7000"); // without the list
+        asserter.push_success(&"0x6800"); // with the list
+
+        let (list, gas) = mocked_adapter(asserter).optimize_access_list(&call()).await.unwrap().unwrap();
+        assert_eq!(list.0.len(), 1);
+        assert_eq!(list.0[0].address, Address::repeat_byte(0x22));
+        assert_eq!(gas, 0x6800);
+    }
+
+    #[tokio::test]
+    async fn test_optimize_access_list_dropped_when_not_cheaper() {
+        let asserter = Asserter::new();
+        asserter.push_success(&access_list_response());
+        asserter.push_success(&"0x6800");
+        asserter.push_success(&"0x6800");
+
+        assert!(mocked_adapter(asserter).optimize_access_list(&call()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_optimize_access_list_empty_list() {
+        let asserter = Asserter::new();
+        asserter.push_success(&json!({ "accessList": [], "gasUsed": "0x5208" }));
+
+        assert!(mocked_adapter(asserter).optimize_access_list(&call()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_optimize_access_list_reports_errors() {
+        let asserter = Asserter::new();
+        asserter.push_success(&json!({ "accessList": [], "gasUsed": "0x0", "error": "execution reverted" }));
+        assert!(matches!(
+            mocked_adapter(asserter).optimize_access_list(&call()).await,
+            Err(WalletError::GasEstimationFailed(_))
+        ));
+
+        let asserter = Asserter::new();
+        asserter.push_failure_msg("the method eth_createAccessList does not exist");
+        assert!(matches!(
+            mocked_adapter(asserter).optimize_access_list(&call()).await,
+            Err(WalletError::RpcError(_))
+        ));
+    }
 }
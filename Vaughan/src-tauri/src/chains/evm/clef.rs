@@ -0,0 +1,292 @@
+// ============================================================================
+// Vaughan Wallet - Clef External Signer
+// ============================================================================
+//
+// JSON-RPC client for Clef (go-ethereum's external signer) and anything
+// speaking its external API:
+//
+// - `account_list`: addresses the signer will sign for
+// - `account_signTransaction`: signs a transaction, returns the raw bytes
+// - `account_signData` (`text/plain`): EIP-191 personal messages
+// - `account_signTypedData`: EIP-712 typed data
+//
+// Clef is reached over HTTP (`clef --http`) or its UNIX socket
+// (`clef.ipc`). Each signing request waits for the user to confirm on the
+// signer side, so the timeout is long. Clef never signs raw hashes.
+//
+// ============================================================================
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use alloy::consensus::TxEnvelope;
+use alloy::eips::eip2718::Decodable2718;
+use alloy::primitives::{Address, Bytes, Signature};
+use alloy::rpc::types::TransactionRequest;
+use alloy_dyn_abi::TypedData;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+
+use crate::error::WalletError;
+
+/// How long to wait for the signer (includes the user confirming there)
+pub const CLEF_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Where the signer listens
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClefEndpoint {
+    Http(String),
+    Ipc(PathBuf),
+}
+
+impl ClefEndpoint {
+    /// `http(s)://host:port`, or a socket path (optionally `ipc://` or `unix://`)
+    pub fn parse(url: &str) -> Result<Self, WalletError> {
+        let url = url.trim();
+        if url.starts_with("http://") || url.starts_with("https://") {
+            return Ok(Self::Http(url.to_string()));
+        }
+        let path = url
+            .strip_prefix("ipc://")
+            .or_else(|| url.strip_prefix("unix://"))
+            .unwrap_or(url);
+        if path.is_empty() {
+            return Err(WalletError::ExternalSigner("Signer URL is empty".to_string()));
+        }
+        Ok(Self::Ipc(PathBuf::from(path)))
+    }
+}
+
+impl std::fmt::Display for ClefEndpoint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Http(url) => write!(f, "{}", url),
+            Self::Ipc(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// `account_signTransaction` result
+#[derive(Debug, Deserialize)]
+struct SignTransactionResult {
+    raw: Bytes,
+}
+
+/// JSON-RPC client for a Clef-compatible signer
+#[derive(Debug)]
+pub struct ClefClient {
+    endpoint: ClefEndpoint,
+    client: reqwest::Client,
+}
+
+impl ClefClient {
+    pub fn new(url: &str) -> Result<Self, WalletError> {
+        let client = reqwest::Client::builder()
+            .timeout(CLEF_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Ok(Self {
+            endpoint: ClefEndpoint::parse(url)?,
+            client,
+        })
+    }
+
+    pub fn endpoint(&self) -> &ClefEndpoint {
+        &self.endpoint
+    }
+
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<T, WalletError> {
+        let payload = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let body = match &self.endpoint {
+            ClefEndpoint::Http(url) => self.send_http(url, &payload).await?,
+            ClefEndpoint::Ipc(path) => tokio::time::timeout(CLEF_TIMEOUT, send_ipc(path, &payload))
+                .await
+                .map_err(|_| WalletError::ConnectionTimeout(format!("Signer {} did not respond", self.endpoint)))??,
+        };
+
+        if let Some(error) = body.get("error") {
+            let message = error
+                .get("message")
+                .and_then(|m| m.as_str())
+                .unwrap_or("unknown error");
+            // Clef answers "Request denied" when the user declines
+            if message.to_lowercase().contains("denied") {
+                return Err(WalletError::UserRejected);
+            }
+            return Err(WalletError::ExternalSigner(format!("{}: {}", method, message)));
+        }
+
+        let result = body.get("result").cloned().unwrap_or(serde_json::Value::Null);
+        serde_json::from_value(result)
+            .map_err(|e| WalletError::ExternalSigner(format!("{}: unexpected result: {}", method, e)))
+    }
+
+    async fn send_http(&self, url: &str, payload: &serde_json::Value) -> Result<serde_json::Value, WalletError> {
+        let response = self
+            .client
+            .post(url)
+            .json(payload)
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    WalletError::ConnectionTimeout(format!("Signer {} did not respond", url))
+                } else {
+                    WalletError::ExternalSigner(format!("Cannot reach {}: {}", url, e))
+                }
+            })?;
+        response
+            .json()
+            .await
+            .map_err(|e| WalletError::ExternalSigner(format!("Invalid response from {}: {}", url, e)))
+    }
+
+    /// Accounts the signer holds
+    pub async fn list_accounts(&self) -> Result<Vec<Address>, WalletError> {
+        self.request("account_list", serde_json::json!([])).await
+    }
+
+    /// Sign a complete transaction (nonce, gas, fees and chain ID set)
+    ///
+    /// The returned transaction is checked to be signed by `tx.from`.
+    pub async fn sign_transaction(&self, tx: &TransactionRequest) -> Result<TxEnvelope, WalletError> {
+        let from = tx
+            .from
+            .ok_or_else(|| WalletError::InvalidTransaction("Missing sender".to_string()))?;
+        let result: SignTransactionResult = self
+            .request("account_signTransaction", serde_json::json!([tx]))
+            .await?;
+
+        let envelope = TxEnvelope::decode_2718(&mut result.raw.as_ref())
+            .map_err(|e| WalletError::ExternalSigner(format!("Invalid signed transaction: {}", e)))?;
+        let signer = envelope
+            .signature()
+            .recover_address_from_prehash(&envelope.signature_hash())
+            .map_err(|e| WalletError::ExternalSigner(format!("Invalid transaction signature: {}", e)))?;
+        check_signer(from, signer)?;
+        Ok(envelope)
+    }
+
+    /// Sign an EIP-191 personal message (`personal_sign`)
+    pub async fn sign_text(&self, address: Address, message: &[u8]) -> Result<Signature, WalletError> {
+        let signature: Bytes = self
+            .request(
+                "account_signData",
+                serde_json::json!(["text/plain", address, format!("0x{}", hex::encode(message))]),
+            )
+            .await?;
+        let signature = parse_signature(&signature)?;
+        let signer = signature
+            .recover_address_from_msg(message)
+            .map_err(|e| WalletError::ExternalSigner(format!("Invalid signature: {}", e)))?;
+        check_signer(address, signer)?;
+        Ok(signature)
+    }
+
+    /// Sign EIP-712 typed data
+    pub async fn sign_typed_data(&self, address: Address, typed_data: &TypedData) -> Result<Signature, WalletError> {
+        let hash = typed_data
+            .eip712_signing_hash()
+            .map_err(|e| WalletError::Custom(format!("Failed to hash typed data: {}", e)))?;
+        let signature: Bytes = self
+            .request("account_signTypedData", serde_json::json!([address, typed_data]))
+            .await?;
+        let signature = parse_signature(&signature)?;
+        let signer = signature
+            .recover_address_from_prehash(&hash)
+            .map_err(|e| WalletError::ExternalSigner(format!("Invalid signature: {}", e)))?;
+        check_signer(address, signer)?;
+        Ok(signature)
+    }
+}
+
+/// 65-byte `r || s || v` signature (`v` = 27/28, as Clef returns it)
+fn parse_signature(bytes: &[u8]) -> Result<Signature, WalletError> {
+    Signature::from_raw(bytes)
+        .map_err(|e| WalletError::ExternalSigner(format!("Invalid signature: {}", e)))
+}
+
+fn check_signer(expected: Address, actual: Address) -> Result<(), WalletError> {
+    if expected != actual {
+        return Err(WalletError::ExternalSigner(format!(
+            "Signer signed as {} instead of {}",
+            actual, expected
+        )));
+    }
+    Ok(())
+}
+
+/// One JSON-RPC exchange over a UNIX socket
+///
+/// Like geth's IPC, messages are not framed: the response is complete once
+/// it parses as a JSON value.
+#[cfg(unix)]
+async fn send_ipc(path: &std::path::Path, payload: &serde_json::Value) -> Result<serde_json::Value, WalletError> {
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    let unreachable = |e: std::io::Error| WalletError::ExternalSigner(format!("Cannot reach {}: {}", path.display(), e));
+    let mut stream = tokio::net::UnixStream::connect(path).await.map_err(unreachable)?;
+
+    let mut request = serde_json::to_vec(payload)
+        .map_err(|e| WalletError::InternalError(format!("Failed to encode request: {}", e)))?;
+    request.push(b'\n');
+    stream.write_all(&request).await.map_err(unreachable)?;
+
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    loop {
+        let read = stream.read(&mut chunk).await.map_err(unreachable)?;
+        if read == 0 {
+            return Err(WalletError::ExternalSigner(format!(
+                "{} closed the connection",
+                path.display()
+            )));
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        match serde_json::from_slice(&buffer) {
+            Ok(value) => return Ok(value),
+            Err(e) if e.is_eof() => continue,
+            Err(e) => {
+                return Err(WalletError::ExternalSigner(format!(
+                    "Invalid response from {}: {}",
+                    path.display(),
+                    e
+                )))
+            }
+        }
+    }
+}
+
+#[cfg(not(unix))]
+async fn send_ipc(path: &std::path::Path, _payload: &serde_json::Value) -> Result<serde_json::Value, WalletError> {
+    Err(WalletError::ExternalSigner(format!(
+        "UNIX sockets are not supported on this platform ({}); use Clef's HTTP endpoint",
+        path.display()
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_endpoint() {
+        assert_eq!(
+            ClefEndpoint::parse("http://127.0.0.1:8550").unwrap(),
+            ClefEndpoint::Http("http://127.0.0.1:8550".to_string())
+        );
+        assert_eq!(
+            ClefEndpoint::parse("ipc:///home/me/.clef/clef.ipc").unwrap(),
+            ClefEndpoint::Ipc(PathBuf::from("/home/me/.clef/clef.ipc"))
+        );
+        assert_eq!(
+            ClefEndpoint::parse(" /tmp/clef.ipc ").unwrap(),
+            ClefEndpoint::Ipc(PathBuf::from("/tmp/clef.ipc"))
+        );
+        assert!(ClefEndpoint::parse("  ").is_err());
+    }
+}
@@ -0,0 +1,90 @@
+// ============================================================================
+// Vaughan Wallet - Disperse Contract
+// ============================================================================
+//
+// Disperse (disperse.app) pays many recipients in one transaction. It is
+// deployed at the same address on most EVM chains.
+//
+// - Native: `disperseEther(recipients, values)` with `msg.value` = total
+// - Tokens: `approve(disperse, total)` on the token, then
+//   `disperseToken(token, recipients, values)`
+//
+// ============================================================================
+
+use alloy::primitives::{address, Address, Bytes, U256};
+use alloy::providers::{Provider, RootProvider};
+use alloy::sol;
+use alloy::sol_types::SolCall;
+
+use crate::error::WalletError;
+
+/// Disperse contract address (same on all chains it is deployed to)
+pub const DISPERSE_ADDRESS: Address = address!("D152f549545093347A162Dce210e7293f1452150");
+
+/// Fixed gas of a Disperse call
+pub const DISPERSE_BASE_GAS: u64 = 60_000;
+
+/// Extra gas per recipient (covers a cold token balance slot)
+pub const DISPERSE_GAS_PER_RECIPIENT: u64 = 35_000;
+
+/// Gas of an ERC20 `approve`
+pub const APPROVE_GAS: u64 = 60_000;
+
+sol! {
+    contract Disperse {
+        function disperseEther(address[] recipients, uint256[] values) external payable;
+        function disperseToken(address token, address[] recipients, uint256[] values) external;
+    }
+
+    function approve(address spender, uint256 amount) external returns (bool);
+}
+
+/// Gas limit for a Disperse call to `recipients` addresses
+pub fn disperse_gas(recipients: usize) -> u64 {
+    DISPERSE_BASE_GAS + DISPERSE_GAS_PER_RECIPIENT * recipients as u64
+}
+
+/// `disperseEther` calldata
+pub fn disperse_ether_call_data(recipients: Vec<Address>, values: Vec<U256>) -> Bytes {
+    Disperse::disperseEtherCall { recipients, values }.abi_encode().into()
+}
+
+/// `disperseToken` calldata
+pub fn disperse_token_call_data(token: Address, recipients: Vec<Address>, values: Vec<U256>) -> Bytes {
+    Disperse::disperseTokenCall { token, recipients, values }.abi_encode().into()
+}
+
+/// ERC20 `approve` calldata
+pub fn approve_call_data(spender: Address, amount: U256) -> Bytes {
+    approveCall { spender, amount }.abi_encode().into()
+}
+
+/// Whether Disperse is deployed on the provider's chain
+pub async fn is_deployed(provider: &RootProvider) -> Result<bool, WalletError> {
+    let code = provider
+        .get_code_at(DISPERSE_ADDRESS)
+        .await
+        .map_err(|e| WalletError::RpcError(e.to_string()))?;
+    Ok(!code.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_call_data_selectors() {
+        let ether = disperse_ether_call_data(vec![Address::repeat_byte(1)], vec![U256::from(1u64)]);
+        // disperseEther(address[],uint256[])
+        assert_eq!(&ether[..4], &[0xe6, 0x3d, 0x38, 0xed]);
+
+        let approve = approve_call_data(DISPERSE_ADDRESS, U256::MAX);
+        // approve(address,uint256)
+        assert_eq!(&approve[..4], &[0x09, 0x5e, 0xa7, 0xb3]);
+    }
+
+    #[test]
+    fn test_disperse_gas_grows_with_recipients() {
+        assert!(disperse_gas(10) > disperse_gas(1));
+    }
+}
@@ -0,0 +1,239 @@
+// ============================================================================
+// Vaughan Wallet - EIP-7702 Delegation
+// ============================================================================
+//
+// EIP-7702 lets an EOA delegate its code to a contract by signing an
+// authorization tuple `(chain_id, address, nonce)` that is carried in a
+// type-4 (set-code) transaction. A delegated account's code is
+// `0xef0100 ++ delegate`.
+//
+// A delegate has full control over the account, so approvals check it
+// against the list of known delegate implementations below.
+//
+// ============================================================================
+
+use alloy::eips::eip7702::{Authorization, SignedAuthorization};
+use alloy::primitives::{address, Address, Bytes, U256};
+use alloy::providers::{Provider, RootProvider};
+
+use crate::chains::types::EvmAuthorization;
+use crate::error::WalletError;
+
+/// Code prefix of a delegated account (`0xef0100 ++ address`)
+pub const DELEGATION_PREFIX: [u8; 3] = [0xef, 0x01, 0x00];
+
+/// Audited delegate implementation
+#[derive(Debug, Clone, Copy)]
+pub struct KnownDelegate {
+    pub address: Address,
+    pub name: &'static str,
+}
+
+/// Delegate contracts we recognise
+pub const KNOWN_DELEGATES: &[KnownDelegate] = &[
+    KnownDelegate {
+        address: address!("63c0c19a282a1b52b07dd5a65b58948a07dae32b"),
+        name: "MetaMask Delegator",
+    },
+    KnownDelegate {
+        address: address!("4cd241e8d1510e30b2076397afc7508ae59c66c9"),
+        name: "Simple7702Account (eth-infinitism)",
+    },
+    KnownDelegate {
+        address: address!("000000009b1d0af20d8c6d0a44e162d11f9b8f00"),
+        name: "Uniswap Calibur",
+    },
+    KnownDelegate {
+        address: address!("5a7fc11397e9a8ad41bf10bf13f22b0a63f96f6d"),
+        name: "Ambire",
+    },
+];
+
+/// Look up a known delegate
+pub fn known_delegate(delegate: Address) -> Option<&'static KnownDelegate> {
+    KNOWN_DELEGATES.iter().find(|d| d.address == delegate)
+}
+
+/// Result of checking a delegate before approval
+#[derive(Debug, Clone, PartialEq)]
+pub struct DelegateCheck {
+    pub delegate: Address,
+    /// Name of a known delegate
+    pub name: Option<String>,
+    pub known: bool,
+    /// Delegating to the zero address clears the account's delegation
+    pub revocation: bool,
+    /// Shown to the user in the approval
+    pub warning: Option<String>,
+}
+
+/// Check a delegate against the known delegates list
+pub fn check_delegate(delegate: Address) -> DelegateCheck {
+    if delegate == Address::ZERO {
+        return DelegateCheck {
+            delegate,
+            name: Some("Remove delegation".to_string()),
+            known: true,
+            revocation: true,
+            warning: None,
+        };
+    }
+
+    match known_delegate(delegate) {
+        Some(known) => DelegateCheck {
+            delegate,
+            name: Some(known.name.to_string()),
+            known: true,
+            revocation: false,
+            warning: None,
+        },
+        None => DelegateCheck {
+            delegate,
+            name: None,
+            known: false,
+            revocation: false,
+            warning: Some(format!(
+                "{} is not a known delegate. It will get full control of this account, \
+                 including all of its funds.",
+                delegate
+            )),
+        },
+    }
+}
+
+/// Delegate of an account from its code, if the account is delegated
+pub fn delegation_target(code: &[u8]) -> Option<Address> {
+    (code.len() == 23 && code[..3] == DELEGATION_PREFIX).then(|| Address::from_slice(&code[3..]))
+}
+
+/// Current delegate of an account
+pub async fn current_delegate(
+    provider: &RootProvider,
+    account: Address,
+) -> Result<Option<Address>, WalletError> {
+    let code = provider
+        .get_code_at(account)
+        .await
+        .map_err(|e| WalletError::RpcError(e.to_string()))?;
+    Ok(delegation_target(&code))
+}
+
+/// Type-4 (set-code) transaction to sign and send
+#[derive(Debug, Clone)]
+pub struct SetCodeRequest {
+    pub from: Address,
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+    /// Estimated when `None`
+    pub gas_limit: Option<u64>,
+    pub authorizations: Vec<SignedAuthorization>,
+}
+
+/// Unsigned authorization tuple
+pub fn authorization(chain_id: u64, delegate: Address, nonce: u64) -> Authorization {
+    Authorization {
+        chain_id: U256::from(chain_id),
+        address: delegate,
+        nonce,
+    }
+}
+
+impl From<&SignedAuthorization> for EvmAuthorization {
+    fn from(auth: &SignedAuthorization) -> Self {
+        Self {
+            chain_id: auth.chain_id().saturating_to(),
+            address: format!("{:?}", auth.address()),
+            nonce: auth.nonce(),
+            y_parity: auth.y_parity(),
+            r: format!("{:#x}", auth.r()),
+            s: format!("{:#x}", auth.s()),
+        }
+    }
+}
+
+impl TryFrom<&EvmAuthorization> for SignedAuthorization {
+    type Error = WalletError;
+
+    fn try_from(auth: &EvmAuthorization) -> Result<Self, Self::Error> {
+        let delegate: Address = auth
+            .address
+            .parse()
+            .map_err(|_| WalletError::InvalidAddress(auth.address.clone()))?;
+        let parse_word = |v: &str| {
+            U256::from_str_radix(v.trim_start_matches("0x"), 16)
+                .map_err(|_| WalletError::InvalidTransaction(format!("Invalid authorization signature: {}", v)))
+        };
+        if auth.y_parity > 1 {
+            return Err(WalletError::InvalidTransaction(
+                "Invalid authorization signature parity".to_string(),
+            ));
+        }
+        Ok(SignedAuthorization::new_unchecked(
+            authorization(auth.chain_id, delegate, auth.nonce),
+            auth.y_parity,
+            parse_word(&auth.r)?,
+            parse_word(&auth.s)?,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::signers::local::PrivateKeySigner;
+    use alloy::signers::SignerSync;
+
+    #[test]
+    fn test_check_known_delegate() {
+        let check = check_delegate(KNOWN_DELEGATES[0].address);
+        assert!(check.known);
+        assert!(check.warning.is_none());
+        assert_eq!(check.name.as_deref(), Some(KNOWN_DELEGATES[0].name));
+    }
+
+    #[test]
+    fn test_check_unknown_delegate_warns() {
+        let check = check_delegate(Address::repeat_byte(0x42));
+        assert!(!check.known);
+        assert!(check.warning.is_some());
+    }
+
+    #[test]
+    fn test_check_revocation() {
+        let check = check_delegate(Address::ZERO);
+        assert!(check.revocation);
+        assert!(check.warning.is_none());
+    }
+
+    #[test]
+    fn test_delegation_target() {
+        let delegate = Address::repeat_byte(0x42);
+        let mut code = DELEGATION_PREFIX.to_vec();
+        code.extend_from_slice(delegate.as_slice());
+        assert_eq!(delegation_target(&code), Some(delegate));
+
+        assert_eq!(delegation_target(&[]), None);
+        assert_eq!(delegation_target(&[0x60, 0x80, 0x60, 0x40]), None);
+    }
+
+    #[test]
+    fn test_signed_authorization_roundtrip() {
+        let signer: PrivateKeySigner =
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".parse().unwrap();
+        let auth = authorization(1, KNOWN_DELEGATES[1].address, 7);
+        let signature = signer.sign_hash_sync(&auth.signature_hash()).unwrap();
+        let signed = auth.into_signed(signature);
+        let recovered = signed
+            .signature()
+            .unwrap()
+            .recover_address_from_prehash(&signed.signature_hash())
+            .unwrap();
+        assert_eq!(recovered, signer.address());
+
+        let exported = EvmAuthorization::from(&signed);
+        assert_eq!(exported.nonce, 7);
+        let back = SignedAuthorization::try_from(&exported).unwrap();
+        assert_eq!(back, signed);
+    }
+}
@@ -0,0 +1,461 @@
+// ============================================================================
+// Vaughan Wallet - ERC-4337 Account Abstraction
+// ============================================================================
+//
+// Building blocks for ERC-4337 smart accounts against EntryPoint v0.7:
+//
+// - `UserOperation`: the unpacked v0.7 form bundlers accept over JSON-RPC,
+//   plus the packing and hashing rules the EntryPoint uses on-chain.
+// - SimpleAccount-style factory/account ABI: `createAccount(owner, salt)`,
+//   `getAddress(owner, salt)` and `execute(dest, value, func)`.
+// - `BundlerClient`: `eth_estimateUserOperationGas`, `eth_sendUserOperation`
+//   and `eth_getUserOperationReceipt`.
+//
+// ============================================================================
+
+use std::time::Duration;
+
+use alloy::primitives::{address, aliases::U192, keccak256, Address, Bytes, B256, U256};
+use alloy::providers::{Provider, RootProvider};
+use alloy::sol;
+use alloy::sol_types::{SolCall, SolValue};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+
+use crate::error::WalletError;
+
+/// EntryPoint v0.7 (same address on every chain)
+pub const ENTRY_POINT_V07: Address = address!("0000000071727De22E5E9d8BAf0edAc6f37da032");
+
+/// eth-infinitism SimpleAccountFactory for EntryPoint v0.7
+pub const SIMPLE_ACCOUNT_FACTORY_V07: Address =
+    address!("91E60e0613810449d098b0b5Ec8b51A0FE8c8985");
+
+/// Placeholder signature used while estimating gas
+///
+/// Valid ECDSA shape (65 bytes, low `s`) so `ecrecover` in the account's
+/// validation runs its normal path instead of reverting.
+pub const DUMMY_SIGNATURE: [u8; 65] = {
+    let mut sig = [0u8; 65];
+    let mut i = 0;
+    while i < 15 {
+        sig[i] = 0xff;
+        i += 1;
+    }
+    sig[15] = 0xf0;
+    sig[31] = 0x07;
+    let mut i = 32;
+    while i < 64 {
+        sig[i] = 0xaa;
+        i += 1;
+    }
+    sig[64] = 0x1c;
+    sig
+};
+
+/// Timeout for bundler requests
+const BUNDLER_TIMEOUT: Duration = Duration::from_secs(30);
+
+sol! {
+    #[sol(rpc)]
+    contract EntryPoint {
+        function getNonce(address sender, uint192 key) external view returns (uint256 nonce);
+    }
+
+    #[sol(rpc)]
+    contract SimpleAccountFactory {
+        function createAccount(address owner, uint256 salt) external returns (address ret);
+        function getAddress(address owner, uint256 salt) external view returns (address);
+    }
+
+    #[sol(rpc)]
+    contract SimpleAccount {
+        function execute(address dest, uint256 value, bytes calldata func) external;
+    }
+}
+
+// ============================================================================
+// UserOperation
+// ============================================================================
+
+/// ERC-4337 v0.7 UserOperation, in the unpacked form used by bundler RPCs
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperation {
+    pub sender: Address,
+    pub nonce: U256,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub factory: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub factory_data: Option<Bytes>,
+    pub call_data: Bytes,
+    pub call_gas_limit: U256,
+    pub verification_gas_limit: U256,
+    pub pre_verification_gas: U256,
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster: Option<Address>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_verification_gas_limit: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_post_op_gas_limit: Option<U256>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub paymaster_data: Option<Bytes>,
+    pub signature: Bytes,
+}
+
+/// Two 128-bit values packed into one 32-byte word (`high << 128 | low`)
+fn pack_u128_pair(high: U256, low: U256) -> B256 {
+    let mask = U256::from(u128::MAX);
+    B256::from(((high & mask) << 128) | (low & mask))
+}
+
+impl UserOperation {
+    /// `factory ++ factoryData`, or empty once the account is deployed
+    pub fn init_code(&self) -> Bytes {
+        match self.factory {
+            Some(factory) => {
+                let mut out = factory.to_vec();
+                if let Some(data) = &self.factory_data {
+                    out.extend_from_slice(data);
+                }
+                out.into()
+            },
+            None => Bytes::new(),
+        }
+    }
+
+    /// `verificationGasLimit ++ callGasLimit`
+    pub fn account_gas_limits(&self) -> B256 {
+        pack_u128_pair(self.verification_gas_limit, self.call_gas_limit)
+    }
+
+    /// `maxPriorityFeePerGas ++ maxFeePerGas`
+    pub fn gas_fees(&self) -> B256 {
+        pack_u128_pair(self.max_priority_fee_per_gas, self.max_fee_per_gas)
+    }
+
+    /// `paymaster ++ verificationGasLimit ++ postOpGasLimit ++ paymasterData`
+    pub fn paymaster_and_data(&self) -> Bytes {
+        match self.paymaster {
+            Some(paymaster) => {
+                let mut out = paymaster.to_vec();
+                let limits = pack_u128_pair(
+                    self.paymaster_verification_gas_limit.unwrap_or_default(),
+                    self.paymaster_post_op_gas_limit.unwrap_or_default(),
+                );
+                out.extend_from_slice(limits.as_slice());
+                if let Some(data) = &self.paymaster_data {
+                    out.extend_from_slice(data);
+                }
+                out.into()
+            },
+            None => Bytes::new(),
+        }
+    }
+
+    /// UserOperation hash, as computed by `EntryPoint.getUserOpHash`
+    ///
+    /// This is what the account owner signs.
+    pub fn hash(&self, entry_point: Address, chain_id: u64) -> B256 {
+        let packed = (
+            self.sender,
+            self.nonce,
+            keccak256(self.init_code()),
+            keccak256(&self.call_data),
+            self.account_gas_limits(),
+            self.pre_verification_gas,
+            self.gas_fees(),
+            keccak256(self.paymaster_and_data()),
+        )
+            .abi_encode();
+
+        keccak256((keccak256(packed), entry_point, U256::from(chain_id)).abi_encode())
+    }
+
+    /// Total gas the operation may consume (for cost display)
+    pub fn total_gas(&self) -> U256 {
+        self.call_gas_limit
+            + self.verification_gas_limit
+            + self.pre_verification_gas
+            + self.paymaster_verification_gas_limit.unwrap_or_default()
+            + self.paymaster_post_op_gas_limit.unwrap_or_default()
+    }
+
+    /// Apply a bundler gas estimate
+    pub fn apply_gas_estimate(&mut self, estimate: &UserOperationGasEstimate) {
+        self.call_gas_limit = estimate.call_gas_limit;
+        self.verification_gas_limit = estimate.verification_gas_limit;
+        self.pre_verification_gas = estimate.pre_verification_gas;
+        if self.paymaster.is_some() {
+            self.paymaster_verification_gas_limit = estimate.paymaster_verification_gas_limit;
+        }
+    }
+}
+
+/// Result of `eth_estimateUserOperationGas`
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationGasEstimate {
+    pub pre_verification_gas: U256,
+    pub verification_gas_limit: U256,
+    pub call_gas_limit: U256,
+    #[serde(default)]
+    pub paymaster_verification_gas_limit: Option<U256>,
+}
+
+/// Result of `eth_getUserOperationReceipt`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationReceipt {
+    pub user_op_hash: B256,
+    pub success: bool,
+    #[serde(default)]
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub actual_gas_cost: Option<U256>,
+    pub receipt: UserOperationTxReceipt,
+}
+
+/// Bundle transaction that included a UserOperation
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperationTxReceipt {
+    pub transaction_hash: B256,
+}
+
+// ============================================================================
+// SimpleAccount ABI helpers
+// ============================================================================
+
+/// Factory calldata that deploys the account for `owner`/`salt`
+pub fn factory_data(owner: Address, salt: U256) -> Bytes {
+    SimpleAccountFactory::createAccountCall { owner, salt }.abi_encode().into()
+}
+
+/// Account calldata that makes the account call `to` with `value` and `data`
+pub fn execute_call_data(to: Address, value: U256, data: Bytes) -> Bytes {
+    SimpleAccount::executeCall { dest: to, value, func: data }.abi_encode().into()
+}
+
+/// Counterfactual account address from the factory's `getAddress`
+pub async fn counterfactual_address(
+    provider: &RootProvider,
+    factory: Address,
+    owner: Address,
+    salt: U256,
+) -> Result<Address, WalletError> {
+    SimpleAccountFactory::new(factory, provider)
+        .getAddress(owner, salt)
+        .call()
+        .await
+        .map_err(|e| WalletError::RpcError(format!("Account factory getAddress: {}", e)))
+}
+
+/// Whether the account contract has been deployed
+pub async fn is_deployed(provider: &RootProvider, account: Address) -> Result<bool, WalletError> {
+    let code = provider
+        .get_code_at(account)
+        .await
+        .map_err(|e| WalletError::RpcError(e.to_string()))?;
+    Ok(!code.is_empty())
+}
+
+/// Next EntryPoint nonce for the account (key 0)
+pub async fn get_nonce(
+    provider: &RootProvider,
+    entry_point: Address,
+    sender: Address,
+) -> Result<U256, WalletError> {
+    EntryPoint::new(entry_point, provider)
+        .getNonce(sender, U192::ZERO)
+        .call()
+        .await
+        .map_err(|e| WalletError::RpcError(format!("EntryPoint getNonce: {}", e)))
+}
+
+// ============================================================================
+// Bundler Client
+// ============================================================================
+
+/// JSON-RPC client for an ERC-4337 bundler
+pub struct BundlerClient {
+    url: String,
+    client: reqwest::Client,
+}
+
+impl BundlerClient {
+    pub fn new(url: &str) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(BUNDLER_TIMEOUT)
+            .build()
+            .unwrap_or_default();
+        Self {
+            url: url.to_string(),
+            client,
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<T, WalletError> {
+        let payload = serde_json::json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response = self
+            .client
+            .post(&self.url)
+            .json(&payload)
+            .send()
+            .await
+            .map_err(|e| {
+                if e.is_timeout() {
+                    WalletError::ConnectionTimeout(format!("Bundler {} did not respond", self.url))
+                } else {
+                    WalletError::NetworkError(format!("Bundler unreachable: {}", e))
+                }
+            })?;
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| WalletError::RpcError(format!("Invalid bundler response: {}", e)))?;
+
+        if let Some(error) = body.get("error") {
+            let message = error
+                .get("message")
+                .and_then(|m| m.as_str())
+                .unwrap_or("unknown error");
+            return Err(WalletError::RpcError(format!("{}: {}", method, message)));
+        }
+
+        let result = body.get("result").cloned().unwrap_or(serde_json::Value::Null);
+        serde_json::from_value(result)
+            .map_err(|e| WalletError::RpcError(format!("{}: unexpected result: {}", method, e)))
+    }
+
+    /// EntryPoints the bundler accepts
+    pub async fn supported_entry_points(&self) -> Result<Vec<Address>, WalletError> {
+        self.request("eth_supportedEntryPoints", serde_json::json!([]))
+            .await
+    }
+
+    /// Gas limits for a UserOperation (signature may be a placeholder)
+    pub async fn estimate_user_operation_gas(
+        &self,
+        op: &UserOperation,
+        entry_point: Address,
+    ) -> Result<UserOperationGasEstimate, WalletError> {
+        self.request(
+            "eth_estimateUserOperationGas",
+            serde_json::json!([op, entry_point]),
+        )
+        .await
+        .map_err(|e| WalletError::GasEstimationFailed(e.to_string()))
+    }
+
+    /// Submit a signed UserOperation, returning its hash
+    pub async fn send_user_operation(
+        &self,
+        op: &UserOperation,
+        entry_point: Address,
+    ) -> Result<B256, WalletError> {
+        self.request("eth_sendUserOperation", serde_json::json!([op, entry_point]))
+            .await
+            .map_err(|e| WalletError::TransactionFailed(e.to_string()))
+    }
+
+    /// Receipt for a UserOperation, or `None` while it is still pending
+    pub async fn get_user_operation_receipt(
+        &self,
+        user_op_hash: B256,
+    ) -> Result<Option<UserOperationReceipt>, WalletError> {
+        self.request(
+            "eth_getUserOperationReceipt",
+            serde_json::json!([user_op_hash]),
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_op() -> UserOperation {
+        UserOperation {
+            sender: Address::repeat_byte(0x11),
+            nonce: U256::from(1u64),
+            call_data: Bytes::from(vec![0xab; 4]),
+            call_gas_limit: U256::from(100_000u64),
+            verification_gas_limit: U256::from(200_000u64),
+            pre_verification_gas: U256::from(50_000u64),
+            max_fee_per_gas: U256::from(3_000_000_000u64),
+            max_priority_fee_per_gas: U256::from(1_000_000_000u64),
+            signature: Bytes::from(DUMMY_SIGNATURE.to_vec()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_pack_gas_fields() {
+        let op = sample_op();
+        let limits = op.account_gas_limits();
+        assert_eq!(U256::from_be_slice(&limits[..16]), U256::from(200_000u64));
+        assert_eq!(U256::from_be_slice(&limits[16..]), U256::from(100_000u64));
+
+        let fees = op.gas_fees();
+        assert_eq!(U256::from_be_slice(&fees[..16]), U256::from(1_000_000_000u64));
+        assert_eq!(U256::from_be_slice(&fees[16..]), U256::from(3_000_000_000u64));
+    }
+
+    #[test]
+    fn test_init_code() {
+        let mut op = sample_op();
+        assert!(op.init_code().is_empty());
+
+        let owner = Address::repeat_byte(0x22);
+        op.factory = Some(SIMPLE_ACCOUNT_FACTORY_V07);
+        op.factory_data = Some(factory_data(owner, U256::ZERO));
+        let init_code = op.init_code();
+        assert_eq!(&init_code[..20], SIMPLE_ACCOUNT_FACTORY_V07.as_slice());
+        assert_eq!(init_code.len(), 20 + 4 + 64);
+    }
+
+    #[test]
+    fn test_hash_binds_chain_and_entry_point() {
+        let op = sample_op();
+        let hash = op.hash(ENTRY_POINT_V07, 1);
+        assert_eq!(hash, op.hash(ENTRY_POINT_V07, 1));
+        assert_ne!(hash, op.hash(ENTRY_POINT_V07, 10));
+        assert_ne!(hash, op.hash(Address::ZERO, 1));
+
+        // The signature is not part of the hash
+        let mut signed = op.clone();
+        signed.signature = Bytes::from(vec![0x01; 65]);
+        assert_eq!(hash, signed.hash(ENTRY_POINT_V07, 1));
+    }
+
+    #[test]
+    fn test_rpc_serialization() {
+        let op = sample_op();
+        let json = serde_json::to_value(&op).unwrap();
+        assert_eq!(json["callGasLimit"], "0x186a0");
+        assert!(json.get("factory").is_none());
+        assert!(json.get("paymaster").is_none());
+
+        let back: UserOperation = serde_json::from_value(json).unwrap();
+        assert_eq!(back, op);
+    }
+
+    #[test]
+    fn test_execute_call_data_selector() {
+        let data = execute_call_data(Address::repeat_byte(0x33), U256::from(1u64), Bytes::new());
+        // execute(address,uint256,bytes)
+        assert_eq!(&data[..4], &[0xb6, 0x1d, 0x27, 0xf6]);
+    }
+}
@@ -0,0 +1,289 @@
+// ============================================================================
+// Vaughan Wallet - L2 Data Fee Estimation
+// ============================================================================
+//
+// Rollups charge for posting transaction data to L1 on top of L2 execution gas.
+//
+// - OP Stack (Optimism, Base, ...): the L1 fee is charged separately and is
+//   NOT included in `gas * gas_price`. Quoted by the `GasPriceOracle`
+//   predeploy's `getL1Fee(bytes)`.
+// - Arbitrum: the L1 component is folded into the gas limit returned by
+//   `eth_estimateGas`. `NodeInterface.gasEstimateL1Component` tells us how
+//   much of it is L1, falling back to `ArbGasInfo.getPricesInWei`.
+//
+// ============================================================================
+
+use alloy::consensus::{SignableTransaction, TxEip1559};
+use alloy::primitives::{address, Address, Bytes, TxKind, U256};
+use alloy::providers::RootProvider;
+use alloy::sol;
+
+use crate::error::WalletError;
+
+/// OP Stack `GasPriceOracle` predeploy
+pub const OP_GAS_PRICE_ORACLE: Address = address!("420000000000000000000000000000000000000F");
+
+/// Arbitrum `NodeInterface` (virtual contract, eth_call only)
+pub const ARB_NODE_INTERFACE: Address = address!("00000000000000000000000000000000000000C8");
+
+/// Arbitrum `ArbGasInfo` precompile
+pub const ARB_GAS_INFO: Address = address!("000000000000000000000000000000000000006C");
+
+/// Approximate size of a signed transaction without calldata (bytes)
+const ARB_TX_OVERHEAD_BYTES: u64 = 140;
+
+sol! {
+    #[sol(rpc)]
+    contract GasPriceOracle {
+        function getL1Fee(bytes memory _data) external view returns (uint256);
+    }
+
+    #[sol(rpc)]
+    contract NodeInterface {
+        function gasEstimateL1Component(address to, bool contractCreation, bytes calldata data)
+            external
+            payable
+            returns (uint64 gasEstimateForL1, uint256 baseFee, uint256 l1BaseFeeEstimate);
+    }
+
+    #[sol(rpc)]
+    contract ArbGasInfo {
+        function getPricesInWei()
+            external
+            view
+            returns (
+                uint256 perL2Tx,
+                uint256 perL1CalldataByte,
+                uint256 perStorageAllocation,
+                uint256 perArbGasBase,
+                uint256 perArbGasCongestion,
+                uint256 perArbGasTotal
+            );
+    }
+}
+
+/// Rollup family, which determines how the L1 data fee is charged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RollupKind {
+    /// OP Stack: L1 fee charged on top of L2 gas
+    OpStack,
+    /// Arbitrum Nitro: L1 fee folded into the L2 gas limit
+    Arbitrum,
+}
+
+impl RollupKind {
+    /// Whether the L1 fee is already part of `gas_limit * gas_price`
+    pub fn l1_fee_in_gas(&self) -> bool {
+        matches!(self, Self::Arbitrum)
+    }
+}
+
+/// Rollup family for a chain ID, or `None` for L1s and unknown chains
+pub fn rollup_kind(chain_id: u64) -> Option<RollupKind> {
+    match chain_id {
+        // Optimism, Base, Zora, Mode, Fraxtal, Unichain, World Chain, OP/Base Sepolia
+        10 | 8453 | 7777777 | 34443 | 252 | 130 | 480 | 11155420 | 84532 => {
+            Some(RollupKind::OpStack)
+        },
+        // Arbitrum One, Arbitrum Nova, Arbitrum Sepolia
+        42161 | 42170 | 421614 => Some(RollupKind::Arbitrum),
+        _ => None,
+    }
+}
+
+/// Part of an L1 fee that comes on top of `gas_limit * gas_price`
+///
+/// The full L1 fee on OP Stack chains, zero on Arbitrum (already in the gas
+/// limit) and on non-rollups.
+pub fn l1_fee_surcharge(chain_id: u64, l1_fee: Option<U256>) -> U256 {
+    match (l1_fee, rollup_kind(chain_id)) {
+        (Some(fee), Some(kind)) if !kind.l1_fee_in_gas() => fee,
+        _ => U256::ZERO,
+    }
+}
+
+/// Outcome of an L1 data fee lookup for a fee estimate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum L1FeeEstimate {
+    /// Not a known rollup, or a contract deployment
+    NotApplicable,
+    /// L1 portion of the fee (in wei)
+    Known(U256),
+    /// The rollup's fee oracle could not be queried
+    Unavailable,
+}
+
+impl L1FeeEstimate {
+    /// From an `estimate_l1_fee` result; a failed lookup is `Unavailable`
+    pub fn from_result(result: Result<Option<U256>, WalletError>) -> Self {
+        match result {
+            Ok(Some(fee)) => Self::Known(fee),
+            Ok(None) => Self::NotApplicable,
+            Err(_) => Self::Unavailable,
+        }
+    }
+
+    /// The L1 fee, if it is known
+    pub fn amount(&self) -> Option<U256> {
+        match self {
+            Self::Known(fee) => Some(*fee),
+            _ => None,
+        }
+    }
+
+    pub fn is_unavailable(&self) -> bool {
+        matches!(self, Self::Unavailable)
+    }
+}
+
+/// Warning shown with a fee whose L1 portion could not be estimated
+pub const L1_FEE_UNAVAILABLE_WARNING: &str =
+    "The L1 data fee could not be estimated; the fee shown covers L2 execution only";
+
+/// Transaction fields needed to price its L1 data
+#[derive(Debug, Clone)]
+pub struct L1FeeRequest {
+    pub chain_id: u64,
+    pub from: Address,
+    pub to: Address,
+    pub value: U256,
+    pub input: Bytes,
+    pub nonce: u64,
+    pub gas_limit: u64,
+    pub gas_price: u128,
+}
+
+impl L1FeeRequest {
+    /// Unsigned, fully RLP-encoded EIP-1559 transaction (what `getL1Fee` expects)
+    fn unsigned_rlp(&self) -> Bytes {
+        let tx = TxEip1559 {
+            chain_id: self.chain_id,
+            nonce: self.nonce,
+            gas_limit: self.gas_limit,
+            max_fee_per_gas: self.gas_price,
+            max_priority_fee_per_gas: self.gas_price,
+            to: TxKind::Call(self.to),
+            value: self.value,
+            input: self.input.clone(),
+            ..Default::default()
+        };
+        tx.encoded_for_signing().into()
+    }
+}
+
+/// Estimate the L1 data fee (in wei) for a transaction on a rollup
+///
+/// # Returns
+///
+/// * `Ok(Some(fee))` - L1 portion of the fee
+/// * `Ok(None)` - Not a known rollup
+/// * `Err(WalletError)` - Oracle call failed
+pub async fn estimate_l1_fee(
+    provider: &RootProvider,
+    request: &L1FeeRequest,
+) -> Result<Option<U256>, WalletError> {
+    match rollup_kind(request.chain_id) {
+        Some(RollupKind::OpStack) => op_l1_fee(provider, request).await.map(Some),
+        Some(RollupKind::Arbitrum) => arbitrum_l1_fee(provider, request).await.map(Some),
+        None => Ok(None),
+    }
+}
+
+async fn op_l1_fee(provider: &RootProvider, request: &L1FeeRequest) -> Result<U256, WalletError> {
+    let oracle = GasPriceOracle::new(OP_GAS_PRICE_ORACLE, provider);
+    oracle
+        .getL1Fee(request.unsigned_rlp())
+        .call()
+        .await
+        .map_err(|e| WalletError::GasEstimationFailed(format!("GasPriceOracle.getL1Fee: {}", e)))
+}
+
+async fn arbitrum_l1_fee(
+    provider: &RootProvider,
+    request: &L1FeeRequest,
+) -> Result<U256, WalletError> {
+    let node = NodeInterface::new(ARB_NODE_INTERFACE, provider);
+    let estimate = node
+        .gasEstimateL1Component(request.to, false, request.input.clone())
+        .from(request.from)
+        .value(request.value)
+        .call()
+        .await;
+
+    match estimate {
+        Ok(result) => Ok(U256::from(result.gasEstimateForL1) * result.baseFee),
+        Err(_) => {
+            // Older nodes or proxies that don't serve NodeInterface: price the
+            // calldata directly from ArbGasInfo
+            let prices = ArbGasInfo::new(ARB_GAS_INFO, provider)
+                .getPricesInWei()
+                .call()
+                .await
+                .map_err(|e| {
+                    WalletError::GasEstimationFailed(format!("ArbGasInfo.getPricesInWei: {}", e))
+                })?;
+            let size = ARB_TX_OVERHEAD_BYTES + request.input.len() as u64;
+            Ok(U256::from(size) * prices.perL1CalldataByte)
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rollup_kind() {
+        assert_eq!(rollup_kind(10), Some(RollupKind::OpStack));
+        assert_eq!(rollup_kind(8453), Some(RollupKind::OpStack));
+        assert_eq!(rollup_kind(42161), Some(RollupKind::Arbitrum));
+        assert_eq!(rollup_kind(1), None);
+        assert_eq!(rollup_kind(369), None);
+    }
+
+    #[test]
+    fn test_l1_fee_in_gas() {
+        assert!(RollupKind::Arbitrum.l1_fee_in_gas());
+        assert!(!RollupKind::OpStack.l1_fee_in_gas());
+    }
+
+    #[test]
+    fn test_l1_fee_surcharge() {
+        let fee = Some(U256::from(1000u64));
+        assert_eq!(l1_fee_surcharge(10, fee), U256::from(1000u64));
+        assert_eq!(l1_fee_surcharge(42161, fee), U256::ZERO);
+        assert_eq!(l1_fee_surcharge(1, fee), U256::ZERO);
+        assert_eq!(l1_fee_surcharge(10, None), U256::ZERO);
+    }
+
+    #[test]
+    fn test_l1_fee_estimate_from_result() {
+        let fee = U256::from(1000u64);
+        assert_eq!(L1FeeEstimate::from_result(Ok(Some(fee))), L1FeeEstimate::Known(fee));
+        assert_eq!(L1FeeEstimate::from_result(Ok(None)), L1FeeEstimate::NotApplicable);
+
+        let failed = L1FeeEstimate::from_result(Err(WalletError::RpcError("timeout".into())));
+        assert!(failed.is_unavailable());
+        assert_eq!(failed.amount(), None);
+        assert_eq!(l1_fee_surcharge(10, failed.amount()), U256::ZERO);
+    }
+
+    #[test]
+    fn test_unsigned_rlp_is_typed_eip1559() {
+        let request = L1FeeRequest {
+            chain_id: 10,
+            from: Address::ZERO,
+            to: Address::repeat_byte(0x11),
+            value: U256::from(1u64),
+            input: Bytes::from(vec![0xab; 32]),
+            nonce: 7,
+            gas_limit: 21_000,
+            gas_price: 1_000_000,
+        };
+        let rlp = request.unsigned_rlp();
+
+        // Type byte 0x02 followed by an RLP list containing the calldata
+        assert_eq!(rlp[0], 0x02);
+        assert!(rlp.len() > 32);
+    }
+}
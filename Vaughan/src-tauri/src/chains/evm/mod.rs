@@ -6,11 +6,21 @@
 //
 // ============================================================================
 
+pub mod abi;
 pub mod adapter;
+pub mod clef;
+pub mod disperse;
+pub mod eip7702;
+pub mod erc4337;
+pub mod l2;
 pub mod networks;
+pub mod registry;
+pub mod safe;
+pub mod signature;
 pub mod utils;
 
 // Re-export main types
 pub use adapter::EvmAdapter;
 pub use networks::{all_networks, get_network, get_network_by_chain_id, EvmNetworkConfig};
+pub use registry::{registry, NetworkRegistry};
 pub use utils::*;
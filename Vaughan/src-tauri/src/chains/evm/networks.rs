@@ -2,11 +2,12 @@
 // Vaughan Wallet - EVM Network Configurations
 // ============================================================================
 //
-// Predefined configurations for popular EVM-compatible networks.
-// Makes it easy to add support for new EVM chains.
+// EVM network configuration type and lookups. All network data comes from the
+// network registry (see `registry.rs`).
 //
 // ============================================================================
 
+use super::registry::registry;
 use serde::{Deserialize, Serialize};
 
 /// EVM network configuration
@@ -39,6 +40,10 @@ pub struct EvmNetworkConfig {
 
     /// Native token decimals (usually 18 for EVM)
     pub decimals: u8,
+
+    /// Whether this is a testnet
+    #[serde(default)]
+    pub is_testnet: bool,
 }
 
 impl EvmNetworkConfig {
@@ -61,6 +66,7 @@ impl EvmNetworkConfig {
             native_symbol,
             native_name,
             decimals: 18,
+            is_testnet: false,
         }
     }
 
@@ -78,212 +84,24 @@ impl EvmNetworkConfig {
 }
 
 // ============================================================================
-// Predefined Network Configurations
-// ============================================================================
-
-/// Get Ethereum Mainnet configuration
-pub fn ethereum_mainnet() -> EvmNetworkConfig {
-    EvmNetworkConfig::new(
-        "ethereum".to_string(),
-        "Ethereum Mainnet".to_string(),
-        1,
-        "https://eth.llamarpc.com".to_string(),
-        "ETH".to_string(),
-        "Ethereum".to_string(),
-    )
-    .with_explorer("https://etherscan.io".to_string())
-    .with_explorer_api("https://api.etherscan.io/api".to_string())
-}
-
-/// Get PulseChain Mainnet configuration
-pub fn pulsechain_mainnet() -> EvmNetworkConfig {
-    EvmNetworkConfig::new(
-        "pulsechain".to_string(),
-        "PulseChain Mainnet".to_string(),
-        369,
-        "https://rpc.pulsechain.com".to_string(),
-        "PLS".to_string(),
-        "PulseChain".to_string(),
-    )
-    .with_explorer("https://scan.pulsechain.com".to_string())
-    .with_explorer_api("https://api.scan.pulsechain.com/api".to_string())
-}
-
-/// Get Polygon Mainnet configuration
-pub fn polygon_mainnet() -> EvmNetworkConfig {
-    EvmNetworkConfig::new(
-        "polygon".to_string(),
-        "Polygon Mainnet".to_string(),
-        137,
-        "https://polygon-bor-rpc.publicnode.com".to_string(),
-        "MATIC".to_string(),
-        "Polygon".to_string(),
-    )
-    .with_explorer("https://polygonscan.com".to_string())
-}
-
-/// Get Binance Smart Chain Mainnet configuration
-pub fn bsc_mainnet() -> EvmNetworkConfig {
-    EvmNetworkConfig::new(
-        "bsc".to_string(),
-        "BSC Mainnet".to_string(),
-        56,
-        "https://bsc-dataseed.binance.org".to_string(),
-        "BNB".to_string(),
-        "Binance Coin".to_string(),
-    )
-    .with_explorer("https://bscscan.com".to_string())
-}
-
-/// Get Arbitrum One configuration
-pub fn arbitrum_one() -> EvmNetworkConfig {
-    EvmNetworkConfig::new(
-        "arbitrum".to_string(),
-        "Arbitrum One".to_string(),
-        42161,
-        "https://arb1.arbitrum.io/rpc".to_string(),
-        "ETH".to_string(),
-        "Ethereum".to_string(),
-    )
-    .with_explorer("https://arbiscan.io".to_string())
-}
-
-/// Get Optimism Mainnet configuration
-pub fn optimism_mainnet() -> EvmNetworkConfig {
-    EvmNetworkConfig::new(
-        "optimism".to_string(),
-        "Optimism Mainnet".to_string(),
-        10,
-        "https://mainnet.optimism.io".to_string(),
-        "ETH".to_string(),
-        "Ethereum".to_string(),
-    )
-    .with_explorer("https://optimistic.etherscan.io".to_string())
-}
-
-/// Get Avalanche C-Chain configuration
-pub fn avalanche_cchain() -> EvmNetworkConfig {
-    EvmNetworkConfig::new(
-        "avalanche".to_string(),
-        "Avalanche C-Chain".to_string(),
-        43114,
-        "https://api.avax.network/ext/bc/C/rpc".to_string(),
-        "AVAX".to_string(),
-        "Avalanche".to_string(),
-    )
-    .with_explorer("https://snowtrace.io".to_string())
-}
-
-/// Get Base Mainnet configuration
-pub fn base_mainnet() -> EvmNetworkConfig {
-    EvmNetworkConfig::new(
-        "base".to_string(),
-        "Base Mainnet".to_string(),
-        8453,
-        "https://mainnet.base.org".to_string(),
-        "ETH".to_string(),
-        "Ethereum".to_string(),
-    )
-    .with_explorer("https://basescan.org".to_string())
-}
-
-/// Get PulseChain Testnet V4 configuration
-pub fn pulsechain_testnet_v4() -> EvmNetworkConfig {
-    EvmNetworkConfig::new(
-        "pulsechain-testnet-v4".to_string(),
-        "PulseChain Testnet V4".to_string(),
-        943,
-        "https://rpc.v4.testnet.pulsechain.com".to_string(),
-        "tPLS".to_string(),
-        "Test PulseChain".to_string(),
-    )
-    .with_explorer("https://scan.v4.testnet.pulsechain.com".to_string())
-    .with_explorer_api("https://api.scan.v4.testnet.pulsechain.com/api".to_string())
-}
-
-/// Get Ethereum Sepolia configuration
-pub fn ethereum_sepolia() -> EvmNetworkConfig {
-    EvmNetworkConfig::new(
-        "sepolia".to_string(),
-        "Ethereum Sepolia".to_string(),
-        11155111,
-        "https://ethereum-sepolia-rpc.publicnode.com".to_string(),
-        "ETH".to_string(),
-        "Sepolia Ether".to_string(),
-    )
-    .with_explorer("https://sepolia.etherscan.io".to_string())
-    .with_explorer_api("https://api-sepolia.etherscan.io/api".to_string())
-}
-
-/// Get Polygon Amoy configuration
-pub fn polygon_amoy() -> EvmNetworkConfig {
-    EvmNetworkConfig::new(
-        "polygon-amoy".to_string(),
-        "Polygon Amoy".to_string(),
-        80002,
-        "https://polygon-amoy-bor-rpc.publicnode.com".to_string(),
-        "MATIC".to_string(),
-        "Amoy Matic".to_string(),
-    )
-    .with_explorer("https://amoy.polygonscan.com".to_string())
-    .with_explorer_api("https://api-amoy.polygonscan.com/api".to_string())
-}
-
-// ============================================================================
-// Network Registry
+// Network Lookups
 // ============================================================================
 
-/// Get all predefined network configurations
+/// Get all predefined (featured) network configurations
 pub fn all_networks() -> Vec<EvmNetworkConfig> {
-    vec![
-        ethereum_mainnet(),
-        ethereum_sepolia(),
-        pulsechain_mainnet(),
-        pulsechain_testnet_v4(),
-        polygon_mainnet(),
-        polygon_amoy(),
-        bsc_mainnet(),
-        arbitrum_one(),
-        optimism_mainnet(),
-        avalanche_cchain(),
-        base_mainnet(),
-    ]
+    registry().featured()
 }
 
 /// Get network configuration by ID
 pub fn get_network(id: &str) -> Option<EvmNetworkConfig> {
-    match id {
-        "ethereum" => Some(ethereum_mainnet()),
-        "sepolia" => Some(ethereum_sepolia()),
-        "pulsechain" => Some(pulsechain_mainnet()),
-        "pulsechain-testnet-v4" => Some(pulsechain_testnet_v4()),
-        "polygon" => Some(polygon_mainnet()),
-        "polygon-amoy" => Some(polygon_amoy()),
-        "bsc" => Some(bsc_mainnet()),
-        "arbitrum" => Some(arbitrum_one()),
-        "optimism" => Some(optimism_mainnet()),
-        "avalanche" => Some(avalanche_cchain()),
-        "base" => Some(base_mainnet()),
-        _ => None,
-    }
+    registry().get_by_id(id)
 }
 
 /// Get network configuration by chain ID
+///
+/// Searches the whole registry, not only the predefined networks.
 pub fn get_network_by_chain_id(chain_id: u64) -> Option<EvmNetworkConfig> {
-    match chain_id {
-        1 => Some(ethereum_mainnet()),
-        11155111 => Some(ethereum_sepolia()),
-        369 => Some(pulsechain_mainnet()),
-        943 => Some(pulsechain_testnet_v4()),
-        137 => Some(polygon_mainnet()),
-        80002 => Some(polygon_amoy()),
-        56 => Some(bsc_mainnet()),
-        42161 => Some(arbitrum_one()),
-        10 => Some(optimism_mainnet()),
-        43114 => Some(avalanche_cchain()),
-        8453 => Some(base_mainnet()),
-        _ => None,
-    }
+    registry().get_by_chain_id(chain_id)
 }
 
 #[cfg(test)]
@@ -292,16 +110,17 @@ mod tests {
 
     #[test]
     fn test_ethereum_config() {
-        let config = ethereum_mainnet();
+        let config = get_network("ethereum").unwrap();
         assert_eq!(config.id, "ethereum");
         assert_eq!(config.chain_id, 1);
         assert_eq!(config.native_symbol, "ETH");
         assert!(config.explorer_url.is_some());
+        assert!(config.explorer_api_url.is_some());
     }
 
     #[test]
     fn test_pulsechain_config() {
-        let config = pulsechain_mainnet();
+        let config = get_network("pulsechain").unwrap();
         assert_eq!(config.id, "pulsechain");
         assert_eq!(config.chain_id, 369);
         assert_eq!(config.native_symbol, "PLS");
@@ -324,6 +143,7 @@ mod tests {
     #[test]
     fn test_all_networks() {
         let networks = all_networks();
-        assert_eq!(networks.len(), 9);
+        assert_eq!(networks.len(), 11);
+        assert!(networks.iter().all(|n| get_network(&n.id).is_some()));
     }
 }
@@ -0,0 +1,471 @@
+// ============================================================================
+// Vaughan Wallet - EVM Network Registry
+// ============================================================================
+//
+// Single source of truth for known EVM networks.
+//
+// The registry is seeded from a bundled `chains.json` in the ethereum-lists /
+// chainlist format. A full `chains.json` can be dropped into the app data
+// directory (or imported at runtime) to make every chain searchable.
+//
+// A small set of chains is "featured": these are the predefined networks shown
+// by default and get stable IDs (e.g. "ethereum") that are persisted in state.
+//
+// ============================================================================
+
+use super::networks::EvmNetworkConfig;
+use crate::error::WalletError;
+use lazy_static::lazy_static;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+/// Bundled chain list (ethereum-lists format)
+const BUNDLED_CHAINS_JSON: &str = include_str!("chains.json");
+
+/// File name of the user-supplied chain list in the app data directory
+const USER_CHAINS_FILE: &str = "chains.json";
+
+/// Featured networks: (chain ID, stable network ID, Etherscan-compatible API URL)
+///
+/// Order is the order networks are presented in the UI.
+const FEATURED: &[(u64, &str, Option<&str>)] = &[
+    (1, "ethereum", Some("https://api.etherscan.io/api")),
+    (369, "pulsechain", Some("https://api.scan.pulsechain.com/api")),
+    (137, "polygon", None),
+    (56, "bsc", None),
+    (42161, "arbitrum", None),
+    (10, "optimism", None),
+    (43114, "avalanche", None),
+    (8453, "base", None),
+    (
+        943,
+        "pulsechain-testnet-v4",
+        Some("https://api.scan.v4.testnet.pulsechain.com/api"),
+    ),
+    (11155111, "sepolia", Some("https://api-sepolia.etherscan.io/api")),
+    (80002, "polygon-amoy", Some("https://api-amoy.polygonscan.com/api")),
+];
+
+/// Name fragments that mark a chain as a testnet when it has no faucets listed
+const TESTNET_MARKERS: &[&str] = &["testnet", "sepolia", "holesky", "goerli", "devnet"];
+
+// ============================================================================
+// chains.json Format
+// ============================================================================
+
+/// One entry of an ethereum-lists `chains.json`
+///
+/// Only the fields the wallet needs are parsed; everything else is ignored.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainListEntry {
+    pub name: String,
+
+    pub chain_id: u64,
+
+    #[serde(default)]
+    pub short_name: String,
+
+    #[serde(default)]
+    pub rpc: Vec<String>,
+
+    #[serde(default)]
+    pub faucets: Vec<String>,
+
+    pub native_currency: ChainListCurrency,
+
+    #[serde(default)]
+    pub explorers: Vec<ChainListExplorer>,
+
+    /// "active", "incubating" or "deprecated"
+    #[serde(default)]
+    pub status: Option<String>,
+}
+
+/// Native currency of a chain list entry
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChainListCurrency {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+/// Block explorer of a chain list entry
+#[derive(Debug, Clone, Deserialize)]
+pub struct ChainListExplorer {
+    #[serde(default)]
+    pub name: String,
+
+    pub url: String,
+
+    /// Usually "EIP3091" (standard /tx/ and /address/ paths) or "none"
+    #[serde(default)]
+    pub standard: Option<String>,
+}
+
+impl ChainListEntry {
+    /// Convert to a network configuration
+    ///
+    /// Returns `None` for deprecated chains and chains without a usable public
+    /// HTTP(S) RPC (templated URLs such as `${INFURA_API_KEY}` are skipped).
+    pub fn to_network_config(&self) -> Option<EvmNetworkConfig> {
+        if self.status.as_deref() == Some("deprecated") {
+            return None;
+        }
+
+        let rpc_url = self
+            .rpc
+            .iter()
+            .find(|url| {
+                (url.starts_with("https://") || url.starts_with("http://")) && !url.contains("${")
+            })?
+            .clone();
+
+        let explorer_url = self
+            .explorers
+            .iter()
+            .find(|e| e.standard.as_deref() == Some("EIP3091"))
+            .or_else(|| self.explorers.first())
+            .map(|e| e.url.trim_end_matches('/').to_string());
+
+        let featured = FEATURED.iter().find(|(id, _, _)| *id == self.chain_id);
+
+        let id = match featured {
+            Some((_, id, _)) => id.to_string(),
+            None if !self.short_name.is_empty() => self.short_name.to_lowercase(),
+            None => format!("chain-{}", self.chain_id),
+        };
+
+        let lower_name = self.name.to_lowercase();
+        let is_testnet = !self.faucets.is_empty()
+            || TESTNET_MARKERS.iter().any(|m| lower_name.contains(m));
+
+        Some(EvmNetworkConfig {
+            id,
+            name: self.name.clone(),
+            chain_id: self.chain_id,
+            rpc_url,
+            explorer_url,
+            explorer_api_url: featured.and_then(|(_, _, api)| api.map(|s| s.to_string())),
+            native_symbol: self.native_currency.symbol.clone(),
+            native_name: self.native_currency.name.clone(),
+            decimals: self.native_currency.decimals,
+            is_testnet,
+        })
+    }
+}
+
+// ============================================================================
+// Registry
+// ============================================================================
+
+/// Registry of known EVM networks
+#[derive(Debug, Clone, Default)]
+pub struct NetworkRegistry {
+    networks: Vec<EvmNetworkConfig>,
+}
+
+impl NetworkRegistry {
+    /// Parse a registry from `chains.json` content
+    ///
+    /// Entries that cannot be used (deprecated, no public RPC) are skipped.
+    /// If a chain ID appears more than once, the first entry wins.
+    pub fn from_chains_json(json: &str) -> Result<Self, WalletError> {
+        let entries: Vec<ChainListEntry> = serde_json::from_str(json)
+            .map_err(|e| WalletError::InvalidNetwork(format!("Invalid chains.json: {}", e)))?;
+
+        let mut registry = Self::default();
+        for network in entries.iter().filter_map(ChainListEntry::to_network_config) {
+            registry.insert_if_missing(network);
+        }
+        Ok(registry)
+    }
+
+    /// Registry built from the bundled chain list
+    pub fn bundled() -> Self {
+        // The bundled list is covered by tests; an empty registry is the safe fallback
+        Self::from_chains_json(BUNDLED_CHAINS_JSON).unwrap_or_else(|e| {
+            eprintln!("[Registry] Failed to parse bundled chain list: {}", e);
+            Self::default()
+        })
+    }
+
+    /// Merge another registry into this one
+    ///
+    /// Chains already present are kept as-is, so the curated bundled entries
+    /// take precedence over a full imported list.
+    pub fn merge(&mut self, other: NetworkRegistry) {
+        for network in other.networks {
+            self.insert_if_missing(network);
+        }
+    }
+
+    fn insert_if_missing(&mut self, network: EvmNetworkConfig) {
+        if !self.networks.iter().any(|n| n.chain_id == network.chain_id) {
+            self.networks.push(network);
+        }
+    }
+
+    /// Number of networks in the registry
+    pub fn len(&self) -> usize {
+        self.networks.len()
+    }
+
+    /// Whether the registry is empty
+    pub fn is_empty(&self) -> bool {
+        self.networks.is_empty()
+    }
+
+    /// Featured (predefined) networks, in display order
+    pub fn featured(&self) -> Vec<EvmNetworkConfig> {
+        FEATURED
+            .iter()
+            .filter_map(|(chain_id, _, _)| self.get_by_chain_id(*chain_id))
+            .collect()
+    }
+
+    /// Whether a chain ID is one of the featured networks
+    pub fn is_featured(&self, chain_id: u64) -> bool {
+        FEATURED.iter().any(|(id, _, _)| *id == chain_id)
+    }
+
+    /// Look up a network by chain ID
+    pub fn get_by_chain_id(&self, chain_id: u64) -> Option<EvmNetworkConfig> {
+        self.networks.iter().find(|n| n.chain_id == chain_id).cloned()
+    }
+
+    /// Look up a network by ID
+    pub fn get_by_id(&self, id: &str) -> Option<EvmNetworkConfig> {
+        self.networks.iter().find(|n| n.id == id).cloned()
+    }
+
+    /// Search networks by name, ID, native symbol or exact chain ID
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - Case-insensitive search text
+    /// * `include_testnets` - Whether testnets are included in the results
+    /// * `limit` - Maximum number of results
+    pub fn search(
+        &self,
+        query: &str,
+        include_testnets: bool,
+        limit: usize,
+    ) -> Vec<EvmNetworkConfig> {
+        let query = query.trim().to_lowercase();
+        let chain_id = query.parse::<u64>().ok();
+
+        self.networks
+            .iter()
+            .filter(|n| include_testnets || !n.is_testnet)
+            .filter(|n| {
+                query.is_empty()
+                    || Some(n.chain_id) == chain_id
+                    || n.name.to_lowercase().contains(&query)
+                    || n.id.contains(&query)
+                    || n.native_symbol.to_lowercase() == query
+            })
+            .take(limit)
+            .cloned()
+            .collect()
+    }
+}
+
+// ============================================================================
+// Global Registry
+// ============================================================================
+
+lazy_static! {
+    static ref REGISTRY: RwLock<Arc<NetworkRegistry>> = RwLock::new(Arc::new(load_initial()));
+}
+
+/// Bundled registry merged with the user's `chains.json` (if present)
+fn load_initial() -> NetworkRegistry {
+    let mut registry = NetworkRegistry::bundled();
+
+    if let Some(path) = user_registry_path().filter(|p| p.exists()) {
+        match std::fs::read_to_string(&path)
+            .map_err(|e| WalletError::InternalError(e.to_string()))
+            .and_then(|json| NetworkRegistry::from_chains_json(&json))
+        {
+            Ok(user) => registry.merge(user),
+            Err(e) => eprintln!(
+                "[Registry] Ignoring invalid chain list at {}: {}",
+                path.display(),
+                e
+            ),
+        }
+    }
+
+    registry
+}
+
+/// Path of the user-supplied chain list (`<data dir>/vaughan/chains.json`)
+pub fn user_registry_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("vaughan").join(USER_CHAINS_FILE))
+}
+
+/// Get the current network registry
+pub fn registry() -> Arc<NetworkRegistry> {
+    REGISTRY
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+}
+
+/// Import a `chains.json` file into the registry
+///
+/// The file is validated, copied into the app data directory so it is loaded
+/// on next start, and merged into the live registry.
+///
+/// # Arguments
+///
+/// * `path` - Path to a chains.json file (ethereum-lists format)
+///
+/// # Returns
+///
+/// * `Ok(usize)` - Total number of networks in the registry
+/// * `Err(WalletError)` - File unreadable or not in chains.json format
+pub fn import_registry_file(path: &Path) -> Result<usize, WalletError> {
+    let json = std::fs::read_to_string(path).map_err(|e| {
+        WalletError::InternalError(format!("Failed to read {}: {}", path.display(), e))
+    })?;
+    let imported = NetworkRegistry::from_chains_json(&json)?;
+
+    let target = user_registry_path().ok_or_else(|| {
+        WalletError::InternalError("Failed to resolve app data directory".to_string())
+    })?;
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| {
+            WalletError::InternalError(format!("Failed to create data directory: {}", e))
+        })?;
+    }
+    std::fs::write(&target, &json).map_err(|e| {
+        WalletError::InternalError(format!("Failed to save chain list: {}", e))
+    })?;
+
+    let mut registry = NetworkRegistry::bundled();
+    registry.merge(imported);
+    let total = registry.len();
+
+    *REGISTRY
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Arc::new(registry);
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"[
+        {
+            "name": "Example Chain",
+            "chain": "EXC",
+            "rpc": ["https://rpc.example.com/${API_KEY}", "wss://ws.example.com", "https://rpc.example.com"],
+            "faucets": [],
+            "nativeCurrency": { "name": "Example", "symbol": "EXC", "decimals": 18 },
+            "infoURL": "https://example.com",
+            "shortName": "EXC",
+            "chainId": 424242,
+            "networkId": 424242,
+            "explorers": [{ "name": "exscan", "url": "https://scan.example.com/", "standard": "EIP3091" }]
+        },
+        {
+            "name": "Old Chain",
+            "chain": "OLD",
+            "rpc": ["https://rpc.old.example.com"],
+            "nativeCurrency": { "name": "Old", "symbol": "OLD", "decimals": 18 },
+            "shortName": "old",
+            "chainId": 434343,
+            "status": "deprecated"
+        },
+        {
+            "name": "Example Testnet",
+            "chain": "EXC",
+            "rpc": ["https://testnet.example.com"],
+            "faucets": ["https://faucet.example.com"],
+            "nativeCurrency": { "name": "Test Example", "symbol": "tEXC", "decimals": 18 },
+            "shortName": "texc",
+            "chainId": 424243
+        },
+        {
+            "name": "Ethereum Mainnet",
+            "chain": "ETH",
+            "rpc": ["https://ethereum.example.com"],
+            "nativeCurrency": { "name": "Ether", "symbol": "ETH", "decimals": 18 },
+            "shortName": "eth",
+            "chainId": 1
+        }
+    ]"#;
+
+    #[test]
+    fn test_bundled_registry_has_featured_networks() {
+        let registry = NetworkRegistry::bundled();
+        let featured = registry.featured();
+
+        assert_eq!(featured.len(), FEATURED.len());
+        assert_eq!(featured[0].id, "ethereum");
+        assert_eq!(featured[0].rpc_url, "https://eth.llamarpc.com");
+        assert!(registry.len() > featured.len());
+    }
+
+    #[test]
+    fn test_parse_chain_list_entry() {
+        let registry = NetworkRegistry::from_chains_json(SAMPLE).unwrap();
+        let network = registry.get_by_chain_id(424242).unwrap();
+
+        // Templated and websocket RPCs are skipped
+        assert_eq!(network.rpc_url, "https://rpc.example.com");
+        assert_eq!(network.id, "exc");
+        assert_eq!(network.explorer_url.as_deref(), Some("https://scan.example.com"));
+        assert!(!network.is_testnet);
+    }
+
+    #[test]
+    fn test_deprecated_chains_skipped() {
+        let registry = NetworkRegistry::from_chains_json(SAMPLE).unwrap();
+        assert!(registry.get_by_chain_id(434343).is_none());
+    }
+
+    #[test]
+    fn test_testnet_detection() {
+        let registry = NetworkRegistry::from_chains_json(SAMPLE).unwrap();
+        assert!(registry.get_by_chain_id(424243).unwrap().is_testnet);
+        assert!(registry.get_by_id("sepolia").is_none());
+        assert!(NetworkRegistry::bundled().get_by_id("sepolia").unwrap().is_testnet);
+    }
+
+    #[test]
+    fn test_merge_keeps_bundled_entries() {
+        let mut registry = NetworkRegistry::bundled();
+        let before = registry.len();
+        registry.merge(NetworkRegistry::from_chains_json(SAMPLE).unwrap());
+
+        // Ethereum keeps the curated RPC; new chains are added
+        assert_eq!(
+            registry.get_by_chain_id(1).unwrap().rpc_url,
+            "https://eth.llamarpc.com"
+        );
+        assert_eq!(registry.len(), before + 2);
+    }
+
+    #[test]
+    fn test_search() {
+        let registry = NetworkRegistry::bundled();
+
+        assert!(registry
+            .search("pulse", true, 10)
+            .iter()
+            .any(|n| n.chain_id == 369));
+        assert_eq!(registry.search("8453", false, 10)[0].id, "base");
+
+        // Testnets filtered out unless requested
+        assert!(registry.search("sepolia", false, 10).is_empty());
+        assert!(!registry.search("sepolia", true, 10).is_empty());
+
+        assert_eq!(registry.search("", true, 3).len(), 3);
+    }
+}
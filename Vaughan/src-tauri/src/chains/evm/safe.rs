@@ -0,0 +1,280 @@
+// ============================================================================
+// Vaughan Wallet - Safe (Gnosis Safe) Multisig
+// ============================================================================
+//
+// Safe transactions are signed off-chain by the owners and executed with
+// `execTransaction` once `threshold` signatures are collected.
+//
+// - The signed message is the EIP-712 hash of `SafeTx` under the domain
+//   `EIP712Domain(uint256 chainId,address verifyingContract)` (Safe >= 1.3).
+// - Owner signatures are plain ECDSA over that hash (`v` = 27/28), packed
+//   as `r ++ s ++ v` and concatenated in ascending owner address order.
+// - Owners sign the full typed data (`safe_tx_typed_data`), so external
+//   signers that refuse raw hashes can sign too.
+//
+// ============================================================================
+
+use alloy::primitives::{Address, Bytes, Signature, B256, U256};
+use alloy::providers::RootProvider;
+use alloy::sol;
+use alloy::sol_types::{Eip712Domain, SolCall, SolStruct};
+use alloy_dyn_abi::TypedData;
+
+use crate::error::WalletError;
+
+sol! {
+    /// Safe transaction (EIP-712 struct signed by the owners)
+    #[derive(Debug, PartialEq, serde::Serialize)]
+    struct SafeTx {
+        address to;
+        uint256 value;
+        bytes data;
+        uint8 operation;
+        uint256 safeTxGas;
+        uint256 baseGas;
+        uint256 gasPrice;
+        address gasToken;
+        address refundReceiver;
+        uint256 nonce;
+    }
+
+    #[sol(rpc)]
+    contract GnosisSafe {
+        function getOwners() external view returns (address[] memory);
+        function getThreshold() external view returns (uint256);
+        function nonce() external view returns (uint256);
+        function VERSION() external view returns (string memory);
+        function getTransactionHash(
+            address to,
+            uint256 value,
+            bytes calldata data,
+            uint8 operation,
+            uint256 safeTxGas,
+            uint256 baseGas,
+            uint256 gasPrice,
+            address gasToken,
+            address refundReceiver,
+            uint256 _nonce
+        ) external view returns (bytes32);
+        function execTransaction(
+            address to,
+            uint256 value,
+            bytes calldata data,
+            uint8 operation,
+            uint256 safeTxGas,
+            uint256 baseGas,
+            uint256 gasPrice,
+            address gasToken,
+            address refundReceiver,
+            bytes memory signatures
+        ) external payable returns (bool success);
+    }
+}
+
+/// `operation` value for a regular call
+pub const OPERATION_CALL: u8 = 0;
+
+/// `operation` value for a delegatecall
+pub const OPERATION_DELEGATE_CALL: u8 = 1;
+
+/// On-chain Safe state
+#[derive(Debug, Clone, PartialEq)]
+pub struct SafeState {
+    pub owners: Vec<Address>,
+    pub threshold: u64,
+    pub nonce: U256,
+    pub version: String,
+}
+
+impl SafeTx {
+    /// Plain call with no gas refund
+    pub fn call(to: Address, value: U256, data: Bytes, nonce: U256) -> Self {
+        Self {
+            to,
+            value,
+            data,
+            operation: OPERATION_CALL,
+            safeTxGas: U256::ZERO,
+            baseGas: U256::ZERO,
+            gasPrice: U256::ZERO,
+            gasToken: Address::ZERO,
+            refundReceiver: Address::ZERO,
+            nonce,
+        }
+    }
+}
+
+/// EIP-712 domain of a Safe (>= 1.3)
+pub fn safe_domain(chain_id: u64, safe: Address) -> Eip712Domain {
+    Eip712Domain::new(None, None, Some(U256::from(chain_id)), Some(safe), None)
+}
+
+/// Hash the owners sign (`getTransactionHash`)
+pub fn safe_tx_hash(chain_id: u64, safe: Address, tx: &SafeTx) -> B256 {
+    tx.eip712_signing_hash(&safe_domain(chain_id, safe))
+}
+
+/// SafeTx as EIP-712 typed data (signing hash is `safe_tx_hash`)
+pub fn safe_tx_typed_data(chain_id: u64, safe: Address, tx: &SafeTx) -> TypedData {
+    TypedData::from_struct(tx, Some(safe_domain(chain_id, safe)))
+}
+
+/// Owner that produced a signature over a Safe transaction hash
+pub fn recover_owner(hash: B256, signature: &[u8]) -> Result<Address, WalletError> {
+    let signature = Signature::from_raw(signature)
+        .map_err(|e| WalletError::SigningFailed(format!("Invalid signature: {}", e)))?;
+    signature
+        .recover_address_from_prehash(&hash)
+        .map_err(|e| WalletError::SigningFailed(format!("Invalid signature: {}", e)))
+}
+
+/// Concatenate owner signatures in ascending owner order, as Safe requires
+pub fn encode_signatures(signatures: &[(Address, Bytes)]) -> Bytes {
+    let mut sorted: Vec<&(Address, Bytes)> = signatures.iter().collect();
+    sorted.sort_by_key(|(owner, _)| *owner);
+    sorted
+        .into_iter()
+        .flat_map(|(_, sig)| sig.iter().copied())
+        .collect::<Vec<u8>>()
+        .into()
+}
+
+/// `execTransaction` calldata
+pub fn exec_transaction_call_data(tx: &SafeTx, signatures: Bytes) -> Bytes {
+    GnosisSafe::execTransactionCall {
+        to: tx.to,
+        value: tx.value,
+        data: tx.data.clone(),
+        operation: tx.operation,
+        safeTxGas: tx.safeTxGas,
+        baseGas: tx.baseGas,
+        gasPrice: tx.gasPrice,
+        gasToken: tx.gasToken,
+        refundReceiver: tx.refundReceiver,
+        signatures,
+    }
+    .abi_encode()
+    .into()
+}
+
+/// Read owners, threshold, nonce and version of a Safe
+///
+/// Fails if the address is not a Safe.
+pub async fn fetch_state(provider: &RootProvider, safe: Address) -> Result<SafeState, WalletError> {
+    let contract = GnosisSafe::new(safe, provider);
+    let not_a_safe = |e: alloy::contract::Error| {
+        WalletError::InvalidAddress(format!("{} is not a Safe: {}", safe, e))
+    };
+
+    let owners = contract.getOwners().call().await.map_err(not_a_safe)?;
+    let threshold = contract.getThreshold().call().await.map_err(not_a_safe)?;
+    let nonce = contract.nonce().call().await.map_err(not_a_safe)?;
+    let version = contract.VERSION().call().await.unwrap_or_default();
+
+    Ok(SafeState {
+        owners,
+        threshold: threshold.saturating_to(),
+        nonce,
+        version,
+    })
+}
+
+/// `getTransactionHash` as computed by the Safe itself
+pub async fn fetch_tx_hash(
+    provider: &RootProvider,
+    safe: Address,
+    tx: &SafeTx,
+) -> Result<B256, WalletError> {
+    GnosisSafe::new(safe, provider)
+        .getTransactionHash(
+            tx.to,
+            tx.value,
+            tx.data.clone(),
+            tx.operation,
+            tx.safeTxGas,
+            tx.baseGas,
+            tx.gasPrice,
+            tx.gasToken,
+            tx.refundReceiver,
+            tx.nonce,
+        )
+        .call()
+        .await
+        .map_err(|e| WalletError::RpcError(format!("Safe getTransactionHash: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::b256;
+    use alloy::signers::local::PrivateKeySigner;
+    use alloy::signers::SignerSync;
+
+    #[test]
+    fn test_type_hashes_match_safe_contract() {
+        // DOMAIN_SEPARATOR_TYPEHASH and SAFE_TX_TYPEHASH from Safe.sol (1.3+)
+        let domain = safe_domain(1, Address::repeat_byte(0x11));
+        assert_eq!(
+            domain.type_hash(),
+            b256!("47e79534a245952e8b16893a336b85a3d9ea9fa8c573f3d803afb92a79469218")
+        );
+
+        let tx = SafeTx::call(Address::ZERO, U256::ZERO, Bytes::new(), U256::ZERO);
+        assert_eq!(
+            tx.eip712_type_hash(),
+            b256!("bb8310d486368db6bd6f849402fdd73ad53d316b5a4b2644ad6efe0f941286d8")
+        );
+    }
+
+    #[test]
+    fn test_hash_binds_safe_and_chain() {
+        let safe = Address::repeat_byte(0x11);
+        let tx = SafeTx::call(Address::repeat_byte(0x22), U256::from(1u64), Bytes::new(), U256::ZERO);
+        let hash = safe_tx_hash(1, safe, &tx);
+        assert_ne!(hash, safe_tx_hash(10, safe, &tx));
+        assert_ne!(hash, safe_tx_hash(1, Address::repeat_byte(0x33), &tx));
+    }
+
+    #[test]
+    fn test_typed_data_hash_matches_safe_tx_hash() {
+        let safe = Address::repeat_byte(0x11);
+        let tx = SafeTx::call(
+            Address::repeat_byte(0x22),
+            U256::from(5u64),
+            Bytes::from(vec![0xde, 0xad]),
+            U256::from(7u64),
+        );
+        let typed_data = safe_tx_typed_data(100, safe, &tx);
+        assert_eq!(typed_data.eip712_signing_hash().unwrap(), safe_tx_hash(100, safe, &tx));
+
+        // What an external signer receives over JSON-RPC hashes the same
+        let json = serde_json::to_string(&typed_data).unwrap();
+        let parsed: TypedData = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.eip712_signing_hash().unwrap(), safe_tx_hash(100, safe, &tx));
+    }
+
+    #[test]
+    fn test_recover_owner() {
+        let signer: PrivateKeySigner =
+            "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".parse().unwrap();
+        let hash = B256::repeat_byte(0xab);
+        let signature = signer.sign_hash_sync(&hash).unwrap();
+        let recovered = recover_owner(hash, &signature.as_bytes()).unwrap();
+        assert_eq!(recovered, signer.address());
+
+        assert!(recover_owner(hash, &[0u8; 10]).is_err());
+    }
+
+    #[test]
+    fn test_encode_signatures_sorted_by_owner() {
+        let low = Address::repeat_byte(0x01);
+        let high = Address::repeat_byte(0xff);
+        let encoded = encode_signatures(&[
+            (high, Bytes::from(vec![0xbb; 65])),
+            (low, Bytes::from(vec![0xaa; 65])),
+        ]);
+        assert_eq!(encoded.len(), 130);
+        assert_eq!(encoded[0], 0xaa);
+        assert_eq!(encoded[65], 0xbb);
+    }
+}
@@ -0,0 +1,167 @@
+// ============================================================================
+// Vaughan Wallet - Signature Verification
+// ============================================================================
+//
+// Checks that a signature was produced by an address:
+//
+// - EOAs: ECDSA recovery over the message hash (EIP-191 personal message or
+//   EIP-712 typed data digest).
+// - Contract wallets (Safe, smart accounts, ...): EIP-1271
+//   `isValidSignature(hash, signature)` must return the magic value.
+//
+// ============================================================================
+
+use alloy::primitives::{eip191_hash_message, fixed_bytes, Address, Bytes, FixedBytes, Signature, B256};
+use alloy::providers::{Provider, RootProvider};
+use alloy::sol;
+use serde::Serialize;
+use specta::Type;
+
+use crate::error::WalletError;
+
+/// `bytes4(keccak256("isValidSignature(bytes32,bytes)"))`
+pub const EIP1271_MAGIC_VALUE: FixedBytes<4> = fixed_bytes!("1626ba7e");
+
+sol! {
+    #[sol(rpc)]
+    contract IERC1271 {
+        function isValidSignature(bytes32 hash, bytes memory signature) external view returns (bytes4 magicValue);
+    }
+}
+
+/// How a signature was verified
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum VerificationMethod {
+    /// Recovered signer matches the address
+    Ecdsa,
+    /// Contract at the address accepted it via EIP-1271
+    Eip1271,
+    /// Not valid for the address
+    None,
+}
+
+/// Outcome of a signature check
+#[derive(Debug, Clone, PartialEq, Serialize, Type)]
+pub struct SignatureVerification {
+    /// Address the signature was checked against
+    pub address: String,
+    /// Signer recovered by ECDSA (`null` if the signature is not ECDSA)
+    pub recovered_signer: Option<String>,
+    pub valid: bool,
+    pub method: VerificationMethod,
+    /// Digest the signature was checked over
+    pub hash: String,
+}
+
+/// EIP-191 hash of a personal message
+pub fn personal_message_hash(message: &[u8]) -> B256 {
+    eip191_hash_message(message)
+}
+
+/// Signer of a 65-byte ECDSA signature over `hash`, if it is one
+pub fn recover_signer(hash: B256, signature: &[u8]) -> Option<Address> {
+    Signature::from_raw(signature)
+        .ok()?
+        .recover_address_from_prehash(&hash)
+        .ok()
+}
+
+/// Ask a contract whether it accepts `signature` for `hash` (EIP-1271)
+///
+/// Reverts and empty returns count as "not valid".
+pub async fn is_valid_signature_1271(
+    provider: &RootProvider,
+    contract: Address,
+    hash: B256,
+    signature: &[u8],
+) -> bool {
+    IERC1271::new(contract, provider)
+        .isValidSignature(hash, Bytes::copy_from_slice(signature))
+        .call()
+        .await
+        .map(|magic| magic == EIP1271_MAGIC_VALUE)
+        .unwrap_or(false)
+}
+
+/// Verify a signature over `hash` for `address`
+///
+/// Tries ECDSA recovery first and falls back to EIP-1271 when the address
+/// has code. `provider` may be `None` to check ECDSA only.
+pub async fn verify_hash(
+    provider: Option<&RootProvider>,
+    address: Address,
+    hash: B256,
+    signature: &[u8],
+) -> Result<SignatureVerification, WalletError> {
+    let recovered = recover_signer(hash, signature);
+
+    let method = if recovered == Some(address) {
+        VerificationMethod::Ecdsa
+    } else {
+        match provider {
+            Some(provider) => {
+                let code = provider
+                    .get_code_at(address)
+                    .await
+                    .map_err(|e| WalletError::RpcError(e.to_string()))?;
+                if !code.is_empty() && is_valid_signature_1271(provider, address, hash, signature).await {
+                    VerificationMethod::Eip1271
+                } else {
+                    VerificationMethod::None
+                }
+            }
+            None => VerificationMethod::None,
+        }
+    };
+
+    Ok(SignatureVerification {
+        address: format!("{:?}", address),
+        recovered_signer: recovered.map(|a| format!("{:?}", a)),
+        valid: method != VerificationMethod::None,
+        method,
+        hash: format!("{:?}", hash),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::signers::local::PrivateKeySigner;
+    use alloy::signers::SignerSync;
+
+    fn signer() -> PrivateKeySigner {
+        "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_verify_personal_message() {
+        let signer = signer();
+        let signature = signer.sign_message_sync(b"I own this address").unwrap();
+        let hash = personal_message_hash(b"I own this address");
+
+        let result = verify_hash(None, signer.address(), hash, &signature.as_bytes()).await.unwrap();
+        assert!(result.valid);
+        assert_eq!(result.method, VerificationMethod::Ecdsa);
+        assert_eq!(result.recovered_signer, Some(format!("{:?}", signer.address())));
+    }
+
+    #[tokio::test]
+    async fn test_verify_wrong_address() {
+        let signer = signer();
+        let signature = signer.sign_message_sync(b"hello").unwrap();
+        let hash = personal_message_hash(b"hello");
+
+        let result = verify_hash(None, Address::repeat_byte(0x11), hash, &signature.as_bytes())
+            .await
+            .unwrap();
+        assert!(!result.valid);
+        assert_eq!(result.method, VerificationMethod::None);
+        assert!(result.recovered_signer.is_some());
+    }
+
+    #[test]
+    fn test_recover_signer_rejects_garbage() {
+        assert_eq!(recover_signer(B256::ZERO, &[1, 2, 3]), None);
+    }
+}
@@ -19,8 +19,8 @@ use alloy::rpc::types::eth::TransactionRequest;
 
 // Re-export all types for convenience
 pub use types::{
-    Balance, ChainInfo, ChainTransaction, ChainType, EvmTransaction, Fee, Signature, TokenInfo,
-    TxHash, TxRecord, TxStatus,
+    Balance, ChainInfo, ChainTransaction, ChainType, EvmAuthorization, EvmTransaction, Fee,
+    Signature, TokenInfo, TxHash, TxRecord, TxStatus,
 };
 
 /// Network identifier type (e.g. "ethereum-mainnet")
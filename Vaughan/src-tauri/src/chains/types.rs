@@ -209,6 +209,35 @@ pub struct EvmTransaction {
 
     /// Chain ID
     pub chain_id: u64,
+
+    /// Signed EIP-7702 authorizations (makes this a type-4 set-code transaction)
+    #[serde(default)]
+    pub authorization_list: Option<Vec<EvmAuthorization>>,
+}
+
+/// Signed EIP-7702 authorization tuple
+///
+/// Delegates the authority's account code to `address` on `chain_id`
+/// (0 = any chain). Delegating to the zero address clears the delegation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct EvmAuthorization {
+    /// Chain ID the authorization is valid on (0 = all chains)
+    pub chain_id: u64,
+
+    /// Delegate contract address
+    pub address: String,
+
+    /// Authority's account nonce at the time of inclusion
+    pub nonce: u64,
+
+    /// Signature parity (0 or 1)
+    pub y_parity: u8,
+
+    /// Signature `r` (hex)
+    pub r: String,
+
+    /// Signature `s` (hex)
+    pub s: String,
 }
 
 /// Stellar transaction (placeholder for future implementation)
@@ -358,6 +387,13 @@ pub struct Fee {
 
     /// Gas price (for EVM chains)
     pub gas_price: Option<String>,
+
+    /// L1 data fee portion of `amount` (for rollups, in smallest unit)
+    pub l1_fee: Option<String>,
+
+    /// The rollup's L1 data fee could not be looked up; `amount` covers
+    /// L2 execution only
+    pub l1_fee_unavailable: bool,
 }
 
 impl Fee {
@@ -369,6 +405,8 @@ impl Fee {
             usd_value: None,
             gas_limit: None,
             gas_price: None,
+            l1_fee: None,
+            l1_fee_unavailable: false,
         }
     }
 
@@ -384,6 +422,18 @@ impl Fee {
         self.gas_price = Some(gas_price);
         self
     }
+
+    /// Add the L1 data fee portion (for rollups)
+    pub fn with_l1_fee(mut self, l1_fee: String) -> Self {
+        self.l1_fee = Some(l1_fee);
+        self
+    }
+
+    /// Mark the L1 data fee as unknown (for rollups whose fee lookup failed)
+    pub fn with_l1_fee_unavailable(mut self) -> Self {
+        self.l1_fee_unavailable = true;
+        self
+    }
 }
 
 // ============================================================================
@@ -0,0 +1,142 @@
+//! Address Book Commands
+//!
+//! Tauri commands for managing named contacts.
+//!
+//! ## Commands
+//!
+//! - `get_contacts` - List contacts (optionally those for one chain)
+//! - `add_contact` - Add a contact
+//! - `update_contact` - Edit a contact
+//! - `delete_contact` - Remove a contact
+//! - `import_contacts_csv` - Import contacts from CSV (pasted or a file)
+//! - `export_contacts_csv` - Export all contacts as CSV
+//!
+//! Contact names are used as labels in history, dApp transaction approvals
+//! and send validation (`known_contact`).
+
+use crate::core::{ContactImportResult, ContactInput};
+use crate::error::WalletError;
+use crate::models::contact::Contact;
+use crate::state::VaughanState;
+use serde::Deserialize;
+use specta::Type;
+use tauri::State;
+
+/// Contact fields
+#[derive(Debug, Deserialize, Type)]
+pub struct ContactRequest {
+    pub name: String,
+    pub address: String,
+    /// Chain the contact applies to (`null` = all chains)
+    pub chain_id: Option<u64>,
+    #[serde(default)]
+    pub notes: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl From<ContactRequest> for ContactInput {
+    fn from(request: ContactRequest) -> Self {
+        Self {
+            name: request.name,
+            address: request.address,
+            chain_id: request.chain_id,
+            notes: request.notes,
+            tags: request.tags,
+        }
+    }
+}
+
+/// List contacts sorted by name
+///
+/// # Arguments
+///
+/// * `chain_id` - Only contacts that apply to this chain (optional)
+#[tauri::command]
+#[specta::specta]
+pub async fn get_contacts(
+    state: State<'_, VaughanState>,
+    chain_id: Option<u64>,
+) -> Result<Vec<Contact>, WalletError> {
+    Ok(state.contacts(chain_id))
+}
+
+/// Add a contact
+///
+/// # Example
+///
+/// ```typescript
+/// const contact = await invoke('add_contact', {
+///   request: { name: 'Alice', address: '0x...', chainId: null, notes: '', tags: ['team'] }
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn add_contact(
+    state: State<'_, VaughanState>,
+    request: ContactRequest,
+) -> Result<Contact, WalletError> {
+    state.add_contact(request.into())
+}
+
+/// Edit a contact
+#[tauri::command]
+#[specta::specta]
+pub async fn update_contact(
+    state: State<'_, VaughanState>,
+    id: String,
+    request: ContactRequest,
+) -> Result<Contact, WalletError> {
+    state.update_contact(&id, request.into())
+}
+
+/// Remove a contact
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_contact(state: State<'_, VaughanState>, id: String) -> Result<(), WalletError> {
+    state.delete_contact(&id)
+}
+
+/// Import contacts from CSV (exactly one of `csv` / `file_path`)
+///
+/// Expects a header with `name` and `address` (and optionally `chain_id`,
+/// `notes`, `tags`). Existing contacts with the same address and chain
+/// scope are updated; bad rows are reported in `errors`.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_contacts_csv(
+    state: State<'_, VaughanState>,
+    csv: Option<String>,
+    file_path: Option<String>,
+) -> Result<ContactImportResult, WalletError> {
+    let csv = match (csv, file_path) {
+        (Some(csv), None) => csv,
+        (None, Some(path)) => std::fs::read_to_string(&path)
+            .map_err(|e| WalletError::Custom(format!("Cannot read {}: {}", path, e)))?,
+        _ => {
+            return Err(WalletError::Custom(
+                "Provide either the CSV or a file path".to_string(),
+            ))
+        }
+    };
+    state.import_contacts_csv(&csv)
+}
+
+/// Export all contacts as CSV
+///
+/// # Arguments
+///
+/// * `file_path` - Also write the CSV to this file (optional)
+#[tauri::command]
+#[specta::specta]
+pub async fn export_contacts_csv(
+    state: State<'_, VaughanState>,
+    file_path: Option<String>,
+) -> Result<String, WalletError> {
+    let csv = state.export_contacts_csv();
+    if let Some(path) = file_path {
+        std::fs::write(&path, &csv)
+            .map_err(|e| WalletError::Custom(format!("Cannot write {}: {}", path, e)))?;
+    }
+    Ok(csv)
+}
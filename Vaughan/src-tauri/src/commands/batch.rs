@@ -0,0 +1,131 @@
+//! Batch Payout Commands
+//!
+//! Tauri commands for paying many recipients from a CSV.
+//!
+//! ## Commands
+//!
+//! - `create_batch_job` - Validate a CSV and create a batch job
+//! - `get_batch_jobs` - List batch jobs
+//! - `get_batch_job` - Batch job with its per-row report
+//! - `run_batch_job` - Send (or resume) a batch
+//! - `refresh_batch_job` - Update sent rows from their receipts
+//! - `delete_batch_job` - Forget a batch job
+//!
+//! `run_batch_job` emits `batch_progress` (the updated job) to the calling
+//! window after every transaction.
+
+use crate::core::{BatchJob, BatchMode};
+use crate::error::WalletError;
+use crate::state::VaughanState;
+use alloy::primitives::Address;
+use serde::Deserialize;
+use specta::Type;
+use tauri::{Emitter, State, Window};
+
+/// Batch creation request (exactly one of `csv` / `file_path`)
+#[derive(Debug, Deserialize, Type)]
+pub struct CreateBatchJobRequest {
+    /// Paying account
+    pub from: String,
+    /// `recipient,amount[,token]` rows
+    pub csv: Option<String>,
+    /// Path to a CSV file
+    pub file_path: Option<String>,
+    pub mode: BatchMode,
+}
+
+/// Create a batch payout job on the active network
+///
+/// Invalid rows are reported on the returned job with an error each; a
+/// job with invalid rows can't be run.
+///
+/// # Errors
+///
+/// * `WalletError::InsufficientBalance` - Totals plus gas exceed a balance
+/// * `WalletError::UnsupportedNetwork` - Disperse mode on a chain without Disperse
+///
+/// # Example
+///
+/// ```typescript
+/// const job = await invoke('create_batch_job', {
+///   request: { from: '0x...', csv: 'recipient,amount\n0x...,1.5', filePath: null, mode: 'sequential' }
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn create_batch_job(
+    state: State<'_, VaughanState>,
+    request: CreateBatchJobRequest,
+) -> Result<BatchJob, WalletError> {
+    let from: Address = request
+        .from
+        .parse()
+        .map_err(|_| WalletError::InvalidAddress(request.from.clone()))?;
+
+    let csv = match (request.csv, request.file_path) {
+        (Some(csv), None) => csv,
+        (None, Some(path)) => std::fs::read_to_string(&path)
+            .map_err(|e| WalletError::Custom(format!("Cannot read {}: {}", path, e)))?,
+        _ => {
+            return Err(WalletError::Custom(
+                "Provide either the CSV or a file path".to_string(),
+            ))
+        }
+    };
+
+    state.create_batch_job(from, &csv, request.mode).await
+}
+
+/// List batch jobs, newest first
+#[tauri::command]
+#[specta::specta]
+pub async fn get_batch_jobs(state: State<'_, VaughanState>) -> Result<Vec<BatchJob>, WalletError> {
+    Ok(state.batch_jobs())
+}
+
+/// Get a batch job with its per-row report
+#[tauri::command]
+#[specta::specta]
+pub async fn get_batch_job(state: State<'_, VaughanState>, id: String) -> Result<BatchJob, WalletError> {
+    state.batch_job(&id)
+}
+
+/// Send a batch, or resume it after a failure or restart
+///
+/// Rows already sent are skipped and rows that failed to send are retried.
+/// Stops at the first send error (recorded on the row).
+///
+/// # Example
+///
+/// ```typescript
+/// const unlisten = await listen('batch_progress', (e) => render(e.payload));
+/// const job = await invoke('run_batch_job', { id, password });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn run_batch_job(
+    window: Window,
+    state: State<'_, VaughanState>,
+    id: String,
+    password: String,
+) -> Result<BatchJob, WalletError> {
+    state
+        .run_batch_job(&id, &password, |job| {
+            let _ = window.emit("batch_progress", job);
+        })
+        .await
+}
+
+/// Update sent rows from their receipts (confirmed / reverted)
+#[tauri::command]
+#[specta::specta]
+pub async fn refresh_batch_job(state: State<'_, VaughanState>, id: String) -> Result<BatchJob, WalletError> {
+    state.refresh_batch_job(&id).await
+}
+
+/// Forget a batch job (sent transactions are not affected)
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_batch_job(state: State<'_, VaughanState>, id: String) -> Result<(), WalletError> {
+    state.delete_batch_job(&id).await
+}
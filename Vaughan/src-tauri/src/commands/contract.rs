@@ -0,0 +1,306 @@
+//! Contract Commands
+//!
+//! Tauri commands for interacting with arbitrary contracts from their ABI.
+//!
+//! ## Commands
+//!
+//! - `import_contract_abi` - Import an ABI (pasted JSON or a file) for a contract
+//! - `get_contract_abis` - List imported ABIs
+//! - `get_contract_functions` - Functions of an imported ABI
+//! - `remove_contract_abi` - Forget an imported ABI
+//! - `call_contract_function` - Run a read function and decode the result
+//! - `build_contract_transaction` - Build a transaction for a write function
+//!
+//! Write functions go through the regular transaction pipeline: the built
+//! transaction is sent with `send_transaction` using the returned `data`.
+
+use crate::chains::evm::abi::{self, AbiFunctionInfo, AbiValue};
+use crate::chains::ChainAdapter;
+use crate::commands::transaction::{build_transaction, BuildTransactionRequest, BuildTransactionResponse};
+use crate::error::WalletError;
+use crate::models::contract::ContractAbiEntry;
+use crate::state::VaughanState;
+use alloy::primitives::{Address, U256};
+use alloy::rpc::types::TransactionRequest;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::State;
+
+/// ABI import request (exactly one of `abi_json` / `file_path`)
+#[derive(Debug, Deserialize, Type)]
+pub struct ImportContractAbiRequest {
+    /// Contract address
+    pub address: String,
+    /// Chain ID (defaults to the active network)
+    pub chain_id: Option<u64>,
+    /// Display name (defaults to the artifact's contract name or the address)
+    pub name: Option<String>,
+    /// Pasted ABI or compiler artifact JSON
+    pub abi_json: Option<String>,
+    /// Path to an ABI or compiler artifact JSON file
+    pub file_path: Option<String>,
+}
+
+/// Imported ABI
+#[derive(Debug, Serialize, Type)]
+pub struct ContractAbiSummary {
+    pub chain_id: u64,
+    pub address: String,
+    pub name: String,
+    pub functions: Vec<AbiFunctionInfo>,
+}
+
+/// Contract function call
+#[derive(Debug, Deserialize, Type)]
+pub struct ContractCallRequest {
+    /// Contract address
+    pub address: String,
+    /// Function name, or full signature if overloaded (`mint(address,uint256)`)
+    pub function: String,
+    /// Arguments as strings, in order (arrays/tuples as `[..]` / `(..)`)
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Caller for read calls (`msg.sender`), optional
+    pub from: Option<String>,
+}
+
+/// Contract transaction request
+#[derive(Debug, Deserialize, Type)]
+pub struct ContractTransactionRequest {
+    /// Sender
+    pub from: String,
+    /// Contract address
+    pub address: String,
+    /// Function name, or full signature if overloaded
+    pub function: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Native amount to send (payable functions only)
+    pub amount: Option<String>,
+    pub gas_limit: Option<u64>,
+    pub gas_price_gwei: Option<String>,
+    #[serde(default)]
+    pub generate_access_list: bool,
+}
+
+fn parse_address(s: &str) -> Result<Address, WalletError> {
+    s.parse().map_err(|_| WalletError::InvalidAddress(s.to_string()))
+}
+
+async fn active_chain_id(state: &VaughanState) -> Result<u64, WalletError> {
+    Ok(state.current_adapter().await?.chain_id())
+}
+
+/// Import a contract ABI
+///
+/// Accepts a plain ABI array or a Hardhat/Foundry/Truffle artifact. The ABI
+/// is stored per (chain, address) and replaces any earlier import.
+///
+/// # Example
+///
+/// ```typescript
+/// const summary = await invoke('import_contract_abi', {
+///   request: { address: '0x...', chainId: null, name: 'Treasury', abiJson: pasted, filePath: null }
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn import_contract_abi(
+    state: State<'_, VaughanState>,
+    request: ImportContractAbiRequest,
+) -> Result<ContractAbiSummary, WalletError> {
+    let address = parse_address(&request.address)?;
+    let chain_id = match request.chain_id {
+        Some(id) => id,
+        None => active_chain_id(&state).await?,
+    };
+
+    let json = match (request.abi_json, request.file_path) {
+        (Some(json), None) => json,
+        (None, Some(path)) => std::fs::read_to_string(&path)
+            .map_err(|e| WalletError::Custom(format!("Cannot read {}: {}", path, e)))?,
+        _ => {
+            return Err(WalletError::Custom(
+                "Provide either the ABI JSON or a file path".to_string(),
+            ))
+        }
+    };
+
+    let parsed = abi::parse_abi(&json)?;
+    let functions = abi::list_functions(&parsed);
+    if functions.is_empty() {
+        return Err(WalletError::ParseError("ABI has no functions".to_string()));
+    }
+
+    let name = request
+        .name
+        .filter(|n| !n.trim().is_empty())
+        .or_else(|| {
+            serde_json::from_str::<serde_json::Value>(&json)
+                .ok()
+                .and_then(|v| v.get("contractName").and_then(|n| n.as_str()).map(String::from))
+        })
+        .unwrap_or_else(|| format!("{:?}", address));
+
+    let entry = ContractAbiEntry {
+        chain_id,
+        address: format!("{:?}", address),
+        name: name.clone(),
+        abi: serde_json::to_string(&parsed).map_err(|e| WalletError::InternalError(e.to_string()))?,
+    };
+    state.save_contract_abi(entry)?;
+
+    Ok(ContractAbiSummary {
+        chain_id,
+        address: format!("{:?}", address),
+        name,
+        functions,
+    })
+}
+
+/// List imported ABIs
+///
+/// # Arguments
+///
+/// * `chain_id` - Only ABIs of this chain (optional)
+#[tauri::command]
+#[specta::specta]
+pub async fn get_contract_abis(
+    state: State<'_, VaughanState>,
+    chain_id: Option<u64>,
+) -> Result<Vec<ContractAbiSummary>, WalletError> {
+    Ok(state
+        .contract_abis(chain_id)
+        .into_iter()
+        .map(|entry| ContractAbiSummary {
+            functions: abi::parse_abi(&entry.abi)
+                .map(|parsed| abi::list_functions(&parsed))
+                .unwrap_or_default(),
+            chain_id: entry.chain_id,
+            address: entry.address,
+            name: entry.name,
+        })
+        .collect())
+}
+
+/// Get the functions of a contract's imported ABI on the active network
+#[tauri::command]
+#[specta::specta]
+pub async fn get_contract_functions(
+    state: State<'_, VaughanState>,
+    address: String,
+) -> Result<Vec<AbiFunctionInfo>, WalletError> {
+    let address = parse_address(&address)?;
+    let parsed = state.contract_abi(active_chain_id(&state).await?, address)?;
+    Ok(abi::list_functions(&parsed))
+}
+
+/// Forget an imported ABI
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_contract_abi(
+    state: State<'_, VaughanState>,
+    chain_id: u64,
+    address: String,
+) -> Result<(), WalletError> {
+    state.remove_contract_abi(chain_id, parse_address(&address)?)
+}
+
+/// Call a contract function with `eth_call` and decode the result
+///
+/// Meant for `view`/`pure` functions; other functions are simulated
+/// without sending anything.
+///
+/// # Example
+///
+/// ```typescript
+/// const [balance] = await invoke('call_contract_function', {
+///   request: { address: '0x...', function: 'balanceOf', args: ['0x...'], from: null }
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn call_contract_function(
+    state: State<'_, VaughanState>,
+    request: ContractCallRequest,
+) -> Result<Vec<AbiValue>, WalletError> {
+    let address = parse_address(&request.address)?;
+    let adapter = state.current_adapter().await?;
+    let parsed = state.contract_abi(adapter.chain_id(), address)?;
+    let function = abi::find_function(&parsed, &request.function)?;
+    let data = abi::encode_call(function, &request.args)?;
+
+    let mut tx = TransactionRequest::default();
+    tx.to = Some(address.into());
+    tx.input.input = Some(data);
+    if let Some(from) = request.from.as_deref() {
+        tx.from = Some(parse_address(from)?);
+    }
+
+    let result = adapter.call(tx).await?;
+    abi::decode_output(function, &result)
+}
+
+/// Build a transaction calling a contract write function
+///
+/// Encodes the call and runs it through `build_transaction` (gas, fees,
+/// nonce). Send it with `send_transaction` using the returned `data`.
+///
+/// # Example
+///
+/// ```typescript
+/// const tx = await invoke('build_contract_transaction', {
+///   request: { from: '0x...', address: '0x...', function: 'pause', args: [], amount: null }
+/// });
+/// await invoke('send_transaction', {
+///   request: { from: tx.from, to: tx.to, amount: '0', data: tx.data, password }
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn build_contract_transaction(
+    state: State<'_, VaughanState>,
+    request: ContractTransactionRequest,
+) -> Result<BuildTransactionResponse, WalletError> {
+    let address = parse_address(&request.address)?;
+    let adapter = state.current_adapter().await?;
+    let parsed = state.contract_abi(adapter.chain_id(), address)?;
+    let function = abi::find_function(&parsed, &request.function)?;
+
+    if abi::is_read_only(function) {
+        return Err(WalletError::InvalidTransaction(format!(
+            "{} is read-only; call it instead",
+            function.signature()
+        )));
+    }
+
+    let amount = request
+        .amount
+        .filter(|a| !a.trim().is_empty())
+        .unwrap_or_else(|| "0".to_string());
+    let value = crate::chains::evm::utils::parse_eth_to_wei(&amount, adapter.native_decimals())?;
+    if value > U256::ZERO && function.state_mutability != alloy::json_abi::StateMutability::Payable {
+        return Err(WalletError::InvalidTransaction(format!(
+            "{} is not payable",
+            function.signature()
+        )));
+    }
+
+    let data = abi::encode_call(function, &request.args)?;
+    build_transaction(
+        state,
+        BuildTransactionRequest {
+            from: request.from,
+            to: format!("{:?}", address),
+            amount,
+            gas_limit: request.gas_limit,
+            gas_price_gwei: request.gas_price_gwei,
+            nonce: None,
+            token_address: None,
+            data: Some(hex::encode(&data)),
+            generate_access_list: request.generate_access_list,
+        },
+    )
+    .await
+    .map_err(WalletError::InvalidTransaction)
+}
@@ -364,6 +364,7 @@ pub async fn dapp_request(
                 WalletError::InvalidParams => (-32602, "Invalid params".to_string()),
                 WalletError::InvalidAddress(_) => (-32602, "Invalid address".to_string()),
                 WalletError::WalletLocked => (4100, "Wallet is locked".to_string()),
+                WalletError::WatchOnlyAccount(_) => (4100, "Watch-only account cannot sign".to_string()),
                 WalletError::UnsupportedNetwork(_) => (4901, "Chain disconnected".to_string()),
                 e => (-32603, e.to_string()),
             };
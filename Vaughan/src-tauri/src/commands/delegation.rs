@@ -0,0 +1,213 @@
+//! Delegation Commands
+//!
+//! Tauri commands for EIP-7702 account delegation.
+//!
+//! ## Commands
+//!
+//! - `get_known_delegates` - Delegate contracts the wallet recognises
+//! - `get_delegation` - Current delegate of an account
+//! - `sign_authorization` - Sign an authorization tuple for one of our EOAs
+//! - `send_set_code_transaction` - Sign and send a type-4 transaction
+
+use crate::chains::evm::eip7702::{self, SetCodeRequest};
+use crate::chains::EvmAuthorization;
+use crate::error::WalletError;
+use crate::state::VaughanState;
+use alloy::eips::eip7702::SignedAuthorization;
+use alloy::primitives::{Address, Bytes, U256};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::State;
+
+/// Known delegate contract
+#[derive(Debug, Serialize, Type)]
+pub struct KnownDelegateExport {
+    pub address: String,
+    pub name: String,
+}
+
+/// Delegation status of an account
+#[derive(Debug, Serialize, Type)]
+pub struct DelegationStatus {
+    pub address: String,
+    /// Current delegate (`null` if the account has no code)
+    pub delegate: Option<String>,
+    /// Name of a known delegate
+    pub delegate_name: Option<String>,
+    pub known_delegate: bool,
+}
+
+/// Authorization signing request
+#[derive(Debug, Deserialize, Type)]
+pub struct SignAuthorizationRequest {
+    /// Our EOA whose code gets delegated
+    pub authority: String,
+    /// Delegate contract (zero address removes the delegation)
+    pub delegate: String,
+    /// Chain ID (defaults to the active chain; 0 = all chains)
+    pub chain_id: Option<u64>,
+    /// Authority nonce (defaults to the account's current nonce)
+    pub nonce: Option<u64>,
+    pub password: String,
+}
+
+/// Set-code transaction request
+#[derive(Debug, Deserialize, Type)]
+pub struct SendSetCodeTransactionRequest {
+    /// Sender (signs the transaction)
+    pub from: String,
+    /// Delegate for `from` itself; signs an authorization for the sender
+    pub delegate: Option<String>,
+    /// Additional authorizations, already signed by other accounts
+    #[serde(default)]
+    pub authorizations: Vec<EvmAuthorization>,
+    /// Call target (defaults to `from`)
+    pub to: Option<String>,
+    /// Amount in native currency (human-readable)
+    pub amount: Option<String>,
+    /// Optional calldata (hex string)
+    pub data: Option<String>,
+    pub gas_limit: Option<u64>,
+    pub password: String,
+}
+
+fn parse_address(s: &str) -> Result<Address, WalletError> {
+    s.parse().map_err(|_| WalletError::InvalidAddress(s.to_string()))
+}
+
+/// Get the delegate contracts the wallet recognises
+#[tauri::command]
+#[specta::specta]
+pub async fn get_known_delegates() -> Result<Vec<KnownDelegateExport>, WalletError> {
+    Ok(eip7702::KNOWN_DELEGATES
+        .iter()
+        .map(|d| KnownDelegateExport {
+            address: format!("{:?}", d.address),
+            name: d.name.to_string(),
+        })
+        .collect())
+}
+
+/// Get the current EIP-7702 delegate of an account on the active network
+///
+/// # Example
+///
+/// ```typescript
+/// const status = await invoke('get_delegation', { address: '0x...' });
+/// if (status.delegate && !status.knownDelegate) warn(status.delegate);
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn get_delegation(
+    state: State<'_, VaughanState>,
+    address: String,
+) -> Result<DelegationStatus, WalletError> {
+    let account = parse_address(&address)?;
+    let adapter = state.current_adapter().await?;
+    let delegate = eip7702::current_delegate(&adapter.provider(), account).await?;
+    let check = delegate.map(eip7702::check_delegate);
+
+    Ok(DelegationStatus {
+        address: format!("{:?}", account),
+        delegate: delegate.map(|d| format!("{:?}", d)),
+        delegate_name: check.as_ref().and_then(|c| c.name.clone()),
+        known_delegate: check.map(|c| c.known).unwrap_or(false),
+    })
+}
+
+/// Sign an EIP-7702 authorization for one of the wallet's accounts
+///
+/// The signed tuple can be handed to a sponsor who includes it in their
+/// own type-4 transaction.
+///
+/// # Example
+///
+/// ```typescript
+/// const auth = await invoke('sign_authorization', {
+///   request: { authority: '0x...', delegate: '0x...', chainId: null, nonce: null, password }
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn sign_authorization(
+    state: State<'_, VaughanState>,
+    request: SignAuthorizationRequest,
+) -> Result<EvmAuthorization, WalletError> {
+    let authority = parse_address(&request.authority)?;
+    let delegate = parse_address(&request.delegate)?;
+
+    let signed = state
+        .sign_authorization(
+            authority,
+            delegate,
+            request.chain_id,
+            request.nonce,
+            false,
+            &request.password,
+        )
+        .await?;
+    Ok(EvmAuthorization::from(&signed))
+}
+
+/// Sign and send a type-4 (set-code) transaction
+///
+/// With `delegate` set, the sender delegates its own code in the same
+/// transaction. Pre-signed `authorizations` from other accounts are
+/// included as given.
+///
+/// # Returns
+///
+/// The transaction hash
+#[tauri::command]
+#[specta::specta]
+pub async fn send_set_code_transaction(
+    state: State<'_, VaughanState>,
+    request: SendSetCodeTransactionRequest,
+) -> Result<String, WalletError> {
+    let from = parse_address(&request.from)?;
+    let to = match request.to.as_deref() {
+        Some(to) => parse_address(to)?,
+        None => from,
+    };
+
+    let adapter = state.current_adapter().await?;
+    let value = match request.amount.as_deref().filter(|a| !a.is_empty()) {
+        Some(amount) => crate::chains::evm::utils::parse_eth_to_wei(amount, adapter.native_decimals())?,
+        None => U256::ZERO,
+    };
+    let data = match request.data.as_deref().filter(|d| !d.is_empty()) {
+        Some(hex_data) => Bytes::from(
+            hex::decode(hex_data.trim_start_matches("0x"))
+                .map_err(|e| WalletError::InvalidTransaction(format!("Invalid data: {}", e)))?,
+        ),
+        None => Bytes::new(),
+    };
+
+    let mut authorizations = Vec::new();
+    if let Some(delegate) = request.delegate.as_deref() {
+        let delegate = parse_address(delegate)?;
+        authorizations.push(
+            state
+                .sign_authorization(from, delegate, None, None, true, &request.password)
+                .await?,
+        );
+    }
+    for auth in &request.authorizations {
+        authorizations.push(SignedAuthorization::try_from(auth)?);
+    }
+
+    let hash = state
+        .send_set_code_transaction(
+            SetCodeRequest {
+                from,
+                to,
+                value,
+                data,
+                gas_limit: request.gas_limit,
+                authorizations,
+            },
+            &request.password,
+        )
+        .await?;
+    Ok(format!("{:?}", hash))
+}
@@ -32,6 +32,10 @@ pub struct TxRecord {
     pub token_address: Option<String>,
     /// Whether this is an ERC20 token transfer
     pub is_token_transfer: bool,
+    /// Address book name of the sender
+    pub from_label: Option<String>,
+    /// Address book name of the recipient
+    pub to_label: Option<String>,
 }
 
 /// Get transaction history for an address (native + ERC20 token transfers combined)
@@ -89,6 +93,8 @@ pub async fn get_transactions(
             token_symbol: None,
             token_address: None,
             is_token_transfer: false,
+            from_label: None,
+            to_label: None,
         })
         .collect();
 
@@ -112,6 +118,8 @@ pub async fn get_transactions(
             token_symbol: Some("TOKEN".to_string()),
             token_address: None,
             is_token_transfer: true,
+            from_label: None,
+            to_label: None,
         })
         .collect();
 
@@ -119,5 +127,19 @@ pub async fn get_transactions(
     records.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
     records.truncate(limit as usize);
 
+    // Label counterparties from the address book
+    let chain_id = adapter.chain_id();
+    let contacts = state.contacts(Some(chain_id));
+    let label = |address: &str| {
+        state
+            .address_book_service
+            .lookup(&contacts, chain_id, address)
+            .map(|c| c.name.clone())
+    };
+    for record in &mut records {
+        record.from_label = label(&record.from);
+        record.to_label = label(&record.to);
+    }
+
     Ok(records)
 }
@@ -22,11 +22,18 @@
 //! verification to ensure they're only called from the main wallet window,
 //! not from dApp windows.
 
+pub mod address_book;
+pub mod batch;
+pub mod contract;
 pub mod dapp;
 pub mod dapp_ipc;
+pub mod delegation;
 pub mod history;
 pub mod network;
 pub mod persistence;
+pub mod safe;
+pub mod signature;
+pub mod smart_account;
 pub mod token;
 pub mod transaction;
 pub mod wallet;
@@ -6,7 +6,9 @@
 //! Rust backend for network-related functionality.
 
 use crate::chains::ChainAdapter;
+use crate::core::{NetworkConfig, NetworkOverride};
 use crate::error::AnyJson;
+use crate::monitoring::network_health::NetworkHealthReport;
 use crate::state::VaughanState;
 use alloy::providers::Provider;
 use serde::{Deserialize, Serialize};
@@ -230,11 +232,10 @@ pub async fn get_network_info(
         name: chain_info.name,
         chain_id: chain_info.chain_id.unwrap_or(0),
         rpc_url: adapter.rpc_url().to_string(),
-        explorer_url: crate::chains::evm::networks::get_network_by_chain_id(
-            chain_info.chain_id.unwrap_or(0),
-        )
-        .and_then(|n| n.explorer_url)
-        .unwrap_or_default(),
+        explorer_url: state
+            .find_network_by_chain_id(chain_info.chain_id.unwrap_or(0))
+            .and_then(|n| n.explorer_url)
+            .unwrap_or_default(),
         native_token: TokenInfoResponse {
             symbol: chain_info.native_token.symbol,
             name: chain_info.native_token.name,
@@ -304,7 +305,8 @@ pub async fn get_block_number(state: State<'_, VaughanState>) -> Result<u64, Str
 
 /// Get supported networks
 ///
-/// Returns a list of all supported network configurations.
+/// Returns a list of all supported network configurations: predefined
+/// networks (with user overrides applied) followed by custom networks.
 ///
 /// # Arguments
 ///
@@ -325,8 +327,196 @@ pub async fn get_block_number(state: State<'_, VaughanState>) -> Result<u64, Str
 #[specta::specta]
 pub async fn get_supported_networks(
     state: State<'_, VaughanState>,
-) -> Result<Vec<crate::core::NetworkConfig>, String> {
-    Ok(state.network_service.get_predefined_networks())
+) -> Result<Vec<NetworkConfig>, String> {
+    Ok(state.networks())
+}
+
+/// Get user-added custom networks
+///
+/// # Example (from frontend)
+///
+/// ```typescript
+/// const custom = await invoke('get_custom_networks');
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn get_custom_networks(
+    state: State<'_, VaughanState>,
+) -> Result<Vec<NetworkConfig>, String> {
+    Ok(state.state_manager().load().custom_networks)
+}
+
+/// Add a custom network
+///
+/// The configuration is validated and the RPC is queried with `eth_chainId`;
+/// the network is only saved if the reported chain ID matches `config.chain_id`.
+///
+/// # Example (from frontend)
+///
+/// ```typescript
+/// await invoke('add_custom_network', {
+///   config: {
+///     id: 'custom-12345',
+///     name: 'My Chain',
+///     chain_type: 'Evm',
+///     chain_id: 12345,
+///     rpc_url: 'https://rpc.example.com',
+///     explorer_url: null,
+///     native_token: { symbol: 'MYC', name: 'My Coin', decimals: 18 },
+///     is_testnet: false
+///   }
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn add_custom_network(
+    state: State<'_, VaughanState>,
+    config: NetworkConfig,
+) -> Result<(), String> {
+    state
+        .add_custom_network(config)
+        .await
+        .map_err(|e| e.user_message())
+}
+
+/// Edit a custom network
+///
+/// Replaces the custom network with the same `id`. The chain ID is verified
+/// against the RPC again before saving.
+#[tauri::command]
+#[specta::specta]
+pub async fn update_custom_network(
+    state: State<'_, VaughanState>,
+    config: NetworkConfig,
+) -> Result<(), String> {
+    state
+        .update_custom_network(config)
+        .await
+        .map_err(|e| e.user_message())
+}
+
+/// Remove a custom network
+///
+/// The active network cannot be removed.
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_custom_network(
+    state: State<'_, VaughanState>,
+    network_id: String,
+) -> Result<(), String> {
+    state
+        .remove_custom_network(&network_id)
+        .await
+        .map_err(|e| e.user_message())
+}
+
+/// Get RPC/explorer overrides for predefined networks
+#[tauri::command]
+#[specta::specta]
+pub async fn get_network_overrides(
+    state: State<'_, VaughanState>,
+) -> Result<Vec<NetworkOverride>, String> {
+    Ok(state.state_manager().load().network_overrides)
+}
+
+/// Override the RPC and/or explorer URL of a predefined network
+///
+/// Pass `null` for both URLs to restore the defaults.
+///
+/// # Example (from frontend)
+///
+/// ```typescript
+/// await invoke('set_network_override', {
+///   networkId: 'ethereum',
+///   rpcUrl: 'https://my-node.example.com',
+///   explorerUrl: null
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn set_network_override(
+    state: State<'_, VaughanState>,
+    network_id: String,
+    rpc_url: Option<String>,
+    explorer_url: Option<String>,
+) -> Result<(), String> {
+    state
+        .set_network_override(&network_id, rpc_url, explorer_url)
+        .await
+        .map_err(|e| e.user_message())
+}
+
+/// Maximum number of results returned by `search_network_registry`
+const REGISTRY_SEARCH_LIMIT: usize = 50;
+
+/// Search the network registry
+///
+/// Searches every chain known to the registry (bundled list plus any imported
+/// chains.json), not just the predefined networks.
+///
+/// # Arguments
+///
+/// * `query` - Name, ID, native symbol or chain ID
+/// * `include_testnets` - Whether to include testnets
+///
+/// # Example (from frontend)
+///
+/// ```typescript
+/// const results = await invoke('search_network_registry', {
+///   query: 'gnosis',
+///   includeTestnets: false
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn search_network_registry(
+    state: State<'_, VaughanState>,
+    query: String,
+    include_testnets: bool,
+) -> Result<Vec<NetworkConfig>, String> {
+    Ok(state
+        .network_service
+        .search_registry(&query, include_testnets, REGISTRY_SEARCH_LIMIT))
+}
+
+/// Add a network from the registry
+///
+/// # Arguments
+///
+/// * `chain_id` - Chain ID of a registry network
+///
+/// # Returns
+///
+/// * `Ok(NetworkConfig)` - The added network
+/// * `Err(String)` - Unknown chain, already configured, or RPC check failed
+#[tauri::command]
+#[specta::specta]
+pub async fn add_network_from_registry(
+    state: State<'_, VaughanState>,
+    chain_id: u64,
+) -> Result<NetworkConfig, String> {
+    state
+        .add_network_from_registry(chain_id)
+        .await
+        .map_err(|e| e.user_message())
+}
+
+/// Import a chains.json file (ethereum-lists format) into the registry
+///
+/// The file is saved to the app data directory and loaded on every start.
+///
+/// # Arguments
+///
+/// * `path` - Path to the chains.json file
+///
+/// # Returns
+///
+/// * `Ok(usize)` - Number of networks now in the registry
+#[tauri::command]
+#[specta::specta]
+pub async fn import_chain_registry(path: String) -> Result<usize, String> {
+    crate::chains::evm::registry::import_registry_file(std::path::Path::new(&path))
+        .map_err(|e| e.user_message())
 }
 
 /// Generic RPC request bypass
@@ -416,6 +606,43 @@ pub async fn proxy_request(
     }
 }
 
+/// Get health reports (latency, block height, staleness, history) for all
+/// probed networks
+///
+/// Reports are filled by the background health monitor; listen for
+/// `NetworkHealthChangedEvent` to be notified of status changes.
+///
+/// # Example (from frontend)
+///
+/// ```typescript
+/// const reports = await invoke('get_network_health');
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn get_network_health(
+    state: State<'_, VaughanState>,
+) -> Result<Vec<NetworkHealthReport>, String> {
+    Ok(state.network_health.reports().await)
+}
+
+/// Get the health report of the active network
+///
+/// # Returns
+///
+/// * `Ok(Some(report))` - Latest health data
+/// * `Ok(None)` - Active network not probed yet
+#[tauri::command]
+#[specta::specta]
+pub async fn get_active_network_health(
+    state: State<'_, VaughanState>,
+) -> Result<Option<NetworkHealthReport>, String> {
+    let network_id = state
+        .current_network_id()
+        .await
+        .map_err(|e| e.user_message())?;
+    Ok(state.network_health.report(&network_id).await)
+}
+
 
 // ============================================================================
 // Tests
@@ -6,6 +6,7 @@ use crate::core::persistence::{PersistedState, UserPreferences};
 use crate::error::WalletError;
 use crate::models::wallet::AccountExport;
 use crate::state::VaughanState;
+use secrecy::zeroize::Zeroizing;
 use serde::Serialize;
 use specta::Type;
 use tauri::State;
@@ -20,7 +21,13 @@ pub struct PersistedStateExport {
     pub active_account: Option<String>,
     pub accounts: Vec<AccountExport>,
     pub custom_networks: Vec<crate::core::NetworkConfig>,
+    pub network_overrides: Vec<crate::core::NetworkOverride>,
+    pub bundlers: Vec<crate::core::BundlerConfig>,
+    pub safe_proposals: Vec<crate::core::SafeProposal>,
     pub tracked_tokens: Vec<crate::models::token::TrackedToken>,
+    pub contract_abis: Vec<crate::models::contract::ContractAbiEntry>,
+    pub batch_jobs: Vec<crate::core::BatchJob>,
+    pub contacts: Vec<crate::models::contact::Contact>,
     pub preferences: UserPreferences,
 }
 
@@ -34,7 +41,13 @@ impl From<PersistedState> for PersistedStateExport {
             active_account: s.active_account,
             accounts: s.accounts.into_iter().map(AccountExport::from).collect(),
             custom_networks: s.custom_networks,
+            network_overrides: s.network_overrides,
+            bundlers: s.bundlers,
+            safe_proposals: s.safe_proposals,
             tracked_tokens: s.tracked_tokens,
+            contract_abis: s.contract_abis,
+            batch_jobs: s.batch_jobs,
+            contacts: s.contacts,
             preferences: s.preferences,
         }
     }
@@ -50,6 +63,107 @@ pub async fn export_state(state: State<'_, VaughanState>) -> Result<PersistedSta
     Ok(state.state_manager().load().into())
 }
 
+/// Export an encrypted full-wallet backup
+///
+/// One file with the mnemonic, BIP-39 passphrases, imported keys, account
+/// names and types, custom networks, tracked tokens, contacts and
+/// preferences, encrypted under `backup_password`. Wallet must be unlocked.
+///
+/// # Arguments
+///
+/// * `password` - Wallet password
+/// * `backup_password` - Password the backup is encrypted with
+/// * `file_path` - Also write the backup to this file (optional)
+///
+/// # Returns
+///
+/// The backup file contents
+///
+/// # Example
+///
+/// ```typescript
+/// await invoke('export_backup', {
+///   password: 'my_password',
+///   backupPassword: 'backup_password',
+///   filePath: '/media/usb/vaughan.backup'
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn export_backup(
+    state: State<'_, VaughanState>,
+    password: String,
+    backup_password: String,
+    file_path: Option<String>,
+) -> Result<String, WalletError> {
+    let password = Zeroizing::new(password);
+    let backup_password = Zeroizing::new(backup_password);
+    if password.is_empty() || backup_password.is_empty() {
+        return Err(WalletError::InvalidPassword);
+    }
+
+    let backup = state.export_backup(&password, &backup_password).await?;
+    if let Some(path) = file_path {
+        std::fs::write(&path, &backup)
+            .map_err(|e| WalletError::Custom(format!("Cannot write {}: {}", path, e)))?;
+    }
+    Ok(backup)
+}
+
+/// Restore a wallet from an encrypted backup (exactly one of `backup` / `file_path`)
+///
+/// Only on a device without a wallet (reset it first). The backup's keys
+/// are stored under `password`, which may differ from the old wallet
+/// password. The wallet is left unlocked.
+///
+/// # Errors
+///
+/// * `WalletError::DecryptionFailed` - Wrong backup password
+/// * `WalletError::Custom` - Not a backup, unsupported version, damaged
+///   file, or a wallet already exists
+///
+/// # Example
+///
+/// ```typescript
+/// const accounts = await invoke('restore_backup', {
+///   backup: null,
+///   filePath: '/media/usb/vaughan.backup',
+///   backupPassword: 'backup_password',
+///   password: 'new_password'
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn restore_backup(
+    state: State<'_, VaughanState>,
+    backup: Option<String>,
+    file_path: Option<String>,
+    backup_password: String,
+    password: String,
+) -> Result<Vec<AccountExport>, WalletError> {
+    let password = Zeroizing::new(password);
+    let backup_password = Zeroizing::new(backup_password);
+    if password.is_empty() {
+        return Err(WalletError::InvalidPassword);
+    }
+
+    let contents = match (backup, file_path) {
+        (Some(contents), None) => contents,
+        (None, Some(path)) => std::fs::read_to_string(&path)
+            .map_err(|e| WalletError::Custom(format!("Cannot read {}: {}", path, e)))?,
+        _ => {
+            return Err(WalletError::Custom(
+                "Provide either the backup or a file path".to_string(),
+            ))
+        }
+    };
+
+    let accounts = state
+        .restore_backup(&contents, &backup_password, &password)
+        .await?;
+    Ok(accounts.into_iter().map(AccountExport::from).collect())
+}
+
 /// Reset persisted state
 ///
 /// Deletes the state file. The app will use defaults on next restart.
@@ -57,8 +171,7 @@ pub async fn export_state(state: State<'_, VaughanState>) -> Result<PersistedSta
 #[tauri::command]
 #[specta::specta]
 pub async fn reset_state(state: State<'_, VaughanState>) -> Result<(), WalletError> {
-    state.wallet_service.wipe().await?;
-    state.state_manager().reset()
+    state.wipe_wallet().await
 }
 
 /// Get current user preferences
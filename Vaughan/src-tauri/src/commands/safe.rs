@@ -0,0 +1,282 @@
+//! Safe Commands
+//!
+//! Tauri commands for Safe multisig accounts.
+//!
+//! ## Commands
+//!
+//! - `add_safe_account` - Add an existing Safe as an account
+//! - `get_safe_info` - Owners, threshold and nonce of a Safe
+//! - `get_safe_proposals` - Stored Safe transaction proposals
+//! - `propose_safe_transaction` - Create a proposal for a call from a Safe
+//! - `sign_safe_proposal` - Sign a proposal with a local owner
+//! - `export_safe_proposal` - Proposal with signatures as JSON
+//! - `import_safe_proposal` - Merge a proposal/signatures from another owner
+//! - `execute_safe_proposal` - Execute a proposal once the threshold is met
+//! - `delete_safe_proposal` - Discard a proposal
+
+use crate::chains::evm::safe::{SafeState, OPERATION_CALL};
+use crate::core::SafeProposal;
+use crate::error::WalletError;
+use crate::models::wallet::AccountExport;
+use crate::state::VaughanState;
+use alloy::primitives::{Address, Bytes, U256};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::State;
+
+/// Safe owners and threshold
+#[derive(Debug, Serialize, Type)]
+pub struct SafeInfo {
+    pub address: String,
+    pub owners: Vec<String>,
+    pub threshold: u64,
+    /// Nonce of the next transaction the Safe will execute
+    pub nonce: u64,
+    pub version: String,
+    /// Owners whose keys are in this wallet
+    pub local_owners: Vec<String>,
+}
+
+/// Added Safe account
+#[derive(Debug, Serialize, Type)]
+pub struct AddSafeAccountResponse {
+    pub account: AccountExport,
+    pub info: SafeInfo,
+}
+
+/// Safe transaction proposal request
+#[derive(Debug, Deserialize, Type)]
+pub struct ProposeSafeTransactionRequest {
+    /// Safe address
+    pub safe: String,
+    /// Call target
+    pub to: String,
+    /// Amount in native currency (human-readable)
+    pub amount: String,
+    /// Optional calldata (hex string)
+    pub data: Option<String>,
+    /// 0 = call (default), 1 = delegatecall
+    pub operation: Option<u8>,
+}
+
+fn parse_address(s: &str) -> Result<Address, WalletError> {
+    s.parse().map_err(|_| WalletError::InvalidAddress(s.to_string()))
+}
+
+async fn safe_info(
+    state: &VaughanState,
+    address: Address,
+    safe_state: SafeState,
+) -> Result<SafeInfo, WalletError> {
+    let accounts = state.wallet_service.get_accounts().await?;
+    let local_owners = safe_state
+        .owners
+        .iter()
+        .filter(|owner| {
+            accounts
+                .iter()
+                .any(|a| a.address == **owner && a.smart_account.is_none() && a.safe.is_none())
+        })
+        .map(|owner| format!("{:?}", owner))
+        .collect();
+
+    Ok(SafeInfo {
+        address: format!("{:?}", address),
+        owners: safe_state.owners.iter().map(|o| format!("{:?}", o)).collect(),
+        threshold: safe_state.threshold,
+        nonce: safe_state.nonce.saturating_to(),
+        version: safe_state.version,
+        local_owners,
+    })
+}
+
+/// Add an existing Safe on the active network as an account
+///
+/// # Arguments
+///
+/// * `address` - Safe address
+/// * `name` - Account name (optional)
+/// * `password` - Wallet password (for verification)
+///
+/// # Example
+///
+/// ```typescript
+/// const { account, info } = await invoke('add_safe_account', {
+///   address: '0x...',
+///   name: 'Treasury',
+///   password: 'my_password'
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn add_safe_account(
+    state: State<'_, VaughanState>,
+    address: String,
+    name: Option<String>,
+    password: String,
+) -> Result<AddSafeAccountResponse, WalletError> {
+    let address = parse_address(&address)?;
+    let (account, safe_state) = state.add_safe_account(address, name, &password).await?;
+    Ok(AddSafeAccountResponse {
+        account: AccountExport::from(account),
+        info: safe_info(&state, address, safe_state).await?,
+    })
+}
+
+/// Get owners, threshold and nonce of a Safe account
+///
+/// # Example
+///
+/// ```typescript
+/// const info = await invoke('get_safe_info', { address: '0x...' });
+/// console.log(`${info.threshold} of ${info.owners.length}`);
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn get_safe_info(
+    state: State<'_, VaughanState>,
+    address: String,
+) -> Result<SafeInfo, WalletError> {
+    let address = parse_address(&address)?;
+    let safe_state = state.safe_state(address).await?;
+    safe_info(&state, address, safe_state).await
+}
+
+/// Get stored Safe proposals
+///
+/// # Arguments
+///
+/// * `safe` - Only proposals of this Safe (optional)
+#[tauri::command]
+#[specta::specta]
+pub async fn get_safe_proposals(
+    state: State<'_, VaughanState>,
+    safe: Option<String>,
+) -> Result<Vec<SafeProposal>, WalletError> {
+    let safe = safe.as_deref().map(parse_address).transpose()?;
+    Ok(state.safe_proposals(safe))
+}
+
+/// Create a proposal for a call from a Safe
+///
+/// The proposal uses the Safe's next free nonce and has no signatures yet.
+///
+/// # Example
+///
+/// ```typescript
+/// const proposal = await invoke('propose_safe_transaction', {
+///   request: { safe: '0x...', to: '0x...', amount: '1.0', data: null, operation: null }
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn propose_safe_transaction(
+    state: State<'_, VaughanState>,
+    request: ProposeSafeTransactionRequest,
+) -> Result<SafeProposal, WalletError> {
+    let safe = parse_address(&request.safe)?;
+    let to = parse_address(&request.to)?;
+
+    let adapter = state.current_adapter().await?;
+    let value = match request.amount.trim() {
+        "" => U256::ZERO,
+        amount => crate::chains::evm::utils::parse_eth_to_wei(amount, adapter.native_decimals())?,
+    };
+    let data = match request.data.as_deref().filter(|d| !d.is_empty()) {
+        Some(hex_data) => Bytes::from(
+            hex::decode(hex_data.trim_start_matches("0x"))
+                .map_err(|e| WalletError::InvalidTransaction(format!("Invalid data: {}", e)))?,
+        ),
+        None => Bytes::new(),
+    };
+
+    state
+        .propose_safe_transaction(safe, to, value, data, request.operation.unwrap_or(OPERATION_CALL), None)
+        .await
+}
+
+/// Sign a Safe proposal with an owner account in this wallet
+///
+/// # Arguments
+///
+/// * `safe_tx_hash` - Proposal to sign
+/// * `owner` - Owner account address
+/// * `password` - Wallet password
+#[tauri::command]
+#[specta::specta]
+pub async fn sign_safe_proposal(
+    state: State<'_, VaughanState>,
+    safe_tx_hash: String,
+    owner: String,
+    password: String,
+) -> Result<SafeProposal, WalletError> {
+    let owner = parse_address(&owner)?;
+    state.sign_safe_proposal(&safe_tx_hash, owner, &password).await
+}
+
+/// Export a Safe proposal with its signatures as JSON
+///
+/// Send the JSON to the other owners; they sign it and send it back.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_safe_proposal(
+    state: State<'_, VaughanState>,
+    safe_tx_hash: String,
+) -> Result<String, WalletError> {
+    let proposal = state.safe_proposal(&safe_tx_hash)?;
+    serde_json::to_string_pretty(&proposal).map_err(|e| WalletError::InternalError(e.to_string()))
+}
+
+/// Import a Safe proposal exported by another owner
+///
+/// Valid owner signatures are merged into the stored proposal.
+///
+/// # Example
+///
+/// ```typescript
+/// const proposal = await invoke('import_safe_proposal', { json });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn import_safe_proposal(
+    state: State<'_, VaughanState>,
+    json: String,
+) -> Result<SafeProposal, WalletError> {
+    state.import_safe_proposal(&json).await
+}
+
+/// Execute a Safe proposal once it has enough owner signatures
+///
+/// # Arguments
+///
+/// * `safe_tx_hash` - Proposal to execute
+/// * `executor` - Wallet account that sends the transaction and pays gas
+/// * `password` - Wallet password
+///
+/// # Returns
+///
+/// The transaction hash
+#[tauri::command]
+#[specta::specta]
+pub async fn execute_safe_proposal(
+    state: State<'_, VaughanState>,
+    safe_tx_hash: String,
+    executor: String,
+    password: String,
+) -> Result<String, WalletError> {
+    let executor = parse_address(&executor)?;
+    let hash = state
+        .execute_safe_proposal(&safe_tx_hash, executor, &password)
+        .await?;
+    Ok(format!("{:?}", hash))
+}
+
+/// Discard a Safe proposal
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_safe_proposal(
+    state: State<'_, VaughanState>,
+    safe_tx_hash: String,
+) -> Result<(), WalletError> {
+    state.delete_safe_proposal(&safe_tx_hash)
+}
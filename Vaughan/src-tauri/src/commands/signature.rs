@@ -0,0 +1,116 @@
+//! Signature Commands
+//!
+//! Tauri commands for verifying signatures sent by others (e.g. ownership
+//! proofs). Nothing here needs the wallet to be unlocked.
+//!
+//! ## Commands
+//!
+//! - `verify_message_signature` - EIP-191 personal message (`personal_sign`)
+//! - `verify_typed_data_signature` - EIP-712 typed data (`eth_signTypedData_v4`)
+//!
+//! Both fall back to EIP-1271 `isValidSignature` on the active network
+//! when the address is a contract wallet.
+
+use crate::chains::evm::signature::{self, SignatureVerification};
+use crate::dapp::rpc::personal::typed_data_signing_hash;
+use crate::error::WalletError;
+use crate::state::VaughanState;
+use alloy::primitives::{Address, B256};
+use serde::Deserialize;
+use specta::Type;
+use tauri::State;
+
+/// Personal message verification request
+#[derive(Debug, Deserialize, Type)]
+pub struct VerifyMessageRequest {
+    /// Claimed signer
+    pub address: String,
+    /// Signed message
+    pub message: String,
+    /// `message` is hex-encoded bytes rather than text
+    #[serde(default)]
+    pub message_is_hex: bool,
+    /// Signature (hex)
+    pub signature: String,
+}
+
+/// Typed data verification request
+#[derive(Debug, Deserialize, Type)]
+pub struct VerifyTypedDataRequest {
+    /// Claimed signer
+    pub address: String,
+    /// Typed data JSON as passed to `eth_signTypedData_v4`
+    pub typed_data: String,
+    /// Signature (hex)
+    pub signature: String,
+}
+
+fn decode_hex(s: &str, what: &str) -> Result<Vec<u8>, WalletError> {
+    hex::decode(s.trim().trim_start_matches("0x"))
+        .map_err(|e| WalletError::ParseError(format!("Invalid {}: {}", what, e)))
+}
+
+async fn verify(
+    state: &VaughanState,
+    address: &str,
+    hash: B256,
+    signature: &str,
+) -> Result<SignatureVerification, WalletError> {
+    let address: Address = address
+        .parse()
+        .map_err(|_| WalletError::InvalidAddress(address.to_string()))?;
+    let signature = decode_hex(signature, "signature")?;
+
+    // EIP-1271 needs a network; ECDSA alone works without one
+    let adapter = state.current_adapter().await.ok();
+    let provider = adapter.as_ref().map(|a| a.provider());
+    signature::verify_hash(provider.as_deref(), address, hash, &signature).await
+}
+
+/// Verify an EIP-191 personal message signature
+///
+/// # Returns
+///
+/// The recovered signer and whether the signature is valid for `address`
+///
+/// # Example
+///
+/// ```typescript
+/// const result = await invoke('verify_message_signature', {
+///   request: { address: '0x...', message: 'I own this address', messageIsHex: false, signature: '0x...' }
+/// });
+/// if (result.valid) console.log(`verified via ${result.method}`);
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn verify_message_signature(
+    state: State<'_, VaughanState>,
+    request: VerifyMessageRequest,
+) -> Result<SignatureVerification, WalletError> {
+    let message = if request.message_is_hex {
+        decode_hex(&request.message, "message")?
+    } else {
+        request.message.into_bytes()
+    };
+    let hash = signature::personal_message_hash(&message);
+    verify(&state, &request.address, hash, &request.signature).await
+}
+
+/// Verify an EIP-712 typed data signature
+///
+/// # Example
+///
+/// ```typescript
+/// const result = await invoke('verify_typed_data_signature', {
+///   request: { address: '0x...', typedData: JSON.stringify(data), signature: '0x...' }
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn verify_typed_data_signature(
+    state: State<'_, VaughanState>,
+    request: VerifyTypedDataRequest,
+) -> Result<SignatureVerification, WalletError> {
+    let hash = typed_data_signing_hash(&request.typed_data)?;
+    verify(&state, &request.address, hash, &request.signature).await
+}
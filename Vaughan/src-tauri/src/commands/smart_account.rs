@@ -0,0 +1,193 @@
+//! Smart Account Commands
+//!
+//! Tauri commands for ERC-4337 smart accounts.
+//!
+//! ## Commands
+//!
+//! - `get_bundlers` - Get configured bundler endpoints
+//! - `set_bundler_url` - Set or clear the bundler for a chain
+//! - `create_smart_account` - Add a smart account owned by a wallet account
+//! - `send_user_operation` - Send a call from a smart account
+//! - `get_user_operation_receipt` - Look up a sent UserOperation
+
+use crate::core::smart_account::SmartAccountCall;
+use crate::core::BundlerConfig;
+use crate::error::WalletError;
+use crate::models::wallet::AccountExport;
+use crate::state::VaughanState;
+use alloy::primitives::{Address, Bytes, B256};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::State;
+
+/// Send UserOperation request
+#[derive(Debug, Deserialize, Type)]
+pub struct SendUserOperationRequest {
+    /// Smart account address
+    pub from: String,
+    /// Call target
+    pub to: String,
+    /// Amount in native currency (human-readable)
+    pub amount: String,
+    /// Optional calldata (hex string)
+    pub data: Option<String>,
+    /// Wallet password (signs with the owner account)
+    pub password: String,
+}
+
+/// UserOperation receipt
+#[derive(Debug, Serialize, Type)]
+pub struct UserOperationReceiptExport {
+    pub user_op_hash: String,
+    pub success: bool,
+    pub reason: Option<String>,
+    /// Bundle transaction hash
+    pub transaction_hash: String,
+    /// Gas cost paid by the account, in wei
+    pub actual_gas_cost: Option<String>,
+}
+
+/// Get configured bundler endpoints
+///
+/// # Example
+///
+/// ```typescript
+/// const bundlers = await invoke('get_bundlers');
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn get_bundlers(state: State<'_, VaughanState>) -> Result<Vec<BundlerConfig>, WalletError> {
+    Ok(state.bundlers())
+}
+
+/// Set or clear the ERC-4337 bundler URL for a chain
+///
+/// The bundler must support EntryPoint v0.7.
+///
+/// # Arguments
+///
+/// * `chain_id` - Chain the bundler serves
+/// * `url` - Bundler JSON-RPC URL (`null` removes it)
+///
+/// # Example
+///
+/// ```typescript
+/// await invoke('set_bundler_url', {
+///   chainId: 8453,
+///   url: 'https://bundler.example.com/rpc'
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn set_bundler_url(
+    state: State<'_, VaughanState>,
+    chain_id: u64,
+    url: Option<String>,
+) -> Result<(), WalletError> {
+    let url = url.map(|u| u.trim().to_string()).filter(|u| !u.is_empty());
+    state.set_bundler_url(chain_id, url).await
+}
+
+/// Add a smart account owned by an existing wallet account
+///
+/// The address is computed from the factory on the active network; the
+/// contract is deployed with its first UserOperation.
+///
+/// # Arguments
+///
+/// * `owner` - Owner account address (must be an HD or imported account)
+/// * `name` - Account name (optional)
+/// * `password` - Wallet password (for verification)
+///
+/// # Example
+///
+/// ```typescript
+/// const account = await invoke('create_smart_account', {
+///   owner: '0x...',
+///   name: 'Savings',
+///   password: 'my_password'
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn create_smart_account(
+    state: State<'_, VaughanState>,
+    owner: String,
+    name: Option<String>,
+    password: String,
+) -> Result<AccountExport, WalletError> {
+    let owner: Address = owner.parse().map_err(|_| WalletError::InvalidAddress(owner.clone()))?;
+    let account = state.create_smart_account(owner, name, &password).await?;
+    Ok(AccountExport::from(account))
+}
+
+/// Send a call from a smart account as a UserOperation
+///
+/// # Returns
+///
+/// The UserOperation hash (use `get_user_operation_receipt` to track it)
+///
+/// # Example
+///
+/// ```typescript
+/// const userOpHash = await invoke('send_user_operation', {
+///   request: { from: '0x...', to: '0x...', amount: '0.1', data: null, password }
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn send_user_operation(
+    state: State<'_, VaughanState>,
+    request: SendUserOperationRequest,
+) -> Result<String, WalletError> {
+    let from: Address = request
+        .from
+        .parse()
+        .map_err(|_| WalletError::InvalidAddress(request.from.clone()))?;
+    let to: Address = request
+        .to
+        .parse()
+        .map_err(|_| WalletError::InvalidAddress(request.to.clone()))?;
+
+    let adapter = state.current_adapter().await?;
+    let value = crate::chains::evm::utils::parse_eth_to_wei(&request.amount, adapter.native_decimals())?;
+    let data = match request.data.as_deref().filter(|d| !d.is_empty()) {
+        Some(hex_data) => Bytes::from(
+            hex::decode(hex_data.trim_start_matches("0x"))
+                .map_err(|e| WalletError::InvalidTransaction(format!("Invalid data: {}", e)))?,
+        ),
+        None => Bytes::new(),
+    };
+
+    let hash = state
+        .send_user_operation(from, SmartAccountCall { to, value, data }, &request.password)
+        .await?;
+    Ok(format!("{:?}", hash))
+}
+
+/// Look up a UserOperation on the active network's bundler
+///
+/// # Returns
+///
+/// The receipt, or `null` while the operation is still pending
+#[tauri::command]
+#[specta::specta]
+pub async fn get_user_operation_receipt(
+    state: State<'_, VaughanState>,
+    user_op_hash: String,
+) -> Result<Option<UserOperationReceiptExport>, WalletError> {
+    let hash: B256 = user_op_hash
+        .parse()
+        .map_err(|_| WalletError::ParseError(format!("Invalid UserOperation hash: {}", user_op_hash)))?;
+    let adapter = state.current_adapter().await?;
+    let bundler = state.bundler_for_chain(adapter.chain_id())?;
+
+    let receipt = bundler.get_user_operation_receipt(hash).await?;
+    Ok(receipt.map(|r| UserOperationReceiptExport {
+        user_op_hash: format!("{:?}", r.user_op_hash),
+        success: r.success,
+        reason: r.reason,
+        transaction_hash: format!("{:?}", r.receipt.transaction_hash),
+        actual_gas_cost: r.actual_gas_cost.map(|c| c.to_string()),
+    }))
+}
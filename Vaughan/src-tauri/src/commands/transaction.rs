@@ -5,10 +5,12 @@
 //! **Security Note**: Transaction signing commands require origin verification
 //! to ensure they're only called from the main wallet window, not dApp windows.
 
+use crate::chains::evm::l2;
 use crate::chains::ChainAdapter;
 use crate::state::VaughanState;
 use alloy::eips::eip2718::Encodable2718;
-use alloy::primitives::{Address, U256};
+use alloy::eips::eip2930::{AccessList, AccessListItem};
+use alloy::primitives::{Address, B256, U256};
 use alloy::providers::Provider;
 use alloy::rpc::types::TransactionRequest;
 use serde::de::Error;
@@ -52,6 +54,15 @@ pub struct ValidateTransactionRequest {
     pub data: Option<String>,
 }
 
+/// Transaction validation result
+#[derive(Debug, Serialize, Type)]
+pub struct ValidateTransactionResponse {
+    /// Recipient is in the address book (for the active chain)
+    pub known_contact: bool,
+    /// Contact name of the recipient
+    pub contact_name: Option<String>,
+}
+
 /// Gas estimation response
 #[derive(Debug, Serialize, Type)]
 pub struct EstimateGasResponse {
@@ -59,17 +70,25 @@ pub struct EstimateGasResponse {
     pub gas_limit: u64,
     /// Estimated gas price (in gwei)
     pub gas_price_gwei: String,
-    /// Estimated total fee (in ETH)
+    /// Estimated total fee (in ETH), including any L1 data fee
     pub total_fee_eth: String,
+    /// L1 data fee portion on rollups (in ETH)
+    pub l1_fee_eth: Option<String>,
+    /// The rollup's L1 data fee could not be estimated; `total_fee_eth`
+    /// covers L2 execution only
+    pub l1_fee_unavailable: bool,
 }
 
 /// Validate transaction parameters
+///
+/// Also reports whether the recipient is a known contact, so the UI can
+/// warn before sending to an unfamiliar address.
 #[tauri::command]
 #[specta::specta]
 pub async fn validate_transaction(
     state: State<'_, VaughanState>,
     request: ValidateTransactionRequest,
-) -> Result<(), String> {
+) -> Result<ValidateTransactionResponse, String> {
     let _to = Address::from_str(&request.to)
         .map_err(|_| format!("Invalid recipient address format: {}", request.to))?;
 
@@ -88,7 +107,11 @@ pub async fn validate_transaction(
         if gas_limit < 21000 { return Err("Gas limit too low".to_string()); }
     }
 
-    Ok(())
+    let contact = state.contact_for(adapter.chain_id(), &request.to);
+    Ok(ValidateTransactionResponse {
+        known_contact: contact.is_some(),
+        contact_name: contact.map(|c| c.name),
+    })
 }
 
 /// Estimate gas for a simple transfer
@@ -133,32 +156,124 @@ pub async fn estimate_gas_simple(
         tx_request.value = Some(value);
     }
 
-    let gas_limit_val = match adapter.estimate_gas(tx_request).await {
+    let gas_limit_val = match adapter.estimate_gas(tx_request.clone()).await {
         Ok(gas) => gas,
         Err(_) => 21000,
     };
 
-    let total_fee_wei = U256::from(gas_limit_val) * U256::from(gas_price);
-    let total_fee_eth = crate::chains::evm::utils::format_wei_to_eth(total_fee_wei, 18);
+    // L1 data fee on rollups; an oracle failure is reported, not fatal
+    let l1_fee = adapter.l1_fee_estimate(&tx_request, gas_limit_val, gas_price).await;
+
+    let decimals = adapter.native_decimals();
+    let total_fee_wei = U256::from(gas_limit_val) * U256::from(gas_price)
+        + l2::l1_fee_surcharge(adapter.chain_id(), l1_fee.amount());
+    let total_fee_eth = crate::chains::evm::utils::format_wei_to_eth(total_fee_wei, decimals);
 
     Ok(EstimateGasResponse {
         gas_limit: gas_limit_val,
         gas_price_gwei,
         total_fee_eth,
+        l1_fee_eth: l1_fee.amount().map(|fee| crate::chains::evm::utils::format_wei_to_eth(fee, decimals)),
+        l1_fee_unavailable: l1_fee.is_unavailable(),
     })
 }
 
-/// Sign transaction request
+/// EIP-2930 access list entry
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct AccessListItemExport {
+    pub address: String,
+    pub storage_keys: Vec<String>,
+}
+
+fn access_list_to_export(list: &AccessList) -> Vec<AccessListItemExport> {
+    list.0
+        .iter()
+        .map(|item| AccessListItemExport {
+            address: item.address.to_string(),
+            storage_keys: item.storage_keys.iter().map(|k| k.to_string()).collect(),
+        })
+        .collect()
+}
+
+fn access_list_from_export(items: &[AccessListItemExport]) -> Result<AccessList, String> {
+    items
+        .iter()
+        .map(|item| {
+            Ok(AccessListItem {
+                address: Address::from_str(&item.address)
+                    .map_err(|_| format!("Invalid access list address: {}", item.address))?,
+                storage_keys: item
+                    .storage_keys
+                    .iter()
+                    .map(|k| B256::from_str(k).map_err(|_| format!("Invalid storage key: {}", k)))
+                    .collect::<Result<_, _>>()?,
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()
+        .map(AccessList::from)
+}
+
+/// Fully specified transaction (gas, price and nonce already chosen)
 #[derive(Debug, Deserialize, Type)]
-pub struct SignTransactionRequest {
+pub struct UnsignedTransactionRequest {
     pub from: String,
     pub to: String,
     pub value: String,
     pub gas_limit: u64,
     pub gas_price: String,
     pub nonce: u64,
-    pub password: String,
     pub data: Option<String>,
+    /// EIP-2930 access list (a type-1 transaction when present)
+    #[serde(default)]
+    pub access_list: Option<Vec<AccessListItemExport>>,
+}
+
+impl UnsignedTransactionRequest {
+    fn to_transaction_request(&self, chain_id: u64) -> Result<TransactionRequest, String> {
+        let from = Address::from_str(&self.from).map_err(|_| "Invalid from".to_string())?;
+        let to = Address::from_str(&self.to).map_err(|_| "Invalid to".to_string())?;
+        let value = U256::from_str(&self.value).map_err(|_| "Invalid value".to_string())?;
+        let gas_price = U256::from_str(&self.gas_price).map_err(|_| "Invalid gas price".to_string())?;
+
+        let mut tx_request = TransactionRequest::default();
+        tx_request.from = Some(from);
+        tx_request.to = Some(to.into());
+        tx_request.gas = Some(self.gas_limit);
+        tx_request.gas_price = Some(gas_price.to::<u128>());
+        tx_request.nonce = Some(self.nonce);
+        tx_request.chain_id = Some(chain_id);
+        tx_request.value = Some(value);
+
+        if let Some(token_data) = &self.data {
+            let input_bytes = hex::decode(token_data.trim_start_matches("0x")).map_err(|_| "Invalid data".to_string())?;
+            tx_request.input.input = Some(input_bytes.into());
+        }
+
+        if let Some(items) = &self.access_list {
+            tx_request.access_list = Some(access_list_from_export(items)?);
+        }
+        Ok(tx_request)
+    }
+}
+
+/// Sign transaction request
+#[derive(Debug, Deserialize, Type)]
+pub struct SignTransactionRequest {
+    #[serde(flatten)]
+    pub transaction: UnsignedTransactionRequest,
+    pub password: String,
+}
+
+/// Unsigned transaction for signing elsewhere (e.g. a cold wallet)
+#[derive(Debug, Serialize, Type)]
+pub struct UnsignedTransactionResponse {
+    /// Transaction fields as JSON (`eth_signTransaction` format)
+    pub transaction: String,
+    /// EIP-2718 unsigned payload, hex (what the signer signs over)
+    pub unsigned_tx: String,
+    /// Keccak-256 of `unsigned_tx`
+    pub signing_hash: String,
+    pub chain_id: u64,
 }
 
 /// Built transaction response
@@ -173,6 +288,15 @@ pub struct BuildTransactionResponse {
     pub chain_id: u64,
     pub total_cost_eth: String,
     pub data: Option<String>,
+    /// L1 data fee on rollups (in wei), included in `total_cost_eth`
+    pub l1_fee: Option<String>,
+    /// The rollup's L1 data fee could not be estimated; `total_cost_eth`
+    /// leaves it out
+    pub l1_fee_unavailable: bool,
+    /// Access list, only present when it lowers the gas cost
+    pub access_list: Option<Vec<AccessListItemExport>>,
+    /// Non-fatal issues to show before sending (e.g. lagging RPC)
+    pub warnings: Vec<String>,
 }
 
 /// Build transaction request
@@ -186,6 +310,9 @@ pub struct BuildTransactionRequest {
     pub nonce: Option<u64>,
     pub token_address: Option<String>,
     pub data: Option<String>,
+    /// Try `eth_createAccessList` and keep the list if it saves gas
+    #[serde(default)]
+    pub generate_access_list: bool,
 }
 
 /// Build transaction
@@ -227,8 +354,20 @@ pub async fn build_transaction(
         tx_request.value = Some(value);
     }
 
-    let gas_limit = if let Some(gl) = request.gas_limit { gl } else {
-        match adapter.estimate_gas(tx_request).await {
+    let access_list = if request.generate_access_list {
+        adapter.optimize_access_list(&tx_request).await.unwrap_or_else(|e| {
+            eprintln!("[build_transaction] Access list generation failed: {}", e);
+            None
+        })
+    } else {
+        None
+    };
+    if let Some((list, _)) = &access_list {
+        tx_request.access_list = Some(list.clone());
+    }
+
+    let gas_limit = if let Some(gl) = request.gas_limit { gl } else if let Some((_, gas)) = &access_list { *gas } else {
+        match adapter.estimate_gas(tx_request.clone()).await {
             Ok(gas) => gas,
             Err(_) => 21000,
         }
@@ -244,13 +383,31 @@ pub async fn build_transaction(
         adapter.get_transaction_count(from).await.map_err(|e| format!("Failed to get nonce: {}", e))?
     };
 
-    let total_cost = value + (U256::from(gas_limit) * gas_price);
-    let total_cost_eth = crate::chains::evm::utils::format_wei_to_eth(total_cost, 18);
+    tx_request.nonce = Some(nonce);
+    let l1_fee = adapter
+        .l1_fee_estimate(&tx_request, gas_limit, gas_price.saturating_to::<u128>())
+        .await;
+
+    let total_cost = value + (U256::from(gas_limit) * gas_price)
+        + l2::l1_fee_surcharge(chain_id, l1_fee.amount());
+    let total_cost_eth = crate::chains::evm::utils::format_wei_to_eth(total_cost, adapter.native_decimals());
+
+    let mut warnings = Vec::new();
+    if l1_fee.is_unavailable() {
+        warnings.push(l2::L1_FEE_UNAVAILABLE_WARNING.to_string());
+    }
+    match state.check_send_health().await {
+        Ok(Some(warning)) => warnings.push(warning),
+        Ok(None) => {},
+        Err(e) => warnings.push(e.user_message()),
+    }
 
     Ok(BuildTransactionResponse {
         from: from.to_string(), to: to.to_string(), value: value.to_string(),
         gas_limit, gas_price: gas_price.to_string(), nonce, chain_id,
-        total_cost_eth, data: data_hex,
+        total_cost_eth, data: data_hex, l1_fee: l1_fee.amount().map(|fee| fee.to_string()),
+        l1_fee_unavailable: l1_fee.is_unavailable(),
+        access_list: access_list.map(|(list, _)| access_list_to_export(&list)), warnings,
     })
 }
 
@@ -263,37 +420,61 @@ pub async fn sign_transaction(
 ) -> Result<String, String> {
     state.wallet_service.verify_password(&request.password).await.map_err(|e| e.user_message())?;
 
-    let from = Address::from_str(&request.from).map_err(|_| "Invalid from".to_string())?;
-    let to = Address::from_str(&request.to).map_err(|_| "Invalid to".to_string())?;
-    let value = U256::from_str(&request.value).map_err(|_| "Invalid value".to_string())?;
-    let gas_price = U256::from_str(&request.gas_price).map_err(|_| "Invalid gas price".to_string())?;
-
+    let from = Address::from_str(&request.transaction.from).map_err(|_| "Invalid from".to_string())?;
     let signer = state.wallet_service.get_signer(&from).await.map_err(|e| e.user_message())?;
     let adapter = state.current_adapter().await.map_err(|e| e.user_message())?;
-    let chain_id = adapter.chain_id();
+    let tx_request = request.transaction.to_transaction_request(adapter.chain_id())?;
 
-    use alloy::network::{EthereumWallet, TransactionBuilder};
-    use alloy::rpc::types::TransactionRequest;
+    let envelope = signer.sign_transaction(tx_request).await.map_err(|e| e.to_string())?;
+    Ok(format!("0x{}", hex::encode(envelope.encoded_2718())))
+}
 
-    let mut tx_request = TransactionRequest::default();
-    tx_request.from = Some(from);
-    tx_request.gas = Some(request.gas_limit);
-    tx_request.gas_price = Some(gas_price.to::<u128>());
-    tx_request.nonce = Some(request.nonce);
-    tx_request.chain_id = Some(chain_id);
-    tx_request.value = Some(value);
-
-    if let Some(token_data) = request.data {
-        let input_bytes = hex::decode(token_data).map_err(|_| "Invalid data".to_string())?;
-        tx_request.to = Some(to.into());
-        tx_request.input.input = Some(input_bytes.into());
-    } else {
-        tx_request.to = Some(to.into());
-    }
+/// Export a transaction unsigned
+///
+/// For accounts whose key is not in this wallet (watch-only): the payload
+/// is signed offline and the result sent with `broadcast_signed_transaction`.
+#[tauri::command]
+#[specta::specta]
+pub async fn export_unsigned_transaction(
+    state: State<'_, VaughanState>,
+    request: UnsignedTransactionRequest,
+) -> Result<UnsignedTransactionResponse, String> {
+    use alloy::consensus::SignableTransaction;
 
-    let wallet = EthereumWallet::from(signer);
-    let envelope = tx_request.build(&wallet).await.map_err(|e| format!("Build failed: {}", e))?;
-    Ok(format!("0x{}", hex::encode(envelope.encoded_2718())))
+    let adapter = state.current_adapter().await.map_err(|e| e.user_message())?;
+    let chain_id = adapter.chain_id();
+    let tx_request = request.to_transaction_request(chain_id)?;
+
+    let transaction = serde_json::to_string_pretty(&tx_request).map_err(|e| e.to_string())?;
+    let typed = tx_request
+        .build_typed_tx()
+        .map_err(|_| "Transaction is missing required fields".to_string())?;
+
+    Ok(UnsignedTransactionResponse {
+        transaction,
+        unsigned_tx: format!("0x{}", hex::encode(typed.encoded_for_signing())),
+        signing_hash: format!("{:?}", typed.signature_hash()),
+        chain_id,
+    })
+}
+
+/// Broadcast a transaction signed elsewhere
+///
+/// # Returns
+///
+/// The transaction hash
+#[tauri::command]
+#[specta::specta]
+pub async fn broadcast_signed_transaction(
+    state: State<'_, VaughanState>,
+    signed_tx: String,
+) -> Result<String, String> {
+    state.check_send_health().await.map_err(|e| e.user_message())?;
+
+    let tx_bytes = hex::decode(signed_tx.trim().trim_start_matches("0x")).map_err(|_| "Invalid signed transaction".to_string())?;
+    let adapter = state.current_adapter().await.map_err(|e| e.user_message())?;
+    let pending = adapter.provider().send_raw_transaction(&tx_bytes).await.map_err(|e| e.to_string())?;
+    Ok(format!("{:?}", pending.tx_hash()))
 }
 
 /// Send transaction request
@@ -308,6 +489,9 @@ pub struct SendTransactionRequest {
     pub password: String,
     pub token_address: Option<String>,
     pub data: Option<String>,
+    /// Try `eth_createAccessList` and keep the list if it saves gas
+    #[serde(default)]
+    pub generate_access_list: bool,
 }
 
 /// Transaction response
@@ -326,10 +510,14 @@ pub async fn send_transaction(
 ) -> Result<TransactionResponse, String> {
     state.wallet_service.verify_password(&request.password).await.map_err(|e| e.user_message())?;
 
+    // Refuse to send through an RPC that is far behind the chain head
+    state.check_send_health().await.map_err(|e| e.user_message())?;
+
     let build_req = BuildTransactionRequest {
         from: request.from.clone(), to: request.to.clone(), amount: request.amount.clone(),
         gas_limit: request.gas_limit, gas_price_gwei: request.gas_price_gwei.clone(),
         nonce: None, token_address: request.token_address.clone(), data: request.data.clone(),
+        generate_access_list: request.generate_access_list,
     };
     let built_tx = build_transaction(state.clone(), build_req).await?;
 
@@ -340,9 +528,13 @@ pub async fn send_transaction(
     };
 
     let sign_req = SignTransactionRequest {
-        from: request.from.clone(), to: to_sign, value: val_sign,
-        gas_limit: built_tx.gas_limit, gas_price: built_tx.gas_price.clone(),
-        nonce: built_tx.nonce, password: request.password.clone(), data: built_tx.data.clone(),
+        transaction: UnsignedTransactionRequest {
+            from: request.from.clone(), to: to_sign, value: val_sign,
+            gas_limit: built_tx.gas_limit, gas_price: built_tx.gas_price.clone(),
+            nonce: built_tx.nonce, data: built_tx.data.clone(),
+            access_list: built_tx.access_list.clone(),
+        },
+        password: request.password.clone(),
     };
     let signed_tx = sign_transaction(state.clone(), sign_req).await?;
 
@@ -355,3 +547,49 @@ pub async fn send_transaction(
         details: built_tx,
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(address: &str, storage_keys: &[&str]) -> AccessListItemExport {
+        AccessListItemExport {
+            address: address.to_string(),
+            storage_keys: storage_keys.iter().map(|k| k.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_access_list_export_round_trip() {
+        let list = AccessList::from(vec![AccessListItem {
+            address: Address::repeat_byte(0x11),
+            storage_keys: vec![B256::repeat_byte(0x22), B256::ZERO],
+        }]);
+
+        let exported = access_list_to_export(&list);
+        assert_eq!(exported.len(), 1);
+        assert_eq!(exported[0].storage_keys.len(), 2);
+        assert_eq!(access_list_from_export(&exported).unwrap(), list);
+    }
+
+    #[test]
+    fn test_access_list_from_export_empty() {
+        assert!(access_list_from_export(&[]).unwrap().0.is_empty());
+    }
+
+    #[test]
+    fn test_access_list_from_export_rejects_bad_address() {
+        let err = access_list_from_export(&[item("0x1234", &[])]).unwrap_err();
+        assert!(err.contains("Invalid access list address"));
+    }
+
+    #[test]
+    fn test_access_list_from_export_rejects_bad_storage_key() {
+        let address = "0x1111111111111111111111111111111111111111";
+        let err = access_list_from_export(&[item(address, &["0x01"])]).unwrap_err();
+        assert!(err.contains("Invalid storage key"));
+
+        let err = access_list_from_export(&[item(address, &["not hex"])]).unwrap_err();
+        assert!(err.contains("Invalid storage key"));
+    }
+}
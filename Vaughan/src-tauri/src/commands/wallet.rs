@@ -19,12 +19,30 @@
 //! - `wallet_exists` - Check if wallet exists
 //! - `get_accounts` - Get all accounts
 //! - `create_account` - Create new HD account
+//! - `discover_accounts` - Find and add used accounts of the seed (gap-limit scan)
+//! - `add_hidden_wallet` - Add a hidden wallet (same mnemonic, another BIP-39 passphrase)
 //! - `import_account` - Import account from private key
+//! - `import_account_keystore` - Import account from a V3 keystore JSON
+//! - `export_account_keystore` - Export an account as a V3 keystore JSON
+//! - `add_watch_only_account` - Monitor an address without its key
+//! - `add_external_signer` - Add the accounts of a Clef-compatible external signer
 //! - `delete_account` - Delete account
+//! - `create_seed_shares` - Split the seed into SLIP-39 shares
+//! - `restore_wallet_from_shares` - Restore a wallet from a quorum of SLIP-39 shares
+//! - `get_secret_storage` - Where the encrypted keys are stored
+//! - `migrate_secret_storage` - Move the encrypted keys to another backend
+//! - `change_password` - Change the wallet password (re-encrypts every key)
 
+use crate::core::{DiscoveryOptions, DiscoveryResult};
 use crate::error::WalletError;
-use crate::models::wallet::AccountExport;
+use crate::models::wallet::{AccountExport, DerivationScheme};
+use crate::monitoring::auto_lock::{self, LockReason};
+use crate::security::slip39::ShareGroup;
+use crate::security::SecretBackend;
 use crate::state::VaughanState;
+use secrecy::zeroize::Zeroizing;
+use serde::Serialize;
+use specta::Type;
 use tauri::{AppHandle, Emitter, Manager, State};
 
 /// Create a new wallet with BIP-39 mnemonic
@@ -36,6 +54,7 @@ use tauri::{AppHandle, Emitter, Manager, State};
 ///
 /// * `password` - Password for encrypting the seed
 /// * `word_count` - Number of words (12 or 24)
+/// * `passphrase` - Optional BIP-39 passphrase ("25th word")
 ///
 /// # Returns
 ///
@@ -46,13 +65,16 @@ use tauri::{AppHandle, Emitter, Manager, State};
 /// - Mnemonic is only returned once
 /// - User must save it securely
 /// - Seed is encrypted with password and stored in OS keychain
+/// - The passphrase is stored the same way; without it the mnemonic
+///   restores different accounts
 ///
 /// # Example
 ///
 /// ```typescript
 /// const mnemonic = await invoke('create_wallet', {
 ///   password: 'my_secure_password',
-///   wordCount: 12
+///   wordCount: 12,
+///   passphrase: null
 /// });
 /// console.log('BACKUP THIS MNEMONIC:', mnemonic);
 /// ```
@@ -62,7 +84,12 @@ pub async fn create_wallet(
     state: State<'_, VaughanState>,
     password: String,
     word_count: usize,
+    passphrase: Option<String>,
 ) -> Result<String, WalletError> {
+    // Wiped from memory when the command returns
+    let password = Zeroizing::new(password);
+    let passphrase = passphrase.map(Zeroizing::new);
+
     // Validate inputs
     if password.is_empty() {
         return Err(WalletError::InvalidPassword);
@@ -77,7 +104,7 @@ pub async fn create_wallet(
     // Create wallet
     let mnemonic = state
         .wallet_service
-        .create_wallet(&password, word_count)
+        .create_wallet(&password, word_count, passphrase.as_deref().map(|p| p.as_str()))
         .await?;
 
     // Persist initial accounts
@@ -96,10 +123,12 @@ pub async fn create_wallet(
 /// * `mnemonic` - BIP-39 mnemonic phrase
 /// * `password` - Password for encrypting the seed
 /// * `account_count` - Number of accounts to derive (default: 1)
+/// * `passphrase` - BIP-39 passphrase the mnemonic was used with (e.g. on a hardware wallet)
+/// * `discovery` - Also scan for used accounts (see `discover_accounts`); `null` = no scan
 ///
 /// # Returns
 ///
-/// List of derived account addresses
+/// List of derived account addresses (including discovered ones)
 ///
 /// # Example
 ///
@@ -107,7 +136,9 @@ pub async fn create_wallet(
 /// const addresses = await invoke('import_wallet', {
 ///   mnemonic: 'abandon abandon abandon...',
 ///   password: 'my_secure_password',
-///   accountCount: 3
+///   accountCount: 3,
+///   passphrase: null,
+///   discovery: { schemes: ['bip44', 'ledgerLive'], gap_limit: 5 }
 /// });
 /// ```
 #[tauri::command]
@@ -117,7 +148,13 @@ pub async fn import_wallet(
     mnemonic: String,
     password: String,
     account_count: u32,
+    passphrase: Option<String>,
+    discovery: Option<DiscoveryOptions>,
 ) -> Result<Vec<String>, WalletError> {
+    // Wiped from memory when the command returns
+    let (mnemonic, password) = (Zeroizing::new(mnemonic), Zeroizing::new(password));
+    let passphrase = passphrase.map(Zeroizing::new);
+
     // Validate inputs
     if password.is_empty() {
         return Err(WalletError::InvalidPassword);
@@ -136,11 +173,25 @@ pub async fn import_wallet(
     }
 
     // Import wallet
-    let addresses = state
+    let mut addresses = state
         .wallet_service
-        .import_wallet(&mnemonic, &password, account_count)
+        .import_wallet(&mnemonic, &password, account_count, passphrase.as_deref().map(|p| p.as_str()))
         .await?;
 
+    // The import stands even if the scan fails (it can be rerun)
+    if let Some(options) = discovery {
+        match state.discover_accounts(&password, options).await {
+            Ok(result) => addresses.extend(
+                result
+                    .accounts
+                    .iter()
+                    .filter(|a| a.added)
+                    .filter_map(|a| a.address.parse::<alloy::primitives::Address>().ok()),
+            ),
+            Err(e) => tracing::warn!("[Wallet] Account discovery after import failed: {}", e),
+        }
+    }
+
     // Persist initial accounts
     let _ = state.save_state().await;
 
@@ -157,6 +208,12 @@ pub async fn import_wallet(
 ///
 /// * `password` - Wallet password
 ///
+/// # Errors
+///
+/// * `WalletError::InvalidPassword` - Wrong password (counted by the
+///   unlock throttle, see `security::throttle`)
+/// * `WalletError::UnlockThrottled` - Too many wrong passwords; wait
+///
 /// # Example
 ///
 /// ```typescript
@@ -168,6 +225,8 @@ pub async fn unlock_wallet(
     state: State<'_, VaughanState>,
     password: String,
 ) -> Result<(), WalletError> {
+    // Wiped from memory when the command returns
+    let password = Zeroizing::new(password);
     if password.is_empty() {
         return Err(WalletError::InvalidPassword);
     }
@@ -177,7 +236,8 @@ pub async fn unlock_wallet(
 
 /// Lock wallet
 ///
-/// Clears all keys from memory.
+/// Clears all keys from memory, rejects pending dApp approvals and emits
+/// `WalletLockedEvent`.
 /// User must unlock again to perform key operations.
 ///
 /// # Example
@@ -187,8 +247,8 @@ pub async fn unlock_wallet(
 /// ```
 #[tauri::command]
 #[specta::specta]
-pub async fn lock_wallet(state: State<'_, VaughanState>) -> Result<(), WalletError> {
-    state.lock_wallet().await;
+pub async fn lock_wallet(app: AppHandle) -> Result<(), WalletError> {
+    auto_lock::lock_wallet(&app, LockReason::Manual).await;
     Ok(())
 }
 
@@ -232,6 +292,97 @@ pub async fn wallet_exists(state: State<'_, VaughanState>) -> Result<bool, Walle
     Ok(state.wallet_service.wallet_exists())
 }
 
+/// Secret storage status
+#[derive(Debug, Serialize, Type)]
+pub struct SecretStorageInfo {
+    /// Backend holding the encrypted seed and imported keys
+    pub backend: SecretBackend,
+    /// Whether the OS keychain works on this system
+    pub os_keyring_available: bool,
+}
+
+/// Get where the encrypted keys are stored
+///
+/// The backend is picked at startup: `VAUGHAN_SECRET_STORE` if set, the
+/// encrypted file vault if it holds keys, else the OS keychain if it works,
+/// else the file vault.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_secret_storage(state: State<'_, VaughanState>) -> Result<SecretStorageInfo, WalletError> {
+    Ok(SecretStorageInfo {
+        backend: state.wallet_service.secret_backend(),
+        os_keyring_available: state.wallet_service.os_keyring_available(),
+    })
+}
+
+/// Move the encrypted seed and imported keys to another backend
+///
+/// Keys are copied and verified before they are removed from the old
+/// backend. Requires the wallet to be unlocked.
+///
+/// # Returns
+///
+/// Number of keys moved
+///
+/// # Example
+///
+/// ```typescript
+/// const moved = await invoke('migrate_secret_storage', { backend: 'osKeyring', password });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn migrate_secret_storage(
+    state: State<'_, VaughanState>,
+    backend: SecretBackend,
+    password: String,
+) -> Result<usize, WalletError> {
+    let password = Zeroizing::new(password);
+    if password.is_empty() {
+        return Err(WalletError::InvalidPassword);
+    }
+    state.wallet_service.migrate_secret_storage(backend, &password).await
+}
+
+/// Change the wallet password
+///
+/// Re-encrypts the seed, BIP-39 passphrases and imported keys under the new
+/// password, including accounts unlock could not load. All or nothing: if
+/// any key fails, every key stays under the old password. Wallet must be
+/// unlocked.
+///
+/// # Returns
+///
+/// Number of keys re-encrypted
+///
+/// # Errors
+///
+/// * `WalletError::InvalidPassword` - Wrong current password or empty new one
+/// * `WalletError::WalletLocked` - Wallet is locked
+///
+/// # Example
+///
+/// ```typescript
+/// await invoke('change_password', { oldPassword: 'old', newPassword: 'new' });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn change_password(
+    state: State<'_, VaughanState>,
+    old_password: String,
+    new_password: String,
+) -> Result<usize, WalletError> {
+    let old_password = Zeroizing::new(old_password);
+    let new_password = Zeroizing::new(new_password);
+    if old_password.is_empty() || new_password.is_empty() {
+        return Err(WalletError::InvalidPassword);
+    }
+    let persisted = state.state_manager().load();
+    state
+        .wallet_service
+        .change_password(&old_password, &new_password, &persisted.accounts)
+        .await
+}
+
 /// Get all accounts
 ///
 /// Returns list of all accounts (HD and imported).
@@ -272,7 +423,9 @@ pub async fn get_accounts(state: State<'_, VaughanState>) -> Result<Vec<AccountE
 ///
 /// ```typescript
 /// const account = await invoke('create_account', {
-///   password: 'my_password'
+///   password: 'my_password',
+///   passphraseId: null,
+///   scheme: { custom: "m/44'/60'/0'/1/{i}" } // or 'bip44' (default), 'ledgerLive', 'legacyMew'
 /// });
 /// console.log('New account:', account.address);
 /// ```
@@ -281,12 +434,98 @@ pub async fn get_accounts(state: State<'_, VaughanState>) -> Result<Vec<AccountE
 pub async fn create_account(
     state: State<'_, VaughanState>,
     password: String,
+    passphrase_id: Option<String>,
+    scheme: Option<DerivationScheme>,
 ) -> Result<AccountExport, WalletError> {
     if password.is_empty() {
         return Err(WalletError::InvalidPassword);
     }
 
-    let account = state.wallet_service.create_account(&password).await?;
+    let account = state
+        .wallet_service
+        .create_account(&password, passphrase_id.as_deref(), &scheme.unwrap_or_default())
+        .await?;
+    let _ = state.save_state().await;
+    Ok(AccountExport::from(account))
+}
+
+/// Find and add the used accounts of the wallet seed
+///
+/// Derives addresses on each scheme (BIP-44, Ledger Live, legacy MEW or
+/// custom paths) and checks their nonce and balance on the configured
+/// networks, stopping after `gap_limit` unused addresses in a row. Used
+/// accounts not yet in the wallet are added.
+///
+/// # Example
+///
+/// ```typescript
+/// const result = await invoke('discover_accounts', {
+///   password: 'my_password',
+///   options: { schemes: ['bip44', 'ledgerLive', 'legacyMew'], gap_limit: 5, chain_ids: null, passphrase_id: null }
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn discover_accounts(
+    state: State<'_, VaughanState>,
+    password: String,
+    options: DiscoveryOptions,
+) -> Result<DiscoveryResult, WalletError> {
+    let password = Zeroizing::new(password);
+    if password.is_empty() {
+        return Err(WalletError::InvalidPassword);
+    }
+
+    let result = state.discover_accounts(&password, options).await?;
+    let _ = state.save_state().await;
+    Ok(result)
+}
+
+/// Add a hidden wallet
+///
+/// Derives the first account of the same mnemonic with another BIP-39
+/// passphrase. The passphrase is stored encrypted and used at unlock; more
+/// accounts of the hidden wallet come from `create_account` with its
+/// `passphraseId`.
+///
+/// # Arguments
+///
+/// * `passphrase` - BIP-39 passphrase of the hidden wallet
+/// * `name` - Name of its first account
+/// * `password` - Wallet password
+///
+/// # Example
+///
+/// ```typescript
+/// const account = await invoke('add_hidden_wallet', {
+///   passphrase: 'my hidden passphrase',
+///   name: 'Savings',
+///   password: 'my_password'
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn add_hidden_wallet(
+    state: State<'_, VaughanState>,
+    passphrase: String,
+    name: String,
+    password: String,
+) -> Result<AccountExport, WalletError> {
+    let (passphrase, password) = (Zeroizing::new(passphrase), Zeroizing::new(password));
+    if password.is_empty() {
+        return Err(WalletError::InvalidPassword);
+    }
+
+    if name.trim().is_empty() {
+        return Err(WalletError::InternalError(
+            "Account name is empty".to_string(),
+        ));
+    }
+
+    let account = state
+        .wallet_service
+        .add_hidden_wallet(&passphrase, name, &password)
+        .await?;
     let _ = state.save_state().await;
     Ok(AccountExport::from(account))
 }
@@ -371,6 +610,178 @@ pub async fn import_account(
     Ok(AccountExport::from(account))
 }
 
+/// Import account from a V3 keystore (exactly one of `keystore` / `file_path`)
+///
+/// Reads Web3 Secret Storage files from geth, Foundry `cast wallet` or
+/// MyEtherWallet (scrypt or pbkdf2). Wallet must be unlocked.
+///
+/// # Arguments
+///
+/// * `keystore` - Keystore JSON
+/// * `file_path` - Path to a keystore file
+/// * `keystore_password` - Password the keystore was encrypted with
+/// * `name` - Account name
+/// * `password` - Wallet password (the key is re-encrypted with it)
+///
+/// # Errors
+///
+/// * `WalletError::InvalidPrivateKey` - Not a V3 keystore or unsupported KDF
+/// * `WalletError::DecryptionFailed` - Wrong keystore password
+///
+/// # Example
+///
+/// ```typescript
+/// const account = await invoke('import_account_keystore', {
+///   keystore: null,
+///   filePath: '/home/me/.foundry/keystores/deployer',
+///   keystorePassword: 'keystore_password',
+///   name: 'Deployer',
+///   password: 'my_password'
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn import_account_keystore(
+    state: State<'_, VaughanState>,
+    keystore: Option<String>,
+    file_path: Option<String>,
+    keystore_password: String,
+    name: String,
+    password: String,
+) -> Result<AccountExport, WalletError> {
+    let password = Zeroizing::new(password);
+    let keystore_password = Zeroizing::new(keystore_password);
+    if password.is_empty() {
+        return Err(WalletError::InvalidPassword);
+    }
+
+    if name.trim().is_empty() {
+        return Err(WalletError::InternalError(
+            "Account name is empty".to_string(),
+        ));
+    }
+
+    let json = match (keystore, file_path) {
+        (Some(json), None) => json,
+        (None, Some(path)) => std::fs::read_to_string(&path)
+            .map_err(|e| WalletError::Custom(format!("Cannot read {}: {}", path, e)))?,
+        _ => {
+            return Err(WalletError::Custom(
+                "Provide either the keystore JSON or a file path".to_string(),
+            ))
+        }
+    };
+
+    let account = state
+        .wallet_service
+        .import_keystore(&json, &keystore_password, name, &password)
+        .await?;
+
+    let _ = state.save_state().await;
+    Ok(AccountExport::from(account))
+}
+
+/// Add a watch-only account
+///
+/// Monitors an address (e.g. a cold wallet) without holding its key:
+/// balances, tokens, history and dApp connections work, signing fails with
+/// `WATCH_ONLY_ACCOUNT`. Transactions can be exported unsigned with
+/// `export_unsigned_transaction`. Wallet must be unlocked.
+///
+/// # Arguments
+///
+/// * `address` - Address to watch
+/// * `name` - Account name
+/// * `password` - Wallet password
+///
+/// # Example
+///
+/// ```typescript
+/// const account = await invoke('add_watch_only_account', {
+///   address: '0x742d35Cc6634C0532925a3b844Bc9e7595f0bEb',
+///   name: 'Treasury (cold)',
+///   password: 'my_password'
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn add_watch_only_account(
+    state: State<'_, VaughanState>,
+    address: String,
+    name: String,
+    password: String,
+) -> Result<AccountExport, WalletError> {
+    let password = Zeroizing::new(password);
+    if password.is_empty() {
+        return Err(WalletError::InvalidPassword);
+    }
+
+    if name.trim().is_empty() {
+        return Err(WalletError::InternalError(
+            "Account name is empty".to_string(),
+        ));
+    }
+
+    let address = address
+        .trim()
+        .parse()
+        .map_err(|_| WalletError::InvalidAddress(address.clone()))?;
+
+    let account = state
+        .wallet_service
+        .add_watch_only_account(address, name, &password)
+        .await?;
+
+    let _ = state.save_state().await;
+    Ok(AccountExport::from(account))
+}
+
+/// Add the accounts of an external signer
+///
+/// Connects to a Clef-compatible signer (`account_list`) and adds each of
+/// its accounts. Keys stay in the signer: transactions, messages and typed
+/// data are sent to it and confirmed there, including dApp requests. Raw
+/// hash signatures (Safe, EIP-7702 authorizations) are not supported.
+/// Wallet must be unlocked.
+///
+/// # Arguments
+///
+/// * `url` - `http://127.0.0.1:8550` (`clef --http`) or the path of `clef.ipc`
+/// * `password` - Wallet password
+///
+/// # Returns
+///
+/// The accounts added (ones already in the wallet are skipped)
+///
+/// # Example
+///
+/// ```typescript
+/// const accounts = await invoke('add_external_signer', {
+///   url: '/home/me/.clef/clef.ipc',
+///   password: 'my_password'
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn add_external_signer(
+    state: State<'_, VaughanState>,
+    url: String,
+    password: String,
+) -> Result<Vec<AccountExport>, WalletError> {
+    let password = Zeroizing::new(password);
+    if password.is_empty() {
+        return Err(WalletError::InvalidPassword);
+    }
+
+    let accounts = state
+        .wallet_service
+        .add_external_accounts(&url, &password)
+        .await?;
+
+    let _ = state.save_state().await;
+    Ok(accounts.into_iter().map(AccountExport::from).collect())
+}
+
 /// Delete account
 ///
 /// Removes an account from the wallet.
@@ -451,6 +862,64 @@ pub async fn rename_account(
     Ok(())
 }
 
+/// Export an account as a V3 keystore
+///
+/// Works for HD and imported accounts. The keystore is encrypted (scrypt)
+/// with `keystore_password`, which may differ from the wallet password,
+/// and can be opened by geth, Foundry or MyEtherWallet.
+///
+/// # Arguments
+///
+/// * `address` - Account address to export
+/// * `password` - Wallet password to authorize
+/// * `keystore_password` - Password to encrypt the keystore with
+/// * `file_path` - Also write the keystore to this file (optional)
+///
+/// # Returns
+///
+/// The keystore JSON
+///
+/// # Example
+///
+/// ```typescript
+/// const json = await invoke('export_account_keystore', {
+///   address: '0x1234...',
+///   password: 'my_password',
+///   keystorePassword: 'keystore_password',
+///   filePath: null
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn export_account_keystore(
+    state: State<'_, VaughanState>,
+    address: String,
+    password: String,
+    keystore_password: String,
+    file_path: Option<String>,
+) -> Result<String, WalletError> {
+    let password = Zeroizing::new(password);
+    let keystore_password = Zeroizing::new(keystore_password);
+    if password.is_empty() || keystore_password.is_empty() {
+        return Err(WalletError::InvalidPassword);
+    }
+
+    let parsed_address = address
+        .parse()
+        .map_err(|_| WalletError::InvalidAddress(address.clone()))?;
+
+    let json = state
+        .wallet_service
+        .export_keystore(&parsed_address, &password, &keystore_password)
+        .await?;
+
+    if let Some(path) = file_path {
+        std::fs::write(&path, &json)
+            .map_err(|e| WalletError::Custom(format!("Cannot write {}: {}", path, e)))?;
+    }
+    Ok(json)
+}
+
 /// Export private key for an account
 ///
 /// Returns the raw private key for a specific account.
@@ -620,6 +1089,101 @@ pub async fn export_mnemonic(
     state.wallet_service.export_mnemonic(&password).await
 }
 
+/// SLIP-39 shares of the seed
+#[derive(Debug, Serialize, Type)]
+pub struct SeedSharesResponse {
+    /// Member shares of each group (space-separated words)
+    pub groups: Vec<Vec<String>>,
+    /// First account (`m/44'/60'/0'/0/0`), to note with the shares;
+    /// required by `restore_wallet_from_shares`
+    pub first_account: String,
+}
+
+/// Split the seed into SLIP-39 shares
+///
+/// Each group needs `threshold` of its `count` shares, and
+/// `group_threshold` groups are needed to restore. Splits where one share
+/// would be enough are rejected. A BIP-39 passphrase is not included.
+///
+/// # Example
+///
+/// ```typescript
+/// // 2-of-3 shares
+/// const { groups, firstAccount } = await invoke('create_seed_shares', {
+///   password: 'my_password',
+///   groupThreshold: 1,
+///   groups: [{ threshold: 2, count: 3 }]
+/// });
+/// ```
+#[tauri::command]
+#[specta::specta]
+pub async fn create_seed_shares(
+    state: State<'_, VaughanState>,
+    password: String,
+    group_threshold: u8,
+    groups: Vec<ShareGroup>,
+) -> Result<SeedSharesResponse, WalletError> {
+    let password = Zeroizing::new(password);
+    if password.is_empty() {
+        return Err(WalletError::InvalidPassword);
+    }
+
+    let (groups, first_account) = state
+        .wallet_service
+        .create_seed_shares(&password, group_threshold, &groups)
+        .await?;
+    Ok(SeedSharesResponse {
+        groups,
+        first_account: format!("{:?}", first_account),
+    })
+}
+
+/// Restore a wallet from a quorum of SLIP-39 shares
+///
+/// Works like `import_wallet` with the combined seed, after checking that
+/// it derives `first_account`.
+///
+/// # Arguments
+///
+/// * `shares` - Shares (space-separated words each), from enough groups
+/// * `password` - Password for encrypting the seed
+/// * `passphrase` - BIP-39 passphrase, if the wallet had one
+/// * `first_account` - First account noted when the shares were made
+///
+/// # Errors
+///
+/// * `WalletError::InvalidMnemonic` - Bad share or not enough shares
+/// * `WalletError::Custom` - The shares restore a different wallet, or a
+///   wallet already exists (reset it first)
+#[tauri::command]
+#[specta::specta]
+pub async fn restore_wallet_from_shares(
+    state: State<'_, VaughanState>,
+    shares: Vec<String>,
+    password: String,
+    passphrase: Option<String>,
+    first_account: String,
+) -> Result<Vec<String>, WalletError> {
+    let shares = Zeroizing::new(shares);
+    let password = Zeroizing::new(password);
+    let passphrase = passphrase.map(Zeroizing::new);
+    if password.is_empty() {
+        return Err(WalletError::InvalidPassword);
+    }
+
+    let first_account = first_account
+        .parse()
+        .map_err(|_| WalletError::InvalidAddress(first_account.clone()))?;
+
+    let addresses = state
+        .wallet_service
+        .restore_from_shares(&shares, &password, passphrase.as_deref().map(|p| p.as_str()), first_account)
+        .await?;
+
+    let _ = state.save_state().await;
+    Ok(addresses.iter().map(|addr| addr.to_string()).collect())
+}
+
 /// Get the Railgun Mnemonic
 ///
 /// Derives a deterministic 24-word mnemonic specifically for the Railgun Privacy Engine
@@ -662,6 +1226,6 @@ pub async fn set_focused_asset(
 #[tauri::command]
 #[specta::specta]
 pub async fn report_activity(state: State<'_, VaughanState>) -> Result<(), ()> {
-    *state.last_activity.lock().await = std::time::Instant::now();
+    state.activity.touch();
     Ok(())
 }
@@ -0,0 +1,338 @@
+// ============================================================================
+// Vaughan Wallet - Address Book
+// ============================================================================
+//
+// Named contacts, optionally scoped to one chain. Labels from the address
+// book are shown in history, dApp transaction approvals and send
+// validation.
+//
+// CSV format (header required on import):
+//
+//   name,address,chain_id,notes,tags
+//   Alice,0x...,,Payroll,team;monthly
+//
+// Empty `chain_id` = all chains; tags are `;`-separated.
+//
+// ============================================================================
+
+use alloy::primitives::Address;
+use serde::Serialize;
+use specta::Type;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::core::csv::{join_line, split_line};
+use crate::error::WalletError;
+use crate::models::contact::Contact;
+
+/// CSV header of exported address books
+pub const CSV_HEADER: &str = "name,address,chain_id,notes,tags";
+
+/// Contact fields as entered by the user
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContactInput {
+    pub name: String,
+    pub address: String,
+    pub chain_id: Option<u64>,
+    pub notes: String,
+    pub tags: Vec<String>,
+}
+
+/// Result of a CSV import
+#[derive(Debug, Clone, Default, Serialize, PartialEq, Type)]
+pub struct ContactImportResult {
+    pub added: u32,
+    pub updated: u32,
+    /// Rejected lines (`Line N: reason`)
+    pub errors: Vec<String>,
+}
+
+/// Address book service - validates contacts, looks up labels, CSV import/export
+///
+/// # Design
+///
+/// - **Stateless**: Contacts live in `PersistedState`; methods take the list
+/// - **Unique**: One contact per (address, chain scope)
+pub struct AddressBookService;
+
+impl AddressBookService {
+    /// Create new address book service
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Validate and normalise contact input
+    pub fn normalize(&self, input: ContactInput) -> Result<ContactInput, WalletError> {
+        let name = input.name.trim().to_string();
+        if name.is_empty() {
+            return Err(WalletError::Custom("Contact name is required".to_string()));
+        }
+        let address: Address = input
+            .address
+            .trim()
+            .parse()
+            .map_err(|_| WalletError::InvalidAddress(input.address.clone()))?;
+        if address == Address::ZERO {
+            return Err(WalletError::InvalidAddress("Zero address".to_string()));
+        }
+
+        let mut tags: Vec<String> = Vec::new();
+        for tag in input.tags.iter().map(|t| t.trim()).filter(|t| !t.is_empty()) {
+            if !tags.iter().any(|t| t.eq_ignore_ascii_case(tag)) {
+                tags.push(tag.to_string());
+            }
+        }
+
+        Ok(ContactInput {
+            name,
+            address: address.to_checksum(None),
+            chain_id: input.chain_id,
+            notes: input.notes.trim().to_string(),
+            tags,
+        })
+    }
+
+    /// Add a contact
+    ///
+    /// # Errors
+    ///
+    /// * `WalletError::Custom` - A contact with the same address and chain scope exists
+    pub fn add(&self, contacts: &mut Vec<Contact>, input: ContactInput) -> Result<Contact, WalletError> {
+        let input = self.normalize(input)?;
+        if let Some(existing) = self.find_exact(contacts, &input.address, input.chain_id) {
+            return Err(WalletError::Custom(format!(
+                "{} is already in the address book as {}",
+                input.address, existing.name
+            )));
+        }
+
+        let contact = Contact {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: input.name,
+            address: input.address,
+            chain_id: input.chain_id,
+            notes: input.notes,
+            tags: input.tags,
+            created_at: now(),
+        };
+        contacts.push(contact.clone());
+        Ok(contact)
+    }
+
+    /// Update a contact
+    pub fn update(&self, contacts: &mut [Contact], id: &str, input: ContactInput) -> Result<Contact, WalletError> {
+        let input = self.normalize(input)?;
+        if let Some(existing) = self.find_exact(contacts, &input.address, input.chain_id) {
+            if existing.id != id {
+                return Err(WalletError::Custom(format!(
+                    "{} is already in the address book as {}",
+                    input.address, existing.name
+                )));
+            }
+        }
+
+        let contact = contacts
+            .iter_mut()
+            .find(|c| c.id == id)
+            .ok_or_else(|| WalletError::Custom(format!("Contact not found: {}", id)))?;
+        contact.name = input.name;
+        contact.address = input.address;
+        contact.chain_id = input.chain_id;
+        contact.notes = input.notes;
+        contact.tags = input.tags;
+        Ok(contact.clone())
+    }
+
+    fn find_exact<'a>(&self, contacts: &'a [Contact], address: &str, chain_id: Option<u64>) -> Option<&'a Contact> {
+        contacts
+            .iter()
+            .find(|c| c.chain_id == chain_id && c.address.eq_ignore_ascii_case(address))
+    }
+
+    /// Contact for an address on a chain
+    ///
+    /// A contact scoped to the chain wins over one for all chains.
+    pub fn lookup<'a>(&self, contacts: &'a [Contact], chain_id: u64, address: &str) -> Option<&'a Contact> {
+        let address = address.trim();
+        let matches = |c: &&Contact| c.address.eq_ignore_ascii_case(address);
+        contacts
+            .iter()
+            .filter(matches)
+            .find(|c| c.chain_id == Some(chain_id))
+            .or_else(|| contacts.iter().filter(matches).find(|c| c.chain_id.is_none()))
+    }
+
+    /// Import contacts from CSV
+    ///
+    /// Rows matching an existing contact (address and chain scope) update
+    /// it; bad rows are reported and skipped.
+    pub fn import_csv(&self, contacts: &mut Vec<Contact>, csv: &str) -> Result<ContactImportResult, WalletError> {
+        let mut lines = csv
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty());
+
+        let header = lines
+            .next()
+            .map(|(_, line)| split_line(line))
+            .ok_or_else(|| WalletError::ParseError("CSV is empty".to_string()))?;
+        let column = |name: &str| header.iter().position(|h| h.eq_ignore_ascii_case(name));
+        let (Some(name_col), Some(address_col)) = (column("name"), column("address")) else {
+            return Err(WalletError::ParseError(
+                "CSV needs a header with at least `name` and `address`".to_string(),
+            ));
+        };
+        let (chain_col, notes_col, tags_col) = (column("chain_id"), column("notes"), column("tags"));
+
+        let mut result = ContactImportResult::default();
+        for (index, line) in lines {
+            let fields = split_line(line);
+            let field = |col: Option<usize>| col.and_then(|i| fields.get(i)).cloned().unwrap_or_default();
+
+            let chain = field(chain_col);
+            let chain_id = if chain.is_empty() {
+                None
+            } else {
+                match chain.parse::<u64>() {
+                    Ok(id) => Some(id),
+                    Err(_) => {
+                        result.errors.push(format!("Line {}: invalid chain_id {}", index + 1, chain));
+                        continue;
+                    }
+                }
+            };
+            let input = ContactInput {
+                name: field(Some(name_col)),
+                address: field(Some(address_col)),
+                chain_id,
+                notes: field(notes_col),
+                tags: field(tags_col).split(';').map(String::from).collect(),
+            };
+
+            let outcome = match self.normalize(input.clone()) {
+                Ok(normalized) => match self.find_exact(contacts, &normalized.address, chain_id) {
+                    Some(existing) => {
+                        let id = existing.id.clone();
+                        self.update(contacts, &id, input).map(|_| false)
+                    }
+                    None => self.add(contacts, input).map(|_| true),
+                },
+                Err(e) => Err(e),
+            };
+            match outcome {
+                Ok(true) => result.added += 1,
+                Ok(false) => result.updated += 1,
+                Err(e) => result.errors.push(format!("Line {}: {}", index + 1, e.user_message())),
+            }
+        }
+        Ok(result)
+    }
+
+    /// Export contacts as CSV (with header)
+    pub fn export_csv(&self, contacts: &[Contact]) -> String {
+        let mut csv = String::from(CSV_HEADER);
+        csv.push('\n');
+        for contact in contacts {
+            let chain = contact.chain_id.map(|id| id.to_string()).unwrap_or_default();
+            csv.push_str(&join_line(&[
+                contact.name.as_str(),
+                contact.address.as_str(),
+                chain.as_str(),
+                contact.notes.as_str(),
+                contact.tags.join(";").as_str(),
+            ]));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+impl Default for AddressBookService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "0x1111111111111111111111111111111111111111";
+
+    fn input(name: &str, address: &str, chain_id: Option<u64>) -> ContactInput {
+        ContactInput {
+            name: name.to_string(),
+            address: address.to_string(),
+            chain_id,
+            notes: String::new(),
+            tags: vec![" team ".to_string(), "Team".to_string(), String::new()],
+        }
+    }
+
+    #[test]
+    fn test_add_normalizes_and_rejects_duplicates() {
+        let service = AddressBookService::new();
+        let mut contacts = Vec::new();
+        let alice = service.add(&mut contacts, input(" Alice ", ALICE, None)).unwrap();
+        assert_eq!(alice.name, "Alice");
+        assert_eq!(alice.tags, vec!["team"]);
+
+        assert!(service.add(&mut contacts, input("Alice 2", ALICE, None)).is_err());
+        assert!(service.add(&mut contacts, input("Alice on L2", ALICE, Some(10))).is_ok());
+        assert!(service.add(&mut contacts, input("", ALICE, Some(1))).is_err());
+        assert!(service.add(&mut contacts, input("Bad", "0x12", None)).is_err());
+    }
+
+    #[test]
+    fn test_lookup_prefers_chain_scope() {
+        let service = AddressBookService::new();
+        let mut contacts = Vec::new();
+        service.add(&mut contacts, input("Alice", ALICE, None)).unwrap();
+        service.add(&mut contacts, input("Alice (Optimism)", ALICE, Some(10))).unwrap();
+
+        assert_eq!(service.lookup(&contacts, 10, ALICE).unwrap().name, "Alice (Optimism)");
+        assert_eq!(service.lookup(&contacts, 1, ALICE).unwrap().name, "Alice");
+        assert!(service.lookup(&contacts, 1, "0x2222222222222222222222222222222222222222").is_none());
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let service = AddressBookService::new();
+        let mut contacts = Vec::new();
+        let csv = format!(
+            "name,address,chain_id,notes,tags\n\
+             \"Alice, Inc.\",{},1,\"Pays \"\"net 30\"\"\",team;vendor\n\
+             Bob,0x12,,,\n\
+             Carol,0x3333333333333333333333333333333333333333,abc,,\n",
+            ALICE
+        );
+        let result = service.import_csv(&mut contacts, &csv).unwrap();
+        assert_eq!(result.added, 1);
+        assert_eq!(result.errors.len(), 2);
+        assert_eq!(contacts[0].notes, "Pays \"net 30\"");
+        assert_eq!(contacts[0].tags, vec!["team", "vendor"]);
+
+        let exported = service.export_csv(&contacts);
+        let mut reimported = Vec::new();
+        service.import_csv(&mut reimported, &exported).unwrap();
+        assert_eq!(reimported[0].name, contacts[0].name);
+        assert_eq!(reimported[0].chain_id, Some(1));
+
+        // Same address and scope updates instead of duplicating
+        let result = service.import_csv(&mut reimported, &exported).unwrap();
+        assert_eq!((result.added, result.updated), (0, 1));
+        assert_eq!(reimported.len(), 1);
+    }
+
+    #[test]
+    fn test_import_requires_header() {
+        let service = AddressBookService::new();
+        assert!(service.import_csv(&mut Vec::new(), &format!("Alice,{}\n", ALICE)).is_err());
+    }
+}
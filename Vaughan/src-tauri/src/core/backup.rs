@@ -0,0 +1,261 @@
+// ============================================================================
+// Vaughan Wallet - Encrypted Wallet Backup
+// ============================================================================
+//
+// One file to move a whole wallet to another machine: the mnemonic, BIP-39
+// passphrases, imported keys, accounts (names, types, paths), custom
+// networks, tracked tokens, contacts and preferences.
+//
+// File format (JSON):
+//
+//   {
+//     "format": "vaughan-backup",
+//     "version": 1,
+//     "created_at": <unix seconds>,
+//     "checksum": "<keccak256 of the ciphertext, hex>",
+//     "data": "<base64 of encrypt_data(payload, backup password)>"
+//   }
+//
+// The payload is sealed with `security::encryption::encrypt_data` (Argon2id
+// + AES-256-GCM), which authenticates it. The checksum tells a damaged file
+// apart from a wrong password before the password is tried. The payload
+// repeats the version and creation time so the header cannot be swapped.
+//
+// ============================================================================
+
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use alloy::primitives::{keccak256, Address};
+use base64ct::{Base64, Encoding};
+use secrecy::zeroize::{Zeroize, Zeroizing};
+use serde::{Deserialize, Serialize};
+
+use crate::core::network::{NetworkConfig, NetworkOverride};
+use crate::core::persistence::{PersistedState, UserPreferences};
+use crate::error::WalletError;
+use crate::models::contact::Contact;
+use crate::models::token::TrackedToken;
+use crate::models::wallet::Account;
+use crate::security::encryption::{decrypt_data, encrypt_data};
+
+/// `format` field of every backup file
+pub const BACKUP_FORMAT: &str = "vaughan-backup";
+
+/// Current backup version
+pub const BACKUP_VERSION: u32 = 1;
+
+/// Keys and passphrases in a backup (wiped from memory when dropped)
+#[derive(Serialize, Deserialize)]
+pub struct BackupSecrets {
+    pub mnemonic: String,
+
+    /// The wallet's own BIP-39 passphrase
+    #[serde(default)]
+    pub passphrase: Option<String>,
+
+    /// Hidden wallet passphrases by `passphrase_id`
+    #[serde(default)]
+    pub hidden_passphrases: BTreeMap<String, String>,
+
+    /// Imported private keys (hex) by address
+    #[serde(default)]
+    pub imported_keys: BTreeMap<Address, String>,
+}
+
+impl Drop for BackupSecrets {
+    fn drop(&mut self) {
+        self.mnemonic.zeroize();
+        if let Some(passphrase) = self.passphrase.as_mut() {
+            passphrase.zeroize();
+        }
+        self.hidden_passphrases.values_mut().for_each(Zeroize::zeroize);
+        self.imported_keys.values_mut().for_each(Zeroize::zeroize);
+    }
+}
+
+/// Contents of a backup file
+#[derive(Serialize, Deserialize)]
+pub struct WalletBackup {
+    pub version: u32,
+    pub created_at: u64,
+    pub secrets: BackupSecrets,
+    pub accounts: Vec<Account>,
+    #[serde(default)]
+    pub custom_networks: Vec<NetworkConfig>,
+    #[serde(default)]
+    pub network_overrides: Vec<NetworkOverride>,
+    #[serde(default)]
+    pub tracked_tokens: Vec<TrackedToken>,
+    #[serde(default)]
+    pub contacts: Vec<Contact>,
+    pub preferences: UserPreferences,
+}
+
+impl WalletBackup {
+    /// Backup of the current wallet (settings taken from the persisted state)
+    pub fn new(
+        secrets: BackupSecrets,
+        accounts: Vec<Account>,
+        tracked_tokens: Vec<TrackedToken>,
+        persisted: PersistedState,
+    ) -> Self {
+        Self {
+            version: BACKUP_VERSION,
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0),
+            secrets,
+            accounts,
+            custom_networks: persisted.custom_networks,
+            network_overrides: persisted.network_overrides,
+            tracked_tokens,
+            contacts: persisted.contacts,
+            preferences: persisted.preferences,
+        }
+    }
+}
+
+/// Backup file as written to disk
+#[derive(Serialize, Deserialize)]
+struct BackupFile {
+    format: String,
+    version: u32,
+    created_at: u64,
+    checksum: String,
+    data: String,
+}
+
+/// Encrypt a backup under `password` into the file contents
+pub fn seal(backup: &WalletBackup, password: &str) -> Result<String, WalletError> {
+    if password.is_empty() {
+        return Err(WalletError::InvalidPassword);
+    }
+    let payload = Zeroizing::new(
+        serde_json::to_vec(backup)
+            .map_err(|e| WalletError::InternalError(format!("Failed to serialize backup: {}", e)))?,
+    );
+    let ciphertext = encrypt_data(&payload, password)?;
+
+    let file = BackupFile {
+        format: BACKUP_FORMAT.to_string(),
+        version: backup.version,
+        created_at: backup.created_at,
+        checksum: hex::encode(keccak256(&ciphertext)),
+        data: Base64::encode_string(&ciphertext),
+    };
+    serde_json::to_string_pretty(&file)
+        .map_err(|e| WalletError::InternalError(format!("Failed to serialize backup: {}", e)))
+}
+
+/// Check and decrypt backup file contents
+///
+/// # Errors
+///
+/// * `WalletError::Custom` - Not a backup, newer version, or damaged file
+/// * `WalletError::DecryptionFailed` - Wrong backup password
+pub fn open(contents: &str, password: &str) -> Result<WalletBackup, WalletError> {
+    let file: BackupFile = serde_json::from_str(contents)
+        .ok()
+        .filter(|f: &BackupFile| f.format == BACKUP_FORMAT)
+        .ok_or_else(|| WalletError::Custom("Not a Vaughan backup file".to_string()))?;
+    if file.version == 0 || file.version > BACKUP_VERSION {
+        return Err(WalletError::Custom(format!(
+            "Backup version {} is not supported (latest is {})",
+            file.version, BACKUP_VERSION
+        )));
+    }
+
+    let damaged = || WalletError::Custom("Backup file is damaged (checksum mismatch)".to_string());
+    let ciphertext = Base64::decode_vec(&file.data).map_err(|_| damaged())?;
+    if hex::encode(keccak256(&ciphertext)) != file.checksum.trim_start_matches("0x").to_lowercase() {
+        return Err(damaged());
+    }
+
+    // The file is intact, so a failure here is the password
+    let payload = Zeroizing::new(
+        decrypt_data(&ciphertext, password)
+            .map_err(|_| WalletError::DecryptionFailed("Wrong backup password".to_string()))?,
+    );
+    let backup: WalletBackup = serde_json::from_slice(&payload)
+        .map_err(|e| WalletError::Custom(format!("Backup contents are invalid: {}", e)))?;
+    if backup.version != file.version || backup.created_at != file.created_at {
+        return Err(WalletError::Custom(
+            "Backup header does not match its contents".to_string(),
+        ));
+    }
+    Ok(backup)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::wallet::AccountType;
+
+    fn sample() -> WalletBackup {
+        let mut secrets = BackupSecrets {
+            mnemonic: "test test test test test test test test test test test junk".to_string(),
+            passphrase: Some("25th word".to_string()),
+            hidden_passphrases: BTreeMap::new(),
+            imported_keys: BTreeMap::new(),
+        };
+        secrets.imported_keys.insert(Address::repeat_byte(0x11), "ab".repeat(32));
+        let mut persisted = PersistedState::default();
+        persisted.preferences.theme = "light".to_string();
+        let account = Account {
+            address: Address::repeat_byte(0x11),
+            name: "Imported".to_string(),
+            account_type: AccountType::Imported,
+            index: None,
+            derivation_path: None,
+            passphrase_id: None,
+            smart_account: None,
+            safe: None,
+            external_signer: None,
+        };
+        WalletBackup::new(secrets, vec![account], Vec::new(), persisted)
+    }
+
+    #[test]
+    fn test_seal_and_open() {
+        let sealed = seal(&sample(), "backup pw").unwrap();
+        assert!(!sealed.contains("test junk"));
+
+        let backup = open(&sealed, "backup pw").unwrap();
+        assert_eq!(backup.version, BACKUP_VERSION);
+        assert_eq!(backup.secrets.passphrase.as_deref(), Some("25th word"));
+        assert_eq!(backup.secrets.imported_keys[&Address::repeat_byte(0x11)], "ab".repeat(32));
+        assert_eq!(backup.accounts[0].name, "Imported");
+        assert_eq!(backup.preferences.theme, "light");
+
+        assert!(matches!(open(&sealed, "wrong"), Err(WalletError::DecryptionFailed(_))));
+    }
+
+    #[test]
+    fn test_open_rejects_bad_files() {
+        let sealed = seal(&sample(), "backup pw").unwrap();
+        let file: serde_json::Value = serde_json::from_str(&sealed).unwrap();
+
+        assert!(open("{}", "backup pw").is_err());
+        assert!(open("not json", "backup pw").is_err());
+
+        let mut newer = file.clone();
+        newer["version"] = (BACKUP_VERSION + 1).into();
+        let err = open(&newer.to_string(), "backup pw").unwrap_err();
+        assert!(err.to_string().contains("not supported"));
+
+        // Flip one ciphertext byte: caught by the checksum, not the password
+        let mut ciphertext = Base64::decode_vec(file["data"].as_str().unwrap()).unwrap();
+        ciphertext[40] ^= 1;
+        let mut damaged = file.clone();
+        damaged["data"] = Base64::encode_string(&ciphertext).into();
+        let err = open(&damaged.to_string(), "backup pw").unwrap_err();
+        assert!(err.to_string().contains("damaged"));
+
+        let mut swapped = file;
+        swapped["created_at"] = 1.into();
+        let err = open(&swapped.to_string(), "backup pw").unwrap_err();
+        assert!(err.to_string().contains("does not match"));
+    }
+}
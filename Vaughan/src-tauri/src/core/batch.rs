@@ -0,0 +1,581 @@
+// ============================================================================
+// Vaughan Wallet - Batch Payouts
+// ============================================================================
+//
+// Pays many recipients from one account, from a CSV of
+// `recipient,amount[,token]` rows (amounts human-readable; empty token =
+// native currency).
+//
+// A batch is a persisted job with a status per row, so it can be resumed
+// after a failure or restart:
+//
+// - Sequential: one transaction per row with consecutive nonces
+// - Disperse: one Disperse contract call per asset (plus an `approve` for
+//   tokens)
+//
+// ============================================================================
+
+use alloy::primitives::{Address, U256};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::chains::evm::disperse::{disperse_gas, APPROVE_GAS};
+use crate::chains::evm::utils::parse_eth_to_wei;
+use crate::chains::types::EvmTransaction;
+use crate::core::csv::split_line;
+use crate::core::TransactionService;
+use crate::error::WalletError;
+
+/// Gas of a native transfer
+pub const NATIVE_TRANSFER_GAS: u64 = 21_000;
+
+/// Typical gas of an ERC20 `transfer` (used for the up-front estimate only)
+pub const TOKEN_TRANSFER_GAS: u64 = 65_000;
+
+/// Symbol and decimals per asset (`None` = native currency)
+pub type BatchAssets = HashMap<Option<Address>, (String, u8)>;
+
+/// How a batch is sent
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchMode {
+    /// One transaction per row
+    Sequential,
+    /// One Disperse contract call per asset
+    Disperse,
+}
+
+/// Status of a batch row
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum BatchRowStatus {
+    /// Failed validation; the batch can't run until the CSV is fixed
+    Invalid,
+    /// Not sent yet
+    Pending,
+    /// Sent, waiting for a receipt
+    Sent,
+    /// Included and succeeded
+    Confirmed,
+    /// Included but reverted (not retried automatically)
+    Reverted,
+    /// Could not be sent (retried when the batch is resumed, unless its
+    /// nonce was used in the meantime)
+    Failed,
+}
+
+/// One payout
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Type)]
+pub struct BatchRow {
+    /// Line in the CSV (1-based)
+    pub line: u32,
+    pub recipient: String,
+    /// Amount as given in the CSV
+    pub amount: String,
+    /// Token contract (`null` = native currency)
+    pub token: Option<String>,
+    /// Amount in the asset's smallest unit
+    pub amount_raw: String,
+    pub status: BatchRowStatus,
+    pub tx_hash: Option<String>,
+    /// Nonce of the transaction paying this row, recorded before each
+    /// send attempt
+    pub nonce: Option<u64>,
+    pub error: Option<String>,
+}
+
+/// Total paid out per asset
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Type)]
+pub struct BatchTotal {
+    /// Token contract (`null` = native currency)
+    pub token: Option<String>,
+    pub symbol: String,
+    pub decimals: u8,
+    /// Total in the smallest unit
+    pub amount_raw: String,
+}
+
+/// Batch payout job
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Type)]
+pub struct BatchJob {
+    pub id: String,
+    /// Paying account
+    pub from: String,
+    pub chain_id: u64,
+    pub mode: BatchMode,
+    pub rows: Vec<BatchRow>,
+    pub totals: Vec<BatchTotal>,
+    /// Estimated gas cost of the whole batch (wei)
+    pub estimated_fee: String,
+    /// Unix timestamp (seconds)
+    pub created_at: u64,
+    pub updated_at: u64,
+}
+
+/// CSV row before validation
+#[derive(Debug, Clone, PartialEq)]
+pub struct CsvRow {
+    pub line: u32,
+    pub recipient: String,
+    pub amount: String,
+    pub token: Option<String>,
+}
+
+/// Batch service - parses, validates and summarises payout batches
+///
+/// # Design
+///
+/// - **Stateless**: Jobs are persisted and sent by the caller
+/// - **Validation**: Every row goes through `TransactionService`
+pub struct BatchService;
+
+impl BatchService {
+    /// Create new batch service
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse `recipient,amount[,token]` CSV
+    ///
+    /// Skips blank lines, `#` comments and a header row. Fields may be
+    /// double-quoted.
+    pub fn parse_csv(&self, csv: &str) -> Result<Vec<CsvRow>, WalletError> {
+        let mut rows = Vec::new();
+        for (index, line) in csv.lines().enumerate() {
+            let line_no = index as u32 + 1;
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            let fields = split_line(trimmed);
+            if fields.len() < 2 || fields.len() > 3 {
+                return Err(WalletError::ParseError(format!(
+                    "Line {}: expected recipient,amount[,token]",
+                    line_no
+                )));
+            }
+
+            // Header: the amount column isn't a number
+            if rows.is_empty() && fields[1].parse::<f64>().is_err() && !fields[0].starts_with("0x") {
+                continue;
+            }
+
+            rows.push(CsvRow {
+                line: line_no,
+                recipient: fields[0].clone(),
+                amount: fields[1].clone(),
+                token: fields.get(2).filter(|t| !t.is_empty()).cloned(),
+            });
+        }
+
+        if rows.is_empty() {
+            return Err(WalletError::ParseError("CSV has no payout rows".to_string()));
+        }
+        Ok(rows)
+    }
+
+    /// Validate CSV rows and build a job
+    ///
+    /// `assets` must hold the native currency and every token contract in
+    /// the CSV that could be read. Invalid rows are kept with status
+    /// `Invalid` and an error.
+    pub fn create_job(
+        &self,
+        transaction_service: &TransactionService,
+        from: Address,
+        chain_id: u64,
+        mode: BatchMode,
+        rows: Vec<CsvRow>,
+        assets: &BatchAssets,
+    ) -> BatchJob {
+        let rows = rows
+            .into_iter()
+            .map(|row| self.validate_row(transaction_service, from, chain_id, row, assets))
+            .collect::<Vec<_>>();
+        let totals = self.totals(&rows, assets);
+        let now = now();
+
+        BatchJob {
+            id: uuid::Uuid::new_v4().to_string(),
+            from: format!("{:?}", from),
+            chain_id,
+            mode,
+            rows,
+            totals,
+            estimated_fee: "0".to_string(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    fn validate_row(
+        &self,
+        transaction_service: &TransactionService,
+        from: Address,
+        chain_id: u64,
+        row: CsvRow,
+        assets: &BatchAssets,
+    ) -> BatchRow {
+        let mut batch_row = BatchRow {
+            line: row.line,
+            recipient: row.recipient.clone(),
+            amount: row.amount.clone(),
+            token: row.token.clone(),
+            amount_raw: "0".to_string(),
+            status: BatchRowStatus::Pending,
+            tx_hash: None,
+            nonce: None,
+            error: None,
+        };
+
+        let result = (|| -> Result<(Address, Option<Address>, U256), WalletError> {
+            let recipient: Address = row
+                .recipient
+                .parse()
+                .map_err(|_| WalletError::InvalidAddress(row.recipient.clone()))?;
+            let token = row
+                .token
+                .as_deref()
+                .map(|t| t.parse::<Address>().map_err(|_| WalletError::InvalidAddress(t.to_string())))
+                .transpose()?;
+            let decimals = assets
+                .get(&token)
+                .map(|(_, decimals)| *decimals)
+                .ok_or_else(|| {
+                    WalletError::InvalidAddress(format!(
+                        "{} is not a token",
+                        row.token.as_deref().unwrap_or_default()
+                    ))
+                })?;
+            let amount = parse_eth_to_wei(&row.amount, decimals)?;
+            if amount.is_zero() {
+                return Err(WalletError::InvalidAmount("Amount must be greater than zero".to_string()));
+            }
+
+            // Validate the payout itself (recipient, amount) with the
+            // wallet's usual send rules
+            transaction_service.validate_evm_transaction(&EvmTransaction {
+                from: format!("{:?}", from),
+                to: format!("{:?}", recipient),
+                value: amount.to_string(),
+                data: None,
+                gas_limit: None,
+                gas_price: None,
+                max_fee_per_gas: None,
+                max_priority_fee_per_gas: None,
+                nonce: None,
+                chain_id,
+                authorization_list: None,
+            })?;
+            if recipient == from {
+                return Err(WalletError::InvalidAddress("Recipient is the paying account".to_string()));
+            }
+            Ok((recipient, token, amount))
+        })();
+
+        match result {
+            Ok((recipient, token, amount)) => {
+                batch_row.recipient = format!("{:?}", recipient);
+                batch_row.token = token.map(|t| format!("{:?}", t));
+                batch_row.amount_raw = amount.to_string();
+            }
+            Err(e) => {
+                batch_row.status = BatchRowStatus::Invalid;
+                batch_row.error = Some(e.user_message());
+            }
+        }
+        batch_row
+    }
+
+    /// Total per asset over all valid rows
+    pub fn totals(&self, rows: &[BatchRow], assets: &BatchAssets) -> Vec<BatchTotal> {
+        let mut totals: Vec<BatchTotal> = Vec::new();
+        for row in rows.iter().filter(|r| r.status != BatchRowStatus::Invalid) {
+            let amount = row.amount_raw.parse::<U256>().unwrap_or_default();
+            match totals.iter_mut().find(|t| t.token == row.token) {
+                Some(total) => {
+                    let sum = total.amount_raw.parse::<U256>().unwrap_or_default() + amount;
+                    total.amount_raw = sum.to_string();
+                }
+                None => {
+                    let asset = row.token.as_deref().and_then(|t| t.parse::<Address>().ok());
+                    let (symbol, decimals) = assets
+                        .get(&asset)
+                        .cloned()
+                        .unwrap_or_else(|| ("?".to_string(), 18));
+                    totals.push(BatchTotal {
+                        token: row.token.clone(),
+                        symbol,
+                        decimals,
+                        amount_raw: amount.to_string(),
+                    });
+                }
+            }
+        }
+        totals
+    }
+}
+
+impl Default for BatchService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BatchJob {
+    /// Whether any row failed validation
+    pub fn has_invalid_rows(&self) -> bool {
+        self.rows.iter().any(|r| r.status == BatchRowStatus::Invalid)
+    }
+
+    /// Whether every row is settled (confirmed, reverted or invalid)
+    pub fn is_finished(&self) -> bool {
+        self.rows.iter().all(|r| {
+            matches!(
+                r.status,
+                BatchRowStatus::Confirmed | BatchRowStatus::Reverted | BatchRowStatus::Invalid
+            )
+        })
+    }
+
+    /// Pending row indices grouped by asset (in CSV order)
+    pub fn pending_groups(&self) -> Vec<(Option<String>, Vec<usize>)> {
+        let mut groups: Vec<(Option<String>, Vec<usize>)> = Vec::new();
+        for (index, row) in self.rows.iter().enumerate() {
+            if row.status != BatchRowStatus::Pending {
+                continue;
+            }
+            match groups.iter_mut().find(|(token, _)| *token == row.token) {
+                Some((_, indices)) => indices.push(index),
+                None => groups.push((row.token.clone(), vec![index])),
+            }
+        }
+        groups
+    }
+
+    /// Sum of `amount_raw` over some rows
+    pub fn sum(&self, indices: &[usize]) -> U256 {
+        indices
+            .iter()
+            .map(|&i| self.rows[i].amount_raw.parse::<U256>().unwrap_or_default())
+            .fold(U256::ZERO, |acc, amount| acc + amount)
+    }
+
+    /// Gas needed to send the pending rows
+    pub fn estimated_gas(&self) -> u64 {
+        self.pending_groups()
+            .iter()
+            .map(|(token, indices)| match (self.mode, token) {
+                (BatchMode::Sequential, None) => NATIVE_TRANSFER_GAS * indices.len() as u64,
+                (BatchMode::Sequential, Some(_)) => TOKEN_TRANSFER_GAS * indices.len() as u64,
+                (BatchMode::Disperse, None) => disperse_gas(indices.len()),
+                (BatchMode::Disperse, Some(_)) => APPROVE_GAS + disperse_gas(indices.len()),
+            })
+            .sum()
+    }
+
+    /// Next nonce to use: the on-chain count, or past the last sent row if
+    /// those transactions are still pending
+    pub fn next_nonce(&self, on_chain: u64) -> u64 {
+        self.rows
+            .iter()
+            .filter(|r| r.status == BatchRowStatus::Sent)
+            .filter_map(|r| r.nonce)
+            .map(|n| n + 1)
+            .fold(on_chain, u64::max)
+    }
+
+    /// Make rows whose send failed or was interrupted retryable
+    ///
+    /// A send can fail after the transaction went out (e.g. an RPC timeout),
+    /// so a row is only retried if its recorded nonce is still unused:
+    /// below `pending_count` (the account's nonce including pending
+    /// transactions) it may have paid the row. Such rows stay `Failed`.
+    pub fn reset_unsent_rows(&mut self, pending_count: u64) {
+        for row in self.rows.iter_mut() {
+            let attempted = match row.status {
+                BatchRowStatus::Failed => true,
+                BatchRowStatus::Pending => row.nonce.is_some(),
+                _ => false,
+            };
+            if !attempted {
+                continue;
+            }
+            match row.nonce {
+                Some(nonce) if nonce < pending_count => {
+                    row.status = BatchRowStatus::Failed;
+                    row.error = Some(format!(
+                        "Nonce {} was used after this payment was attempted; check whether it was sent before paying it again",
+                        nonce
+                    ));
+                }
+                _ => {
+                    row.status = BatchRowStatus::Pending;
+                    row.nonce = None;
+                    row.error = None;
+                }
+            }
+        }
+    }
+
+    /// Mark the job as changed
+    pub fn touch(&mut self) {
+        self.updated_at = now();
+    }
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FROM: &str = "0x1111111111111111111111111111111111111111";
+    const TOKEN: &str = "0x2222222222222222222222222222222222222222";
+
+    fn job(csv: &str) -> BatchJob {
+        let service = BatchService::new();
+        let rows = service.parse_csv(csv).unwrap();
+        let mut assets = BatchAssets::new();
+        assets.insert(None, ("ETH".to_string(), 18));
+        assets.insert(Some(TOKEN.parse().unwrap()), ("USDC".to_string(), 6));
+        service.create_job(
+            &TransactionService::new(),
+            FROM.parse().unwrap(),
+            1,
+            BatchMode::Sequential,
+            rows,
+            &assets,
+        )
+    }
+
+    #[test]
+    fn test_parse_csv_skips_header_and_comments() {
+        let rows = BatchService::new()
+            .parse_csv(
+                "recipient,amount,token\n# payroll\n\n0x3333333333333333333333333333333333333333,1.5\n\"0x4444444444444444444444444444444444444444\",\"2\",0x2222222222222222222222222222222222222222\n",
+            )
+            .unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].line, 4);
+        assert_eq!(rows[0].token, None);
+        assert_eq!(rows[1].recipient, "0x4444444444444444444444444444444444444444");
+        assert_eq!(rows[1].token.as_deref(), Some(TOKEN));
+    }
+
+    #[test]
+    fn test_parse_csv_rejects_bad_shape() {
+        assert!(BatchService::new().parse_csv("0x33,1,2,3").is_err());
+        assert!(BatchService::new().parse_csv("recipient,amount\n").is_err());
+    }
+
+    #[test]
+    fn test_create_job_validates_rows() {
+        let job = job(
+            "0x3333333333333333333333333333333333333333,1.5\n\
+             0x0000000000000000000000000000000000000000,1\n\
+             0x3333333333333333333333333333333333333333,0\n\
+             0x4444444444444444444444444444444444444444,2.5,0x2222222222222222222222222222222222222222\n\
+             0x4444444444444444444444444444444444444444,1,0x5555555555555555555555555555555555555555\n",
+        );
+        let statuses: Vec<_> = job.rows.iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                BatchRowStatus::Pending,
+                BatchRowStatus::Invalid,
+                BatchRowStatus::Invalid,
+                BatchRowStatus::Pending,
+                BatchRowStatus::Invalid,
+            ]
+        );
+        assert!(job.has_invalid_rows());
+        assert_eq!(job.rows[3].amount_raw, "2500000");
+    }
+
+    #[test]
+    fn test_totals_per_asset() {
+        let job = job(
+            "0x3333333333333333333333333333333333333333,1\n\
+             0x4444444444444444444444444444444444444444,0.5\n\
+             0x4444444444444444444444444444444444444444,3,0x2222222222222222222222222222222222222222\n",
+        );
+        assert_eq!(job.totals.len(), 2);
+        assert_eq!(job.totals[0].amount_raw, "1500000000000000000");
+        assert_eq!(job.totals[1].symbol, "USDC");
+        assert_eq!(job.totals[1].amount_raw, "3000000");
+    }
+
+    #[test]
+    fn test_pending_groups_and_gas() {
+        let mut job = job(
+            "0x3333333333333333333333333333333333333333,1\n\
+             0x4444444444444444444444444444444444444444,3,0x2222222222222222222222222222222222222222\n\
+             0x4444444444444444444444444444444444444444,0.5\n",
+        );
+        let groups = job.pending_groups();
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].1, vec![0, 2]);
+        assert_eq!(job.sum(&groups[0].1), U256::from(1_500_000_000_000_000_000u128));
+        assert_eq!(job.estimated_gas(), 2 * NATIVE_TRANSFER_GAS + TOKEN_TRANSFER_GAS);
+
+        job.mode = BatchMode::Disperse;
+        assert_eq!(job.estimated_gas(), disperse_gas(2) + APPROVE_GAS + disperse_gas(1));
+
+        job.rows[0].status = BatchRowStatus::Confirmed;
+        assert_eq!(job.pending_groups()[0].1, vec![2]);
+    }
+
+    #[test]
+    fn test_next_nonce_skips_pending_rows() {
+        let mut job = job("0x3333333333333333333333333333333333333333,1\n");
+        assert_eq!(job.next_nonce(7), 7);
+        job.rows[0].status = BatchRowStatus::Sent;
+        job.rows[0].nonce = Some(9);
+        assert_eq!(job.next_nonce(7), 10);
+        job.rows[0].status = BatchRowStatus::Confirmed;
+        assert_eq!(job.next_nonce(7), 7);
+    }
+
+    #[test]
+    fn test_reset_unsent_rows_only_retries_unused_nonces() {
+        let mut job = job(
+            "0x3333333333333333333333333333333333333333,1\n\
+             0x4444444444444444444444444444444444444444,1\n\
+             0x5555555555555555555555555555555555555555,1\n\
+             0x6666666666666666666666666666666666666666,1\n",
+        );
+        // Failed before broadcast, failed after broadcast, interrupted
+        // mid-send, never attempted
+        job.rows[0].status = BatchRowStatus::Failed;
+        job.rows[0].nonce = Some(5);
+        job.rows[0].error = Some("connection refused".to_string());
+        job.rows[1].status = BatchRowStatus::Failed;
+        job.rows[1].nonce = Some(4);
+        job.rows[2].nonce = Some(3);
+
+        job.reset_unsent_rows(5);
+        let statuses: Vec<_> = job.rows.iter().map(|r| r.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                BatchRowStatus::Pending,
+                BatchRowStatus::Failed,
+                BatchRowStatus::Failed,
+                BatchRowStatus::Pending,
+            ]
+        );
+        assert_eq!(job.rows[0].nonce, None);
+        assert_eq!(job.rows[0].error, None);
+        assert!(job.rows[1].error.as_deref().unwrap().contains("Nonce 4"));
+        assert_eq!(job.pending_groups()[0].1, vec![0, 3]);
+    }
+}
@@ -0,0 +1,68 @@
+// ============================================================================
+// Vaughan Wallet - CSV Helpers
+// ============================================================================
+//
+// Minimal CSV reading/writing for the wallet's imports and exports (batch
+// payouts, address book): comma-separated, double-quoted fields, `""` for a
+// literal quote. One record per line.
+//
+// ============================================================================
+
+/// Split a CSV line, honouring double quotes
+pub fn split_line(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ',' if !in_quotes => fields.push(std::mem::take(&mut field).trim().to_string()),
+            _ => field.push(c),
+        }
+    }
+    fields.push(field.trim().to_string());
+    fields
+}
+
+/// Quote a field if it contains a comma, quote or line break
+pub fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Join fields into a CSV line
+pub fn join_line<S: AsRef<str>>(fields: &[S]) -> String {
+    fields
+        .iter()
+        .map(|f| escape_field(f.as_ref()))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_line_quotes() {
+        assert_eq!(split_line("a, b ,c"), vec!["a", "b", "c"]);
+        assert_eq!(split_line("\"a,b\",\"say \"\"hi\"\"\","), vec!["a,b", "say \"hi\"", ""]);
+    }
+
+    #[test]
+    fn test_join_line_round_trip() {
+        let fields = ["Alice, Inc.", "0x1111", "said \"hi\"", ""];
+        let line = join_line(&fields);
+        assert_eq!(line, "\"Alice, Inc.\",0x1111,\"said \"\"hi\"\"\",");
+        assert_eq!(split_line(&line), fields);
+    }
+}
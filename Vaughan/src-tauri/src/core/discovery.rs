@@ -0,0 +1,156 @@
+// ============================================================================
+// Vaughan Wallet - Account Discovery
+// ============================================================================
+//
+// Finds the used HD accounts of an imported seed. For each derivation
+// scheme, addresses are derived at increasing indices and checked for a
+// nonce or balance on the scanned networks; the scan stops after
+// `gap_limit` unused addresses in a row (BIP-44 account discovery).
+//
+// ============================================================================
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::models::wallet::DerivationScheme;
+
+/// Unused addresses in a row before a scheme's scan stops
+pub const DEFAULT_GAP_LIMIT: u32 = 5;
+
+/// Largest accepted gap limit (BIP-44's)
+pub const MAX_GAP_LIMIT: u32 = 20;
+
+/// Highest index scanned per scheme, whatever the gap
+pub const MAX_SCAN_INDEX: u32 = 100;
+
+/// What to scan
+#[derive(Debug, Clone, Deserialize, Type)]
+pub struct DiscoveryOptions {
+    /// Derivation schemes to scan (default: BIP-44, Ledger Live, legacy MEW)
+    #[serde(default = "default_schemes")]
+    pub schemes: Vec<DerivationScheme>,
+    /// Unused addresses in a row before stopping (default 5, at most 20)
+    #[serde(default)]
+    pub gap_limit: Option<u32>,
+    /// Chains to check (default: all configured mainnets)
+    #[serde(default)]
+    pub chain_ids: Option<Vec<u64>>,
+    /// Hidden wallet to scan (`null` = the main wallet)
+    #[serde(default)]
+    pub passphrase_id: Option<String>,
+}
+
+fn default_schemes() -> Vec<DerivationScheme> {
+    vec![
+        DerivationScheme::Bip44,
+        DerivationScheme::LedgerLive,
+        DerivationScheme::LegacyMew,
+    ]
+}
+
+impl Default for DiscoveryOptions {
+    fn default() -> Self {
+        Self {
+            schemes: default_schemes(),
+            gap_limit: None,
+            chain_ids: None,
+            passphrase_id: None,
+        }
+    }
+}
+
+impl DiscoveryOptions {
+    /// Gap limit, clamped to `1..=MAX_GAP_LIMIT`
+    pub fn gap_limit(&self) -> u32 {
+        self.gap_limit
+            .unwrap_or(DEFAULT_GAP_LIMIT)
+            .clamp(1, MAX_GAP_LIMIT)
+    }
+}
+
+/// A used account found by the scan
+#[derive(Debug, Clone, Serialize, Type)]
+pub struct DiscoveredAccount {
+    pub address: String,
+    pub derivation_path: String,
+    pub index: u32,
+    /// Chains where the address has a nonce or balance
+    pub chain_ids: Vec<u64>,
+    /// Added by this scan (`false` = already in the wallet)
+    pub added: bool,
+}
+
+/// Scan report
+#[derive(Debug, Clone, Default, Serialize, Type)]
+pub struct DiscoveryResult {
+    pub accounts: Vec<DiscoveredAccount>,
+    /// Chains that were checked
+    pub scanned_chain_ids: Vec<u64>,
+    /// Chains skipped because their RPC failed
+    pub unreachable_chain_ids: Vec<u64>,
+}
+
+/// Gap-limit bookkeeping for one scheme's scan
+#[derive(Debug)]
+pub struct GapScan {
+    gap_limit: u32,
+    unused_run: u32,
+}
+
+impl GapScan {
+    pub fn new(gap_limit: u32) -> Self {
+        Self {
+            gap_limit,
+            unused_run: 0,
+        }
+    }
+
+    /// Record whether the next address was used
+    pub fn record(&mut self, used: bool) {
+        self.unused_run = if used { 0 } else { self.unused_run + 1 };
+    }
+
+    /// Whether `gap_limit` unused addresses in a row have been seen
+    pub fn is_done(&self) -> bool {
+        self.unused_run >= self.gap_limit
+    }
+}
+
+/// Name of a discovered account
+pub fn account_name(scheme: &DerivationScheme, index: u32) -> String {
+    let label = match scheme {
+        DerivationScheme::Bip44 => "HD Wallet",
+        DerivationScheme::LedgerLive => "Ledger Live",
+        DerivationScheme::LegacyMew => "Legacy MEW",
+        DerivationScheme::Custom(_) => "Custom Path",
+    };
+    format!("{} {}", label, index + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gap_scan_resets_on_use() {
+        let mut scan = GapScan::new(3);
+        for used in [false, false, true, false, false] {
+            scan.record(used);
+            assert!(!scan.is_done());
+        }
+        scan.record(false);
+        assert!(scan.is_done());
+    }
+
+    #[test]
+    fn test_options_defaults_and_clamp() {
+        let options: DiscoveryOptions = serde_json::from_str("{}").unwrap();
+        assert_eq!(options.schemes.len(), 3);
+        assert_eq!(options.gap_limit(), DEFAULT_GAP_LIMIT);
+
+        let options: DiscoveryOptions =
+            serde_json::from_str(r#"{"schemes":[{"custom":"m/44'/60'/{i}'/0/0"}],"gap_limit":500}"#).unwrap();
+        assert_eq!(options.gap_limit(), MAX_GAP_LIMIT);
+        assert_eq!(options.schemes[0].path(2), "m/44'/60'/2'/0/0");
+    }
+}
@@ -6,18 +6,33 @@
 //
 // ============================================================================
 
+pub mod address_book;
+pub mod backup;
+pub mod batch;
+pub mod csv;
+pub mod discovery;
 pub mod network;
 pub mod persistence;
 pub mod price;
+pub mod safe;
+pub mod signer;
+pub mod smart_account;
 pub mod transaction;
 pub mod wallet;
 
 // Re-export main types
-pub use network::{NetworkConfig, NetworkInfo, NetworkService, TokenInfo};
+pub use address_book::{AddressBookService, ContactImportResult, ContactInput};
+pub use backup::{BackupSecrets, WalletBackup};
+pub use batch::{BatchAssets, BatchJob, BatchMode, BatchRow, BatchRowStatus, BatchService, BatchTotal};
+pub use discovery::{DiscoveredAccount, DiscoveryOptions, DiscoveryResult};
+pub use network::{NetworkConfig, NetworkInfo, NetworkOverride, NetworkService, TokenInfo};
 pub use persistence::{PersistedState, StateManager, UserPreferences};
 pub use price::PriceService;
+pub use safe::{SafeProposal, SafeService, SafeSignature};
+pub use signer::{AccountSigner, ExternalSigner};
+pub use smart_account::{BundlerConfig, SmartAccountService};
 pub use transaction::TransactionService;
 pub use wallet::WalletService;
 
 // Re-export account types from models to maintain back-compat and keep mod.rs clean
-pub use crate::models::wallet::{Account, AccountType};
+pub use crate::models::wallet::{Account, AccountType, DerivationScheme};
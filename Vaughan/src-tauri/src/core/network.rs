@@ -30,12 +30,16 @@
 //! # }
 //! ```
 
+use crate::chains::evm::{registry, EvmNetworkConfig};
 use crate::chains::{Balance, ChainAdapter, ChainInfo, ChainType};
 use crate::error::WalletError;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use url::Url;
 
+/// Timeout for `eth_chainId` verification requests
+const CHAIN_ID_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
 /// Network configuration
 ///
 /// Defines all information needed to connect to and interact with a blockchain network.
@@ -81,6 +85,41 @@ pub struct TokenInfo {
     pub decimals: u8,
 }
 
+impl From<EvmNetworkConfig> for NetworkConfig {
+    fn from(network: EvmNetworkConfig) -> Self {
+        Self {
+            id: network.id,
+            name: network.name,
+            chain_type: ChainType::Evm,
+            chain_id: network.chain_id,
+            rpc_url: network.rpc_url,
+            explorer_url: network.explorer_url,
+            native_token: TokenInfo {
+                symbol: network.native_symbol,
+                name: network.native_name,
+                decimals: network.decimals,
+            },
+            is_testnet: network.is_testnet,
+        }
+    }
+}
+
+/// User override for a predefined network
+///
+/// Lets users point a built-in network at their own RPC node or explorer
+/// without having to re-add the whole network as a custom one.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Type)]
+pub struct NetworkOverride {
+    /// ID of the predefined network being overridden (e.g., "ethereum")
+    pub network_id: String,
+
+    /// Replacement RPC endpoint URL (None = keep default)
+    pub rpc_url: Option<String>,
+
+    /// Replacement block explorer URL (None = keep default)
+    pub explorer_url: Option<String>,
+}
+
 /// Extended network information
 ///
 /// Combines static configuration with dynamic network state.
@@ -332,7 +371,7 @@ impl NetworkService {
 
     /// Get predefined network configurations
     ///
-    /// Returns a list of well-known networks that are pre-configured.
+    /// Returns the featured networks from the network registry.
     ///
     /// # Returns
     ///
@@ -349,173 +388,44 @@ impl NetworkService {
     /// }
     /// ```
     pub fn get_predefined_networks(&self) -> Vec<NetworkConfig> {
-        vec![
-            // Ethereum Mainnet
-            NetworkConfig {
-                id: "ethereum".to_string(),
-                name: "Ethereum Mainnet".to_string(),
-                chain_type: ChainType::Evm,
-                chain_id: 1,
-                rpc_url: "https://eth.llamarpc.com".to_string(),
-                explorer_url: Some("https://etherscan.io".to_string()),
-                native_token: TokenInfo {
-                    symbol: "ETH".to_string(),
-                    name: "Ether".to_string(),
-                    decimals: 18,
-                },
-                is_testnet: false,
-            },
-            // PulseChain
-            NetworkConfig {
-                id: "pulsechain".to_string(),
-                name: "PulseChain".to_string(),
-                chain_type: ChainType::Evm,
-                chain_id: 369,
-                rpc_url: "https://rpc.pulsechain.com".to_string(),
-                explorer_url: Some("https://scan.pulsechain.com".to_string()),
-                native_token: TokenInfo {
-                    symbol: "PLS".to_string(),
-                    name: "Pulse".to_string(),
-                    decimals: 18,
-                },
-                is_testnet: false,
-            },
-            // Polygon
-            NetworkConfig {
-                id: "polygon".to_string(),
-                name: "Polygon".to_string(),
-                chain_type: ChainType::Evm,
-                chain_id: 137,
-                rpc_url: "https://polygon-rpc.com".to_string(),
-                explorer_url: Some("https://polygonscan.com".to_string()),
-                native_token: TokenInfo {
-                    symbol: "MATIC".to_string(),
-                    name: "Matic".to_string(),
-                    decimals: 18,
-                },
-                is_testnet: false,
-            },
-            // Binance Smart Chain
-            NetworkConfig {
-                id: "bsc".to_string(),
-                name: "BNB Smart Chain".to_string(),
-                chain_type: ChainType::Evm,
-                chain_id: 56,
-                rpc_url: "https://bsc-dataseed.binance.org".to_string(),
-                explorer_url: Some("https://bscscan.com".to_string()),
-                native_token: TokenInfo {
-                    symbol: "BNB".to_string(),
-                    name: "BNB".to_string(),
-                    decimals: 18,
-                },
-                is_testnet: false,
-            },
-            // Arbitrum One
-            NetworkConfig {
-                id: "arbitrum".to_string(),
-                name: "Arbitrum One".to_string(),
-                chain_type: ChainType::Evm,
-                chain_id: 42161,
-                rpc_url: "https://arb1.arbitrum.io/rpc".to_string(),
-                explorer_url: Some("https://arbiscan.io".to_string()),
-                native_token: TokenInfo {
-                    symbol: "ETH".to_string(),
-                    name: "Ether".to_string(),
-                    decimals: 18,
-                },
-                is_testnet: false,
-            },
-            // Optimism
-            NetworkConfig {
-                id: "optimism".to_string(),
-                name: "Optimism".to_string(),
-                chain_type: ChainType::Evm,
-                chain_id: 10,
-                rpc_url: "https://mainnet.optimism.io".to_string(),
-                explorer_url: Some("https://optimistic.etherscan.io".to_string()),
-                native_token: TokenInfo {
-                    symbol: "ETH".to_string(),
-                    name: "Ether".to_string(),
-                    decimals: 18,
-                },
-                is_testnet: false,
-            },
-            // Avalanche C-Chain
-            NetworkConfig {
-                id: "avalanche".to_string(),
-                name: "Avalanche C-Chain".to_string(),
-                chain_type: ChainType::Evm,
-                chain_id: 43114,
-                rpc_url: "https://api.avax.network/ext/bc/C/rpc".to_string(),
-                explorer_url: Some("https://snowtrace.io".to_string()),
-                native_token: TokenInfo {
-                    symbol: "AVAX".to_string(),
-                    name: "Avalanche".to_string(),
-                    decimals: 18,
-                },
-                is_testnet: false,
-            },
-            // Base
-            NetworkConfig {
-                id: "base".to_string(),
-                name: "Base".to_string(),
-                chain_type: ChainType::Evm,
-                chain_id: 8453,
-                rpc_url: "https://mainnet.base.org".to_string(),
-                explorer_url: Some("https://basescan.org".to_string()),
-                native_token: TokenInfo {
-                    symbol: "ETH".to_string(),
-                    name: "Ether".to_string(),
-                    decimals: 18,
-                },
-                is_testnet: false,
-            },
-            // PulseChain Testnet V4
-            NetworkConfig {
-                id: "pulsechain-testnet-v4".to_string(),
-                name: "PulseChain Testnet-V4".to_string(),
-                chain_type: ChainType::Evm,
-                chain_id: 943,
-                rpc_url: "https://rpc.v4.testnet.pulsechain.com".to_string(),
-                explorer_url: Some("https://scan.v4.testnet.pulsechain.com".to_string()),
-                native_token: TokenInfo {
-                    symbol: "tPLS".to_string(),
-                    name: "Pulse".to_string(),
-                    decimals: 18,
-                },
-                is_testnet: true,
-            },
-            // Ethereum Sepolia (Railgun Testnet)
-            NetworkConfig {
-                id: "sepolia".to_string(),
-                name: "Ethereum Sepolia".to_string(),
-                chain_type: ChainType::Evm,
-                chain_id: 11155111,
-                rpc_url: "https://ethereum-sepolia-rpc.publicnode.com".to_string(),
-                explorer_url: Some("https://sepolia.etherscan.io".to_string()),
-                native_token: TokenInfo {
-                    symbol: "ETH".to_string(),
-                    name: "Sepolia Ether".to_string(),
-                    decimals: 18,
-                },
-                is_testnet: true,
-            },
-            // Polygon Amoy (Railgun Testnet)
-            NetworkConfig {
-                id: "polygon-amoy".to_string(),
-                name: "Polygon Amoy".to_string(),
-                chain_type: ChainType::Evm,
-                chain_id: 80002,
-                rpc_url: "https://polygon-amoy-bor-rpc.publicnode.com".to_string(),
-                explorer_url: Some("https://amoy.polygonscan.com".to_string()),
-                native_token: TokenInfo {
-                    symbol: "MATIC".to_string(),
-                    name: "Amoy Matic".to_string(),
-                    decimals: 18,
-                },
-                is_testnet: true,
-            },
-        ]
+        registry()
+            .featured()
+            .into_iter()
+            .map(NetworkConfig::from)
+            .collect()
+    }
+
+    /// Search the network registry
+    ///
+    /// Matches name, ID, native symbol or exact chain ID. Unlike
+    /// `get_predefined_networks`, this covers every chain in the registry.
+    ///
+    /// # Arguments
+    ///
+    /// * `query` - Case-insensitive search text
+    /// * `include_testnets` - Whether testnets are included
+    /// * `limit` - Maximum number of results
+    pub fn search_registry(
+        &self,
+        query: &str,
+        include_testnets: bool,
+        limit: usize,
+    ) -> Vec<NetworkConfig> {
+        registry()
+            .search(query, include_testnets, limit)
+            .into_iter()
+            .map(NetworkConfig::from)
+            .collect()
+    }
+
+    /// Look up any registry network by chain ID
+    ///
+    /// # Returns
+    ///
+    /// * `Some(NetworkConfig)` - Network from the registry
+    /// * `None` - Chain is not in the registry
+    pub fn find_registry_network(&self, chain_id: u64) -> Option<NetworkConfig> {
+        registry().get_by_chain_id(chain_id).map(NetworkConfig::from)
     }
 
     /// Find network configuration by chain ID
@@ -553,8 +463,101 @@ impl NetworkService {
             .into_iter()
             .find(|config| config.id == id)
     }
+
+    /// Resolve the full network list
+    ///
+    /// Applies user overrides to the predefined networks and appends the
+    /// user-added custom networks.
+    ///
+    /// # Arguments
+    ///
+    /// * `custom_networks` - User-added networks (from persisted state)
+    /// * `overrides` - RPC/explorer overrides for predefined networks
+    ///
+    /// # Returns
+    ///
+    /// Predefined networks (with overrides applied) followed by custom networks
+    pub fn resolve_networks(
+        &self,
+        custom_networks: &[NetworkConfig],
+        overrides: &[NetworkOverride],
+    ) -> Vec<NetworkConfig> {
+        let mut networks: Vec<NetworkConfig> = self
+            .get_predefined_networks()
+            .into_iter()
+            .map(|mut config| {
+                if let Some(o) = overrides.iter().find(|o| o.network_id == config.id) {
+                    if let Some(ref rpc_url) = o.rpc_url {
+                        config.rpc_url = rpc_url.clone();
+                    }
+                    if let Some(ref explorer_url) = o.explorer_url {
+                        config.explorer_url = Some(explorer_url.clone());
+                    }
+                }
+                config
+            })
+            .collect();
+
+        networks.extend(custom_networks.iter().cloned());
+        networks
+    }
+
+    /// Query the chain ID reported by an RPC endpoint
+    ///
+    /// # Arguments
+    ///
+    /// * `rpc_url` - RPC endpoint URL
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(u64)` - Chain ID returned by `eth_chainId`
+    /// * `Err(WalletError)` - Invalid URL, timeout, or RPC error
+    pub async fn fetch_chain_id(&self, rpc_url: &str) -> Result<u64, WalletError> {
+        use alloy::providers::{Provider, RootProvider};
+        use alloy::rpc::client::RpcClient;
+
+        let url = Url::parse(rpc_url)
+            .map_err(|e| WalletError::InvalidNetwork(format!("Invalid RPC URL: {}", e)))?;
+        let provider = RootProvider::<alloy::network::Ethereum>::new(RpcClient::new_http(url));
+
+        tokio::time::timeout(CHAIN_ID_TIMEOUT, provider.get_chain_id())
+            .await
+            .map_err(|_| WalletError::ConnectionTimeout(rpc_url.to_string()))?
+            .map_err(|e| WalletError::RpcError(e.to_string()))
+    }
+
+    /// Verify that an RPC endpoint serves the declared chain
+    ///
+    /// Protects against typos and malicious RPC URLs that would otherwise
+    /// make us sign transactions for a different chain than the user expects.
+    ///
+    /// # Arguments
+    ///
+    /// * `rpc_url` - RPC endpoint URL
+    /// * `expected_chain_id` - Chain ID the network claims to have
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(())` - RPC reports the expected chain ID
+    /// * `Err(WalletError::InvalidNetwork)` - Chain ID mismatch
+    /// * `Err(WalletError)` - RPC unreachable
+    pub async fn verify_chain_id(
+        &self,
+        rpc_url: &str,
+        expected_chain_id: u64,
+    ) -> Result<(), WalletError> {
+        let actual = self.fetch_chain_id(rpc_url).await?;
+        if actual != expected_chain_id {
+            return Err(WalletError::InvalidNetwork(format!(
+                "RPC reports chain ID {}, expected {}",
+                actual, expected_chain_id
+            )));
+        }
+        Ok(())
+    }
 }
 
+
 impl Default for NetworkService {
     fn default() -> Self {
         Self::new()
@@ -723,6 +726,76 @@ mod tests {
         assert!(unknown.is_none());
     }
 
+    #[test]
+    fn test_resolve_networks_applies_overrides() {
+        let service = NetworkService::new();
+        let overrides = vec![NetworkOverride {
+            network_id: "ethereum".to_string(),
+            rpc_url: Some("https://my-node.example.com".to_string()),
+            explorer_url: None,
+        }];
+
+        let networks = service.resolve_networks(&[], &overrides);
+        let ethereum = networks.iter().find(|n| n.id == "ethereum").unwrap();
+
+        // RPC overridden, explorer untouched
+        assert_eq!(ethereum.rpc_url, "https://my-node.example.com");
+        assert_eq!(ethereum.explorer_url.as_deref(), Some("https://etherscan.io"));
+
+        // Other networks unaffected
+        let pulsechain = networks.iter().find(|n| n.id == "pulsechain").unwrap();
+        assert_eq!(pulsechain.rpc_url, "https://rpc.pulsechain.com");
+    }
+
+    #[test]
+    fn test_resolve_networks_appends_custom() {
+        let service = NetworkService::new();
+        let custom = NetworkConfig {
+            id: "custom-12345".to_string(),
+            name: "My Chain".to_string(),
+            chain_type: ChainType::Evm,
+            chain_id: 12345,
+            rpc_url: "https://rpc.example.com".to_string(),
+            explorer_url: None,
+            native_token: TokenInfo {
+                symbol: "MYC".to_string(),
+                name: "My Coin".to_string(),
+                decimals: 18,
+            },
+            is_testnet: false,
+        };
+
+        let networks = service.resolve_networks(&[custom.clone()], &[]);
+        assert_eq!(
+            networks.len(),
+            service.get_predefined_networks().len() + 1
+        );
+        assert_eq!(networks.last().unwrap(), &custom);
+    }
+
+    #[test]
+    fn test_predefined_networks_match_registry() {
+        let service = NetworkService::new();
+
+        // Every predefined network resolves the same way through the EVM lookup
+        for network in service.get_predefined_networks() {
+            let evm = crate::chains::evm::get_network_by_chain_id(network.chain_id).unwrap();
+            assert_eq!(evm.id, network.id);
+            assert_eq!(evm.rpc_url, network.rpc_url);
+        }
+    }
+
+    #[test]
+    fn test_search_registry() {
+        let service = NetworkService::new();
+
+        let results = service.search_registry("gnosis", false, 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].chain_id, 100);
+        assert!(service.find_network_by_chain_id(100).is_none());
+        assert!(service.find_registry_network(100).is_some());
+    }
+
     #[test]
     fn test_all_predefined_networks_valid() {
         let service = NetworkService::new();
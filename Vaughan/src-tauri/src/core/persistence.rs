@@ -8,19 +8,28 @@
 //! ```text
 //! <data_dir>/vaughan/
 //!   ├── state.json          ← App state (this module)
+//!   ├── unlock_throttle.json ← Wrong password count (this module, own file)
+//!   ├── secrets.vault       ← Encrypted keys when no OS keychain (secret_store.rs)
 //!   └── certs/              ← TLS certificates (cert.rs)
 //! ```
 //!
 //! ## Security Note
 //!
-//! Private keys are stored in the OS keychain via `keyring_service.rs`.
+//! Private keys are stored password-encrypted via `keyring_service.rs`, in
+//! the OS keychain or the encrypted file vault.
 //! This module only persists non-sensitive data like active network,
 //! account address, and user preferences.
 
-use crate::core::network::NetworkConfig;
+use crate::core::batch::BatchJob;
+use crate::core::network::{NetworkConfig, NetworkOverride};
+use crate::core::safe::SafeProposal;
+use crate::core::smart_account::BundlerConfig;
 use crate::error::WalletError;
+use crate::models::contact::Contact;
+use crate::models::contract::ContractAbiEntry;
 use crate::models::token::TrackedToken;
 use crate::models::wallet::Account;
+use crate::security::throttle::UnlockThrottle;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::fs;
@@ -32,6 +41,9 @@ const STATE_VERSION: u32 = 1;
 /// Default state file name
 const STATE_FILE: &str = "state.json";
 
+/// Wrong password count, kept apart so state writers can't overwrite it
+const THROTTLE_FILE: &str = "unlock_throttle.json";
+
 /// User preferences
 #[derive(Debug, Clone, Serialize, Deserialize, Type)]
 pub struct UserPreferences {
@@ -49,6 +61,10 @@ pub struct UserPreferences {
 
     /// Whether privacy features (Railgun Shadow Engine) are enabled
     pub privacy_enabled: bool,
+
+    /// Wipe the wallet after this many wrong passwords in a row (0 = never)
+    #[serde(default)]
+    pub wipe_after_failed_unlocks: u32,
 }
 
 impl Default for UserPreferences {
@@ -59,6 +75,7 @@ impl Default for UserPreferences {
             auto_lock_seconds: 300, // 5 minutes
             gas_multiplier: 1.2,
             privacy_enabled: true,
+            wipe_after_failed_unlocks: 0,
         }
     }
 }
@@ -91,10 +108,34 @@ pub struct PersistedState {
     /// User-added custom network configurations
     pub custom_networks: Vec<NetworkConfig>,
 
+    /// User RPC/explorer overrides for predefined networks
+    #[serde(default)]
+    pub network_overrides: Vec<NetworkOverride>,
+
+    /// ERC-4337 bundler endpoints (one per chain)
+    #[serde(default)]
+    pub bundlers: Vec<BundlerConfig>,
+
+    /// Safe multisig proposals (pending and executed)
+    #[serde(default)]
+    pub safe_proposals: Vec<SafeProposal>,
+
     /// User-tracked custom tokens
     #[serde(default)] // For backward compatibility with existing state files
     pub tracked_tokens: Vec<TrackedToken>,
 
+    /// Imported contract ABIs (one per chain + address)
+    #[serde(default)]
+    pub contract_abis: Vec<ContractAbiEntry>,
+
+    /// Batch payout jobs (kept so interrupted batches can be resumed)
+    #[serde(default)]
+    pub batch_jobs: Vec<BatchJob>,
+
+    /// Address book
+    #[serde(default)]
+    pub contacts: Vec<Contact>,
+
     /// User preferences
     pub preferences: UserPreferences,
 }
@@ -109,7 +150,13 @@ impl Default for PersistedState {
             active_account: None,
             accounts: Vec::new(),
             custom_networks: Vec::new(),
+            network_overrides: Vec::new(),
+            bundlers: Vec::new(),
+            safe_proposals: Vec::new(),
             tracked_tokens: Vec::new(),
+            contract_abis: Vec::new(),
+            batch_jobs: Vec::new(),
+            contacts: Vec::new(),
             preferences: UserPreferences::default(),
         }
     }
@@ -122,6 +169,9 @@ impl Default for PersistedState {
 pub struct StateManager {
     /// Path to the state file
     state_path: PathBuf,
+
+    /// Path to the wrong password count
+    throttle_path: PathBuf,
 }
 
 impl StateManager {
@@ -145,13 +195,17 @@ impl StateManager {
 
         Ok(Self {
             state_path: data_dir.join(STATE_FILE),
+            throttle_path: data_dir.join(THROTTLE_FILE),
         })
     }
 
     /// Create a StateManager with a custom path (for testing)
     #[cfg(test)]
     pub fn with_path(path: PathBuf) -> Self {
-        Self { state_path: path }
+        Self {
+            throttle_path: path.with_extension("throttle.json"),
+            state_path: path,
+        }
     }
 
     /// Load persisted state from disk
@@ -209,28 +263,29 @@ impl StateManager {
     pub fn save(&self, state: &PersistedState) -> Result<(), WalletError> {
         let json = serde_json::to_string_pretty(state)
             .map_err(|e| WalletError::InternalError(format!("Failed to serialize state: {}", e)))?;
+        write_atomic(&self.state_path, &json)
+    }
 
-        // Atomic write: write to .tmp then rename
-        let tmp_path = self.state_path.with_extension("json.tmp");
+    /// Load the wrong password count (none if missing or unreadable)
+    pub fn load_unlock_throttle(&self) -> UnlockThrottle {
+        let Ok(contents) = fs::read_to_string(&self.throttle_path) else {
+            return UnlockThrottle::default();
+        };
+        serde_json::from_str(&contents).unwrap_or_else(|e| {
+            eprintln!("[StateManager] Failed to parse unlock throttle (using defaults): {}", e);
+            UnlockThrottle::default()
+        })
+    }
 
-        fs::write(&tmp_path, &json).map_err(|e| {
-            WalletError::InternalError(format!("Failed to write temp state file: {}", e))
+    /// Save the wrong password count
+    ///
+    /// Kept out of `state.json`: every other writer does load-modify-save
+    /// on that file and could put back an older count.
+    pub fn save_unlock_throttle(&self, throttle: &UnlockThrottle) -> Result<(), WalletError> {
+        let json = serde_json::to_string(throttle).map_err(|e| {
+            WalletError::InternalError(format!("Failed to serialize unlock throttle: {}", e))
         })?;
-
-        if let Err(rename_err) = fs::rename(&tmp_path, &self.state_path) {
-            // If rename fails (e.g. cross-device), fall back to direct write
-            eprintln!(
-                "[StateManager] Atomic rename failed ({}), falling back to direct write",
-                rename_err
-            );
-            fs::write(&self.state_path, &json).map_err(|e| {
-                WalletError::InternalError(format!("Failed to save state file: {}", e))
-            })?;
-            // Clean up tmp file
-            let _ = fs::remove_file(&tmp_path);
-        }
-
-        Ok(())
+        write_atomic(&self.throttle_path, &json)
     }
 
     /// Get the state file path (for display/debugging)
@@ -238,17 +293,43 @@ impl StateManager {
         &self.state_path
     }
 
-    /// Delete state file (reset to defaults on next load)
+    /// Delete the state files (reset to defaults on next load)
     pub fn reset(&self) -> Result<(), WalletError> {
-        if self.state_path.exists() {
-            fs::remove_file(&self.state_path).map_err(|e| {
-                WalletError::InternalError(format!("Failed to delete state file: {}", e))
-            })?;
+        for path in [&self.state_path, &self.throttle_path] {
+            if path.exists() {
+                fs::remove_file(path).map_err(|e| {
+                    WalletError::InternalError(format!("Failed to delete state file: {}", e))
+                })?;
+            }
         }
         Ok(())
     }
 }
 
+/// Write a file atomically (write to .tmp, then rename)
+fn write_atomic(path: &PathBuf, contents: &str) -> Result<(), WalletError> {
+    let tmp_path = path.with_extension("json.tmp");
+
+    fs::write(&tmp_path, contents).map_err(|e| {
+        WalletError::InternalError(format!("Failed to write temp state file: {}", e))
+    })?;
+
+    if let Err(rename_err) = fs::rename(&tmp_path, path) {
+        // If rename fails (e.g. cross-device), fall back to direct write
+        eprintln!(
+            "[StateManager] Atomic rename failed ({}), falling back to direct write",
+            rename_err
+        );
+        fs::write(path, contents).map_err(|e| {
+            WalletError::InternalError(format!("Failed to save state file: {}", e))
+        })?;
+        // Clean up tmp file
+        let _ = fs::remove_file(&tmp_path);
+    }
+
+    Ok(())
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -431,6 +512,27 @@ mod tests {
         println!("✅ Reset deletes state file");
     }
 
+    #[test]
+    fn test_unlock_throttle_kept_apart_from_state() {
+        let path = temp_state_path();
+        let manager = StateManager::with_path(path.clone());
+        assert_eq!(manager.load_unlock_throttle(), UnlockThrottle::default());
+
+        let throttle = UnlockThrottle { failed_attempts: 4, last_failure: 1_700_000_000 };
+        manager.save_unlock_throttle(&throttle).unwrap();
+
+        // A state written from an older load doesn't touch the count
+        let stale = manager.load();
+        manager.save(&stale).unwrap();
+        assert_eq!(manager.load_unlock_throttle(), throttle);
+
+        // Reset clears it with the state
+        manager.reset().unwrap();
+        assert_eq!(manager.load_unlock_throttle(), UnlockThrottle::default());
+        assert!(!path.exists());
+        println!("✅ Unlock throttle survives state writes and is reset with them");
+    }
+
     #[test]
     fn test_state_manager_new() {
         // Should succeed on all platforms
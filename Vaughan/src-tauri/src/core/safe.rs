@@ -0,0 +1,359 @@
+// ============================================================================
+// Vaughan Wallet - Safe Multisig Service
+// ============================================================================
+//
+// Safe transactions are collected as proposals. Each wallet owner signs the
+// EIP-712 `SafeTx` typed data; signatures of other owners are exchanged as
+// JSON (the exported proposal itself). Once `threshold` valid owner
+// signatures are present, any wallet account can execute the proposal.
+//
+// ============================================================================
+
+use alloy::primitives::{Address, Bytes, B256, U256};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashSet;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::chains::evm::safe::{self, SafeTx};
+use crate::core::WalletService;
+use crate::error::WalletError;
+
+/// Owner signature on a Safe proposal
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Type)]
+pub struct SafeSignature {
+    /// Owner address
+    pub owner: String,
+
+    /// 65-byte ECDSA signature over the SafeTx hash (hex)
+    pub signature: String,
+}
+
+/// Safe transaction awaiting owner signatures
+///
+/// This is also the JSON format exchanged with other owners.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Type)]
+pub struct SafeProposal {
+    /// Safe address
+    pub safe: String,
+
+    /// Chain the Safe is deployed on
+    pub chain_id: u64,
+
+    /// EIP-712 SafeTx hash signed by the owners
+    pub safe_tx_hash: String,
+
+    /// Call target
+    pub to: String,
+
+    /// Value in wei (decimal)
+    pub value: String,
+
+    /// Calldata (hex)
+    pub data: String,
+
+    /// 0 = call, 1 = delegatecall
+    pub operation: u8,
+
+    /// Gas refund parameters (decimal; zero for no refund)
+    pub safe_tx_gas: String,
+    pub base_gas: String,
+    pub gas_price: String,
+    pub gas_token: String,
+    pub refund_receiver: String,
+
+    /// Safe nonce the transaction is bound to
+    pub nonce: u64,
+
+    /// Collected owner signatures
+    #[serde(default)]
+    pub signatures: Vec<SafeSignature>,
+
+    /// dApp that proposed the transaction
+    #[serde(default)]
+    pub origin: Option<String>,
+
+    /// Unix timestamp (seconds)
+    #[serde(default)]
+    pub created_at: u64,
+
+    /// Execution transaction hash, once executed
+    #[serde(default)]
+    pub executed_tx_hash: Option<String>,
+}
+
+/// Safe service - builds proposals and checks owner signatures
+///
+/// # Design
+///
+/// - **Stateless**: Proposals are persisted by the caller
+/// - **Owner-signed**: Owner keys never leave `WalletService`
+pub struct SafeService;
+
+impl SafeService {
+    /// Create new Safe service
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Proposal for a Safe transaction
+    pub fn create_proposal(
+        &self,
+        chain_id: u64,
+        safe_address: Address,
+        tx: &SafeTx,
+        origin: Option<String>,
+    ) -> SafeProposal {
+        let created_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+
+        SafeProposal {
+            safe: format!("{:?}", safe_address),
+            chain_id,
+            safe_tx_hash: format!("{:?}", safe::safe_tx_hash(chain_id, safe_address, tx)),
+            to: format!("{:?}", tx.to),
+            value: tx.value.to_string(),
+            data: format!("0x{}", hex::encode(&tx.data)),
+            operation: tx.operation,
+            safe_tx_gas: tx.safeTxGas.to_string(),
+            base_gas: tx.baseGas.to_string(),
+            gas_price: tx.gasPrice.to_string(),
+            gas_token: format!("{:?}", tx.gasToken),
+            refund_receiver: format!("{:?}", tx.refundReceiver),
+            nonce: tx.nonce.saturating_to(),
+            signatures: Vec::new(),
+            origin,
+            created_at,
+            executed_tx_hash: None,
+        }
+    }
+
+    /// Safe address of a proposal
+    pub fn safe_address(&self, proposal: &SafeProposal) -> Result<Address, WalletError> {
+        parse_address(&proposal.safe)
+    }
+
+    /// Rebuild the SafeTx of a proposal
+    pub fn safe_tx(&self, proposal: &SafeProposal) -> Result<SafeTx, WalletError> {
+        if proposal.operation > safe::OPERATION_DELEGATE_CALL {
+            return Err(WalletError::InvalidTransaction(format!(
+                "Invalid Safe operation: {}",
+                proposal.operation
+            )));
+        }
+        let data = hex::decode(proposal.data.trim_start_matches("0x"))
+            .map_err(|e| WalletError::InvalidTransaction(format!("Invalid data: {}", e)))?;
+
+        Ok(SafeTx {
+            to: parse_address(&proposal.to)?,
+            value: parse_u256(&proposal.value)?,
+            data: Bytes::from(data),
+            operation: proposal.operation,
+            safeTxGas: parse_u256(&proposal.safe_tx_gas)?,
+            baseGas: parse_u256(&proposal.base_gas)?,
+            gasPrice: parse_u256(&proposal.gas_price)?,
+            gasToken: parse_address(&proposal.gas_token)?,
+            refundReceiver: parse_address(&proposal.refund_receiver)?,
+            nonce: U256::from(proposal.nonce),
+        })
+    }
+
+    /// Recompute the SafeTx hash and check it matches the proposal
+    ///
+    /// Guards against imported proposals whose fields were altered after
+    /// signing.
+    pub fn verify_hash(&self, proposal: &SafeProposal) -> Result<B256, WalletError> {
+        let hash = safe::safe_tx_hash(
+            proposal.chain_id,
+            self.safe_address(proposal)?,
+            &self.safe_tx(proposal)?,
+        );
+        let claimed: B256 = proposal
+            .safe_tx_hash
+            .parse()
+            .map_err(|_| WalletError::ParseError(format!("Invalid SafeTx hash: {}", proposal.safe_tx_hash)))?;
+        if hash != claimed {
+            return Err(WalletError::InvalidTransaction(
+                "SafeTx hash does not match the transaction".to_string(),
+            ));
+        }
+        Ok(hash)
+    }
+
+    /// Signatures that recover to their claimed owner and that owner is
+    /// in `owners` (one per owner)
+    pub fn valid_signatures(
+        &self,
+        proposal: &SafeProposal,
+        owners: &[Address],
+    ) -> Result<Vec<(Address, Bytes)>, WalletError> {
+        let hash = self.verify_hash(proposal)?;
+        let mut seen = HashSet::new();
+        let mut valid = Vec::new();
+
+        for sig in &proposal.signatures {
+            let Ok(owner) = parse_address(&sig.owner) else { continue };
+            let Ok(bytes) = hex::decode(sig.signature.trim_start_matches("0x")) else { continue };
+            let recovered = safe::recover_owner(hash, &bytes).ok();
+            if recovered == Some(owner) && owners.contains(&owner) && seen.insert(owner) {
+                valid.push((owner, Bytes::from(bytes)));
+            }
+        }
+        Ok(valid)
+    }
+
+    /// Sign a proposal with an owner account (local key or external signer)
+    ///
+    /// The SafeTx is signed as EIP-712 typed data, so signers that refuse
+    /// raw hashes can sign too. Replaces any earlier signature of the same
+    /// owner.
+    pub async fn sign(
+        &self,
+        wallet: &WalletService,
+        proposal: &mut SafeProposal,
+        owner: Address,
+        password: &str,
+    ) -> Result<(), WalletError> {
+        let hash = self.verify_hash(proposal)?;
+        let typed_data =
+            safe::safe_tx_typed_data(proposal.chain_id, self.safe_address(proposal)?, &self.safe_tx(proposal)?);
+        let signature = wallet.sign_typed_data(&owner, &typed_data, password).await?;
+        if safe::recover_owner(hash, &signature)? != owner {
+            return Err(WalletError::SigningFailed(
+                "Signature does not match the SafeTx hash".to_string(),
+            ));
+        }
+
+        let owner = format!("{:?}", owner);
+        proposal.signatures.retain(|s| !s.owner.eq_ignore_ascii_case(&owner));
+        proposal.signatures.push(SafeSignature {
+            owner,
+            signature: format!("0x{}", hex::encode(signature)),
+        });
+        Ok(())
+    }
+
+    /// Add signatures from another copy of the same proposal
+    ///
+    /// Only signatures by current owners that verify against the hash are
+    /// taken over.
+    ///
+    /// # Returns
+    ///
+    /// Number of signatures added
+    pub fn merge_signatures(
+        &self,
+        proposal: &mut SafeProposal,
+        other: &SafeProposal,
+        owners: &[Address],
+    ) -> Result<usize, WalletError> {
+        if !proposal.safe_tx_hash.eq_ignore_ascii_case(&other.safe_tx_hash) {
+            return Err(WalletError::InvalidTransaction(
+                "Signatures are for a different Safe transaction".to_string(),
+            ));
+        }
+
+        let mut added = 0;
+        for (owner, signature) in self.valid_signatures(other, owners)? {
+            let owner = format!("{:?}", owner);
+            if proposal.signatures.iter().any(|s| s.owner.eq_ignore_ascii_case(&owner)) {
+                continue;
+            }
+            proposal.signatures.push(SafeSignature {
+                owner,
+                signature: format!("0x{}", hex::encode(signature)),
+            });
+            added += 1;
+        }
+        Ok(added)
+    }
+}
+
+impl Default for SafeService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn parse_address(s: &str) -> Result<Address, WalletError> {
+    s.parse().map_err(|_| WalletError::InvalidAddress(s.to_string()))
+}
+
+fn parse_u256(s: &str) -> Result<U256, WalletError> {
+    s.parse()
+        .map_err(|_| WalletError::ParseError(format!("Invalid number: {}", s)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::signers::local::PrivateKeySigner;
+    use alloy::signers::SignerSync;
+
+    const KEY: &str = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    fn proposal() -> SafeProposal {
+        let tx = SafeTx::call(Address::repeat_byte(0x22), U256::from(1000u64), Bytes::new(), U256::from(3u64));
+        SafeService::new().create_proposal(1, Address::repeat_byte(0x11), &tx, None)
+    }
+
+    fn signed_by(signer: &PrivateKeySigner, proposal: &SafeProposal) -> SafeSignature {
+        let hash: B256 = proposal.safe_tx_hash.parse().unwrap();
+        SafeSignature {
+            owner: format!("{:?}", signer.address()),
+            signature: format!("0x{}", hex::encode(signer.sign_hash_sync(&hash).unwrap().as_bytes())),
+        }
+    }
+
+    #[test]
+    fn test_proposal_roundtrip() {
+        let service = SafeService::new();
+        let proposal = proposal();
+        let tx = service.safe_tx(&proposal).unwrap();
+        assert_eq!(tx.nonce, U256::from(3u64));
+        assert!(service.verify_hash(&proposal).is_ok());
+    }
+
+    #[test]
+    fn test_tampered_proposal_rejected() {
+        let mut proposal = proposal();
+        proposal.value = "2000".to_string();
+        assert!(SafeService::new().verify_hash(&proposal).is_err());
+    }
+
+    #[test]
+    fn test_valid_signatures_require_owner() {
+        let service = SafeService::new();
+        let signer: PrivateKeySigner = KEY.parse().unwrap();
+        let mut proposal = proposal();
+        proposal.signatures.push(signed_by(&signer, &proposal));
+
+        let valid = service.valid_signatures(&proposal, &[signer.address()]).unwrap();
+        assert_eq!(valid.len(), 1);
+
+        let valid = service.valid_signatures(&proposal, &[Address::repeat_byte(0x33)]).unwrap();
+        assert!(valid.is_empty());
+    }
+
+    #[test]
+    fn test_merge_signatures() {
+        let service = SafeService::new();
+        let signer: PrivateKeySigner = KEY.parse().unwrap();
+        let owners = [signer.address()];
+
+        let mut local = proposal();
+        let mut remote = local.clone();
+        remote.signatures.push(signed_by(&signer, &remote));
+        // Claims to be an owner but is signed by someone else
+        remote.signatures.push(SafeSignature {
+            owner: format!("{:?}", Address::repeat_byte(0x33)),
+            signature: signed_by(&signer, &remote).signature,
+        });
+
+        assert_eq!(service.merge_signatures(&mut local, &remote, &owners).unwrap(), 1);
+        assert_eq!(service.merge_signatures(&mut local, &remote, &owners).unwrap(), 0);
+        assert_eq!(local.signatures.len(), 1);
+    }
+}
@@ -0,0 +1,302 @@
+// ============================================================================
+// Vaughan Wallet - Account Signers
+// ============================================================================
+//
+// What `WalletService::get_signer` hands out: something that signs for one
+// account. Two implementations:
+//
+// - `PrivateKeySigner`: a key unlocked in this process (HD or imported)
+// - `ExternalSigner`: a key held by a Clef-compatible signer, reached over
+//   JSON-RPC; every signature is confirmed on the signer side
+//
+// ============================================================================
+
+use std::sync::Arc;
+
+use alloy::consensus::TxEnvelope;
+use alloy::network::{EthereumWallet, TransactionBuilder};
+use alloy::primitives::{Address, Signature, B256};
+use alloy::rpc::types::TransactionRequest;
+use alloy::signers::local::PrivateKeySigner;
+use alloy_dyn_abi::TypedData;
+use async_trait::async_trait;
+
+use crate::chains::evm::clef::ClefClient;
+use crate::error::WalletError;
+
+/// Signs for one account
+#[async_trait]
+pub trait AccountSigner: Send + Sync {
+    fn address(&self) -> Address;
+
+    /// The key, if it lives in this process (key export needs it)
+    fn local_key(&self) -> Option<&PrivateKeySigner> {
+        None
+    }
+
+    /// Sign a complete transaction (nonce, gas, fees and chain ID set)
+    async fn sign_transaction(&self, tx: TransactionRequest) -> Result<TxEnvelope, WalletError>;
+
+    /// EIP-191 personal message signature
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, WalletError>;
+
+    /// EIP-712 signature
+    async fn sign_typed_data(&self, typed_data: &TypedData) -> Result<Signature, WalletError>;
+
+    /// Signature over a bare 32-byte hash (EIP-7702 authorizations); not
+    /// every signer allows it
+    async fn sign_hash(&self, hash: &B256) -> Result<Signature, WalletError>;
+}
+
+#[async_trait]
+impl AccountSigner for PrivateKeySigner {
+    fn address(&self) -> Address {
+        PrivateKeySigner::address(self)
+    }
+
+    fn local_key(&self) -> Option<&PrivateKeySigner> {
+        Some(self)
+    }
+
+    async fn sign_transaction(&self, tx: TransactionRequest) -> Result<TxEnvelope, WalletError> {
+        let wallet = EthereumWallet::from(self.clone());
+        tx.build(&wallet)
+            .await
+            .map_err(|e| WalletError::SigningFailed(format!("Build failed: {}", e)))
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, WalletError> {
+        alloy::signers::Signer::sign_message(self, message)
+            .await
+            .map_err(|e| WalletError::SigningFailed(e.to_string()))
+    }
+
+    async fn sign_typed_data(&self, typed_data: &TypedData) -> Result<Signature, WalletError> {
+        let hash = typed_data
+            .eip712_signing_hash()
+            .map_err(|e| WalletError::Custom(format!("Failed to hash typed data: {}", e)))?;
+        AccountSigner::sign_hash(self, &hash).await
+    }
+
+    async fn sign_hash(&self, hash: &B256) -> Result<Signature, WalletError> {
+        alloy::signers::Signer::sign_hash(self, hash)
+            .await
+            .map_err(|e| WalletError::SigningFailed(e.to_string()))
+    }
+}
+
+/// Account whose key is held by a Clef-compatible signer
+pub struct ExternalSigner {
+    address: Address,
+    client: Arc<ClefClient>,
+}
+
+impl ExternalSigner {
+    pub fn new(address: Address, client: Arc<ClefClient>) -> Self {
+        Self { address, client }
+    }
+}
+
+#[async_trait]
+impl AccountSigner for ExternalSigner {
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    async fn sign_transaction(&self, mut tx: TransactionRequest) -> Result<TxEnvelope, WalletError> {
+        tx.from = Some(self.address);
+        self.client.sign_transaction(&tx).await
+    }
+
+    async fn sign_message(&self, message: &[u8]) -> Result<Signature, WalletError> {
+        self.client.sign_text(self.address, message).await
+    }
+
+    async fn sign_typed_data(&self, typed_data: &TypedData) -> Result<Signature, WalletError> {
+        self.client.sign_typed_data(self.address, typed_data).await
+    }
+
+    async fn sign_hash(&self, _hash: &B256) -> Result<Signature, WalletError> {
+        Err(WalletError::ExternalSigner(format!(
+            "{} only signs transactions, messages and typed data, not raw hashes",
+            self.client.endpoint()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::consensus::SignableTransaction;
+    use alloy::eips::eip2718::Encodable2718;
+    use alloy::primitives::U256;
+    use alloy::signers::SignerSync;
+    use serde_json::{json, Value};
+
+    /// Clef stand-in: answers the external API by signing with `key`
+    fn stand_in(key: &PrivateKeySigner, request: &Value) -> Value {
+        let params = &request["params"];
+        let result = match request["method"].as_str().unwrap_or_default() {
+            "account_list" => json!([key.address()]),
+            "account_signData" => {
+                let data = hex::decode(params[2].as_str().unwrap().trim_start_matches("0x")).unwrap();
+                let signature = key.sign_message_sync(&data).unwrap();
+                json!(format!("0x{}", hex::encode(signature.as_bytes())))
+            }
+            "account_signTypedData" => {
+                let typed_data: TypedData = serde_json::from_value(params[1].clone()).unwrap();
+                let signature = key.sign_hash_sync(&typed_data.eip712_signing_hash().unwrap()).unwrap();
+                json!(format!("0x{}", hex::encode(signature.as_bytes())))
+            }
+            "account_signTransaction" => {
+                let tx: TransactionRequest = serde_json::from_value(params[0].clone()).unwrap();
+                let typed = tx.build_typed_tx().unwrap();
+                let signature = key.sign_hash_sync(&typed.signature_hash()).unwrap();
+                let raw = typed.into_envelope(signature).encoded_2718();
+                json!({ "raw": format!("0x{}", hex::encode(raw)), "tx": {} })
+            }
+            _ => {
+                return json!({ "jsonrpc": "2.0", "id": request["id"], "error": { "code": -32000, "message": "Request denied" } })
+            }
+        };
+        json!({ "jsonrpc": "2.0", "id": request["id"], "result": result })
+    }
+
+    struct StandInResponder(PrivateKeySigner);
+
+    impl wiremock::Respond for StandInResponder {
+        fn respond(&self, request: &wiremock::Request) -> wiremock::ResponseTemplate {
+            let body: Value = serde_json::from_slice(&request.body).unwrap();
+            wiremock::ResponseTemplate::new(200).set_body_json(stand_in(&self.0, &body))
+        }
+    }
+
+    fn transfer(from: Address) -> TransactionRequest {
+        let mut tx = TransactionRequest::default();
+        tx.from = Some(from);
+        tx.to = Some(Address::repeat_byte(0x22).into());
+        tx.value = Some(U256::from(1_000u64));
+        tx.nonce = Some(7);
+        tx.gas = Some(21_000);
+        tx.max_fee_per_gas = Some(2_000_000_000);
+        tx.max_priority_fee_per_gas = Some(1_000_000_000);
+        tx.chain_id = Some(1);
+        tx
+    }
+
+    const TYPED_DATA: &str = r#"{
+        "types": {
+            "EIP712Domain": [{ "name": "name", "type": "string" }, { "name": "chainId", "type": "uint256" }],
+            "Mail": [{ "name": "contents", "type": "string" }]
+        },
+        "primaryType": "Mail",
+        "domain": { "name": "Test", "chainId": 1 },
+        "message": { "contents": "Hello" }
+    }"#;
+
+    /// Same results from the stand-in as from the local key
+    async fn assert_matches_local(external: &ExternalSigner, key: &PrivateKeySigner) {
+        let message = b"hello clef";
+        assert_eq!(
+            external.sign_message(message).await.unwrap(),
+            AccountSigner::sign_message(key, message).await.unwrap()
+        );
+
+        let typed_data: TypedData = serde_json::from_str(TYPED_DATA).unwrap();
+        assert_eq!(
+            external.sign_typed_data(&typed_data).await.unwrap(),
+            AccountSigner::sign_typed_data(key, &typed_data).await.unwrap()
+        );
+
+        let envelope = external.sign_transaction(transfer(key.address())).await.unwrap();
+        let local = AccountSigner::sign_transaction(key, transfer(key.address())).await.unwrap();
+        assert_eq!(envelope.encoded_2718(), local.encoded_2718());
+
+        assert!(matches!(
+            external.sign_hash(&B256::ZERO).await,
+            Err(WalletError::ExternalSigner(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_external_signer_over_http() {
+        let key = PrivateKeySigner::random();
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(StandInResponder(key.clone()))
+            .mount(&server)
+            .await;
+
+        let client = Arc::new(ClefClient::new(&server.uri()).unwrap());
+        assert_eq!(client.list_accounts().await.unwrap(), vec![key.address()]);
+
+        let external = ExternalSigner::new(key.address(), client);
+        assert!(external.local_key().is_none());
+        assert_matches_local(&external, &key).await;
+    }
+
+    #[tokio::test]
+    async fn test_external_signer_signs_safe_transactions() {
+        use crate::chains::evm::safe::{self, SafeTx};
+        use alloy::primitives::Bytes;
+
+        let key = PrivateKeySigner::random();
+        let server = wiremock::MockServer::start().await;
+        wiremock::Mock::given(wiremock::matchers::method("POST"))
+            .respond_with(StandInResponder(key.clone()))
+            .mount(&server)
+            .await;
+        let external = ExternalSigner::new(key.address(), Arc::new(ClefClient::new(&server.uri()).unwrap()));
+
+        let safe_address = Address::repeat_byte(0x11);
+        let tx = SafeTx::call(Address::repeat_byte(0x22), U256::from(1u64), Bytes::new(), U256::ZERO);
+        let typed_data = safe::safe_tx_typed_data(1, safe_address, &tx);
+        let signature = external.sign_typed_data(&typed_data).await.unwrap();
+        let hash = safe::safe_tx_hash(1, safe_address, &tx);
+        assert_eq!(safe::recover_owner(hash, &signature.as_bytes()).unwrap(), key.address());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_external_signer_over_ipc() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let key = PrivateKeySigner::random();
+        let dir = std::env::temp_dir().join(format!("vaughan-clef-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let socket = dir.join("clef.ipc");
+        let listener = tokio::net::UnixListener::bind(&socket).unwrap();
+
+        let server_key = key.clone();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut buffer = Vec::new();
+                let mut chunk = [0u8; 4096];
+                let request: Value = loop {
+                    let read = stream.read(&mut chunk).await.unwrap();
+                    buffer.extend_from_slice(&chunk[..read]);
+                    if let Ok(request) = serde_json::from_slice(&buffer) {
+                        break request;
+                    }
+                };
+                let response = serde_json::to_vec(&stand_in(&server_key, &request)).unwrap();
+                stream.write_all(&response).await.unwrap();
+            }
+        });
+
+        let client = Arc::new(ClefClient::new(&format!("ipc://{}", socket.display())).unwrap());
+        assert_eq!(client.list_accounts().await.unwrap(), vec![key.address()]);
+        assert_matches_local(&ExternalSigner::new(key.address(), client), &key).await;
+
+        // A signer answering for another key is refused
+        let other = PrivateKeySigner::random();
+        let client = Arc::new(ClefClient::new(socket.to_str().unwrap()).unwrap());
+        let impostor = ExternalSigner::new(other.address(), client);
+        assert!(matches!(
+            impostor.sign_message(b"hi").await,
+            Err(WalletError::ExternalSigner(_))
+        ));
+
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,264 @@
+// ============================================================================
+// Vaughan Wallet - Smart Account Service
+// ============================================================================
+//
+// ERC-4337 smart accounts owned by a regular wallet account.
+//
+// A smart account is a contract wallet at a counterfactual address (factory +
+// owner + salt). Transactions are sent as UserOperations through a bundler:
+// the owner signs the UserOperation hash and the first operation deploys the
+// account via the factory.
+//
+// ============================================================================
+
+use alloy::primitives::{Address, Bytes, B256, U256};
+use alloy::providers::Provider;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::time::Duration;
+
+use crate::chains::evm::erc4337::{self, BundlerClient, UserOperation, UserOperationReceipt};
+use crate::chains::evm::EvmAdapter;
+use crate::core::WalletService;
+use crate::error::WalletError;
+use crate::models::wallet::{Account, SmartAccountConfig};
+
+/// How often to poll the bundler for a UserOperation receipt
+const RECEIPT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Bundler endpoint for a chain
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Type)]
+pub struct BundlerConfig {
+    /// Chain the bundler serves
+    pub chain_id: u64,
+
+    /// Bundler JSON-RPC URL
+    pub url: String,
+}
+
+/// A call made by a smart account
+#[derive(Debug, Clone)]
+pub struct SmartAccountCall {
+    pub to: Address,
+    pub value: U256,
+    pub data: Bytes,
+}
+
+/// Smart account service - builds, signs and submits UserOperations
+///
+/// # Design
+///
+/// - **Stateless**: Receives adapter, bundler and wallet service as parameters
+/// - **Owner-signed**: The owner key never leaves `WalletService`
+pub struct SmartAccountService;
+
+impl SmartAccountService {
+    /// Create new smart account service
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Default parameters for a new smart account owned by `owner`
+    pub fn default_config(&self, owner: Address, salt: u64) -> SmartAccountConfig {
+        SmartAccountConfig {
+            owner,
+            factory: erc4337::SIMPLE_ACCOUNT_FACTORY_V07,
+            entry_point: erc4337::ENTRY_POINT_V07,
+            salt,
+        }
+    }
+
+    /// Validate a bundler URL (http/https only)
+    pub fn validate_bundler_url(&self, url: &str) -> Result<(), WalletError> {
+        let parsed = url::Url::parse(url)
+            .map_err(|e| WalletError::ConfigError(format!("Invalid bundler URL: {}", e)))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(WalletError::ConfigError(
+                "Bundler URL must use http or https".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Counterfactual address of the smart account
+    ///
+    /// Asks the factory on the adapter's chain, so the factory must be
+    /// deployed there.
+    pub async fn compute_address(
+        &self,
+        adapter: &EvmAdapter,
+        config: &SmartAccountConfig,
+    ) -> Result<Address, WalletError> {
+        erc4337::counterfactual_address(
+            &adapter.provider(),
+            config.factory,
+            config.owner,
+            U256::from(config.salt),
+        )
+        .await
+    }
+
+    /// Build an unsigned UserOperation with bundler gas estimates
+    ///
+    /// Adds the factory deployment data if the account is not deployed yet.
+    /// The returned operation carries a placeholder signature.
+    pub async fn prepare_user_operation(
+        &self,
+        adapter: &EvmAdapter,
+        bundler: &BundlerClient,
+        account: &Account,
+        call: SmartAccountCall,
+    ) -> Result<UserOperation, WalletError> {
+        let config = smart_config(account)?;
+        let provider = adapter.provider();
+        let sender = account.address;
+
+        let (factory, factory_data) = if erc4337::is_deployed(&provider, sender).await? {
+            (None, None)
+        } else {
+            (
+                Some(config.factory),
+                Some(erc4337::factory_data(config.owner, U256::from(config.salt))),
+            )
+        };
+
+        let nonce = erc4337::get_nonce(&provider, config.entry_point, sender).await?;
+        let fees = provider
+            .estimate_eip1559_fees()
+            .await
+            .map_err(|e| WalletError::GasEstimationFailed(e.to_string()))?;
+
+        let mut op = UserOperation {
+            sender,
+            nonce,
+            factory,
+            factory_data,
+            call_data: erc4337::execute_call_data(call.to, call.value, call.data),
+            max_fee_per_gas: U256::from(fees.max_fee_per_gas),
+            max_priority_fee_per_gas: U256::from(fees.max_priority_fee_per_gas),
+            signature: Bytes::from(erc4337::DUMMY_SIGNATURE.to_vec()),
+            ..Default::default()
+        };
+
+        let estimate = bundler
+            .estimate_user_operation_gas(&op, config.entry_point)
+            .await?;
+        op.apply_gas_estimate(&estimate);
+
+        Ok(op)
+    }
+
+    /// Sign a UserOperation with the smart account's owner
+    ///
+    /// SimpleAccount verifies an EIP-191 signature over the UserOperation
+    /// hash, so the hash is signed as a personal message.
+    ///
+    /// # Returns
+    ///
+    /// The UserOperation hash
+    pub async fn sign_user_operation(
+        &self,
+        wallet: &WalletService,
+        account: &Account,
+        op: &mut UserOperation,
+        chain_id: u64,
+        password: &str,
+    ) -> Result<B256, WalletError> {
+        let config = smart_config(account)?;
+        let hash = op.hash(config.entry_point, chain_id);
+        let signature = wallet
+            .sign_message(&config.owner, hash.as_slice(), password)
+            .await?;
+        op.signature = signature.into();
+        Ok(hash)
+    }
+
+    /// Wait until the bundler reports the UserOperation as included
+    ///
+    /// # Errors
+    ///
+    /// * `WalletError::TransactionFailed` - Included but reverted, or not
+    ///   included before `timeout`
+    pub async fn wait_for_receipt(
+        &self,
+        bundler: &BundlerClient,
+        user_op_hash: B256,
+        timeout: Duration,
+    ) -> Result<UserOperationReceipt, WalletError> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            if let Some(receipt) = bundler.get_user_operation_receipt(user_op_hash).await? {
+                if !receipt.success {
+                    return Err(WalletError::TransactionFailed(format!(
+                        "UserOperation {} reverted{}",
+                        user_op_hash,
+                        receipt.reason.as_deref().map(|r| format!(": {}", r)).unwrap_or_default()
+                    )));
+                }
+                return Ok(receipt);
+            }
+            if tokio::time::Instant::now() >= deadline {
+                return Err(WalletError::TransactionFailed(format!(
+                    "UserOperation {} not included yet",
+                    user_op_hash
+                )));
+            }
+            tokio::time::sleep(RECEIPT_POLL_INTERVAL).await;
+        }
+    }
+}
+
+impl Default for SmartAccountService {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Smart account parameters of an account, or an error for EOAs
+fn smart_config(account: &Account) -> Result<&SmartAccountConfig, WalletError> {
+    account.smart_account.as_ref().ok_or_else(|| {
+        WalletError::InvalidTransaction(format!("{} is not a smart account", account.address))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::wallet::AccountType;
+
+    #[test]
+    fn test_default_config() {
+        let service = SmartAccountService::new();
+        let owner = Address::repeat_byte(0x11);
+        let config = service.default_config(owner, 2);
+        assert_eq!(config.owner, owner);
+        assert_eq!(config.entry_point, erc4337::ENTRY_POINT_V07);
+        assert_eq!(config.factory, erc4337::SIMPLE_ACCOUNT_FACTORY_V07);
+        assert_eq!(config.salt, 2);
+    }
+
+    #[test]
+    fn test_validate_bundler_url() {
+        let service = SmartAccountService::new();
+        assert!(service.validate_bundler_url("https://bundler.example.com/rpc").is_ok());
+        assert!(service.validate_bundler_url("http://127.0.0.1:4337").is_ok());
+        assert!(service.validate_bundler_url("ws://bundler.example.com").is_err());
+        assert!(service.validate_bundler_url("not a url").is_err());
+    }
+
+    #[test]
+    fn test_smart_config_rejects_eoa() {
+        let account = Account {
+            address: Address::repeat_byte(0x22),
+            name: "Wallet 1".to_string(),
+            account_type: AccountType::Hd,
+            index: Some(0),
+            derivation_path: None,
+            passphrase_id: None,
+            smart_account: None,
+            safe: None,
+            external_signer: None,
+        };
+        assert!(smart_config(&account).is_err());
+    }
+}
@@ -337,6 +337,7 @@ mod tests {
             max_priority_fee_per_gas: None,
             nonce: None,
             chain_id: 1,
+            authorization_list: None,
         };
 
         let result = service.validate_evm_transaction(&tx);
@@ -364,6 +365,7 @@ mod tests {
             max_priority_fee_per_gas: None,
             nonce: None,
             chain_id: 1,
+            authorization_list: None,
         };
 
         // Zero amount is now allowed (for contract interactions)
@@ -386,6 +388,7 @@ mod tests {
             max_priority_fee_per_gas: None,
             nonce: None,
             chain_id: 1,
+            authorization_list: None,
         };
 
         let result = service.validate_evm_transaction(&tx);
@@ -413,6 +416,7 @@ mod tests {
             max_priority_fee_per_gas: None,
             nonce: None,
             chain_id: 1,
+            authorization_list: None,
         };
 
         let result = service.validate_evm_transaction(&tx);
@@ -440,6 +444,7 @@ mod tests {
             max_priority_fee_per_gas: None,
             nonce: None,
             chain_id: 1,
+            authorization_list: None,
         };
 
         let result = service.validate_evm_transaction(&tx);
@@ -461,6 +466,7 @@ mod tests {
             max_priority_fee_per_gas: None,
             nonce: None,
             chain_id: 1,
+            authorization_list: None,
         };
 
         let balance = Balance::new(
@@ -1,39 +1,69 @@
+use alloy::eips::eip7702::{Authorization, SignedAuthorization};
 use alloy::primitives::{Address, B256};
 use alloy::signers::local::PrivateKeySigner;
+use alloy_dyn_abi::TypedData;
+use crate::chains::evm::clef::ClefClient;
+use crate::core::backup::BackupSecrets;
+use crate::core::signer::{AccountSigner, ExternalSigner};
 use crate::error::WalletError;
-use crate::models::wallet::{Account, AccountType};
-use crate::security::KeyringService;
-use crate::security::hd_wallet::{derive_account, generate_mnemonic, validate_mnemonic};
+use crate::models::wallet::{Account, AccountType, DerivationScheme, ExternalSignerConfig, SafeAccountConfig, SmartAccountConfig};
+use crate::security::{KeyringService, SecretBackend};
+use crate::security::hd_wallet::{derive_account, derive_path, generate_mnemonic, mnemonic_to_seed, validate_mnemonic};
+use crate::security::encryption;
+use crate::security::keystore;
+use crate::security::slip39::{self, ShareGroup};
+use crate::security::throttle::{MemoryAttempts, PasswordAttempts};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::{Mutex, RwLock};
 use secrecy::ExposeSecret;
-use alloy::signers::Signer;
+use secrecy::zeroize::Zeroizing;
 
 // ============================================================================
 // Wallet Service
 // ============================================================================
 
 pub struct WalletService {
-    signers: Arc<RwLock<HashMap<Address, PrivateKeySigner>>>,
+    /// Signers of the unlocked accounts (local keys zeroize themselves
+    /// when dropped)
+    signers: Arc<RwLock<HashMap<Address, Arc<dyn AccountSigner>>>>,
     accounts: Arc<RwLock<HashMap<Address, Account>>>,
-    passphrase_hash: Arc<RwLock<Option<String>>>,
+    /// Argon2 verifier (PHC string) of the password while unlocked; the
+    /// password itself is never kept
+    password_verifier: Arc<RwLock<Option<String>>>,
+    /// Wrong password count behind the unlock throttle
+    password_attempts: RwLock<Arc<dyn PasswordAttempts>>,
+    /// Held while a password is checked, so guesses cannot run in parallel
+    password_attempt: Mutex<()>,
     pub(crate) keyring: KeyringService,
 }
 
 impl WalletService {
-    pub fn new() -> Self {
+    pub fn new() -> Result<Self, WalletError> {
+        Ok(Self::with_keyring(KeyringService::new("vaughan-wallet")?))
+    }
+
+    /// Wallet service on a given key store (e.g. in-memory for tests)
+    pub fn with_keyring(keyring: KeyringService) -> Self {
         Self {
             signers: Arc::new(RwLock::new(HashMap::new())),
             accounts: Arc::new(RwLock::new(HashMap::new())),
-            passphrase_hash: Arc::new(RwLock::new(None)),
-            keyring: KeyringService::new("vaughan-wallet").unwrap(),
+            password_verifier: Arc::new(RwLock::new(None)),
+            password_attempts: RwLock::new(Arc::new(MemoryAttempts::default())),
+            password_attempt: Mutex::new(()),
+            keyring,
         }
     }
 
+    /// Keep the wrong password count somewhere else (e.g. persisted)
+    pub async fn set_password_attempts(&self, attempts: Arc<dyn PasswordAttempts>) {
+        *self.password_attempts.write().await = attempts;
+    }
+
     pub async fn add_signer(&self, signer: PrivateKeySigner) {
         let mut signers = self.signers.write().await;
-        signers.insert(signer.address(), signer);
+        signers.insert(signer.address(), Arc::new(signer));
     }
 
     pub async fn add_account(&self, account: Account) {
@@ -41,49 +71,137 @@ impl WalletService {
         accounts.insert(account.address, account.clone());
     }
 
-    pub async fn get_signer(&self, address: &Address) -> Result<PrivateKeySigner, WalletError> {
-        let signers = self.signers.read().await;
-        signers.get(address).cloned().ok_or(WalletError::AccountNotFound(address.to_string()))
+    pub async fn get_account(&self, address: &Address) -> Option<Account> {
+        self.accounts.read().await.get(address).cloned()
+    }
+
+    /// Signer of an unlocked account: its local key or its external signer
+    pub async fn get_signer(&self, address: &Address) -> Result<Arc<dyn AccountSigner>, WalletError> {
+        if let Some(signer) = self.signers.read().await.get(address) {
+            return Ok(signer.clone());
+        }
+        self.ensure_can_sign(address).await?;
+        Err(WalletError::AccountNotFound(address.to_string()))
+    }
+
+    /// Key of an account whose key is in this wallet (for export)
+    async fn local_key(&self, address: &Address) -> Result<PrivateKeySigner, WalletError> {
+        self.get_signer(address).await?.local_key().cloned().ok_or_else(|| {
+            WalletError::ExternalSigner(format!("The key of {} is held by its external signer", address))
+        })
+    }
+
+    /// Fail early for accounts that can never sign (watch-only), so
+    /// callers can refuse before asking the user to approve anything
+    pub async fn ensure_can_sign(&self, address: &Address) -> Result<(), WalletError> {
+        match self.accounts.read().await.get(address) {
+            Some(account) if account.account_type == AccountType::WatchOnly => {
+                Err(WalletError::WatchOnlyAccount(address.to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Like `ensure_can_sign`, for signatures over a raw hash (EIP-7702
+    /// authorizations), which external signers refuse
+    pub async fn ensure_can_sign_hash(&self, address: &Address) -> Result<(), WalletError> {
+        self.ensure_can_sign(address).await?;
+        match self.accounts.read().await.get(address) {
+            Some(account) if account.account_type == AccountType::External => Err(WalletError::ExternalSigner(
+                format!("{} is held by an external signer, which can't sign EIP-7702 authorizations", address),
+            )),
+            _ => Ok(()),
+        }
     }
 
     pub async fn sign_message(&self, address: &Address, message: &[u8], password: &str) -> Result<Vec<u8>, WalletError> {
         self.verify_password(password).await?;
         let signer = self.get_signer(address).await?;
-        let signature = signer.sign_message(message).await.map_err(|e| WalletError::SigningFailed(e.to_string()))?;
+        let signature = signer.sign_message(message).await?;
+        Ok(signature.as_bytes().to_vec())
+    }
+
+    pub async fn sign_typed_data(&self, address: &Address, typed_data: &TypedData, password: &str) -> Result<Vec<u8>, WalletError> {
+        self.verify_password(password).await?;
+        let signer = self.get_signer(address).await?;
+        let signature = signer.sign_typed_data(typed_data).await?;
         Ok(signature.as_bytes().to_vec())
     }
 
     pub async fn sign_hash(&self, address: &Address, hash: B256, password: &str) -> Result<Vec<u8>, WalletError> {
         self.verify_password(password).await?;
         let signer = self.get_signer(address).await?;
-        let signature = signer.sign_hash(&hash).await.map_err(|e| WalletError::SigningFailed(e.to_string()))?;
+        let signature = signer.sign_hash(&hash).await?;
         Ok(signature.as_bytes().to_vec())
     }
 
-    // New function based on the provided snippet, adapted to fit the WalletService context
+    pub async fn sign_authorization(&self, address: &Address, authorization: Authorization, password: &str) -> Result<SignedAuthorization, WalletError> {
+        self.verify_password(password).await?;
+        let signer = self.get_signer(address).await?;
+        let signature = signer.sign_hash(&authorization.signature_hash()).await?;
+        Ok(authorization.into_signed(signature))
+    }
+
+    /// Check the wallet password
+    ///
+    /// While unlocked, the password is checked against the in-memory Argon2
+    /// verifier (constant-time comparison). Otherwise it is checked by
+    /// decrypting the seed from the keychain, which leaves the wallet locked:
+    /// only `unlock` loads the keys and installs the verifier.
+    ///
+    /// Every password check goes through the unlock throttle
+    /// (`security::throttle`): attempts run one at a time, and after too
+    /// many wrong passwords the next one is refused until the delay is over.
+    ///
+    /// # Errors
+    ///
+    /// * `WalletError::InvalidPassword` - Wrong password
+    /// * `WalletError::UnlockThrottled` - Called again before the delay is over
+    /// * `WalletError::KeyringError` - The keychain can't be read (not
+    ///   counted as a wrong password)
     pub async fn verify_password(&self, password: &str) -> Result<(), WalletError> {
-        // 1. Check in-memory cache
-        {
-            let hash = self.passphrase_hash.read().await;
-            if let Some(h) = hash.as_ref() {
-                if h == password {
-                    return Ok(());
+        let _one_at_a_time = self.password_attempt.lock().await;
+        let attempts = self.password_attempts.read().await.clone();
+
+        let mut throttle = attempts.load();
+        let retry_after = throttle.retry_after(unix_now());
+        if retry_after > 0 {
+            return Err(WalletError::UnlockThrottled(retry_after));
+        }
+
+        let result = self.check_password(password).await;
+        match &result {
+            Ok(()) if throttle.failed_attempts > 0 => {
+                throttle.reset();
+                if let Err(e) = attempts.save(&throttle) {
+                    tracing::warn!("[Wallet] Could not clear failed password attempts: {}", e);
                 }
-                return Err(WalletError::InvalidPassword);
             }
+            Err(WalletError::InvalidPassword) => {
+                let now = unix_now();
+                throttle.record_failure(now);
+                // Counted before anything else, so a crash cannot lose a failure
+                attempts.save(&throttle)?;
+                attempts.on_failure(&throttle, throttle.retry_after(now)).await;
+            }
+            _ => {}
         }
+        result
+    }
 
-        // 2. Try to verify via keychain (fallback for across restarts)
-        if self.keyring.key_exists("vaughan_seed") {
+    async fn check_password(&self, password: &str) -> Result<(), WalletError> {
+        // 1. Check the in-memory verifier
+        let verifier = self.password_verifier.read().await.clone();
+        if let Some(verifier) = verifier {
+            return encryption::verify_password(password, &verifier);
+        }
+
+        // 2. Locked: verify via keychain. Only a failed decryption is a
+        // wrong password; a keyring error is passed on and not counted
+        if self.keyring.has_key("vaughan_seed")? {
             match self.keyring.retrieve_key("vaughan_seed", password) {
-                Ok(_) => {
-                    // Password is correct, cache it
-                    self.set_password(password.to_string()).await;
-                    return Ok(());
-                }
-                Err(WalletError::KeyringError(_)) | Err(WalletError::DecryptionFailed(_)) => {
-                    return Err(WalletError::InvalidPassword);
-                }
+                Ok(_) => return Ok(()),
+                Err(WalletError::DecryptionFailed(_)) => return Err(WalletError::InvalidPassword),
                 Err(e) => return Err(e),
             }
         }
@@ -92,13 +210,15 @@ impl WalletService {
     }
 
     pub async fn unlock(&self, password: &str, accounts_to_restore: Vec<Account>) -> Result<(), WalletError> {
-        // 1. Verify password (populates cache)
+        // 1. Verify password
         self.verify_password(password).await?;
         
         // 2. Restore seed and derive HD accounts
         let mnemonic_secret = self.keyring.retrieve_key("vaughan_seed", password)?;
         let mnemonic = mnemonic_secret.expose_secret();
-        let seed = crate::security::hd_wallet::mnemonic_to_seed(mnemonic, None)?;
+        // One seed per BIP-39 passphrase (the wallet's own plus hidden wallets)
+        let mut seeds = HashMap::new();
+        seeds.insert(None, self.derive_seed(mnemonic, None, password)?);
 
         let mut signers = self.signers.write().await;
         let mut accounts = self.accounts.write().await;
@@ -106,9 +226,20 @@ impl WalletService {
         for account in accounts_to_restore {
             match account.account_type {
                 AccountType::Hd => {
-                    if let Some(index) = account.index {
-                        if let Ok(signer) = derive_account(&seed, index) {
-                            signers.insert(signer.address(), signer);
+                    if !seeds.contains_key(&account.passphrase_id) {
+                        match self.derive_seed(mnemonic, account.passphrase_id.as_deref(), password) {
+                            Ok(seed) => {
+                                seeds.insert(account.passphrase_id.clone(), seed);
+                            }
+                            Err(e) => {
+                                tracing::warn!("[Wallet] Hidden wallet passphrase unavailable for {}: {}", account.address, e);
+                                continue;
+                            }
+                        }
+                    }
+                    if let Some(path) = account.hd_path() {
+                        if let Ok(signer) = derive_path(&seeds[&account.passphrase_id], &path) {
+                            signers.insert(signer.address(), Arc::new(signer));
                             accounts.insert(account.address, account.clone());
                         }
                     }
@@ -118,28 +249,65 @@ impl WalletService {
                     if let Ok(pk_secret) = self.keyring.retrieve_key(&key_id, password) {
                         let pk = pk_secret.expose_secret();
                         if let Ok(signer) = pk.parse::<PrivateKeySigner>() {
-                            signers.insert(signer.address(), signer);
+                            signers.insert(signer.address(), Arc::new(signer));
+                            accounts.insert(account.address, account.clone());
+                        }
+                    }
+                }
+                AccountType::Smart | AccountType::Safe => {
+                    // No key of its own; signing goes through the owner account(s)
+                    accounts.insert(account.address, account.clone());
+                }
+                AccountType::WatchOnly => {
+                    accounts.insert(account.address, account.clone());
+                }
+                AccountType::External => {
+                    // Nothing to unlock; the signer is only contacted to sign
+                    let client = account
+                        .external_signer
+                        .as_ref()
+                        .map(|config| ClefClient::new(&config.url));
+                    match client {
+                        Some(Ok(client)) => {
+                            let signer = ExternalSigner::new(account.address, Arc::new(client));
+                            signers.insert(account.address, Arc::new(signer));
                             accounts.insert(account.address, account.clone());
                         }
+                        _ => tracing::warn!("[Wallet] No usable external signer for {}", account.address),
                     }
                 }
             }
         }
+        drop(signers);
+        drop(accounts);
+        // Unlocked only once the keys are loaded
+        self.set_password(password).await?;
+
+        // Keys written with older encryption parameters are re-encrypted
+        // with the current ones; the unlock stands either way
+        let key_ids = self.secret_key_ids().await;
+        if let Err(e) = self.keyring.upgrade_encryption(&key_ids, password) {
+            tracing::warn!("[Wallet] Could not upgrade key encryption: {}", e);
+        }
         Ok(())
     }
 
     pub async fn is_locked(&self) -> bool {
-        self.passphrase_hash.read().await.is_none()
+        self.password_verifier.read().await.is_none()
     }
 
+    /// Lock the wallet: forget the password verifier and drop all unlocked
+    /// keys (which zeroizes them)
     pub async fn lock(&self) {
-        let mut hash = self.passphrase_hash.write().await;
-        *hash = None;
+        *self.password_verifier.write().await = None;
+        self.signers.write().await.clear();
     }
 
-    pub async fn set_password(&self, password: String) {
-        let mut hash = self.passphrase_hash.write().await;
-        *hash = Some(password);
+    /// Keep an Argon2 verifier of the password for the unlocked session
+    pub async fn set_password(&self, password: &str) -> Result<(), WalletError> {
+        let verifier = encryption::hash_password(password)?;
+        *self.password_verifier.write().await = Some(verifier);
+        Ok(())
     }
 
     // --- Restored Methods ---
@@ -149,13 +317,15 @@ impl WalletService {
         self.keyring.key_exists("vaughan_seed")
     }
 
-    pub async fn create_wallet(&self, password: &str, word_count: usize) -> Result<String, WalletError> {
+    /// Create a wallet, optionally with a BIP-39 passphrase (25th word)
+    pub async fn create_wallet(&self, password: &str, word_count: usize, passphrase: Option<&str>) -> Result<String, WalletError> {
         let mnemonic = generate_mnemonic(word_count)?;
         self.keyring.store_key("vaughan_seed", &mnemonic, password)?;
-        self.set_password(password.to_string()).await;
+        self.store_passphrase(passphrase, password)?;
+        self.set_password(password).await?;
         
         // Derive and store first account
-        let seed = crate::security::hd_wallet::mnemonic_to_seed(&mnemonic, None)?;
+        let seed = self.derive_seed(&mnemonic, None, password)?;
         let signer = derive_account(&seed, 0)?;
         let address = signer.address();
         self.add_signer(signer).await;
@@ -164,18 +334,25 @@ impl WalletService {
             name: "Wallet 1".to_string(),
             account_type: AccountType::Hd,
             index: Some(0),
+            derivation_path: Some(DerivationScheme::Bip44.path(0)),
+            passphrase_id: None,
+            smart_account: None,
+            safe: None,
+            external_signer: None,
         }).await;
 
         Ok(mnemonic)
     }
 
-    pub async fn import_wallet(&self, mnemonic: &str, password: &str, account_count: u32) -> Result<Vec<Address>, WalletError> {
+    /// Import a wallet, optionally with a BIP-39 passphrase (25th word)
+    pub async fn import_wallet(&self, mnemonic: &str, password: &str, account_count: u32, passphrase: Option<&str>) -> Result<Vec<Address>, WalletError> {
         validate_mnemonic(mnemonic)?;
         self.keyring.store_key("vaughan_seed", mnemonic, password)?;
-        self.set_password(password.to_string()).await;
+        self.store_passphrase(passphrase, password)?;
+        self.set_password(password).await?;
         
         let mut addresses = Vec::new();
-        let seed = crate::security::hd_wallet::mnemonic_to_seed(mnemonic, None)?;
+        let seed = self.derive_seed(mnemonic, None, password)?;
         for i in 0..account_count {
             let signer = derive_account(&seed, i)?;
             let address = signer.address();
@@ -190,6 +367,11 @@ impl WalletService {
                 name,
                 account_type: AccountType::Hd,
                 index: Some(i),
+                derivation_path: Some(DerivationScheme::Bip44.path(i)),
+                passphrase_id: None,
+                smart_account: None,
+                safe: None,
+                external_signer: None,
             }).await;
             addresses.push(address);
         }
@@ -201,44 +383,182 @@ impl WalletService {
         Ok(accounts.values().cloned().collect())
     }
 
-    pub async fn create_account(&self, password: &str) -> Result<Account, WalletError> {
+    /// Derive the next HD account of the main wallet (`passphrase_id`
+    /// `None`) or of a hidden wallet, on the given derivation scheme
+    pub async fn create_account(&self, password: &str, passphrase_id: Option<&str>, scheme: &DerivationScheme) -> Result<Account, WalletError> {
         self.verify_password(password).await?;
-        let mnemonic_secret = self.keyring.retrieve_key("vaughan_seed", password)?;
-        let mnemonic = mnemonic_secret.expose_secret();
-        
-        // Find next index
-        let signers = self.signers.read().await;
-        let next_index = signers.len() as u32;
-        drop(signers);
 
-        let seed = crate::security::hd_wallet::mnemonic_to_seed(mnemonic, None)?;
-        let signer = derive_account(&seed, next_index)?;
+        // Find next index on this scheme (signers are dropped on lock, so use the accounts)
+        let next_index = self
+            .accounts
+            .read()
+            .await
+            .values()
+            .filter(|a| a.account_type == AccountType::Hd && a.passphrase_id.as_deref() == passphrase_id)
+            .filter_map(|a| a.index.filter(|i| a.hd_path() == Some(scheme.path(*i))))
+            .max()
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        if next_index > 0 && !scheme.is_indexed() {
+            return Err(WalletError::Custom("This derivation path has no {i} and is already in use".to_string()));
+        }
+
+        let name = match passphrase_id {
+            Some(_) => format!("Hidden Wallet {}", next_index + 1),
+            None if next_index == 0 && *scheme == DerivationScheme::Bip44 => "Wallet 1".to_string(),
+            None => format!("HD Wallet {}", next_index + 1),
+        };
+        self.add_hd_account(password, passphrase_id, next_index, &scheme.path(next_index), name).await
+    }
+
+    /// Add the HD account at a derivation path
+    ///
+    /// # Errors
+    ///
+    /// * `WalletError::Custom` - The account is already in the wallet
+    pub async fn add_hd_account(&self, password: &str, passphrase_id: Option<&str>, index: u32, path: &str, name: String) -> Result<Account, WalletError> {
+        self.verify_password(password).await?;
+        let mnemonic_secret = self.keyring.retrieve_key("vaughan_seed", password)?;
+        let seed = self.derive_seed(mnemonic_secret.expose_secret(), passphrase_id, password)?;
+        let signer = derive_path(&seed, path)?;
         let address = signer.address();
+        if self.accounts.read().await.contains_key(&address) {
+            return Err(WalletError::Custom(format!("Account {} already exists", address)));
+        }
         self.add_signer(signer).await;
 
-        let name = if next_index == 0 {
-            "Wallet 1".to_string()
-        } else {
-            format!("HD Wallet {}", next_index + 1)
-        };
         let account = Account {
             address,
             name,
             account_type: AccountType::Hd,
-            index: Some(next_index),
+            index: Some(index),
+            derivation_path: Some(path.to_string()),
+            passphrase_id: passphrase_id.map(str::to_string),
+            smart_account: None,
+            safe: None,
+            external_signer: None,
         };
         self.add_account(account.clone()).await;
 
         Ok(account)
     }
 
-    pub async fn import_account(&self, private_key: &str, name: String, password: &str) -> Result<Account, WalletError> {
+    /// Addresses at consecutive indices of a derivation scheme, without
+    /// adding them (for discovery)
+    ///
+    /// # Returns
+    ///
+    /// `(index, path, address)` for `start..start + count`
+    pub async fn derive_addresses(&self, password: &str, passphrase_id: Option<&str>, scheme: &DerivationScheme, start: u32, count: u32) -> Result<Vec<(u32, String, Address)>, WalletError> {
+        self.verify_password(password).await?;
+        let mnemonic_secret = self.keyring.retrieve_key("vaughan_seed", password)?;
+        let seed = self.derive_seed(mnemonic_secret.expose_secret(), passphrase_id, password)?;
+        (start..start.saturating_add(count))
+            .map(|index| {
+                let path = scheme.path(index);
+                derive_path(&seed, &path).map(|signer| (index, path, signer.address()))
+            })
+            .collect()
+    }
+
+    /// Add a hidden wallet: the same mnemonic with another BIP-39 passphrase
+    ///
+    /// The passphrase is stored encrypted like the seed and used again at
+    /// unlock. Returns the hidden wallet's first account.
+    pub async fn add_hidden_wallet(&self, passphrase: &str, name: String, password: &str) -> Result<Account, WalletError> {
         self.verify_password(password).await?;
+        if passphrase.is_empty() {
+            return Err(WalletError::Custom("Passphrase is empty".to_string()));
+        }
+        let mnemonic_secret = self.keyring.retrieve_key("vaughan_seed", password)?;
+        let seed = Zeroizing::new(mnemonic_to_seed(mnemonic_secret.expose_secret(), Some(passphrase))?);
+        let signer = derive_account(&seed, 0)?;
+        let address = signer.address();
+        if self.accounts.read().await.contains_key(&address) {
+            return Err(WalletError::Custom("A wallet with this passphrase is already added".to_string()));
+        }
+
+        let passphrase_id = uuid::Uuid::new_v4().simple().to_string();
+        self.keyring.store_key(&passphrase_key_id(Some(&passphrase_id)), passphrase, password)?;
+        self.add_signer(signer).await;
+
+        let account = Account {
+            address,
+            name,
+            account_type: AccountType::Hd,
+            index: Some(0),
+            derivation_path: Some(DerivationScheme::Bip44.path(0)),
+            passphrase_id: Some(passphrase_id),
+            smart_account: None,
+            safe: None,
+            external_signer: None,
+        };
+        self.add_account(account.clone()).await;
+        Ok(account)
+    }
+
+    /// Store (or clear) the wallet's own BIP-39 passphrase
+    fn store_passphrase(&self, passphrase: Option<&str>, password: &str) -> Result<(), WalletError> {
+        let key_id = passphrase_key_id(None);
+        match passphrase.filter(|p| !p.is_empty()) {
+            Some(passphrase) => self.keyring.store_key(&key_id, passphrase, password),
+            None => {
+                // A previous wallet's passphrase must not apply to this one
+                let _ = self.keyring.delete_key(&key_id);
+                Ok(())
+            }
+        }
+    }
+
+    /// BIP-39 seed for the wallet's own passphrase or a hidden wallet's
+    fn derive_seed(&self, mnemonic: &str, passphrase_id: Option<&str>, password: &str) -> Result<Zeroizing<Vec<u8>>, WalletError> {
+        let key_id = passphrase_key_id(passphrase_id);
+        // Wallets created without a passphrase have no entry
+        if passphrase_id.is_none() && !self.keyring.key_exists(&key_id) {
+            return Ok(Zeroizing::new(mnemonic_to_seed(mnemonic, None)?));
+        }
+        let passphrase = self.keyring.retrieve_key(&key_id, password)?;
+        Ok(Zeroizing::new(mnemonic_to_seed(mnemonic, Some(passphrase.expose_secret()))?))
+    }
+
+    pub async fn import_account(&self, private_key: &str, name: String, password: &str) -> Result<Account, WalletError> {
         let signer: PrivateKeySigner = private_key.parse().map_err(|_| WalletError::InvalidPrivateKey("Invalid private key hex".to_string()))?;
+        self.import_signer(signer, name, password).await
+    }
+
+    /// Import an account from a V3 keystore JSON
+    ///
+    /// `keystore_password` decrypts the keystore; the key is then stored
+    /// under the wallet password like any imported key.
+    pub async fn import_keystore(&self, json: &str, keystore_password: &str, name: String, password: &str) -> Result<Account, WalletError> {
+        self.verify_password(password).await?;
+        let json = json.to_string();
+        let keystore_password = Zeroizing::new(keystore_password.to_string());
+        // scrypt/pbkdf2 take a while; keep them off the async workers
+        let signer = tokio::task::spawn_blocking(move || keystore::decrypt_keystore(&json, &keystore_password))
+            .await
+            .map_err(|e| WalletError::InternalError(format!("Keystore task failed: {}", e)))??;
+        self.import_signer(signer, name, password).await
+    }
+
+    /// Export any key-holding account (HD or imported) as a V3 keystore
+    /// encrypted with `keystore_password`
+    pub async fn export_keystore(&self, address: &Address, password: &str, keystore_password: &str) -> Result<String, WalletError> {
+        self.verify_password(password).await?;
+        let signer = self.local_key(address).await?;
+        let keystore_password = Zeroizing::new(keystore_password.to_string());
+        tokio::task::spawn_blocking(move || keystore::encrypt_keystore(&signer, &keystore_password))
+            .await
+            .map_err(|e| WalletError::InternalError(format!("Keystore task failed: {}", e)))?
+    }
+
+    async fn import_signer(&self, signer: PrivateKeySigner, name: String, password: &str) -> Result<Account, WalletError> {
+        self.verify_password(password).await?;
         let address = signer.address();
         // Store in keychain
         let key_id = format!("account_{}", address);
-        self.keyring.store_key(&key_id, private_key, password)?;
+        let private_key = Zeroizing::new(hex::encode(signer.to_bytes()));
+        self.keyring.store_key(&key_id, &private_key, password)?;
 
         self.add_signer(signer).await;
         
@@ -247,17 +567,138 @@ impl WalletService {
             name: name,
             account_type: AccountType::Imported,
             index: None,
+            derivation_path: None,
+            passphrase_id: None,
+            smart_account: None,
+            safe: None,
+            external_signer: None,
         };
         self.add_account(account.clone()).await;
         
         Ok(account)
     }
 
+    pub async fn add_smart_account(&self, address: Address, name: String, config: SmartAccountConfig, password: &str) -> Result<Account, WalletError> {
+        self.verify_password(password).await?;
+        // The owner must be a key we hold
+        self.get_signer(&config.owner).await?;
+
+        let account = Account {
+            address,
+            name,
+            account_type: AccountType::Smart,
+            index: None,
+            derivation_path: None,
+            passphrase_id: None,
+            smart_account: Some(config),
+            safe: None,
+            external_signer: None,
+        };
+        self.add_account(account.clone()).await;
+
+        Ok(account)
+    }
+
+    pub async fn add_safe_account(&self, address: Address, name: String, config: SafeAccountConfig, password: &str) -> Result<Account, WalletError> {
+        self.verify_password(password).await?;
+
+        let account = Account {
+            address,
+            name,
+            account_type: AccountType::Safe,
+            index: None,
+            derivation_path: None,
+            passphrase_id: None,
+            smart_account: None,
+            safe: Some(config),
+            external_signer: None,
+        };
+        self.add_account(account.clone()).await;
+
+        Ok(account)
+    }
+
+    /// Add an address to monitor without a key
+    ///
+    /// Balances, tokens and history work as for any account; signing fails
+    /// with `WalletError::WatchOnlyAccount`.
+    pub async fn add_watch_only_account(&self, address: Address, name: String, password: &str) -> Result<Account, WalletError> {
+        self.verify_password(password).await?;
+        if self.get_account(&address).await.is_some() {
+            return Err(WalletError::Custom(format!("Account {} already exists", address)));
+        }
+
+        let account = Account {
+            address,
+            name,
+            account_type: AccountType::WatchOnly,
+            index: None,
+            derivation_path: None,
+            passphrase_id: None,
+            smart_account: None,
+            safe: None,
+            external_signer: None,
+        };
+        self.add_account(account.clone()).await;
+
+        Ok(account)
+    }
+
+    /// Add the accounts of a Clef-compatible external signer
+    ///
+    /// # Returns
+    ///
+    /// The accounts added (addresses already in the wallet are skipped)
+    pub async fn add_external_accounts(&self, url: &str, password: &str) -> Result<Vec<Account>, WalletError> {
+        self.verify_password(password).await?;
+        let client = Arc::new(ClefClient::new(url)?);
+        let addresses = client.list_accounts().await?;
+        if addresses.is_empty() {
+            return Err(WalletError::ExternalSigner(format!("{} has no accounts", client.endpoint())));
+        }
+
+        let mut signers = self.signers.write().await;
+        let mut accounts = self.accounts.write().await;
+        let mut count = accounts.values().filter(|a| a.account_type == AccountType::External).count();
+        let mut added = Vec::new();
+        for address in addresses {
+            if accounts.contains_key(&address) {
+                continue;
+            }
+            count += 1;
+            let account = Account {
+                address,
+                name: format!("External {}", count),
+                account_type: AccountType::External,
+                index: None,
+                derivation_path: None,
+                passphrase_id: None,
+                smart_account: None,
+                safe: None,
+                external_signer: Some(ExternalSignerConfig { url: url.trim().to_string() }),
+            };
+            signers.insert(address, Arc::new(ExternalSigner::new(address, client.clone())));
+            accounts.insert(address, account.clone());
+            added.push(account);
+        }
+        Ok(added)
+    }
+
     pub async fn delete_account(&self, address: &Address) -> Result<(), WalletError> {
         let mut signers = self.signers.write().await;
-        signers.remove(address).ok_or(WalletError::AccountNotFound(address.to_string()))?;
         let mut accounts = self.accounts.write().await;
-        accounts.remove(address);
+        let had_signer = signers.remove(address).is_some();
+        let removed = accounts.remove(address);
+        if !had_signer && removed.is_none() {
+            return Err(WalletError::AccountNotFound(address.to_string()));
+        }
+
+        // Forget a hidden wallet's passphrase with its last account
+        if let Some(passphrase_id) = removed.and_then(|a| a.passphrase_id) {
+            if !accounts.values().any(|a| a.passphrase_id.as_deref() == Some(passphrase_id.as_str())) {
+                let _ = self.keyring.delete_key(&passphrase_key_id(Some(&passphrase_id)));
+            }
+        }
         Ok(())
     }
 
@@ -271,7 +712,7 @@ impl WalletService {
 
     pub async fn export_private_key(&self, address: &Address, password: &str) -> Result<String, WalletError> {
         self.verify_password(password).await?;
-        let signer = self.get_signer(address).await?;
+        let signer = self.local_key(address).await?;
         Ok(format!("0x{}", hex::encode(signer.to_bytes())))
     }
 
@@ -281,20 +722,766 @@ impl WalletService {
         Ok(mnemonic_secret.expose_secret().clone())
     }
 
+    /// Split the seed into SLIP-39 shares
+    ///
+    /// # Returns
+    ///
+    /// The shares per group and the first account address, to note down
+    /// with the shares and check on restore
+    pub async fn create_seed_shares(&self, password: &str, group_threshold: u8, groups: &[ShareGroup]) -> Result<(Vec<Vec<String>>, Address), WalletError> {
+        self.verify_password(password).await?;
+        let mnemonic_secret = self.keyring.retrieve_key("vaughan_seed", password)?;
+        let mnemonic = mnemonic_secret.expose_secret();
+        let shares = slip39::split_mnemonic(mnemonic, group_threshold, groups)?;
+
+        // With the wallet's BIP-39 passphrase, as `restore_from_shares` derives it
+        let seed = self.derive_seed(mnemonic, None, password)?;
+        let first_account = derive_account(&seed, 0)?.address();
+        Ok((shares, first_account))
+    }
+
+    /// Restore a wallet from a quorum of SLIP-39 shares
+    ///
+    /// The combined seed must derive `first_account` at `m/44'/60'/0'/0/0`
+    /// (with `passphrase`) before anything is stored.
+    ///
+    /// # Errors
+    ///
+    /// * `WalletError::InvalidMnemonic` - Bad or insufficient shares
+    /// * `WalletError::Custom` - The shares restore a different wallet, or a
+    ///   wallet already exists
+    pub async fn restore_from_shares(&self, shares: &[String], password: &str, passphrase: Option<&str>, first_account: Address) -> Result<Vec<Address>, WalletError> {
+        if self.wallet_exists() {
+            return Err(WalletError::Custom(
+                "A wallet already exists on this device; reset it before restoring from shares".to_string(),
+            ));
+        }
+        let mnemonic = Zeroizing::new(slip39::combine_shares(shares)?);
+        let seed = Zeroizing::new(mnemonic_to_seed(&mnemonic, passphrase.filter(|p| !p.is_empty()))?);
+        let restored = derive_account(&seed, 0)?.address();
+        if restored != first_account {
+            return Err(WalletError::Custom(format!(
+                "Shares restore {} as the first account, not {}",
+                restored, first_account
+            )));
+        }
+        self.import_wallet(&mnemonic, password, 1, passphrase).await
+    }
+
     pub async fn get_railgun_mnemonic(&self, password: &str) -> Result<String, WalletError> {
         self.verify_password(password).await?;
         // For POC, return same mnemonic. Real version would derive a separate one.
         self.export_mnemonic(password).await
     }
 
-    pub async fn wipe(&self) -> Result<(), WalletError> {
+    /// Secret storage backend in use
+    pub fn secret_backend(&self) -> SecretBackend {
+        self.keyring.backend()
+    }
+
+    /// Whether the OS keychain works on this system
+    pub fn os_keyring_available(&self) -> bool {
+        self.keyring.os_keyring_available()
+    }
+
+    /// Move the seed and imported keys to another secret storage backend
+    ///
+    /// The wallet must be unlocked so every imported account is known.
+    ///
+    /// # Returns
+    ///
+    /// Number of secrets moved
+    pub async fn migrate_secret_storage(&self, backend: SecretBackend, password: &str) -> Result<usize, WalletError> {
+        if self.is_locked().await {
+            return Err(WalletError::WalletLocked);
+        }
+        self.verify_password(password).await?;
+        if backend == SecretBackend::Memory {
+            return Err(WalletError::Custom("In-memory secret storage does not survive a restart".to_string()));
+        }
+
+        let key_ids = self.secret_key_ids().await;
+        self.keyring.migrate_to(backend, &key_ids)
+    }
+
+    /// Change the wallet password
+    ///
+    /// Re-encrypts the seed, BIP-39 passphrases and imported keys under the
+    /// new password, all or nothing (see `KeyringService::change_password`).
+    /// Unlock skips accounts it can't load, so the persisted accounts are
+    /// passed in as well: their keys are changed too, and a key that can't
+    /// be decrypted with the old password fails the whole change.
+    ///
+    /// # Returns
+    ///
+    /// Number of secrets re-encrypted
+    pub async fn change_password(&self, old_password: &str, new_password: &str, persisted_accounts: &[Account]) -> Result<usize, WalletError> {
+        if self.is_locked().await {
+            return Err(WalletError::WalletLocked);
+        }
+        self.verify_password(old_password).await?;
+        if new_password.is_empty() {
+            return Err(WalletError::InvalidPassword);
+        }
+
+        let mut key_ids = self.secret_key_ids().await;
+        key_ids.extend(secret_key_ids(persisted_accounts));
+        key_ids.sort();
+        key_ids.dedup();
+        let changed = self.keyring.change_password(&key_ids, old_password, new_password)?;
+        self.set_password(new_password).await?;
+        Ok(changed)
+    }
+
+    /// Seed, BIP-39 passphrases and imported keys for a full backup
+    ///
+    /// The wallet must be unlocked so every imported account is known.
+    pub async fn backup_secrets(&self, password: &str) -> Result<BackupSecrets, WalletError> {
+        if self.is_locked().await {
+            return Err(WalletError::WalletLocked);
+        }
+        self.verify_password(password).await?;
+
+        let retrieve = |key_id: &str| {
+            self.keyring
+                .retrieve_key(key_id, password)
+                .map(|secret| secret.expose_secret().clone())
+        };
+        let accounts = self.get_accounts().await?;
+        let own_passphrase = passphrase_key_id(None);
+        let mut secrets = BackupSecrets {
+            mnemonic: retrieve("vaughan_seed")?,
+            passphrase: None,
+            hidden_passphrases: Default::default(),
+            imported_keys: Default::default(),
+        };
+        if self.keyring.key_exists(&own_passphrase) {
+            secrets.passphrase = Some(retrieve(&own_passphrase)?);
+        }
+        for account in &accounts {
+            if let Some(id) = &account.passphrase_id {
+                if !secrets.hidden_passphrases.contains_key(id) {
+                    secrets.hidden_passphrases.insert(id.clone(), retrieve(&passphrase_key_id(Some(id)))?);
+                }
+            }
+            if account.account_type == AccountType::Imported {
+                secrets
+                    .imported_keys
+                    .insert(account.address, retrieve(&format!("account_{}", account.address))?);
+            }
+        }
+        Ok(secrets)
+    }
+
+    /// Restore a wallet from a backup's secrets and accounts
+    ///
+    /// Only on a device without a wallet. The keys are checked before
+    /// anything is stored, and if storing or unlocking fails the entries
+    /// written so far are deleted again. The wallet is left unlocked.
+    ///
+    /// # Returns
+    ///
+    /// The accounts restored
+    ///
+    /// # Errors
+    ///
+    /// * `WalletError::Custom` - A wallet already exists
+    /// * `WalletError::InvalidMnemonic` / `InvalidPrivateKey` - Bad secrets
+    pub async fn restore_backup(&self, secrets: &BackupSecrets, accounts: Vec<Account>, password: &str) -> Result<Vec<Account>, WalletError> {
+        if self.wallet_exists() {
+            return Err(WalletError::Custom(
+                "A wallet already exists on this device; reset it before restoring a backup".to_string(),
+            ));
+        }
+        validate_mnemonic(&secrets.mnemonic)?;
+        for (address, key) in &secrets.imported_keys {
+            let signer: PrivateKeySigner = key
+                .parse()
+                .map_err(|_| WalletError::InvalidPrivateKey(format!("Invalid key for {}", address)))?;
+            if signer.address() != *address {
+                return Err(WalletError::InvalidPrivateKey(format!("Key does not belong to {}", address)));
+            }
+        }
+
+        // The seed goes last: the wallet only exists once it is stored
+        let mut entries: Vec<(String, &str)> = Vec::new();
+        match secrets.passphrase.as_deref().filter(|p| !p.is_empty()) {
+            Some(passphrase) => entries.push((passphrase_key_id(None), passphrase)),
+            // A previous wallet's passphrase must not apply to this one
+            None => {
+                let _ = self.keyring.delete_key(&passphrase_key_id(None));
+            }
+        }
+        for (id, passphrase) in &secrets.hidden_passphrases {
+            entries.push((passphrase_key_id(Some(id)), passphrase.as_str()));
+        }
+        for (address, key) in &secrets.imported_keys {
+            entries.push((format!("account_{}", address), key.as_str()));
+        }
+        entries.push(("vaughan_seed".to_string(), secrets.mnemonic.as_str()));
+
+        let mut written = Vec::with_capacity(entries.len());
+        let mut result = Ok(());
+        for (key_id, secret) in &entries {
+            result = self.keyring.store_key(key_id, secret, password);
+            if result.is_err() {
+                break;
+            }
+            written.push(key_id);
+        }
+        if result.is_ok() {
+            result = self.unlock(password, accounts).await;
+        }
+
+        // Delete what was written so a retry starts from a clean device
+        if let Err(e) = result {
+            for key_id in written {
+                let _ = self.keyring.delete_key(key_id);
+            }
+            self.lock().await;
+            self.accounts.write().await.clear();
+            return Err(e);
+        }
+        self.get_accounts().await
+    }
+
+    /// Keyring entries encrypted with the wallet password
+    async fn secret_key_ids(&self) -> Vec<String> {
+        secret_key_ids(self.accounts.read().await.values())
+    }
+
+    /// Delete the wallet's keys and forget its accounts
+    ///
+    /// Accounts are only in memory once unlocked, so the persisted ones
+    /// are passed in to also delete their keys while locked.
+    pub async fn wipe(&self, persisted_accounts: &[Account]) -> Result<(), WalletError> {
+        let mut key_ids = self.secret_key_ids().await;
+        key_ids.extend(secret_key_ids(persisted_accounts));
+        key_ids.sort();
+        key_ids.dedup();
+        for key_id in key_ids {
+            let _ = self.keyring.delete_key(&key_id);
+        }
         let mut signers = self.signers.write().await;
         signers.clear();
         let mut accounts = self.accounts.write().await;
         accounts.clear();
-        let mut pass = self.passphrase_hash.write().await;
-        *pass = None;
-        let _ = self.keyring.delete_key("vaughan_seed");
+        let mut verifier = self.password_verifier.write().await;
+        *verifier = None;
         Ok(())
     }
 }
+
+/// Keyring entries of a wallet with these accounts: the seed, BIP-39
+/// passphrases and imported keys
+fn secret_key_ids<'a>(accounts: impl IntoIterator<Item = &'a Account>) -> Vec<String> {
+    let mut passphrase_ids = vec![passphrase_key_id(None)];
+    let mut imported = Vec::new();
+    for account in accounts {
+        if let Some(id) = account.passphrase_id.as_deref() {
+            passphrase_ids.push(passphrase_key_id(Some(id)));
+        }
+        if account.account_type == AccountType::Imported {
+            imported.push(format!("account_{}", account.address));
+        }
+    }
+    passphrase_ids.sort();
+    passphrase_ids.dedup();
+
+    let mut key_ids = vec!["vaughan_seed".to_string()];
+    key_ids.extend(passphrase_ids);
+    key_ids.extend(imported);
+    key_ids
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Keyring ID of a BIP-39 passphrase (`None` = the wallet's own)
+fn passphrase_key_id(passphrase_id: Option<&str>) -> String {
+    match passphrase_id {
+        Some(id) => format!("vaughan_passphrase_{}", id),
+        None => "vaughan_passphrase".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::security::secret_store::{MemoryStore, SecretStore};
+
+    fn memory_service() -> WalletService {
+        WalletService::with_keyring(KeyringService::with_store("test", Arc::new(MemoryStore::new())))
+    }
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[tokio::test]
+    async fn test_password_verifier_does_not_hold_password() {
+        let service = memory_service();
+        service.set_password("correct horse").await.unwrap();
+
+        let verifier = service.password_verifier.read().await.clone().unwrap();
+        assert!(verifier.starts_with("$argon2"));
+        assert!(!verifier.contains("correct horse"));
+
+        assert!(service.verify_password("correct horse").await.is_ok());
+        assert!(matches!(
+            service.verify_password("wrong").await,
+            Err(WalletError::InvalidPassword)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_lock_wipes_signers_and_verifier() {
+        let service = memory_service();
+        service.set_password("pw").await.unwrap();
+        service.add_signer(PrivateKeySigner::random()).await;
+        assert!(!service.is_locked().await);
+
+        service.lock().await;
+        assert!(service.is_locked().await);
+        assert!(service.signers.read().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_verify_password_while_locked_stays_locked() {
+        let service = memory_service();
+        let addresses = service.import_wallet(MNEMONIC, "pw", 1, None).await.unwrap();
+        let accounts = service.get_accounts().await.unwrap();
+        service.lock().await;
+
+        assert!(service.verify_password("pw").await.is_ok());
+        assert!(matches!(service.verify_password("wrong").await, Err(WalletError::InvalidPassword)));
+        assert!(service.is_locked().await);
+        assert!(service.get_signer(&addresses[0]).await.is_err());
+
+        service.unlock("pw", accounts).await.unwrap();
+        assert!(!service.is_locked().await);
+        assert!(service.get_signer(&addresses[0]).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_wrong_passwords_throttle_every_password_check() {
+        use crate::security::throttle::FREE_ATTEMPTS;
+
+        let service = memory_service();
+        let addresses = service.import_wallet(MNEMONIC, "pw", 1, None).await.unwrap();
+
+        // A wrong password followed by a right one clears the count
+        assert!(service.sign_message(&addresses[0], b"hi", "wrong").await.is_err());
+        assert!(service.sign_message(&addresses[0], b"hi", "pw").await.is_ok());
+
+        for _ in 0..=FREE_ATTEMPTS {
+            assert!(matches!(
+                service.sign_message(&addresses[0], b"hi", "wrong").await,
+                Err(WalletError::InvalidPassword)
+            ));
+        }
+
+        // Not just unlock: signing and changing the password are refused too,
+        // even with the right password
+        assert!(matches!(
+            service.sign_message(&addresses[0], b"hi", "pw").await,
+            Err(WalletError::UnlockThrottled(_))
+        ));
+        assert!(matches!(service.change_password("pw", "new", &[]).await, Err(WalletError::UnlockThrottled(_))));
+        assert!(matches!(service.export_mnemonic("pw").await, Err(WalletError::UnlockThrottled(_))));
+    }
+
+    /// Memory store that can be made unreadable
+    #[derive(Default)]
+    struct UnreadableStore {
+        inner: MemoryStore,
+        broken: std::sync::atomic::AtomicBool,
+    }
+
+    impl SecretStore for UnreadableStore {
+        fn backend(&self) -> SecretBackend {
+            SecretBackend::Memory
+        }
+
+        fn get(&self, key_id: &str) -> Result<Option<String>, WalletError> {
+            if self.broken.load(std::sync::atomic::Ordering::SeqCst) {
+                return Err(WalletError::KeyringError("keychain is locked".to_string()));
+            }
+            self.inner.get(key_id)
+        }
+
+        fn set(&self, key_id: &str, value: &str) -> Result<(), WalletError> {
+            self.inner.set(key_id, value)
+        }
+
+        fn delete(&self, key_id: &str) -> Result<(), WalletError> {
+            self.inner.delete(key_id)
+        }
+    }
+
+    /// Memory count that also records `on_failure` (where the wipe runs)
+    #[derive(Default)]
+    struct RecordingAttempts {
+        count: MemoryAttempts,
+        on_failure_calls: std::sync::atomic::AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl PasswordAttempts for RecordingAttempts {
+        fn load(&self) -> crate::security::throttle::UnlockThrottle {
+            self.count.load()
+        }
+
+        fn save(&self, throttle: &crate::security::throttle::UnlockThrottle) -> Result<(), WalletError> {
+            self.count.save(throttle)
+        }
+
+        async fn on_failure(&self, _throttle: &crate::security::throttle::UnlockThrottle, _retry_after_secs: u64) {
+            self.on_failure_calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_keyring_errors_are_not_wrong_passwords() {
+        use crate::security::throttle::FREE_ATTEMPTS;
+
+        let store = Arc::new(UnreadableStore::default());
+        let service = WalletService::with_keyring(KeyringService::with_store("test", store.clone()));
+        let attempts = Arc::new(RecordingAttempts::default());
+        service.set_password_attempts(attempts.clone()).await;
+        service.import_wallet(MNEMONIC, "pw", 1, None).await.unwrap();
+        service.lock().await;
+
+        store.broken.store(true, std::sync::atomic::Ordering::SeqCst);
+        for _ in 0..FREE_ATTEMPTS * 3 {
+            assert!(matches!(service.verify_password("pw").await, Err(WalletError::KeyringError(_))));
+        }
+        assert_eq!(attempts.load().failed_attempts, 0);
+        assert_eq!(attempts.on_failure_calls.load(std::sync::atomic::Ordering::SeqCst), 0);
+
+        // Nothing was throttled or wiped
+        store.broken.store(false, std::sync::atomic::Ordering::SeqCst);
+        service.verify_password("pw").await.unwrap();
+        assert_eq!(service.export_mnemonic("pw").await.unwrap(), MNEMONIC);
+    }
+
+    #[tokio::test]
+    async fn test_import_with_passphrase_derives_other_accounts() {
+        let plain = memory_service();
+        let plain_addresses = plain.import_wallet(MNEMONIC, "pw", 1, None).await.unwrap();
+
+        let with_passphrase = memory_service();
+        let addresses = with_passphrase.import_wallet(MNEMONIC, "pw", 1, Some("TREZOR")).await.unwrap();
+        assert_ne!(addresses, plain_addresses);
+
+        // The passphrase is used again at unlock
+        let accounts = with_passphrase.get_accounts().await.unwrap();
+        with_passphrase.lock().await;
+        with_passphrase.unlock("pw", accounts).await.unwrap();
+        assert!(with_passphrase.get_signer(&addresses[0]).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_hidden_wallets_on_one_mnemonic() {
+        let service = memory_service();
+        let main = service.import_wallet(MNEMONIC, "pw", 1, None).await.unwrap();
+
+        let hidden = service.add_hidden_wallet("first", "Hidden".to_string(), "pw").await.unwrap();
+        let other = service.add_hidden_wallet("second", "Other".to_string(), "pw").await.unwrap();
+        assert_ne!(hidden.address, main[0]);
+        assert_ne!(hidden.address, other.address);
+        assert!(service.add_hidden_wallet("first", "Again".to_string(), "pw").await.is_err());
+
+        // Next account of a hidden wallet continues its own indices
+        let next = service.create_account("pw", hidden.passphrase_id.as_deref(), &DerivationScheme::Bip44).await.unwrap();
+        assert_eq!(next.index, Some(1));
+        assert_eq!(next.passphrase_id, hidden.passphrase_id);
+
+        let accounts = service.get_accounts().await.unwrap();
+        service.lock().await;
+        service.unlock("pw", accounts).await.unwrap();
+        for address in [main[0], hidden.address, other.address, next.address] {
+            assert!(service.get_signer(&address).await.is_ok());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_create_account_per_scheme() {
+        let service = memory_service();
+        service.import_wallet(MNEMONIC, "pw", 1, None).await.unwrap();
+
+        // Ledger Live index 0 is the BIP-44 index 0 path, which already exists
+        let ledger = service.create_account("pw", None, &DerivationScheme::LedgerLive).await.unwrap();
+        assert_eq!(ledger.index, Some(1));
+        assert_eq!(ledger.derivation_path.as_deref(), Some("m/44'/60'/1'/0/0"));
+
+        let mew = service.create_account("pw", None, &DerivationScheme::LegacyMew).await.unwrap();
+        assert_eq!(mew.derivation_path.as_deref(), Some("m/44'/60'/0'/0"));
+
+        // A fixed custom path can only be added once
+        let custom = DerivationScheme::Custom("m/44'/60'/5'/0/7".to_string());
+        service.create_account("pw", None, &custom).await.unwrap();
+        assert!(service.create_account("pw", None, &custom).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_restore_from_shares_checks_first_account() {
+        let service = memory_service();
+        service.import_wallet(MNEMONIC, "pw", 1, None).await.unwrap();
+        let groups = [ShareGroup { threshold: 2, count: 3 }];
+        let (shares, first_account) = service.create_seed_shares("pw", 1, &groups).await.unwrap();
+        let quorum = vec![shares[0][1].clone(), shares[0][2].clone()];
+
+        let wrong = memory_service();
+        assert!(wrong.restore_from_shares(&quorum, "new pw", None, Address::ZERO).await.is_err());
+        assert!(!wrong.wallet_exists());
+
+        let restored = memory_service();
+        let addresses = restored.restore_from_shares(&quorum, "new pw", None, first_account).await.unwrap();
+        assert_eq!(addresses, vec![first_account]);
+    }
+
+    #[tokio::test]
+    async fn test_restore_from_shares_refuses_existing_wallet() {
+        let service = memory_service();
+        service.import_wallet(MNEMONIC, "pw", 1, None).await.unwrap();
+        let groups = [ShareGroup { threshold: 2, count: 3 }];
+        let (shares, first_account) = service.create_seed_shares("pw", 1, &groups).await.unwrap();
+        let quorum = vec![shares[0][0].clone(), shares[0][1].clone()];
+
+        let existing = memory_service();
+        let other = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+        existing.import_wallet(other, "old pw", 1, None).await.unwrap();
+        let err = existing.restore_from_shares(&quorum, "new pw", None, first_account).await.unwrap_err();
+        assert!(matches!(err, WalletError::Custom(_)));
+
+        // The existing wallet and its password are untouched
+        existing.verify_password("old pw").await.unwrap();
+        let accounts = existing.get_accounts().await.unwrap();
+        assert!(!accounts.iter().any(|a| a.address == first_account));
+    }
+
+    #[tokio::test]
+    async fn test_seed_shares_round_trip_with_passphrase() {
+        let service = memory_service();
+        let addresses = service.import_wallet(MNEMONIC, "pw", 1, Some("TREZOR")).await.unwrap();
+        let groups = [ShareGroup { threshold: 2, count: 3 }];
+        let (shares, first_account) = service.create_seed_shares("pw", 1, &groups).await.unwrap();
+        assert_eq!(first_account, addresses[0]);
+        let quorum = vec![shares[0][0].clone(), shares[0][2].clone()];
+
+        // The passphrase is not in the shares
+        let without = memory_service();
+        assert!(without.restore_from_shares(&quorum, "new pw", None, first_account).await.is_err());
+
+        let restored = memory_service();
+        let restored_addresses = restored
+            .restore_from_shares(&quorum, "new pw", Some("TREZOR"), first_account)
+            .await
+            .unwrap();
+        assert_eq!(restored_addresses, addresses);
+    }
+
+    #[tokio::test]
+    async fn test_watch_only_account_cannot_sign() {
+        let service = memory_service();
+        service.import_wallet(MNEMONIC, "pw", 1, None).await.unwrap();
+        let cold = Address::repeat_byte(0xc0);
+        let account = service.add_watch_only_account(cold, "Cold".to_string(), "pw").await.unwrap();
+        assert_eq!(account.account_type, AccountType::WatchOnly);
+        assert!(service.add_watch_only_account(cold, "Again".to_string(), "pw").await.is_err());
+
+        // Survives lock/unlock, still without a key
+        let accounts = service.get_accounts().await.unwrap();
+        service.lock().await;
+        service.unlock("pw", accounts).await.unwrap();
+        assert!(service.get_account(&cold).await.is_some());
+        assert!(matches!(service.ensure_can_sign(&cold).await, Err(WalletError::WatchOnlyAccount(_))));
+        assert!(matches!(
+            service.sign_message(&cold, b"hello", "pw").await,
+            Err(WalletError::WatchOnlyAccount(_))
+        ));
+
+        // Unknown addresses still report not found
+        assert!(matches!(
+            service.get_signer(&Address::repeat_byte(0x01)).await,
+            Err(WalletError::AccountNotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_external_signer_accounts() {
+        let server = wiremock::MockServer::start().await;
+        let remote = Address::repeat_byte(0xee);
+        wiremock::Mock::given(wiremock::matchers::body_string_contains("account_list"))
+            .respond_with(wiremock::ResponseTemplate::new(200).set_body_json(
+                serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": [remote] }),
+            ))
+            .mount(&server)
+            .await;
+
+        let service = memory_service();
+        service.import_wallet(MNEMONIC, "pw", 1, None).await.unwrap();
+        let added = service.add_external_accounts(&server.uri(), "pw").await.unwrap();
+        assert_eq!(added.len(), 1);
+        assert_eq!(added[0].account_type, AccountType::External);
+        assert!(service.add_external_accounts(&server.uri(), "pw").await.unwrap().is_empty());
+
+        // The signer is reattached at unlock; its key can't be exported
+        let accounts = service.get_accounts().await.unwrap();
+        service.lock().await;
+        service.unlock("pw", accounts).await.unwrap();
+        let signer = service.get_signer(&remote).await.unwrap();
+        assert_eq!(signer.address(), remote);
+        assert!(matches!(
+            service.export_private_key(&remote, "pw").await,
+            Err(WalletError::ExternalSigner(_))
+        ));
+
+        // Raw-hash signing (EIP-7702) is refused before anything is approved
+        assert!(service.ensure_can_sign(&remote).await.is_ok());
+        assert!(matches!(service.ensure_can_sign_hash(&remote).await, Err(WalletError::ExternalSigner(_))));
+        let local = accounts.iter().find(|a| a.address != remote).unwrap();
+        assert!(service.ensure_can_sign_hash(&local.address).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_change_password() {
+        let service = memory_service();
+        service.import_wallet(MNEMONIC, "old", 1, Some("hidden")).await.unwrap();
+        let imported = service.import_account(&hex::encode([0x42u8; 32]), "Imported".to_string(), "old").await.unwrap();
+
+        assert!(matches!(
+            service.change_password("wrong", "new", &[]).await,
+            Err(WalletError::InvalidPassword)
+        ));
+        // Seed, passphrase and imported key
+        assert_eq!(service.change_password("old", "new", &[]).await.unwrap(), 3);
+        assert!(service.verify_password("old").await.is_err());
+
+        let accounts = service.get_accounts().await.unwrap();
+        service.lock().await;
+        assert!(service.unlock("old", accounts.clone()).await.is_err());
+        service.unlock("new", accounts).await.unwrap();
+        assert!(service.get_signer(&imported.address).await.is_ok());
+        assert_eq!(service.export_mnemonic("new").await.unwrap(), MNEMONIC);
+    }
+
+    #[tokio::test]
+    async fn test_change_password_includes_accounts_not_loaded() {
+        let service = memory_service();
+        service.import_wallet(MNEMONIC, "old", 1, None).await.unwrap();
+        let imported = service.import_account(&hex::encode([0x42u8; 32]), "Imported".to_string(), "old").await.unwrap();
+        let persisted = service.get_accounts().await.unwrap();
+
+        // Unlocked without the imported account, as when its key failed to load
+        let loaded: Vec<Account> = persisted.iter().filter(|a| a.address != imported.address).cloned().collect();
+        service.lock().await;
+        service.unlock("old", loaded).await.unwrap();
+
+        // Seed and the imported key
+        assert_eq!(service.change_password("old", "new", &persisted).await.unwrap(), 2);
+        service.lock().await;
+        service.unlock("new", persisted).await.unwrap();
+        assert!(service.get_signer(&imported.address).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_change_password_fails_on_undecryptable_key() {
+        let service = memory_service();
+        service.import_wallet(MNEMONIC, "old", 1, None).await.unwrap();
+        let imported = service.import_account(&hex::encode([0x42u8; 32]), "Imported".to_string(), "old").await.unwrap();
+        let persisted = service.get_accounts().await.unwrap();
+
+        // The imported key is under another password
+        let key_id = format!("account_{}", imported.address);
+        service.keyring.store_key(&key_id, &hex::encode([0x42u8; 32]), "other").unwrap();
+
+        assert!(service.change_password("old", "new", &persisted).await.is_err());
+        service.verify_password("old").await.unwrap();
+        assert_eq!(service.export_mnemonic("old").await.unwrap(), MNEMONIC);
+    }
+
+    #[tokio::test]
+    async fn test_backup_secrets_restore_on_new_device() {
+        let service = memory_service();
+        service.import_wallet(MNEMONIC, "pw", 1, Some("own")).await.unwrap();
+        let hidden = service.add_hidden_wallet("hidden", "Hidden".to_string(), "pw").await.unwrap();
+        let imported = service.import_account(&hex::encode([0x42u8; 32]), "Imported".to_string(), "pw").await.unwrap();
+        let accounts = service.get_accounts().await.unwrap();
+
+        let secrets = service.backup_secrets("pw").await.unwrap();
+        assert_eq!(secrets.mnemonic, MNEMONIC);
+        assert_eq!(secrets.passphrase.as_deref(), Some("own"));
+        assert_eq!(secrets.hidden_passphrases.len(), 1);
+        assert_eq!(secrets.imported_keys.len(), 1);
+
+        // Refused over an existing wallet
+        assert!(service.restore_backup(&secrets, accounts.clone(), "pw").await.is_err());
+
+        let restored = memory_service();
+        let restored_accounts = restored.restore_backup(&secrets, accounts, "new pw").await.unwrap();
+        assert_eq!(restored_accounts.len(), 3);
+        assert!(restored.get_signer(&hidden.address).await.is_ok());
+        assert_eq!(restored.get_account(&imported.address).await.unwrap().name, "Imported");
+
+        // Survives a lock / unlock cycle under the new password
+        restored.lock().await;
+        restored.unlock("new pw", restored_accounts).await.unwrap();
+        assert!(restored.get_signer(&imported.address).await.is_ok());
+    }
+
+    /// Memory store whose writes to one key fail
+    struct FailingStore {
+        inner: Arc<MemoryStore>,
+        fail_on: String,
+    }
+
+    impl SecretStore for FailingStore {
+        fn backend(&self) -> SecretBackend {
+            SecretBackend::Memory
+        }
+
+        fn get(&self, key_id: &str) -> Result<Option<String>, WalletError> {
+            self.inner.get(key_id)
+        }
+
+        fn set(&self, key_id: &str, value: &str) -> Result<(), WalletError> {
+            if key_id == self.fail_on {
+                return Err(WalletError::KeyringError("write failed".to_string()));
+            }
+            self.inner.set(key_id, value)
+        }
+
+        fn delete(&self, key_id: &str) -> Result<(), WalletError> {
+            self.inner.delete(key_id)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_restore_leaves_nothing_behind() {
+        let service = memory_service();
+        service.import_wallet(MNEMONIC, "pw", 1, Some("own")).await.unwrap();
+        service.add_hidden_wallet("hidden", "Hidden".to_string(), "pw").await.unwrap();
+        let imported = service.import_account(&hex::encode([0x42u8; 32]), "Imported".to_string(), "pw").await.unwrap();
+        let accounts = service.get_accounts().await.unwrap();
+        let secrets = service.backup_secrets("pw").await.unwrap();
+
+        // The imported key's write fails after the passphrases were stored
+        let inner = Arc::new(MemoryStore::new());
+        let failing = WalletService::with_keyring(KeyringService::with_store(
+            "test",
+            Arc::new(FailingStore { inner: inner.clone(), fail_on: format!("account_{}", imported.address) }),
+        ));
+        assert!(failing.restore_backup(&secrets, accounts.clone(), "pw").await.is_err());
+        assert!(!failing.wallet_exists());
+        assert!(inner.get(&passphrase_key_id(None)).unwrap().is_none());
+        assert!(failing.get_accounts().await.unwrap().is_empty());
+
+        // A retry on the same device succeeds
+        let retry = WalletService::with_keyring(KeyringService::with_store("test", inner));
+        assert_eq!(retry.restore_backup(&secrets, accounts, "pw").await.unwrap().len(), 3);
+    }
+}
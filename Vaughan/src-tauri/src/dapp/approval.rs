@@ -7,7 +7,9 @@
  * **PHASE 3.4 UPDATE**: Approvals are now window-specific to support
  * proper routing of approval responses to the correct dApp window.
  */
+use crate::chains::evm::eip7702;
 use crate::error::WalletError;
+use alloy::primitives::Address;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::collections::HashMap;
@@ -33,6 +35,12 @@ pub enum ApprovalRequestType {
         gas_limit: Option<u64>,
         gas_price: Option<String>,
         data: Option<String>,
+        /// Address book name of the recipient
+        to_label: Option<String>,
+        /// Recipient is in the address book
+        known_contact: bool,
+        /// RPC health warning to show with the request
+        health_warning: Option<String>,
     },
     /// Message signing request
     PersonalSign {
@@ -65,6 +73,62 @@ pub enum ApprovalRequestType {
         rpc_url: String,
         block_explorer_url: Option<String>,
     },
+    /// EIP-7702 authorization signing request
+    Authorization {
+        origin: String,
+        delegation: DelegationApproval,
+    },
+    /// EIP-7702 set-code (type-4) transaction request
+    SetCodeTransaction {
+        origin: String,
+        from: String,
+        to: String,
+        value: String,
+        gas_limit: Option<u64>,
+        data: Option<String>,
+        delegations: Vec<DelegationApproval>,
+        /// RPC health warning to show with the request
+        health_warning: Option<String>,
+    },
+}
+
+/// EIP-7702 delegation shown in an approval
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct DelegationApproval {
+    /// Account whose code gets delegated
+    pub authority: String,
+    /// Delegate contract
+    pub delegate: String,
+    /// Name of a known delegate
+    pub delegate_name: Option<String>,
+    /// Whether the delegate is on the known delegates list
+    pub known_delegate: bool,
+    /// Chain the authorization is valid on (0 = all chains)
+    pub chain_id: u64,
+    /// Authority nonce the authorization is bound to
+    pub nonce: u64,
+    /// Warnings to show prominently (unknown delegate, all-chain authorization)
+    pub warnings: Vec<String>,
+}
+
+impl DelegationApproval {
+    /// Check a delegation against the known delegates list
+    pub fn new(authority: Address, chain_id: u64, delegate: Address, nonce: u64) -> Self {
+        let check = eip7702::check_delegate(delegate);
+        let mut warnings: Vec<String> = check.warning.into_iter().collect();
+        if chain_id == 0 && !check.revocation {
+            warnings.push("This authorization is valid on every chain.".to_string());
+        }
+        Self {
+            authority: format!("{:?}", authority),
+            delegate: format!("{:?}", delegate),
+            delegate_name: check.name,
+            known_delegate: check.known,
+            chain_id,
+            nonce,
+            warnings,
+        }
+    }
 }
 
 /// Approval request
@@ -538,4 +602,21 @@ mod tests {
         assert_eq!(window2_requests.len(), 1);
         assert!(window2_requests.iter().all(|r| r.window_label == window2));
     }
+
+    #[test]
+    fn test_delegation_approval_warnings() {
+        let authority = Address::repeat_byte(0x11);
+
+        let known = eip7702::KNOWN_DELEGATES[0].address;
+        let approval = DelegationApproval::new(authority, 1, known, 0);
+        assert!(approval.known_delegate);
+        assert!(approval.warnings.is_empty());
+
+        let unknown = DelegationApproval::new(authority, 0, Address::repeat_byte(0x42), 0);
+        assert!(!unknown.known_delegate);
+        assert_eq!(unknown.warnings.len(), 2);
+
+        let revoke = DelegationApproval::new(authority, 0, Address::ZERO, 0);
+        assert!(revoke.warnings.is_empty());
+    }
 }
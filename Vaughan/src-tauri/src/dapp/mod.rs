@@ -11,7 +11,10 @@ pub mod rpc;
 pub mod session;
 pub mod window_registry;
 
-pub use approval::{ApprovalQueue, ApprovalRequest, ApprovalRequestType, ApprovalResponse, ApprovalResponseExport};
+pub use approval::{
+    ApprovalQueue, ApprovalRequest, ApprovalRequestType, ApprovalResponse, ApprovalResponseExport,
+    DelegationApproval,
+};
 pub use health::{HealthMetrics, HealthMonitor};
 pub use profiling::{MethodStats, Profiler};
 pub use rate_limiter::RateLimiter;
@@ -198,6 +198,7 @@ impl MethodRateLimits {
             "personal_sign",
             "wallet_addEthereumChain",
             "wallet_switchEthereumChain",
+            "wallet_signAuthorization",
         ];
 
         for method in sensitive_methods {
@@ -1,12 +1,15 @@
+use alloy::eips::eip7702::SignedAuthorization;
 use alloy::primitives::{Address, U256};
 use crate::chains::ChainAdapter;
+use crate::core::smart_account::SmartAccountCall;
 use crate::error::WalletError;
 use crate::state::VaughanState;
-use alloy::providers::Provider;
-use alloy::rpc::client::RpcClient;
 use serde_json::Value;
 use tauri::{AppHandle, Emitter, Manager};
 
+/// How long `eth_sendTransaction` from a smart account waits for the bundle
+const USER_OPERATION_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+
 // ============================================================================
 // Account Management Handlers
 // ============================================================================
@@ -250,15 +253,23 @@ pub(crate) async fn handle_send_transaction(
     params: Vec<Value>,
 ) -> Result<Value, WalletError> {
     let tx_obj = params.get(0).and_then(|v| v.as_object()).ok_or(WalletError::InvalidParams)?;
+
+    // EIP-7702: an authorization list makes this a type-4 set-code transaction
+    if tx_obj.get("authorizationList").and_then(|v| v.as_array()).is_some_and(|l| !l.is_empty()) {
+        return handle_send_set_code_transaction(app, state, window_label, origin, tx_obj).await;
+    }
+
     let from = tx_obj.get("from").and_then(|v| v.as_str()).ok_or(WalletError::InvalidParams)?;
     let to = tx_obj.get("to").and_then(|v| v.as_str()).ok_or(WalletError::InvalidParams)?;
     let value = tx_obj.get("value").and_then(|v| v.as_str()).unwrap_or("0x0");
     let gas_limit = tx_obj.get("gas").or_else(|| tx_obj.get("gasLimit")).and_then(|v| v.as_str())
         .and_then(|s| u64::from_str_radix(s.trim_start_matches("0x"), 16).ok());
     let data = tx_obj.get("data").and_then(|v| v.as_str());
+    let access_list = parse_access_list(tx_obj)?;
 
     let from_addr: Address = from.parse().map_err(|_| WalletError::InvalidAddress(from.to_string()))?;
     let to_addr: Address = to.parse().map_err(|_| WalletError::InvalidAddress(to.to_string()))?;
+    state.wallet_service.ensure_can_sign(&from_addr).await?;
     
     let value_u256 = if value.starts_with("0x") {
         U256::from_str_radix(value.trim_start_matches("0x"), 16).map_err(|_| WalletError::InvalidParams)?
@@ -268,8 +279,22 @@ pub(crate) async fn handle_send_transaction(
 
     let value_eth = crate::chains::evm::utils::format_wei_to_eth(value_u256, 18);
     let adapter = state.current_adapter().await?;
+
+    // Don't ask for approval if the RPC is too far behind to send safely
+    let health_warning = state.check_send_health().await?;
+
+    // Smart accounts send through a bundler; fail before approval if there is none
+    let is_smart_account = state.smart_account(&from_addr).await.is_some();
+    if is_smart_account {
+        state.bundler_for_chain(adapter.chain_id())?;
+    }
+
+    // Safe accounts can't send directly; the approval creates a proposal
+    let is_safe = state.safe_account(&from_addr).await.is_some();
+
     let gas_price = adapter.get_gas_price().await?;
     let gas_limit_final = gas_limit.unwrap_or(21000);
+    let to_label = state.contact_for(adapter.chain_id(), to).map(|c| c.name);
 
     use crate::dapp::ApprovalRequestType;
     let request_type = ApprovalRequestType::Transaction {
@@ -280,6 +305,9 @@ pub(crate) async fn handle_send_transaction(
         gas_limit: Some(gas_limit_final),
         gas_price: Some(gas_price.to_string()),
         data: data.map(|s| s.to_string()),
+        to_label: to_label.clone(),
+        known_contact: to_label.is_some(),
+        health_warning: health_warning.clone(),
     };
 
     let (id, rx) = state.approval_queue.add_request(window_label.to_string(), request_type).await?;
@@ -287,7 +315,7 @@ pub(crate) async fn handle_send_transaction(
     if let Some(main_window) = app.get_webview_window("main") {
         let payload = serde_json::json!({
             "id": id, "origin": origin, "type": "transaction",
-            "params": { "from": from, "to": to, "value": value_eth, "data": data, "gasLimit": gas_limit_final, "gasPrice": gas_price.to_string() }
+            "params": { "from": from, "to": to, "value": value_eth, "data": data, "gasLimit": gas_limit_final, "gasPrice": gas_price.to_string(), "accessList": access_list, "smartAccount": is_smart_account, "safeProposal": is_safe, "knownContact": to_label.is_some(), "toLabel": to_label, "healthWarning": health_warning }
         });
         let _ = main_window.emit("dapp_request", payload);
     }
@@ -302,28 +330,334 @@ pub(crate) async fn handle_send_transaction(
         .ok_or(WalletError::Custom("Password required".to_string()))?;
 
     state.wallet_service.verify_password(&password).await?;
-    let _signer = state.wallet_service.get_signer(&from_addr).await?;
+
+    if is_smart_account {
+        let data_bytes = match data {
+            Some(data_hex) => hex::decode(data_hex.trim_start_matches("0x")).map_err(|_| WalletError::InvalidParams)?,
+            None => Vec::new(),
+        };
+        let call = SmartAccountCall { to: to_addr, value: value_u256, data: data_bytes.into() };
+        let user_op_hash = state.send_user_operation(from_addr, call, &password).await?;
+
+        // dApps expect a transaction hash, so wait for the bundle
+        let bundler = state.bundler_for_chain(adapter.chain_id())?;
+        let receipt = state.smart_account_service.wait_for_receipt(&bundler, user_op_hash, USER_OPERATION_TIMEOUT).await?;
+        return Ok(serde_json::json!(format!("{:?}", receipt.receipt.transaction_hash)));
+    }
+
+    if is_safe {
+        return propose_safe_transaction(state, origin, from_addr, to_addr, value_u256, data, &password).await;
+    }
 
     use alloy::rpc::types::TransactionRequest;
     let mut tx = TransactionRequest::default();
-    tx.from = Some(from_addr);
     tx.to = Some(to_addr.into());
     tx.value = Some(value_u256);
-    tx.gas = Some(gas_limit_final);
-    tx.gas_price = Some(gas_price);
+    // Estimated on the network when the dApp gave no limit
+    tx.gas = gas_limit;
+    tx.access_list = access_list;
 
     if let Some(data_hex) = data {
         let data_bytes = hex::decode(data_hex.trim_start_matches("0x")).map_err(|_| WalletError::InvalidParams)?;
         tx.input.input = Some(data_bytes.into());
     }
 
-    let nonce = adapter.get_transaction_count(from_addr).await?;
-    tx.nonce = Some(nonce);
+    // Signed by the account's signer (local key or external signer); fees
+    // and nonce come from the active network
+    let tx_hash = state.send_locally_signed(from_addr, tx).await?;
+    Ok(serde_json::json!(format!("{:?}", tx_hash)))
+}
 
-    let url = adapter.rpc_url().parse().map_err(|e| WalletError::NetworkError(format!("Invalid RPC: {}", e)))?;
-    let client = RpcClient::new_http(url);
-    let provider = alloy::providers::RootProvider::new(client);
+/// `eth_sendTransaction` from a Safe account
+///
+/// Stores a Safe proposal and signs it with every owner key in the wallet.
+/// Like Safe{Wallet}, returns the SafeTx hash; the transaction itself is
+/// executed once enough owners have signed.
+async fn propose_safe_transaction(
+    state: &VaughanState,
+    origin: &str,
+    safe: Address,
+    to: Address,
+    value: U256,
+    data: Option<&str>,
+    password: &str,
+) -> Result<Value, WalletError> {
+    let data_bytes = match data {
+        Some(data_hex) => hex::decode(data_hex.trim_start_matches("0x")).map_err(|_| WalletError::InvalidParams)?,
+        None => Vec::new(),
+    };
+    let proposal = state
+        .propose_safe_transaction(
+            safe,
+            to,
+            value,
+            data_bytes.into(),
+            crate::chains::evm::safe::OPERATION_CALL,
+            Some(origin.to_string()),
+        )
+        .await?;
 
-    let pending_tx = Provider::<alloy::network::Ethereum>::send_transaction(&provider, tx).await.map_err(|e| WalletError::TransactionFailed(format!("Failed: {}", e)))?;
-    Ok(serde_json::json!(format!("{}", pending_tx.tx_hash())))
-}
\ No newline at end of file
+    let owners = state.safe_state(safe).await?.owners;
+    for owner in owners {
+        // External signers don't sign the bare SafeTx hash; those owners
+        // sign later like owners outside the wallet
+        let has_key = state
+            .wallet_service
+            .get_signer(&owner)
+            .await
+            .is_ok_and(|signer| signer.local_key().is_some());
+        if has_key {
+            state.sign_safe_proposal(&proposal.safe_tx_hash, owner, password).await?;
+        }
+    }
+
+    eprintln!("[RPC] Safe proposal created: {}", proposal.safe_tx_hash);
+    Ok(serde_json::json!(proposal.safe_tx_hash))
+}
+
+// ============================================================================
+// EIP-7702 Set-Code Transactions
+// ============================================================================
+
+/// Parse a JSON-RPC quantity (hex string or number)
+pub(crate) fn parse_quantity(value: &Value) -> Option<u64> {
+    match value {
+        Value::String(s) => match s.strip_prefix("0x") {
+            Some(hex) => u64::from_str_radix(hex, 16).ok(),
+            None => s.parse().ok(),
+        },
+        Value::Number(n) => n.as_u64(),
+        _ => None,
+    }
+}
+
+/// Authorization list entry from a dApp
+pub(crate) enum AuthorizationParam {
+    /// Already signed (e.g. by a sponsor's user)
+    Signed(SignedAuthorization),
+    /// To be signed by the sender: `{ address | contractAddress, chainId?, nonce? }`
+    Unsigned { delegate: Address, chain_id: Option<u64>, nonce: Option<u64> },
+}
+
+pub(crate) fn parse_authorization_param(value: &Value) -> Result<AuthorizationParam, WalletError> {
+    let obj = value.as_object().ok_or(WalletError::InvalidParams)?;
+    if obj.contains_key("r") {
+        let signed = serde_json::from_value(value.clone()).map_err(|_| WalletError::InvalidParams)?;
+        return Ok(AuthorizationParam::Signed(signed));
+    }
+
+    let delegate = obj
+        .get("contractAddress")
+        .or_else(|| obj.get("address"))
+        .and_then(|v| v.as_str())
+        .ok_or(WalletError::InvalidParams)?;
+    let delegate: Address = delegate.parse().map_err(|_| WalletError::InvalidAddress(delegate.to_string()))?;
+    Ok(AuthorizationParam::Unsigned {
+        delegate,
+        chain_id: obj.get("chainId").and_then(parse_quantity),
+        nonce: obj.get("nonce").and_then(parse_quantity),
+    })
+}
+
+/// `eth_sendTransaction` with an `authorizationList`
+///
+/// Unsigned entries delegate the sender's own account and are signed by it
+/// after approval. Every delegate is checked against the known delegates
+/// list and unknown ones are flagged in the approval.
+async fn handle_send_set_code_transaction(
+    app: &AppHandle,
+    state: &VaughanState,
+    window_label: &str,
+    origin: &str,
+    tx_obj: &serde_json::Map<String, Value>,
+) -> Result<Value, WalletError> {
+    use crate::chains::evm::eip7702::{self, SetCodeRequest};
+    use crate::dapp::{ApprovalRequestType, DelegationApproval};
+
+    enum PendingAuthorization {
+        Sign(alloy::eips::eip7702::Authorization),
+        Signed(SignedAuthorization),
+    }
+
+    let from = tx_obj.get("from").and_then(|v| v.as_str()).ok_or(WalletError::InvalidParams)?;
+    let from_addr: Address = from.parse().map_err(|_| WalletError::InvalidAddress(from.to_string()))?;
+    state.wallet_service.ensure_can_sign(&from_addr).await?;
+    let to_addr: Address = match tx_obj.get("to").and_then(|v| v.as_str()) {
+        Some(to) => to.parse().map_err(|_| WalletError::InvalidAddress(to.to_string()))?,
+        None => from_addr,
+    };
+    let value_u256 = match tx_obj.get("value").and_then(|v| v.as_str()) {
+        Some(v) => match v.strip_prefix("0x") {
+            Some(hex) => U256::from_str_radix(hex, 16),
+            None => U256::from_str_radix(v, 10),
+        }
+        .map_err(|_| WalletError::InvalidParams)?,
+        None => U256::ZERO,
+    };
+    let gas_limit = tx_obj.get("gas").or_else(|| tx_obj.get("gasLimit")).and_then(parse_quantity);
+    let data = tx_obj.get("data").or_else(|| tx_obj.get("input")).and_then(|v| v.as_str());
+    let data_bytes = match data {
+        Some(data_hex) => hex::decode(data_hex.trim_start_matches("0x")).map_err(|_| WalletError::InvalidParams)?,
+        None => Vec::new(),
+    };
+    let entries = tx_obj
+        .get("authorizationList")
+        .and_then(|v| v.as_array())
+        .ok_or(WalletError::InvalidParams)?
+        .iter()
+        .map(parse_authorization_param)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let health_warning = state.check_send_health().await?;
+    let adapter = state.current_adapter().await?;
+    let chain_id = adapter.chain_id();
+
+    // The transaction uses the sender's current nonce; each of the sender's
+    // own authorizations applied after it bumps the nonce by one
+    let mut next_own_nonce = adapter.get_transaction_count(from_addr).await? + 1;
+    let mut delegations = Vec::with_capacity(entries.len());
+    let mut to_sign = Vec::with_capacity(entries.len());
+    for entry in entries {
+        match entry {
+            AuthorizationParam::Signed(signed) => {
+                let authority = signed
+                    .signature()
+                    .ok()
+                    .and_then(|sig| sig.recover_address_from_prehash(&signed.signature_hash()).ok())
+                    .ok_or_else(|| WalletError::InvalidTransaction("Invalid authorization signature".to_string()))?;
+                delegations.push(DelegationApproval::new(
+                    authority,
+                    signed.chain_id().saturating_to(),
+                    *signed.address(),
+                    signed.nonce(),
+                ));
+                to_sign.push(PendingAuthorization::Signed(signed));
+            },
+            AuthorizationParam::Unsigned { delegate, chain_id: auth_chain_id, nonce } => {
+                state.wallet_service.ensure_can_sign_hash(&from_addr).await?;
+                let nonce = nonce.unwrap_or_else(|| {
+                    let n = next_own_nonce;
+                    next_own_nonce += 1;
+                    n
+                });
+                let auth_chain_id = auth_chain_id.unwrap_or(chain_id);
+                delegations.push(DelegationApproval::new(from_addr, auth_chain_id, delegate, nonce));
+                to_sign.push(PendingAuthorization::Sign(eip7702::authorization(auth_chain_id, delegate, nonce)));
+            },
+        }
+    }
+
+    let value_eth = crate::chains::evm::utils::format_wei_to_eth(value_u256, adapter.native_decimals());
+    let request_type = ApprovalRequestType::SetCodeTransaction {
+        origin: origin.to_string(),
+        from: from.to_string(),
+        to: format!("{:?}", to_addr),
+        value: value_eth.clone(),
+        gas_limit,
+        data: data.map(|s| s.to_string()),
+        delegations: delegations.clone(),
+        health_warning: health_warning.clone(),
+    };
+
+    let (id, rx) = state.approval_queue.add_request(window_label.to_string(), request_type).await?;
+
+    if let Some(main_window) = app.get_webview_window("main") {
+        let payload = serde_json::json!({
+            "id": id, "origin": origin, "type": "setCodeTransaction",
+            "params": { "from": from, "to": format!("{:?}", to_addr), "value": value_eth, "data": data, "gasLimit": gas_limit, "delegations": delegations, "healthWarning": health_warning }
+        });
+        let _ = main_window.emit("dapp_request", payload);
+    }
+
+    let response = tokio::time::timeout(tokio::time::Duration::from_secs(300), rx).await
+        .map_err(|_| WalletError::Custom("Approval timed out".to_string()))?
+        .map_err(|_| WalletError::Custom("Approval cancelled".to_string()))?;
+
+    if !response.approved { return Err(WalletError::UserRejected); }
+
+    let password = response.data.and_then(|d| d.get("password").cloned()).and_then(|p| p.as_str().map(|s| s.to_string()))
+        .ok_or(WalletError::Custom("Password required".to_string()))?;
+
+    let mut authorizations = Vec::with_capacity(to_sign.len());
+    for item in to_sign {
+        authorizations.push(match item {
+            PendingAuthorization::Sign(unsigned) => state.wallet_service.sign_authorization(&from_addr, unsigned, &password).await?,
+            PendingAuthorization::Signed(signed) => signed,
+        });
+    }
+
+    let hash = state
+        .send_set_code_transaction(
+            SetCodeRequest {
+                from: from_addr,
+                to: to_addr,
+                value: value_u256,
+                data: data_bytes.into(),
+                gas_limit,
+                authorizations,
+            },
+            &password,
+        )
+        .await?;
+    Ok(serde_json::json!(format!("{:?}", hash)))
+}
+
+/// EIP-2930 `accessList` from a dApp transaction object (`null` or absent is `None`)
+fn parse_access_list(
+    tx_obj: &serde_json::Map<String, Value>,
+) -> Result<Option<alloy::eips::eip2930::AccessList>, WalletError> {
+    tx_obj
+        .get("accessList")
+        .filter(|v| !v.is_null())
+        .map(|v| serde_json::from_value(v.clone()))
+        .transpose()
+        .map_err(|_| WalletError::InvalidParams)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn tx_obj(value: Value) -> serde_json::Map<String, Value> {
+        value.as_object().cloned().unwrap()
+    }
+
+    #[test]
+    fn test_parse_access_list() {
+        let key = format!("0x{}", "00".repeat(31) + "01");
+        let tx = tx_obj(json!({
+            "accessList": [{
+                "address": "0x1111111111111111111111111111111111111111",
+                "storageKeys": [key]
+            }]
+        }));
+
+        let list = parse_access_list(&tx).unwrap().unwrap();
+        assert_eq!(list.0.len(), 1);
+        assert_eq!(list.0[0].address, Address::repeat_byte(0x11));
+        assert_eq!(list.0[0].storage_keys.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_access_list_absent_or_null() {
+        assert!(parse_access_list(&tx_obj(json!({}))).unwrap().is_none());
+        assert!(parse_access_list(&tx_obj(json!({ "accessList": null }))).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_parse_access_list_rejects_malformed_entries() {
+        let malformed = [
+            json!({ "accessList": "0x1234" }),
+            json!({ "accessList": [{ "address": "0x1234", "storageKeys": [] }] }),
+            json!({ "accessList": [{ "address": "0x1111111111111111111111111111111111111111", "storageKeys": ["0x01"] }] }),
+            json!({ "accessList": [{ "storageKeys": [] }] }),
+        ];
+        for value in malformed {
+            assert!(matches!(
+                parse_access_list(&tx_obj(value)),
+                Err(WalletError::InvalidParams)
+            ));
+        }
+    }
+}
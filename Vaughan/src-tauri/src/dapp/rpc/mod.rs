@@ -70,7 +70,14 @@ pub async fn handle_request(
         "wallet_switchEthereumChain" => {
             wallet::handle_switch_chain(state, window_label, origin, params).await
         },
-        "wallet_addEthereumChain" => wallet::handle_add_chain(state, window_label, origin, params).await,
+        "wallet_addEthereumChain" => {
+            wallet::handle_add_chain(app, state, window_label, origin, params).await
+        },
+
+        // Account Delegation (EIP-7702, require approval)
+        "wallet_signAuthorization" => {
+            wallet::handle_sign_authorization(app, state, window_label, origin, params).await
+        },
 
         // Asset Management (EIP-747)
         "wallet_watchAsset" => wallet::handle_watch_asset(app, state, window_label, origin, params).await,
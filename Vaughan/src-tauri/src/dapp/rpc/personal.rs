@@ -1,10 +1,19 @@
-use alloy::primitives::Address;
+use alloy::primitives::{Address, B256};
 use alloy_dyn_abi::TypedData;
 use crate::error::WalletError;
 use crate::state::VaughanState;
 use serde_json::Value;
 use tauri::{AppHandle, Emitter, Manager};
 
+/// EIP-712 signing hash of typed data JSON (`eth_signTypedData_v4` format)
+pub(crate) fn typed_data_signing_hash(typed_data_json: &str) -> Result<B256, WalletError> {
+    let typed_data: TypedData = serde_json::from_str(typed_data_json)
+        .map_err(|e| WalletError::Custom(format!("Invalid typed data: {}", e)))?;
+    typed_data
+        .eip712_signing_hash()
+        .map_err(|e| WalletError::Custom(format!("Failed to hash typed data: {}", e)))
+}
+
 pub(crate) async fn handle_personal_sign(
     app: &AppHandle,
     state: &VaughanState,
@@ -25,6 +34,14 @@ pub(crate) async fn handle_personal_sign(
     let message = hex::decode(clean_hex).map_err(|_| WalletError::InvalidParams)?;
     let message_str = String::from_utf8_lossy(&message).to_string();
 
+    // Use WalletService signer instead of adapter-local signer so that
+    // dApp signing works even when the network adapter was created
+    // without an embedded signer.
+    let address = address_str
+        .parse::<Address>()
+        .map_err(|_| WalletError::InvalidAddress(address_str.to_string()))?;
+    state.wallet_service.ensure_can_sign(&address).await?;
+
     use crate::dapp::ApprovalRequestType;
     let request_type = ApprovalRequestType::PersonalSign {
         origin: origin.to_string(),
@@ -65,13 +82,6 @@ pub(crate) async fn handle_personal_sign(
         .and_then(|p| p.as_str().map(|s| s.to_string()))
         .ok_or(WalletError::Custom("Password required".to_string()))?;
 
-    // Use WalletService signer instead of adapter-local signer so that
-    // dApp signing works even when the network adapter was created
-    // without an embedded signer.
-    let address = address_str
-        .parse::<Address>()
-        .map_err(|_| WalletError::InvalidAddress(address_str.to_string()))?;
-
     let raw_sig = state
         .wallet_service
         .sign_message(&address, &message, &password)
@@ -94,6 +104,8 @@ pub(crate) async fn handle_sign_typed_data_v4(
     let typed_data_val = params.get(1).ok_or(WalletError::InvalidParams)?;
     let typed_data_str = serde_json::to_string(typed_data_val)
         .map_err(|e| WalletError::Custom(format!("Invalid typed data JSON: {}", e)))?;
+    let addr = address.parse::<Address>().map_err(|_| WalletError::InvalidAddress(address.to_string()))?;
+    state.wallet_service.ensure_can_sign(&addr).await?;
 
     use crate::dapp::ApprovalRequestType;
     let request_type = ApprovalRequestType::SignTypedData {
@@ -135,17 +147,13 @@ pub(crate) async fn handle_sign_typed_data_v4(
         .and_then(|p| p.as_str().map(|s| s.to_string()))
         .ok_or(WalletError::Custom("Password required".to_string()))?;
 
-    // In Alloy 1.x, we need to sign the typed data. 
-    // Since we have the raw JSON, we'll use sign_hash on the EIP-712 hash of the data.
+    // Signed as typed data (not as its hash) so external signers can show it
     let typed_data: TypedData = serde_json::from_str(&typed_data_str)
         .map_err(|e| WalletError::Custom(format!("Invalid typed data: {}", e)))?;
-    let hash = typed_data.eip712_signing_hash()
-        .map_err(|e| WalletError::Custom(format!("Failed to hash typed data: {}", e)))?;
 
-    let addr = address.parse::<alloy::primitives::Address>().map_err(|_| WalletError::InvalidAddress(address.to_string()))?;
     let signature = state
         .wallet_service
-        .sign_hash(&addr, hash, &password)
+        .sign_typed_data(&addr, &typed_data, &password)
         .await?;
 
     Ok(serde_json::json!(format!("0x{}", hex::encode(signature))))
@@ -1,4 +1,6 @@
 
+use crate::chains::ChainType;
+use crate::core::{NetworkConfig, TokenInfo};
 use crate::error::WalletError;
 use crate::state::VaughanState;
 use serde_json::Value;
@@ -56,12 +58,19 @@ pub(crate) async fn handle_switch_chain(
     Ok(serde_json::json!(null))
 }
 
+/// Handle wallet_addEthereumChain (EIP-3085)
+///
+/// A chain the wallet already knows is switched to directly. A new one is
+/// only added (and switched to) once the user approves it.
 pub(crate) async fn handle_add_chain(
+    app: &tauri::AppHandle,
     state: &VaughanState,
-    _window_label: &str,
-    _origin: &str,
+    window_label: &str,
+    origin: &str,
     params: Vec<Value>,
 ) -> Result<Value, WalletError> {
+    use crate::dapp::ApprovalRequestType;
+
     // wallet_addEthereumChain params: [{ chainId, chainName, rpcUrls, nativeCurrency }]
     let chain_obj = params
         .get(0)
@@ -80,6 +89,16 @@ pub(crate) async fn handle_add_chain(
         u64::from_str_radix(chain_id_hex, 10).map_err(|_| WalletError::InvalidParams)?
     };
 
+    // Already known (predefined or previously added): just switch to it
+    if state.find_network_by_chain_id(chain_id).is_some() {
+        eprintln!(
+            "[RPC] wallet_addEthereumChain: Chain {} already known, switching",
+            chain_id
+        );
+        state.set_active_chain(chain_id).await?;
+        return Ok(serde_json::json!(null));
+    }
+
     let rpc_url = chain_obj
         .get("rpcUrls")
         .and_then(|v| v.as_array())
@@ -95,12 +114,86 @@ pub(crate) async fn handle_add_chain(
         .unwrap_or("Custom Network")
         .to_string();
 
+    let explorer_url = chain_obj
+        .get("blockExplorerUrls")
+        .and_then(|v| v.as_array())
+        .and_then(|arr| arr.first())
+        .and_then(|v| v.as_str())
+        .map(|s| s.to_string());
+
+    let currency = chain_obj.get("nativeCurrency");
+    let native_token = TokenInfo {
+        symbol: currency
+            .and_then(|c| c.get("symbol"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("ETH")
+            .to_string(),
+        name: currency
+            .and_then(|c| c.get("name"))
+            .and_then(|v| v.as_str())
+            .unwrap_or("Ether")
+            .to_string(),
+        decimals: currency
+            .and_then(|c| c.get("decimals"))
+            .and_then(|v| v.as_u64())
+            .and_then(|d| u8::try_from(d).ok())
+            .unwrap_or(18),
+    };
+
+    let config = NetworkConfig {
+        id: format!("custom-{}", chain_id),
+        name: network_name.clone(),
+        chain_type: ChainType::Evm,
+        chain_id,
+        rpc_url: rpc_url.to_string(),
+        explorer_url,
+        native_token,
+        is_testnet: false,
+    };
+
+    // Refuse malformed configs before asking the user
+    state.network_service.validate_network_config(&config)?;
+
+    let request_type = ApprovalRequestType::AddNetwork {
+        origin: origin.to_string(),
+        chain_id,
+        chain_name: network_name.clone(),
+        rpc_url: rpc_url.to_string(),
+        block_explorer_url: config.explorer_url.clone(),
+    };
+    let (id, rx) = state
+        .approval_queue
+        .add_request(window_label.to_string(), request_type.clone())
+        .await?;
+
+    if let Some(main_window) = app.get_webview_window("main") {
+        let payload = serde_json::json!({
+            "id": id,
+            "origin": origin,
+            "type": "addNetwork",
+            "params": request_type
+        });
+        let _ = main_window.emit("dapp_request", payload);
+    }
+
+    let response = tokio::time::timeout(tokio::time::Duration::from_secs(300), rx)
+        .await
+        .map_err(|_| WalletError::Custom("Approval timed out".to_string()))?
+        .map_err(|_| WalletError::Custom("Approval cancelled".to_string()))?;
+
+    if !response.approved {
+        return Err(WalletError::UserRejected);
+    }
+
     eprintln!(
         "[RPC] wallet_addEthereumChain: Adding chain {} ({})",
         network_name, chain_id
     );
 
-    // Switch to this network (creates adapter on-demand)
+    // Validates the config, verifies eth_chainId against the RPC and persists it
+    state.add_custom_network(config).await?;
+
+    // Switch to the new network (creates adapter on-demand)
     state
         .switch_network(&format!("custom-{}", chain_id), rpc_url, chain_id)
         .await?;
@@ -278,3 +371,87 @@ pub(crate) async fn handle_watch_asset(
     // A full implementation requires returning true only AFTER user clicks "Add"
     Ok(serde_json::json!(true))
 }
+
+// ============================================================================
+// EIP-7702 Authorization Handlers (Require Approval)
+// ============================================================================
+
+/// Handle wallet_signAuthorization (EIP-7702)
+///
+/// Params: `[authority, { contractAddress | address, chainId?, nonce? }]`.
+/// Signs an authorization tuple delegating `authority` to the contract,
+/// typically for a sponsor to include in their own set-code transaction.
+pub(crate) async fn handle_sign_authorization(
+    app: &tauri::AppHandle,
+    state: &VaughanState,
+    window_label: &str,
+    origin: &str,
+    params: Vec<Value>,
+) -> Result<Value, WalletError> {
+    use super::eth::{parse_authorization_param, AuthorizationParam};
+    use crate::chains::evm::eip7702;
+    use crate::dapp::{ApprovalRequestType, DelegationApproval};
+
+    eprintln!("[RPC] wallet_signAuthorization requested by {}", origin);
+
+    let authority_str = params.get(0).and_then(|v| v.as_str()).ok_or(WalletError::InvalidParams)?;
+    let authority: alloy::primitives::Address = authority_str
+        .parse()
+        .map_err(|_| WalletError::InvalidAddress(authority_str.to_string()))?;
+    state.wallet_service.ensure_can_sign_hash(&authority).await?;
+    let (delegate, chain_id, nonce) = match parse_authorization_param(params.get(1).ok_or(WalletError::InvalidParams)?)? {
+        AuthorizationParam::Unsigned { delegate, chain_id, nonce } => (delegate, chain_id, nonce),
+        AuthorizationParam::Signed(_) => return Err(WalletError::InvalidParams),
+    };
+
+    let adapter = state.current_adapter().await?;
+    let chain_id = chain_id.unwrap_or_else(|| adapter.chain_id());
+    let nonce = match nonce {
+        Some(nonce) => nonce,
+        None => adapter.get_transaction_count(authority).await?,
+    };
+
+    let delegation = DelegationApproval::new(authority, chain_id, delegate, nonce);
+    let request_type = ApprovalRequestType::Authorization {
+        origin: origin.to_string(),
+        delegation: delegation.clone(),
+    };
+
+    let (id, rx) = state
+        .approval_queue
+        .add_request(window_label.to_string(), request_type)
+        .await?;
+
+    if let Some(main_window) = app.get_webview_window("main") {
+        let payload = serde_json::json!({
+            "id": id,
+            "origin": origin,
+            "type": "authorization",
+            "params": delegation
+        });
+        let _ = main_window.emit("dapp_request", payload);
+    }
+
+    let response = tokio::time::timeout(tokio::time::Duration::from_secs(300), rx)
+        .await
+        .map_err(|_| WalletError::Custom("Approval timed out".to_string()))?
+        .map_err(|_| WalletError::Custom("Approval cancelled".to_string()))?;
+
+    if !response.approved {
+        return Err(WalletError::UserRejected);
+    }
+
+    let password = response
+        .data
+        .and_then(|d| d.get("password").cloned())
+        .and_then(|p| p.as_str().map(|s| s.to_string()))
+        .ok_or(WalletError::Custom("Password required".to_string()))?;
+
+    let signed = state
+        .wallet_service
+        .sign_authorization(&authority, eip7702::authorization(chain_id, delegate, nonce), &password)
+        .await?;
+
+    serde_json::to_value(&signed).map_err(|e| WalletError::InternalError(e.to_string()))
+}
+
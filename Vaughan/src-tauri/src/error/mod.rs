@@ -50,6 +50,9 @@ pub enum WalletError {
     /// Connection timeout
     ConnectionTimeout(String),
 
+    /// RPC node is behind the chain head
+    RpcBehind(String),
+
     // ===== Address Errors =====
     /// Invalid address format
     InvalidAddress(String),
@@ -89,6 +92,9 @@ pub enum WalletError {
     /// Derivation path error
     InvalidDerivationPath(String),
 
+    /// Watch-only account (no key to sign with)
+    WatchOnlyAccount(String),
+
     // ===== Security Errors =====
     /// Unauthorized operation
     Unauthorized,
@@ -99,6 +105,9 @@ pub enum WalletError {
     /// Invalid password
     InvalidPassword,
 
+    /// Too many wrong passwords; retry after this many seconds
+    UnlockThrottled(u64),
+
     /// Encryption failed
     EncryptionFailed(String),
 
@@ -108,6 +117,9 @@ pub enum WalletError {
     /// Signer not available (adapter created without signer)
     SignerNotAvailable(String),
 
+    /// External signer (Clef) unreachable or failed
+    ExternalSigner(String),
+
     /// Signing operation failed
     SigningFailed(String),
 
@@ -195,6 +207,7 @@ impl fmt::Display for WalletError {
             Self::NetworkError(msg) => write!(f, "Network error: {}", msg),
             Self::RpcError(msg) => write!(f, "RPC error: {}", msg),
             Self::ConnectionTimeout(msg) => write!(f, "Connection timeout: {}", msg),
+            Self::RpcBehind(msg) => write!(f, "RPC node is behind: {}", msg),
 
             // Address Errors
             Self::InvalidAddress(addr) => write!(f, "Invalid address: {}", addr),
@@ -215,14 +228,19 @@ impl fmt::Display for WalletError {
             Self::InvalidPrivateKey(msg) => write!(f, "Invalid private key: {}", msg),
             Self::InvalidMnemonic(msg) => write!(f, "Invalid mnemonic: {}", msg),
             Self::InvalidDerivationPath(path) => write!(f, "Invalid derivation path: {}", path),
+            Self::WatchOnlyAccount(addr) => write!(f, "Watch-only account: {}", addr),
 
             // Security Errors
             Self::Unauthorized => write!(f, "Unauthorized operation"),
             Self::WalletLocked => write!(f, "Wallet is locked"),
             Self::InvalidPassword => write!(f, "Invalid password"),
+            Self::UnlockThrottled(secs) => {
+                write!(f, "Too many wrong passwords, retry in {}s", secs)
+            },
             Self::EncryptionFailed(msg) => write!(f, "Encryption failed: {}", msg),
             Self::DecryptionFailed(msg) => write!(f, "Decryption failed: {}", msg),
             Self::SignerNotAvailable(msg) => write!(f, "Signer not available: {}", msg),
+            Self::ExternalSigner(msg) => write!(f, "External signer error: {}", msg),
             Self::SigningFailed(msg) => write!(f, "Signing failed: {}", msg),
             Self::KeyDerivationFailed(msg) => write!(f, "Key derivation failed: {}", msg),
             Self::KeyringError(msg) => write!(f, "Keyring error: {}", msg),
@@ -272,6 +290,12 @@ impl WalletError {
             Self::NetworkError(_) | Self::RpcError(_) | Self::ConnectionTimeout(_) => {
                 "Network connection failed. Please check your internet connection.".to_string()
             },
+            Self::RpcBehind(msg) => {
+                format!(
+                    "The network's RPC node is out of sync ({}). Try again later or switch RPC.",
+                    msg
+                )
+            },
             Self::RateLimitExceeded => {
                 "Too many requests. Please wait a moment and try again.".to_string()
             },
@@ -294,11 +318,24 @@ impl WalletError {
             Self::TransactionFailed(_) => "Transaction failed. Please try again later.".to_string(),
             Self::WalletLocked => "Wallet is locked. Please unlock to continue.".to_string(),
             Self::InvalidPassword => "Invalid password. Please try again.".to_string(),
+            Self::UnlockThrottled(secs) => format!(
+                "Too many wrong passwords. Please wait {} seconds before trying again.",
+                secs
+            ),
             Self::Unauthorized => {
                 "Unauthorized operation. This action can only be performed from the wallet."
                     .to_string()
             },
             Self::SignerNotAvailable(_) => "Cannot sign: wallet is in read-only mode.".to_string(),
+            Self::ExternalSigner(msg) => {
+                format!("External signer failed: {}. Check that it is running and reachable.", msg)
+            },
+            Self::WatchOnlyAccount(addr) => {
+                format!(
+                    "{} is a watch-only account and cannot sign. Export the unsigned transaction and sign it on the device that holds the key.",
+                    addr
+                )
+            },
             Self::SigningFailed(_) => {
                 "Failed to sign transaction or message. Please try again.".to_string()
             },
@@ -344,6 +381,7 @@ impl WalletError {
             Self::NetworkError(_) => "NETWORK_ERROR",
             Self::RpcError(_) => "RPC_ERROR",
             Self::ConnectionTimeout(_) => "CONNECTION_TIMEOUT",
+            Self::RpcBehind(_) => "RPC_BEHIND",
             Self::InvalidAddress(_) => "INVALID_ADDRESS",
             Self::InvalidChecksum(_) => "INVALID_CHECKSUM",
             Self::InsufficientBalance { .. } => "INSUFFICIENT_BALANCE",
@@ -356,12 +394,15 @@ impl WalletError {
             Self::InvalidPrivateKey(_) => "INVALID_PRIVATE_KEY",
             Self::InvalidMnemonic(_) => "INVALID_MNEMONIC",
             Self::InvalidDerivationPath(_) => "INVALID_DERIVATION_PATH",
+            Self::WatchOnlyAccount(_) => "WATCH_ONLY_ACCOUNT",
             Self::Unauthorized => "UNAUTHORIZED",
             Self::WalletLocked => "WALLET_LOCKED",
             Self::InvalidPassword => "INVALID_PASSWORD",
+            Self::UnlockThrottled(_) => "UNLOCK_THROTTLED",
             Self::EncryptionFailed(_) => "ENCRYPTION_FAILED",
             Self::DecryptionFailed(_) => "DECRYPTION_FAILED",
             Self::SignerNotAvailable(_) => "SIGNER_NOT_AVAILABLE",
+            Self::ExternalSigner(_) => "EXTERNAL_SIGNER_ERROR",
             Self::SigningFailed(_) => "SIGNING_FAILED",
             Self::KeyDerivationFailed(_) => "KEY_DERIVATION_FAILED",
             Self::KeyringError(_) => "KEYRING_ERROR",
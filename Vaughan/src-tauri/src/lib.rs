@@ -31,6 +31,17 @@ fn build_specta_builder() -> tauri_specta::Builder<tauri::Wry> {
         commands::network::get_chain_id,
         commands::network::get_block_number,
         commands::network::get_supported_networks,
+        commands::network::get_custom_networks,
+        commands::network::add_custom_network,
+        commands::network::update_custom_network,
+        commands::network::remove_custom_network,
+        commands::network::get_network_overrides,
+        commands::network::set_network_override,
+        commands::network::search_network_registry,
+        commands::network::add_network_from_registry,
+        commands::network::import_chain_registry,
+        commands::network::get_network_health,
+        commands::network::get_active_network_health,
         commands::network::eth_request,
         commands::network::proxy_request,
         commands::token::get_token_price,
@@ -42,12 +53,16 @@ fn build_specta_builder() -> tauri_specta::Builder<tauri::Wry> {
         commands::token::get_tracked_tokens,
         commands::persistence::export_state,
         commands::persistence::reset_state,
+        commands::persistence::export_backup,
+        commands::persistence::restore_backup,
         commands::persistence::get_user_preferences,
         commands::persistence::update_user_preferences,
         commands::transaction::validate_transaction,
         commands::transaction::estimate_gas_simple,
         commands::transaction::build_transaction,
         commands::transaction::sign_transaction,
+        commands::transaction::export_unsigned_transaction,
+        commands::transaction::broadcast_signed_transaction,
         commands::transaction::send_transaction,
         commands::wallet::create_wallet,
         commands::wallet::import_wallet,
@@ -57,15 +72,64 @@ fn build_specta_builder() -> tauri_specta::Builder<tauri::Wry> {
         commands::wallet::wallet_exists,
         commands::wallet::get_accounts,
         commands::wallet::create_account,
+        commands::wallet::discover_accounts,
+        commands::wallet::add_hidden_wallet,
         commands::wallet::import_account,
+        commands::wallet::import_account_keystore,
+        commands::wallet::add_watch_only_account,
+        commands::wallet::add_external_signer,
         commands::wallet::delete_account,
         commands::wallet::rename_account,
         commands::wallet::set_active_account,
         commands::wallet::export_mnemonic,
+        commands::wallet::create_seed_shares,
+        commands::wallet::restore_wallet_from_shares,
         commands::wallet::export_private_key,
+        commands::wallet::export_account_keystore,
         commands::wallet::get_railgun_mnemonic,
         commands::wallet::set_focused_asset,
         commands::wallet::report_activity,
+        commands::wallet::get_secret_storage,
+        commands::wallet::migrate_secret_storage,
+        commands::wallet::change_password,
+        commands::smart_account::get_bundlers,
+        commands::smart_account::set_bundler_url,
+        commands::smart_account::create_smart_account,
+        commands::smart_account::send_user_operation,
+        commands::smart_account::get_user_operation_receipt,
+        commands::delegation::get_known_delegates,
+        commands::delegation::get_delegation,
+        commands::delegation::sign_authorization,
+        commands::delegation::send_set_code_transaction,
+        commands::safe::add_safe_account,
+        commands::safe::get_safe_info,
+        commands::safe::get_safe_proposals,
+        commands::safe::propose_safe_transaction,
+        commands::safe::sign_safe_proposal,
+        commands::safe::export_safe_proposal,
+        commands::safe::import_safe_proposal,
+        commands::safe::execute_safe_proposal,
+        commands::safe::delete_safe_proposal,
+        commands::signature::verify_message_signature,
+        commands::signature::verify_typed_data_signature,
+        commands::contract::import_contract_abi,
+        commands::contract::get_contract_abis,
+        commands::contract::get_contract_functions,
+        commands::contract::remove_contract_abi,
+        commands::contract::call_contract_function,
+        commands::contract::build_contract_transaction,
+        commands::batch::create_batch_job,
+        commands::batch::get_batch_jobs,
+        commands::batch::get_batch_job,
+        commands::batch::run_batch_job,
+        commands::batch::refresh_batch_job,
+        commands::batch::delete_batch_job,
+        commands::address_book::get_contacts,
+        commands::address_book::add_contact,
+        commands::address_book::update_contact,
+        commands::address_book::delete_contact,
+        commands::address_book::import_contacts_csv,
+        commands::address_book::export_contacts_csv,
         commands::dapp::dapp_request,
         commands::dapp::connect_dapp,
         commands::dapp::disconnect_dapp,
@@ -92,6 +156,9 @@ fn build_specta_builder() -> tauri_specta::Builder<tauri::Wry> {
     ])
         .events(tauri_specta::collect_events![
             monitoring::balance_watcher::RefreshBalanceEvent,
+            monitoring::network_health::NetworkHealthChangedEvent,
+            monitoring::auto_lock::WalletLockedEvent,
+            monitoring::unlock_throttle::SecurityAlertEvent,
         ]);
     #[cfg(debug_assertions)]
     {
@@ -147,6 +214,12 @@ pub fn run() {
                 tauri::async_runtime::block_on(async { state::VaughanState::new().await })
                     .expect("Failed to initialize VaughanState");
 
+            // Every password check counts towards the persisted unlock throttle
+            let attempts = monitoring::unlock_throttle::PersistedAttempts::new(app.handle().clone());
+            tauri::async_runtime::block_on(
+                production_state.wallet_service.set_password_attempts(std::sync::Arc::new(attempts)),
+            );
+
             app.manage(production_state);
             info!("VaughanState initialized");
 
@@ -154,6 +227,14 @@ pub fn run() {
             monitoring::balance_watcher::spawn(app.handle().clone());
             info!("Balance watcher started");
 
+            // Start background network health monitor
+            monitoring::network_health::spawn(app.handle().clone());
+            info!("Network health monitor started");
+
+            // Start auto-lock supervisor (UserPreferences::auto_lock_seconds)
+            monitoring::auto_lock::spawn(app.handle().clone());
+            info!("Auto-lock supervisor started");
+
             // Persist state periodically so accounts survive app close without explicit save
             let handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Address book contact
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Type)]
+pub struct Contact {
+    /// Unique ID
+    pub id: String,
+
+    /// Display name (e.g. "Alice", "Exchange deposit")
+    pub name: String,
+
+    /// Address (checksummed)
+    pub address: String,
+
+    /// Chain the contact applies to (`None` = all chains)
+    pub chain_id: Option<u64>,
+
+    /// Free-form notes
+    #[serde(default)]
+    pub notes: String,
+
+    /// Tags (e.g. "team", "exchange")
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// Unix timestamp (seconds)
+    pub created_at: u64,
+}
@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+/// Contract ABI imported by the user
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Type)]
+pub struct ContractAbiEntry {
+    /// Network Chain ID the contract is deployed on
+    pub chain_id: u64,
+
+    /// Contract address
+    pub address: String,
+
+    /// Display name (e.g. "Treasury", "USDC")
+    pub name: String,
+
+    /// JSON ABI (plain array)
+    pub abi: String,
+}
@@ -1,3 +1,5 @@
+pub mod contact;
+pub mod contract;
 pub mod erc20;
 pub mod token;
 pub use erc20::IERC20;
@@ -11,6 +11,51 @@ pub enum AccountType {
     Hd,
     /// Imported account (from private key)
     Imported,
+    /// ERC-4337 smart contract account (owned by another wallet account)
+    Smart,
+    /// Safe multisig (owned by several signers, possibly outside this wallet)
+    Safe,
+    /// Address monitored without a key (e.g. a cold wallet)
+    WatchOnly,
+    /// Key held by an external signer (Clef)
+    External,
+}
+
+/// HD derivation scheme
+///
+/// Wallets disagree on where the account index goes in the BIP-32 path.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum DerivationScheme {
+    /// BIP-44 (MetaMask, Trezor, most wallets): `m/44'/60'/0'/0/{i}`
+    #[default]
+    Bip44,
+    /// Ledger Live: `m/44'/60'/{i}'/0/0`
+    LedgerLive,
+    /// Legacy MyEtherWallet / Ledger (MEW): `m/44'/60'/0'/{i}`
+    LegacyMew,
+    /// User path; `{i}` is replaced by the account index
+    Custom(String),
+}
+
+impl DerivationScheme {
+    /// Derivation path of the account at `index`
+    pub fn path(&self, index: u32) -> String {
+        match self {
+            Self::Bip44 => format!("m/44'/60'/0'/0/{}", index),
+            Self::LedgerLive => format!("m/44'/60'/{}'/0/0", index),
+            Self::LegacyMew => format!("m/44'/60'/0'/{}", index),
+            Self::Custom(template) => template.trim().replace("{i}", &index.to_string()),
+        }
+    }
+
+    /// Whether the scheme yields a different path per index
+    pub fn is_indexed(&self) -> bool {
+        match self {
+            Self::Custom(template) => template.contains("{i}"),
+            _ => true,
+        }
+    }
 }
 
 /// Deserialize Address from a string (e.g. "0x...") so persisted state still loads
@@ -46,6 +91,99 @@ pub struct Account {
 
     /// Derivation index (for HD accounts)
     pub index: Option<u32>,
+
+    /// BIP-32 path (for HD accounts); `None` = `m/44'/60'/0'/0/{index}`,
+    /// as stored before paths were configurable
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub derivation_path: Option<String>,
+
+    /// Hidden wallet (BIP-39 passphrase) an HD account is derived with;
+    /// `None` = the passphrase given when the wallet was created or imported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub passphrase_id: Option<String>,
+
+    /// Smart account parameters (for smart accounts)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub smart_account: Option<SmartAccountConfig>,
+
+    /// Safe parameters (for Safe accounts)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub safe: Option<SafeAccountConfig>,
+
+    /// Signer holding the key (for external accounts)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_signer: Option<ExternalSignerConfig>,
+}
+
+impl Account {
+    /// BIP-32 path of an HD account
+    pub fn hd_path(&self) -> Option<String> {
+        if self.account_type != AccountType::Hd {
+            return None;
+        }
+        self.derivation_path
+            .clone()
+            .or_else(|| self.index.map(|i| DerivationScheme::Bip44.path(i)))
+    }
+}
+
+/// ERC-4337 smart account parameters
+///
+/// The account address is counterfactual: it is derived from the factory,
+/// owner and salt, and the contract is deployed by the first UserOperation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SmartAccountConfig {
+    /// Wallet account that signs UserOperations
+    #[serde(deserialize_with = "deserialize_address_lenient", serialize_with = "serialize_address_string")]
+    pub owner: Address,
+
+    /// Account factory (SimpleAccountFactory-compatible)
+    #[serde(deserialize_with = "deserialize_address_lenient", serialize_with = "serialize_address_string")]
+    pub factory: Address,
+
+    /// EntryPoint contract the account is bound to
+    #[serde(deserialize_with = "deserialize_address_lenient", serialize_with = "serialize_address_string")]
+    pub entry_point: Address,
+
+    /// Factory salt (lets one owner have several accounts)
+    pub salt: u64,
+}
+
+/// Safe multisig parameters
+///
+/// Owners and threshold can change on-chain, so they are read from the
+/// contract when needed rather than stored.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct SafeAccountConfig {
+    /// Chain the Safe is deployed on
+    pub chain_id: u64,
+}
+
+/// External signer parameters
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Type)]
+pub struct ExternalSignerConfig {
+    /// Clef endpoint: `http(s)://host:port` or the path of its UNIX socket
+    pub url: String,
+}
+
+/// Smart account shape for Specta/TypeScript export
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct SmartAccountExport {
+    pub owner: String,
+    pub factory: String,
+    pub entry_point: String,
+    pub salt: u64,
+}
+
+impl From<SmartAccountConfig> for SmartAccountExport {
+    fn from(c: SmartAccountConfig) -> Self {
+        Self {
+            owner: format!("{:?}", c.owner),
+            factory: format!("{:?}", c.factory),
+            entry_point: format!("{:?}", c.entry_point),
+            salt: c.salt,
+        }
+    }
 }
 
 /// Account shape for Specta/TypeScript export (address as string; same JSON shape as Account).
@@ -55,6 +193,16 @@ pub struct AccountExport {
     pub name: String,
     pub account_type: AccountType,
     pub index: Option<u32>,
+    #[serde(default)]
+    pub derivation_path: Option<String>,
+    #[serde(default)]
+    pub passphrase_id: Option<String>,
+    #[serde(default)]
+    pub smart_account: Option<SmartAccountExport>,
+    #[serde(default)]
+    pub safe: Option<SafeAccountConfig>,
+    #[serde(default)]
+    pub external_signer: Option<ExternalSignerConfig>,
 }
 
 impl From<Account> for AccountExport {
@@ -64,6 +212,11 @@ impl From<Account> for AccountExport {
             name: a.name,
             account_type: a.account_type,
             index: a.index,
+            derivation_path: a.hd_path(),
+            passphrase_id: a.passphrase_id,
+            smart_account: a.smart_account.map(SmartAccountExport::from),
+            safe: a.safe,
+            external_signer: a.external_signer,
         }
     }
 }
@@ -0,0 +1,167 @@
+//! Auto-Lock Supervisor
+//!
+//! Locks the wallet after `UserPreferences::auto_lock_seconds` without user
+//! activity (0 = never). Locking drops the unlocked keys, rejects pending
+//! dApp approvals, tells dApp windows the accounts are gone and emits a
+//! `WalletLockedEvent`.
+//!
+//! Idle time is measured on both the monotonic and the wall clock, and the
+//! larger one wins:
+//!
+//! - The monotonic clock may stop during system sleep; the wall clock
+//!   doesn't, so the wallet still locks after a suspend
+//! - The wall clock can be set backwards; the monotonic clock can't, so
+//!   that can't keep the wallet unlocked
+//!
+//! A wall clock jumping forward locks early, which is the safe direction.
+
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::sync::Mutex;
+use std::time::{Duration, Instant, SystemTime};
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_specta::Event;
+use tracing::{debug, info};
+
+use crate::state::VaughanState;
+
+/// How often the supervisor checks idle time
+const CHECK_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Why the wallet was locked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum LockReason {
+    /// User locked it
+    Manual,
+    /// No activity for the configured auto-lock time
+    Idle,
+}
+
+/// Typed event emitted when the wallet is locked.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, tauri_specta::Event)]
+pub struct WalletLockedEvent {
+    pub reason: LockReason,
+}
+
+/// Last user activity, on both clocks
+pub struct ActivityTracker {
+    last: Mutex<(Instant, SystemTime)>,
+}
+
+impl ActivityTracker {
+    pub fn new() -> Self {
+        Self {
+            last: Mutex::new((Instant::now(), SystemTime::now())),
+        }
+    }
+
+    /// Record user activity
+    pub fn touch(&self) {
+        if let Ok(mut last) = self.last.lock() {
+            *last = (Instant::now(), SystemTime::now());
+        }
+    }
+
+    /// Time since the last activity
+    pub fn idle(&self) -> Duration {
+        match self.last.lock() {
+            Ok(last) => idle_time(last.0.elapsed(), last.1, SystemTime::now()),
+            // Poisoned: treat as idle for as long as possible (locks)
+            Err(_) => Duration::MAX,
+        }
+    }
+}
+
+impl Default for ActivityTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Idle time from the monotonic elapsed time and the wall-clock timestamps
+///
+/// A wall clock that went backwards counts as zero.
+pub fn idle_time(monotonic: Duration, last_wall: SystemTime, now_wall: SystemTime) -> Duration {
+    let wall = now_wall.duration_since(last_wall).unwrap_or_default();
+    monotonic.max(wall)
+}
+
+/// Lock the wallet and tell the UI and dApp windows
+pub async fn lock_wallet(app: &AppHandle, reason: LockReason) {
+    let state = app.state::<VaughanState>();
+    state.lock_wallet().await;
+
+    // A locked wallet exposes no accounts (EIP-1193)
+    let window_labels: Vec<String> = { state.window_registry.get_all_labels().await };
+    for window_label in window_labels {
+        if let Some(window) = app.get_webview_window(&window_label) {
+            if let Err(e) = window.emit("accountsChanged", Vec::<String>::new()) {
+                debug!("[AutoLock] Failed to notify window {}: {}", window_label, e);
+            }
+        }
+    }
+
+    let _ = WalletLockedEvent { reason }.emit(app);
+    info!("[AutoLock] Wallet locked ({:?})", reason);
+}
+
+/// Spawn the auto-lock supervisor.
+pub fn spawn(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        info!("[AutoLock] Started");
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+
+            let state = app_handle.state::<VaughanState>();
+            if state.is_locked().await {
+                continue;
+            }
+
+            let auto_lock_seconds = state.state_manager().load().preferences.auto_lock_seconds;
+            if auto_lock_seconds == 0 {
+                continue;
+            }
+
+            let idle = state.activity.idle();
+            if idle >= Duration::from_secs(auto_lock_seconds) {
+                debug!("[AutoLock] Idle for {:?} (limit {}s)", idle, auto_lock_seconds);
+                lock_wallet(&app_handle, LockReason::Idle).await;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_idle_time_uses_larger_clock() {
+        let last = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000);
+
+        // Normal: both clocks agree
+        let idle = idle_time(Duration::from_secs(60), last, last + Duration::from_secs(60));
+        assert_eq!(idle, Duration::from_secs(60));
+
+        // Suspend: monotonic clock paused, wall clock kept going
+        let idle = idle_time(Duration::from_secs(5), last, last + Duration::from_secs(3_600));
+        assert_eq!(idle, Duration::from_secs(3_600));
+
+        // Wall clock set backwards: monotonic time still counts
+        let idle = idle_time(Duration::from_secs(600), last, last - Duration::from_secs(3_600));
+        assert_eq!(idle, Duration::from_secs(600));
+    }
+
+    #[test]
+    fn test_touch_resets_idle() {
+        let tracker = ActivityTracker::new();
+        std::thread::sleep(Duration::from_millis(20));
+        assert!(tracker.idle() >= Duration::from_millis(20));
+        tracker.touch();
+        assert!(tracker.idle() < Duration::from_millis(20));
+    }
+}
@@ -126,8 +126,7 @@ pub fn spawn(app_handle: AppHandle) {
             }
 
             // Next poll: activity-based back-off (3 → 5 → 10 → 30s)
-            let last = *state.last_activity.lock().await;
-            let elapsed = last.elapsed().as_secs();
+            let elapsed = state.activity.idle().as_secs();
             sleep_duration = if elapsed < ACTIVE_SECS {
                 Duration::from_secs(3)
             } else if elapsed < IDLE_MED_SECS {
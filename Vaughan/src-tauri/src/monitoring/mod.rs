@@ -1 +1,4 @@
+pub mod auto_lock;
 pub mod balance_watcher;
+pub mod network_health;
+pub mod unlock_throttle;
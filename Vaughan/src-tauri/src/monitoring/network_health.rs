@@ -0,0 +1,556 @@
+//! Background Network Health Monitor
+//!
+//! Periodically probes configured RPC endpoints for the latest block and
+//! records latency and staleness (how far the head block's timestamp lags
+//! behind wall-clock time). Keeps a rolling history per network and emits a
+//! `NetworkHealthChangedEvent` when a network's status changes.
+//!
+//! A head block timestamp far in the past only warns: automine dev chains
+//! (Anvil, Hardhat) and quiet chains produce no blocks until a transaction
+//! is sent, and the local clock may be off. Sends are only blocked when an
+//! RPC's block height is behind the best head seen on another RPC of the
+//! same chain.
+//!
+//! The active network is probed every round; other configured networks are
+//! probed less often to avoid hammering public RPCs.
+
+use alloy::eips::BlockNumberOrTag;
+use alloy::network::Ethereum;
+use alloy::providers::{Provider, RootProvider};
+use alloy::rpc::client::RpcClient;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{HashMap, VecDeque};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+use tokio::sync::Mutex;
+use tracing::{debug, info, warn};
+
+use crate::core::NetworkConfig;
+use crate::error::WalletError;
+use crate::state::VaughanState;
+
+/// Interval between probe rounds
+const PROBE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Inactive networks are probed every N rounds (5 minutes)
+const INACTIVE_PROBE_EVERY: u64 = 10;
+
+/// Per-request timeout for a probe
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Samples kept per network (1 hour at the active probe interval)
+pub const HISTORY_LEN: usize = 120;
+
+/// Latency above which a network is considered degraded
+const SLOW_LATENCY_MS: u64 = 2_000;
+
+/// Head block lag above which sends show a warning
+pub const LAG_WARN_SECS: u64 = 60;
+
+/// Blocks behind the chain's best known head above which sends are blocked
+pub const BEHIND_BLOCKS: u64 = 10;
+
+/// Heads seen on other RPCs are only compared against while this recent
+const HEAD_MAX_AGE_SECS: u64 = 2 * INACTIVE_PROBE_EVERY * PROBE_INTERVAL.as_secs();
+
+/// Health status of a network's RPC endpoint
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    /// Responsive and up to date
+    Healthy,
+    /// Slow, or head block timestamp behind wall-clock time
+    Degraded,
+    /// Block height behind another RPC of the same chain
+    Stale,
+    /// Probe failed
+    Down,
+}
+
+/// Result of a single probe
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct HealthSample {
+    /// Unix timestamp (seconds) when the probe ran
+    pub timestamp: u64,
+    /// Latest block number reported by the RPC
+    pub block_number: Option<u64>,
+    /// Round-trip latency in milliseconds
+    pub latency_ms: Option<u64>,
+    /// Seconds between the latest block's timestamp and now
+    pub block_lag_secs: Option<u64>,
+    /// Blocks behind the best head seen on the chain's other RPCs
+    pub blocks_behind: Option<u64>,
+    /// Derived status
+    pub status: HealthStatus,
+    /// Error message if the probe failed
+    pub error: Option<String>,
+}
+
+impl HealthSample {
+    fn down(timestamp: u64, error: String) -> Self {
+        Self {
+            timestamp,
+            block_number: None,
+            latency_ms: None,
+            block_lag_secs: None,
+            blocks_behind: None,
+            status: HealthStatus::Down,
+            error: Some(error),
+        }
+    }
+
+    /// Classify a successful probe by latency, head block lag and blocks
+    /// behind the chain's best known head
+    pub fn classify(latency_ms: u64, block_lag_secs: u64, blocks_behind: u64) -> HealthStatus {
+        if blocks_behind >= BEHIND_BLOCKS {
+            HealthStatus::Stale
+        } else if block_lag_secs >= LAG_WARN_SECS || latency_ms >= SLOW_LATENCY_MS {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Healthy
+        }
+    }
+}
+
+/// Health report for one network, including rolling history
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+pub struct NetworkHealthReport {
+    pub network_id: String,
+    pub name: String,
+    pub chain_id: u64,
+    pub rpc_url: String,
+    /// Status of the most recent sample
+    pub status: HealthStatus,
+    /// Most recent sample
+    pub latest: Option<HealthSample>,
+    /// Rolling history, oldest first
+    pub history: Vec<HealthSample>,
+}
+
+/// Typed event emitted when a network's health status changes.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, tauri_specta::Event)]
+pub struct NetworkHealthChangedEvent {
+    pub network_id: String,
+    pub chain_id: u64,
+    pub previous: Option<HealthStatus>,
+    pub current: HealthStatus,
+    pub sample: HealthSample,
+}
+
+struct HealthRecord {
+    name: String,
+    chain_id: u64,
+    rpc_url: String,
+    history: VecDeque<HealthSample>,
+}
+
+impl HealthRecord {
+    fn to_report(&self, network_id: &str) -> NetworkHealthReport {
+        let latest = self.history.back().cloned();
+        NetworkHealthReport {
+            network_id: network_id.to_string(),
+            name: self.name.clone(),
+            chain_id: self.chain_id,
+            rpc_url: self.rpc_url.clone(),
+            status: latest
+                .as_ref()
+                .map(|s| s.status)
+                .unwrap_or(HealthStatus::Healthy),
+            latest,
+            history: self.history.iter().cloned().collect(),
+        }
+    }
+}
+
+/// In-memory store of network health samples
+///
+/// Owned by `VaughanState`; written by the background monitor and read by
+/// commands and the send path.
+pub struct NetworkHealthMonitor {
+    records: Mutex<HashMap<String, HealthRecord>>,
+}
+
+impl NetworkHealthMonitor {
+    /// Create an empty monitor
+    pub fn new() -> Self {
+        Self {
+            records: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Record a sample for a network
+    ///
+    /// The history is reset if the network's RPC URL changed since the last
+    /// sample, since the old samples describe a different endpoint. The
+    /// sample's height is compared against the latest heads of other
+    /// networks with the same chain ID.
+    ///
+    /// # Returns
+    ///
+    /// `Some(previous)` if the status changed (`previous` is `None` for the
+    /// first sample), `None` otherwise
+    pub async fn record(
+        &self,
+        network: &NetworkConfig,
+        mut sample: HealthSample,
+    ) -> Option<Option<HealthStatus>> {
+        let mut records = self.records.lock().await;
+
+        let best_head = records
+            .iter()
+            .filter(|(id, record)| **id != network.id && record.chain_id == network.chain_id)
+            .filter_map(|(_, record)| record.history.back())
+            .filter(|head| head.timestamp + HEAD_MAX_AGE_SECS >= sample.timestamp)
+            .filter_map(|head| head.block_number)
+            .max();
+        if let (Some(best_head), Some(height), Some(latency_ms), Some(lag)) = (
+            best_head,
+            sample.block_number,
+            sample.latency_ms,
+            sample.block_lag_secs,
+        ) {
+            let behind = best_head.saturating_sub(height);
+            sample.blocks_behind = Some(behind);
+            sample.status = HealthSample::classify(latency_ms, lag, behind);
+        }
+
+        let record = records
+            .entry(network.id.clone())
+            .or_insert_with(|| HealthRecord {
+                name: network.name.clone(),
+                chain_id: network.chain_id,
+                rpc_url: network.rpc_url.clone(),
+                history: VecDeque::with_capacity(HISTORY_LEN),
+            });
+
+        if record.rpc_url != network.rpc_url || record.chain_id != network.chain_id {
+            record.history.clear();
+            record.rpc_url = network.rpc_url.clone();
+            record.chain_id = network.chain_id;
+        }
+        record.name = network.name.clone();
+
+        let previous = record.history.back().map(|s| s.status);
+        let changed = previous != Some(sample.status);
+
+        if record.history.len() >= HISTORY_LEN {
+            record.history.pop_front();
+        }
+        record.history.push_back(sample);
+
+        changed.then_some(previous)
+    }
+
+    /// Drop records for networks that are no longer configured
+    pub async fn retain(&self, network_ids: &[String]) {
+        self.records
+            .lock()
+            .await
+            .retain(|id, _| network_ids.contains(id));
+    }
+
+    /// Health reports for all probed networks
+    pub async fn reports(&self) -> Vec<NetworkHealthReport> {
+        self.records
+            .lock()
+            .await
+            .iter()
+            .map(|(id, record)| record.to_report(id))
+            .collect()
+    }
+
+    /// Health report for one network
+    pub async fn report(&self, network_id: &str) -> Option<NetworkHealthReport> {
+        self.records
+            .lock()
+            .await
+            .get(network_id)
+            .map(|record| record.to_report(network_id))
+    }
+
+    /// Check whether it is safe to send on a network
+    ///
+    /// Uses the latest sample only; no network request is made.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(None)` - No known problem (or no sample yet)
+    /// * `Ok(Some(warning))` - Send allowed, but the UI should show the warning
+    /// * `Err(WalletError::RpcBehind)` - RPC's block height is too far behind
+    ///   another RPC of the same chain
+    pub async fn check_send(&self, network_id: &str) -> Result<Option<String>, WalletError> {
+        let records = self.records.lock().await;
+        let Some(sample) = records.get(network_id).and_then(|r| r.history.back()) else {
+            return Ok(None);
+        };
+
+        match (sample.status, sample.block_lag_secs) {
+            (HealthStatus::Stale, _) => Err(WalletError::RpcBehind(format!(
+                "{} blocks behind the chain head",
+                sample.blocks_behind.unwrap_or_default()
+            ))),
+            (HealthStatus::Degraded, Some(lag)) if lag >= LAG_WARN_SECS => Ok(Some(format!(
+                "The latest block is {}s old: the RPC node may be behind the chain head, or the chain is idle",
+                lag
+            ))),
+            (HealthStatus::Down, _) => Ok(Some(
+                "The RPC node did not respond to the last health check".to_string(),
+            )),
+            _ => Ok(None),
+        }
+    }
+}
+
+impl Default for NetworkHealthMonitor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Probe an RPC endpoint once
+///
+/// Fetches the latest block and measures round-trip latency and head block lag.
+/// Never fails: errors are reported as a `Down` sample.
+pub async fn probe(rpc_url: &str) -> HealthSample {
+    let timestamp = unix_now();
+
+    let url = match rpc_url.parse::<url::Url>() {
+        Ok(url) => url,
+        Err(e) => return HealthSample::down(timestamp, format!("Invalid RPC URL: {}", e)),
+    };
+    let provider = RootProvider::<Ethereum>::new(RpcClient::new_http(url));
+
+    let start = Instant::now();
+    let block = tokio::time::timeout(
+        PROBE_TIMEOUT,
+        provider.get_block_by_number(BlockNumberOrTag::Latest),
+    )
+    .await;
+    let latency_ms = start.elapsed().as_millis() as u64;
+
+    match block {
+        Ok(Ok(Some(block))) => {
+            let block_lag_secs = unix_now().saturating_sub(block.header.timestamp);
+            HealthSample {
+                timestamp,
+                block_number: Some(block.header.number),
+                latency_ms: Some(latency_ms),
+                block_lag_secs: Some(block_lag_secs),
+                blocks_behind: None,
+                status: HealthSample::classify(latency_ms, block_lag_secs, 0),
+                error: None,
+            }
+        },
+        Ok(Ok(None)) => HealthSample::down(timestamp, "No latest block returned".to_string()),
+        Ok(Err(e)) => HealthSample::down(timestamp, e.to_string()),
+        Err(_) => HealthSample::down(timestamp, "Timed out".to_string()),
+    }
+}
+
+/// Spawn the background network health monitor task.
+///
+/// Probes the active network every 30s and all other configured networks
+/// every 5 minutes.
+pub fn spawn(app_handle: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        info!("[HealthMonitor] Started — active network every 30s, others every 5m");
+        let mut round: u64 = 0;
+
+        loop {
+            let state = app_handle.state::<VaughanState>();
+            let networks = state.networks();
+            let active_id = state.current_network_id().await.ok();
+
+            let ids: Vec<String> = networks.iter().map(|n| n.id.clone()).collect();
+            state.network_health.retain(&ids).await;
+
+            let due: Vec<NetworkConfig> = networks
+                .into_iter()
+                .filter(|n| {
+                    active_id.as_deref() == Some(n.id.as_str())
+                        || round % INACTIVE_PROBE_EVERY == 0
+                })
+                .collect();
+
+            // Probe concurrently so one slow RPC doesn't delay the rest
+            let mut probes = tokio::task::JoinSet::new();
+            for network in due {
+                probes.spawn(async move {
+                    let sample = probe(&network.rpc_url).await;
+                    (network, sample)
+                });
+            }
+
+            while let Some(result) = probes.join_next().await {
+                let Ok((network, sample)) = result else {
+                    continue;
+                };
+                debug!(
+                    "[HealthMonitor] {}: {:?} (block {:?}, {:?}ms, lag {:?}s)",
+                    network.id,
+                    sample.status,
+                    sample.block_number,
+                    sample.latency_ms,
+                    sample.block_lag_secs
+                );
+
+                if let Some(previous) = state.network_health.record(&network, sample.clone()).await
+                {
+                    if previous.is_some() {
+                        warn!(
+                            "[HealthMonitor] {} changed {:?} -> {:?}",
+                            network.id, previous, sample.status
+                        );
+                    }
+                    let _ = NetworkHealthChangedEvent {
+                        network_id: network.id.clone(),
+                        chain_id: network.chain_id,
+                        previous,
+                        current: sample.status,
+                        sample,
+                    }
+                    .emit(&app_handle);
+                }
+            }
+
+            round = round.wrapping_add(1);
+            tokio::time::sleep(PROBE_INTERVAL).await;
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chains::ChainType;
+    use crate::core::TokenInfo;
+
+    fn network(rpc_url: &str) -> NetworkConfig {
+        NetworkConfig {
+            id: "test".to_string(),
+            name: "Test".to_string(),
+            chain_type: ChainType::Evm,
+            chain_id: 1,
+            rpc_url: rpc_url.to_string(),
+            explorer_url: None,
+            native_token: TokenInfo {
+                symbol: "ETH".to_string(),
+                name: "Ether".to_string(),
+                decimals: 18,
+            },
+            is_testnet: false,
+        }
+    }
+
+    fn sample(latency_ms: u64, lag: u64) -> HealthSample {
+        at_height(latency_ms, lag, 1)
+    }
+
+    fn at_height(latency_ms: u64, lag: u64, block_number: u64) -> HealthSample {
+        HealthSample {
+            timestamp: 0,
+            block_number: Some(block_number),
+            latency_ms: Some(latency_ms),
+            block_lag_secs: Some(lag),
+            blocks_behind: None,
+            status: HealthSample::classify(latency_ms, lag, 0),
+            error: None,
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(HealthSample::classify(100, 5, 0), HealthStatus::Healthy);
+        assert_eq!(HealthSample::classify(5_000, 5, 0), HealthStatus::Degraded);
+        assert_eq!(HealthSample::classify(100, LAG_WARN_SECS, 0), HealthStatus::Degraded);
+        // An old head block alone never blocks (idle automine chain, skewed clock)
+        assert_eq!(HealthSample::classify(100, 86_400, 0), HealthStatus::Degraded);
+        assert_eq!(HealthSample::classify(100, 5, BEHIND_BLOCKS), HealthStatus::Stale);
+    }
+
+    #[tokio::test]
+    async fn test_record_reports_status_changes() {
+        let monitor = NetworkHealthMonitor::new();
+        let net = network("https://rpc.example.com");
+
+        assert_eq!(monitor.record(&net, sample(100, 5)).await, Some(None));
+        assert_eq!(monitor.record(&net, sample(120, 6)).await, None);
+        assert_eq!(
+            monitor.record(&net, sample(100, LAG_WARN_SECS)).await,
+            Some(Some(HealthStatus::Healthy))
+        );
+
+        let report = monitor.report("test").await.unwrap();
+        assert_eq!(report.status, HealthStatus::Degraded);
+        assert_eq!(report.history.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_history_is_bounded_and_reset_on_rpc_change() {
+        let monitor = NetworkHealthMonitor::new();
+        let net = network("https://rpc.example.com");
+
+        for _ in 0..HISTORY_LEN + 10 {
+            monitor.record(&net, sample(100, 5)).await;
+        }
+        assert_eq!(monitor.report("test").await.unwrap().history.len(), HISTORY_LEN);
+
+        monitor
+            .record(&network("https://other.example.com"), sample(100, 5))
+            .await;
+        assert_eq!(monitor.report("test").await.unwrap().history.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_check_send() {
+        let monitor = NetworkHealthMonitor::new();
+        let net = network("https://rpc.example.com");
+
+        // No samples yet: allowed
+        assert!(monitor.check_send("test").await.unwrap().is_none());
+
+        monitor.record(&net, sample(100, 5)).await;
+        assert!(monitor.check_send("test").await.unwrap().is_none());
+
+        monitor.record(&net, sample(100, LAG_WARN_SECS + 1)).await;
+        assert!(monitor.check_send("test").await.unwrap().is_some());
+
+        // An idle chain only warns, however old its head block is
+        monitor.record(&net, sample(100, 86_400)).await;
+        assert!(monitor.check_send("test").await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_check_send_blocks_rpc_behind_same_chain() {
+        let monitor = NetworkHealthMonitor::new();
+        let net = network("https://rpc.example.com");
+        let mut other = network("https://other.example.com");
+        other.id = "other".to_string();
+
+        monitor.record(&other, at_height(100, 5, 1_000)).await;
+        monitor.record(&net, at_height(100, 5, 1_000 - BEHIND_BLOCKS + 1)).await;
+        assert!(monitor.check_send("test").await.unwrap().is_none());
+
+        monitor.record(&net, at_height(100, 5, 1_000 - BEHIND_BLOCKS)).await;
+        let report = monitor.report("test").await.unwrap();
+        assert_eq!(report.status, HealthStatus::Stale);
+        assert_eq!(report.latest.unwrap().blocks_behind, Some(BEHIND_BLOCKS));
+        assert!(matches!(monitor.check_send("test").await, Err(WalletError::RpcBehind(_))));
+
+        // Another chain's head is not compared against
+        let mut unrelated = network("https://unrelated.example.com");
+        unrelated.id = "unrelated".to_string();
+        unrelated.chain_id = 2;
+        monitor.record(&unrelated, at_height(100, 5, 1)).await;
+        assert_eq!(monitor.report("unrelated").await.unwrap().status, HealthStatus::Healthy);
+    }
+}
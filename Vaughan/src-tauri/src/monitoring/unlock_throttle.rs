@@ -0,0 +1,95 @@
+//! Unlock Throttle
+//!
+//! Keeps the wrong password count of `WalletService::verify_password`,
+//! which every command taking the wallet password goes through:
+//!
+//! - Attempts run one at a time
+//! - Wrong passwords are counted in their own file next to the persisted
+//!   state (nothing else writes it) and delay the next attempt
+//!   exponentially (`security::throttle`); a correct one clears the count
+//! - From `ALERT_AFTER` wrong passwords in a row, each one plays the
+//!   `SecurityAlert` sound and emits a `SecurityAlertEvent`
+//! - With `UserPreferences::wipe_after_failed_unlocks` set, the wallet is
+//!   wiped after that many wrong passwords in a row
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use tauri::{AppHandle, Manager};
+use tauri_specta::Event;
+use tracing::{info, warn};
+
+use crate::audio::AlertSound;
+use crate::error::WalletError;
+use crate::security::throttle::{PasswordAttempts, UnlockThrottle};
+use crate::state::VaughanState;
+
+/// Wrong passwords in a row before each failure raises an alert
+pub const ALERT_AFTER: u32 = 3;
+
+/// Typed event emitted on repeated wrong passwords
+#[derive(Debug, Clone, Serialize, Deserialize, Type, tauri_specta::Event)]
+pub struct SecurityAlertEvent {
+    /// Wrong passwords in a row
+    pub failed_attempts: u32,
+    /// Seconds before the next attempt is allowed
+    pub retry_after_secs: u64,
+    /// The wallet was wiped (`wipe_after_failed_unlocks` reached)
+    pub wallet_wiped: bool,
+}
+
+/// Wrong password count persisted by the `StateManager`, with alerts and
+/// the optional wipe
+///
+/// Installed on the `WalletService` at startup, so it applies to every
+/// password check.
+pub struct PersistedAttempts {
+    app: AppHandle,
+}
+
+impl PersistedAttempts {
+    pub fn new(app: AppHandle) -> Self {
+        Self { app }
+    }
+}
+
+#[async_trait]
+impl PasswordAttempts for PersistedAttempts {
+    fn load(&self) -> UnlockThrottle {
+        self.app.state::<VaughanState>().state_manager().load_unlock_throttle()
+    }
+
+    fn save(&self, throttle: &UnlockThrottle) -> Result<(), WalletError> {
+        self.app.state::<VaughanState>().state_manager().save_unlock_throttle(throttle)
+    }
+
+    async fn on_failure(&self, throttle: &UnlockThrottle, retry_after_secs: u64) {
+        let state = self.app.state::<VaughanState>();
+        let failed_attempts = throttle.failed_attempts;
+        warn!(
+            "[UnlockThrottle] Wrong password ({} in a row, next attempt in {}s)",
+            failed_attempts, retry_after_secs
+        );
+
+        let wipe_after = state.state_manager().load().preferences.wipe_after_failed_unlocks;
+        let wallet_wiped = wipe_after > 0 && failed_attempts >= wipe_after;
+        if wallet_wiped {
+            match state.wipe_wallet().await {
+                Ok(()) => warn!("[UnlockThrottle] Wallet wiped after {} wrong passwords", failed_attempts),
+                Err(e) => warn!("[UnlockThrottle] Wiping the wallet failed: {}", e),
+            }
+        }
+
+        if failed_attempts >= ALERT_AFTER || wallet_wiped {
+            if let Err(e) = state.sound_player.play(AlertSound::SecurityAlert) {
+                info!("[UnlockThrottle] Could not play alert: {}", e);
+            }
+            let _ = SecurityAlertEvent {
+                failed_attempts,
+                retry_after_secs,
+                wallet_wiped,
+            }
+            .emit(&self.app);
+        }
+    }
+}
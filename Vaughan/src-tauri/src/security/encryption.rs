@@ -10,6 +10,9 @@
 //! - **AES-256-GCM**: Authenticated encryption with associated data
 //! - **Secure Random**: Uses OS-provided randomness
 //! - **No Custom Crypto**: Uses audited crates only
+//! - **Versioned Envelope**: Ciphertexts record their version, KDF and
+//!   parameters, so stronger settings can be adopted without breaking old
+//!   data (`needs_upgrade` tells which blobs to re-encrypt)
 //!
 //! ## Usage
 //!
@@ -31,7 +34,7 @@
 
 use crate::error::WalletError;
 use aes_gcm::{
-    aead::{Aead, KeyInit, OsRng},
+    aead::{Aead, KeyInit, OsRng, Payload},
     Aes256Gcm, Nonce,
 };
 use argon2::{
@@ -39,6 +42,7 @@ use argon2::{
     Argon2,
 };
 use rand::RngCore;
+use secrecy::zeroize::Zeroizing;
 
 /// Hash a password using Argon2id
 ///
@@ -71,91 +75,231 @@ pub fn verify_password(password: &str, hash: &str) -> Result<(), WalletError> {
         .map_err(|_| WalletError::InvalidPassword)
 }
 
-/// Derive an encryption key from a password using Argon2
+/// Argon2id cost parameters of an encryption envelope
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KdfParams {
+    /// Memory in KiB
+    pub m_cost: u32,
+    /// Iterations
+    pub t_cost: u32,
+    /// Parallelism
+    pub p_cost: u32,
+}
+
+/// Parameters of blobs written before the envelope existed
+pub const LEGACY_KDF: KdfParams = KdfParams { m_cost: 19456, t_cost: 2, p_cost: 1 };
+
+/// Parameters for new encryptions (RFC 9106, 64 MiB)
+pub const CURRENT_KDF: KdfParams = KdfParams { m_cost: 65536, t_cost: 3, p_cost: 1 };
+
+/// Upper bounds accepted when reading an envelope (256 MiB, a few times
+/// `CURRENT_KDF`), so a crafted header cannot make an unlock attempt
+/// allocate or spin far beyond what a real one costs
+const MAX_KDF: KdfParams = KdfParams { m_cost: 1 << 18, t_cost: 8, p_cost: 4 };
+
+/// First bytes of every envelope
+const ENVELOPE_MAGIC: &[u8; 4] = b"VAUG";
+
+/// Current envelope version
+pub const ENVELOPE_VERSION: u8 = 1;
+
+const KDF_ARGON2ID: u8 = 1;
+const CIPHER_AES_256_GCM: u8 = 1;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const TAG_LEN: usize = 16;
+
+/// magic, version, KDF id, 3 x u32 KDF params, cipher id
+const HEADER_LEN: usize = 4 + 1 + 1 + 12 + 1;
+
+/// How a blob was encrypted
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvelopeFormat {
+    /// `[salt][nonce][ciphertext + tag]` with `LEGACY_KDF`
+    Legacy,
+    /// Versioned envelope with its own KDF parameters
+    Envelope { version: u8, kdf: KdfParams },
+}
+
+/// Derive an encryption key from a password using Argon2id
 ///
 /// Returns a 32-byte key suitable for AES-256.
-fn derive_key(password: &str, salt: &[u8]) -> Result<[u8; 32], WalletError> {
+fn derive_key(password: &str, salt: &[u8], kdf: &KdfParams) -> Result<Zeroizing<[u8; 32]>, WalletError> {
     use argon2::Params;
 
-    let params = Params::new(19456, 2, 1, Some(32))
+    let params = Params::new(kdf.m_cost, kdf.t_cost, kdf.p_cost, Some(32))
         .map_err(|e| WalletError::EncryptionFailed(format!("Invalid Argon2 params: {}", e)))?;
 
     let argon2 = Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params);
 
-    let mut key = [0u8; 32];
+    let mut key = Zeroizing::new([0u8; 32]);
     argon2
-        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .hash_password_into(password.as_bytes(), salt, &mut key[..])
         .map_err(|e| WalletError::EncryptionFailed(format!("Key derivation failed: {}", e)))?;
 
     Ok(key)
 }
 
-/// Encrypt data using AES-256-GCM with password-derived key
+/// Encrypt data using AES-256-GCM with a password-derived key
 ///
-/// Format: [salt (16 bytes)][nonce (12 bytes)][ciphertext + tag]
+/// Envelope format (version 1):
 ///
-/// The salt is used for key derivation, and the nonce is used for encryption.
-/// Both are randomly generated and prepended to the ciphertext.
+/// ```text
+/// ["VAUG"][version: u8][KDF id: u8][m_cost, t_cost, p_cost: u32 BE][cipher id: u8]
+/// [salt (16 bytes)][nonce (12 bytes)][ciphertext + tag]
+/// ```
+///
+/// The salt is used for key derivation, and the nonce is used for
+/// encryption. Everything before the ciphertext is authenticated as
+/// associated data, so the header cannot be altered.
 pub fn encrypt_data(plaintext: &[u8], password: &str) -> Result<Vec<u8>, WalletError> {
+    encrypt_with(plaintext, password, &CURRENT_KDF)
+}
+
+/// `encrypt_data` with given KDF parameters
+pub(crate) fn encrypt_with(plaintext: &[u8], password: &str, kdf: &KdfParams) -> Result<Vec<u8>, WalletError> {
     // Generate random salt for key derivation
-    let mut salt = [0u8; 16];
+    let mut salt = [0u8; SALT_LEN];
     OsRng.fill_bytes(&mut salt);
 
     // Derive encryption key from password
-    let key = derive_key(password, &salt)?;
+    let key = derive_key(password, &salt, kdf)?;
 
     // Create cipher
-    let cipher = Aes256Gcm::new_from_slice(&key)
+    let cipher = Aes256Gcm::new_from_slice(&key[..])
         .map_err(|e| WalletError::EncryptionFailed(format!("Cipher creation failed: {}", e)))?;
 
     // Generate random nonce
-    let mut nonce_bytes = [0u8; 12];
+    let mut nonce_bytes = [0u8; NONCE_LEN];
     OsRng.fill_bytes(&mut nonce_bytes);
     #[allow(deprecated)]
     let nonce = Nonce::from_slice(&nonce_bytes);
 
+    // Header + salt + nonce, authenticated with the ciphertext
+    let mut result = Vec::with_capacity(HEADER_LEN + SALT_LEN + NONCE_LEN + plaintext.len() + TAG_LEN);
+    result.extend_from_slice(ENVELOPE_MAGIC);
+    result.push(ENVELOPE_VERSION);
+    result.push(KDF_ARGON2ID);
+    result.extend_from_slice(&kdf.m_cost.to_be_bytes());
+    result.extend_from_slice(&kdf.t_cost.to_be_bytes());
+    result.extend_from_slice(&kdf.p_cost.to_be_bytes());
+    result.push(CIPHER_AES_256_GCM);
+    result.extend_from_slice(&salt);
+    result.extend_from_slice(&nonce_bytes);
+
     // Encrypt
     let ciphertext = cipher
-        .encrypt(nonce, plaintext)
+        .encrypt(nonce, Payload { msg: plaintext, aad: &result })
         .map_err(|e| WalletError::EncryptionFailed(format!("Encryption failed: {}", e)))?;
-
-    // Combine: salt + nonce + ciphertext
-    let mut result = Vec::with_capacity(salt.len() + nonce_bytes.len() + ciphertext.len());
-    result.extend_from_slice(&salt);
-    result.extend_from_slice(&nonce_bytes);
     result.extend_from_slice(&ciphertext);
 
     Ok(result)
 }
 
-/// Decrypt data using AES-256-GCM with password-derived key
+/// Decrypt data using AES-256-GCM with a password-derived key
 ///
-/// Expects format: [salt (16 bytes)][nonce (12 bytes)][ciphertext + tag]
+/// Reads the envelope written by `encrypt_data` as well as legacy
+/// `[salt][nonce][ciphertext + tag]` blobs.
 pub fn decrypt_data(encrypted: &[u8], password: &str) -> Result<Vec<u8>, WalletError> {
+    if !encrypted.starts_with(ENVELOPE_MAGIC) {
+        return decrypt_legacy(encrypted, password);
+    }
+    match parse_header(encrypted) {
+        Ok(kdf) => decrypt_envelope(encrypted, password, &kdf),
+        // A legacy blob whose random salt happens to start with the magic
+        Err(e) => decrypt_legacy(encrypted, password).map_err(|_| e),
+    }
+}
+
+/// How `encrypted` was encrypted, `None` if it cannot be decrypted by
+/// this version (unknown envelope version, KDF or cipher)
+pub fn envelope_format(encrypted: &[u8]) -> Option<EnvelopeFormat> {
+    if !encrypted.starts_with(ENVELOPE_MAGIC) {
+        return Some(EnvelopeFormat::Legacy);
+    }
+    match parse_header(encrypted) {
+        Ok(kdf) => Some(EnvelopeFormat::Envelope { version: encrypted[4], kdf }),
+        Err(_) => None,
+    }
+}
+
+/// Whether `encrypted` should be re-encrypted with `encrypt_data` to get
+/// the current envelope version and KDF parameters
+pub fn needs_upgrade(encrypted: &[u8]) -> bool {
+    match envelope_format(encrypted) {
+        Some(EnvelopeFormat::Legacy) => true,
+        Some(EnvelopeFormat::Envelope { version, kdf }) => version < ENVELOPE_VERSION || kdf != CURRENT_KDF,
+        None => false,
+    }
+}
+
+/// KDF parameters of a supported envelope header
+fn parse_header(encrypted: &[u8]) -> Result<KdfParams, WalletError> {
+    if encrypted.len() < HEADER_LEN + SALT_LEN + NONCE_LEN + TAG_LEN {
+        return Err(WalletError::DecryptionFailed("Encrypted data too short".to_string()));
+    }
+    if encrypted[4] != ENVELOPE_VERSION {
+        return Err(WalletError::DecryptionFailed(format!(
+            "Unsupported encryption version {}",
+            encrypted[4]
+        )));
+    }
+    if encrypted[5] != KDF_ARGON2ID || encrypted[18] != CIPHER_AES_256_GCM {
+        return Err(WalletError::DecryptionFailed(
+            "Unsupported key derivation or cipher".to_string(),
+        ));
+    }
+
+    let read_u32 = |at: usize| u32::from_be_bytes([encrypted[at], encrypted[at + 1], encrypted[at + 2], encrypted[at + 3]]);
+    let kdf = KdfParams { m_cost: read_u32(6), t_cost: read_u32(10), p_cost: read_u32(14) };
+    if kdf.m_cost > MAX_KDF.m_cost || kdf.t_cost > MAX_KDF.t_cost || kdf.p_cost > MAX_KDF.p_cost {
+        return Err(WalletError::DecryptionFailed(
+            "Key derivation parameters out of range".to_string(),
+        ));
+    }
+    Ok(kdf)
+}
+
+fn decrypt_envelope(encrypted: &[u8], password: &str, kdf: &KdfParams) -> Result<Vec<u8>, WalletError> {
+    let (aad, ciphertext) = encrypted.split_at(HEADER_LEN + SALT_LEN + NONCE_LEN);
+    let salt = &aad[HEADER_LEN..HEADER_LEN + SALT_LEN];
+    let nonce_bytes = &aad[HEADER_LEN + SALT_LEN..];
+    decrypt_with(password, salt, nonce_bytes, Payload { msg: ciphertext, aad }, kdf)
+}
+
+/// Decrypt a pre-envelope blob
+///
+/// Expects format: [salt (16 bytes)][nonce (12 bytes)][ciphertext + tag]
+fn decrypt_legacy(encrypted: &[u8], password: &str) -> Result<Vec<u8>, WalletError> {
     // Validate minimum length
-    if encrypted.len() < 16 + 12 + 16 {
+    if encrypted.len() < SALT_LEN + NONCE_LEN + TAG_LEN {
         return Err(WalletError::DecryptionFailed(
             "Encrypted data too short".to_string(),
         ));
     }
 
     // Extract salt, nonce, and ciphertext
-    let salt = &encrypted[0..16];
-    let nonce_bytes = &encrypted[16..28];
-    let ciphertext = &encrypted[28..];
+    let salt = &encrypted[0..SALT_LEN];
+    let nonce_bytes = &encrypted[SALT_LEN..SALT_LEN + NONCE_LEN];
+    let ciphertext = &encrypted[SALT_LEN + NONCE_LEN..];
+
+    decrypt_with(password, salt, nonce_bytes, Payload { msg: ciphertext, aad: &[] }, &LEGACY_KDF)
+}
 
+fn decrypt_with(password: &str, salt: &[u8], nonce_bytes: &[u8], payload: Payload<'_, '_>, kdf: &KdfParams) -> Result<Vec<u8>, WalletError> {
     // Derive encryption key from password
-    let key = derive_key(password, salt)?;
+    let key = derive_key(password, salt, kdf)?;
 
     // Create cipher
-    let cipher = Aes256Gcm::new_from_slice(&key)
+    let cipher = Aes256Gcm::new_from_slice(&key[..])
         .map_err(|e| WalletError::DecryptionFailed(format!("Cipher creation failed: {}", e)))?;
 
     #[allow(deprecated)]
     let nonce = Nonce::from_slice(nonce_bytes);
 
     // Decrypt
-    let plaintext = cipher.decrypt(nonce, ciphertext).map_err(|_| {
+    let plaintext = cipher.decrypt(nonce, payload).map_err(|_| {
         WalletError::DecryptionFailed("Decryption failed (wrong password?)".to_string())
     })?;
 
@@ -245,17 +389,84 @@ mod tests {
         let salt = [42u8; 16];
 
         // Derive key twice with same inputs
-        let key1 = derive_key(password, &salt).unwrap();
-        let key2 = derive_key(password, &salt).unwrap();
+        let key1 = derive_key(password, &salt, &LEGACY_KDF).unwrap();
+        let key2 = derive_key(password, &salt, &LEGACY_KDF).unwrap();
 
         // Keys should be identical
         assert_eq!(key1, key2);
 
         // Different salt should produce different key
         let different_salt = [43u8; 16];
-        let key3 = derive_key(password, &different_salt).unwrap();
+        let key3 = derive_key(password, &different_salt, &LEGACY_KDF).unwrap();
         assert_ne!(key1, key3);
 
         println!("✅ Key derivation is deterministic");
     }
+
+    /// Blob as written before the envelope existed
+    fn encrypt_legacy(plaintext: &[u8], password: &str) -> Vec<u8> {
+        let salt = [7u8; SALT_LEN];
+        let nonce_bytes = [9u8; NONCE_LEN];
+        let key = derive_key(password, &salt, &LEGACY_KDF).unwrap();
+        let cipher = Aes256Gcm::new_from_slice(&key[..]).unwrap();
+        #[allow(deprecated)]
+        let ciphertext = cipher.encrypt(Nonce::from_slice(&nonce_bytes), plaintext).unwrap();
+        [&salt[..], &nonce_bytes[..], &ciphertext[..]].concat()
+    }
+
+    #[test]
+    fn test_envelope_and_legacy_blobs() {
+        let password = "password";
+        let current = encrypt_data(b"secret", password).unwrap();
+        assert_eq!(
+            envelope_format(&current),
+            Some(EnvelopeFormat::Envelope { version: ENVELOPE_VERSION, kdf: CURRENT_KDF })
+        );
+        assert!(!needs_upgrade(&current));
+
+        // Legacy blobs still decrypt and are flagged for re-encryption
+        let legacy = encrypt_legacy(b"secret", password);
+        assert_eq!(envelope_format(&legacy), Some(EnvelopeFormat::Legacy));
+        assert!(needs_upgrade(&legacy));
+        assert_eq!(decrypt_data(&legacy, password).unwrap(), b"secret");
+        assert!(decrypt_data(&legacy, "wrong").is_err());
+
+        // So do envelopes with older parameters
+        let weaker = encrypt_with(b"secret", password, &LEGACY_KDF).unwrap();
+        assert!(needs_upgrade(&weaker));
+        assert_eq!(decrypt_data(&weaker, password).unwrap(), b"secret");
+
+        println!("✅ Envelope and legacy blobs decrypt");
+    }
+
+    #[test]
+    fn test_envelope_header_is_authenticated() {
+        let password = "password";
+        let encrypted = encrypt_with(b"secret", password, &LEGACY_KDF).unwrap();
+
+        // Different parameters: wrong key, so authentication fails
+        let mut tampered = encrypted.clone();
+        tampered[13] = 3; // t_cost 2 -> 3
+        assert!(decrypt_data(&tampered, password).is_err());
+
+        // Unknown version or parameters out of range are refused up front
+        let mut future = encrypted.clone();
+        future[4] = ENVELOPE_VERSION + 1;
+        assert_eq!(envelope_format(&future), None);
+        assert!(!needs_upgrade(&future));
+        assert!(decrypt_data(&future, password).is_err());
+
+        let out_of_range = |at: usize, value: u32| {
+            let mut crafted = encrypted.clone();
+            crafted[at..at + 4].copy_from_slice(&value.to_be_bytes());
+            matches!(decrypt_data(&crafted, password), Err(WalletError::DecryptionFailed(e)) if e.contains("out of range"))
+        };
+        assert!(out_of_range(6, u32::MAX));
+        assert!(out_of_range(6, MAX_KDF.m_cost + 1));
+        assert!(out_of_range(10, MAX_KDF.t_cost + 1));
+        assert!(out_of_range(14, MAX_KDF.p_cost + 1));
+        assert!(!out_of_range(10, MAX_KDF.t_cost));
+
+        println!("✅ Envelope header is authenticated");
+    }
 }
@@ -12,7 +12,9 @@
 //!
 //! ## Derivation Path
 //!
-//! We use the standard Ethereum derivation path:
+//! By default we use the standard Ethereum derivation path (`derive_account`);
+//! `derive_path` takes any path, e.g. Ledger Live `m/44'/60'/x'/0/0` or
+//! legacy MEW `m/44'/60'/0'/x`:
 //! ```text
 //! m/44'/60'/0'/0/x
 //! │  │   │   │  │  └─ Address index
@@ -119,16 +121,33 @@ pub fn mnemonic_to_seed(mnemonic: &str, passphrase: Option<&str>) -> Result<Vec<
     Ok(seed.to_vec())
 }
 
-/// A PrivateKeySigner
+/// Derive the account at `index` on the standard path `m/44'/60'/0'/0/{index}`
 pub fn derive_account(seed: &[u8], index: u32) -> Result<PrivateKeySigner, WalletError> {
+    derive_path(seed, &format!("m/44'/60'/0'/0/{}", index))
+}
+
+/// Check a BIP-32 derivation path (e.g. `m/44'/60'/0'/0/0`)
+pub fn validate_derivation_path(path: &str) -> Result<(), WalletError> {
+    if !path.starts_with("m/") {
+        return Err(WalletError::KeyDerivationFailed(format!(
+            "Derivation path must start with m/: {}",
+            path
+        )));
+    }
+    DerivationPath::from_str(path)
+        .map(|_| ())
+        .map_err(|e| WalletError::KeyDerivationFailed(format!("Invalid derivation path: {}", e)))
+}
+
+/// Derive the account at any BIP-32 path
+pub fn derive_path(seed: &[u8], path: &str) -> Result<PrivateKeySigner, WalletError> {
     // Create master key from seed
     let master_key = XPriv::root_from_seed(seed, None).map_err(|e| {
         WalletError::KeyDerivationFailed(format!("Master key creation failed: {}", e))
     })?;
 
-    // Standard Ethereum derivation path: m/44'/60'/0'/0/{index}
-    let path = format!("m/44'/60'/0'/0/{}", index);
-    let derivation_path = DerivationPath::from_str(&path)
+    validate_derivation_path(path)?;
+    let derivation_path = DerivationPath::from_str(path)
         .map_err(|e| WalletError::KeyDerivationFailed(format!("Invalid derivation path: {}", e)))?;
 
     // Derive the key
@@ -335,4 +354,21 @@ mod tests {
 
         println!("✅ Different indices produce different accounts");
     }
+
+    #[test]
+    fn test_derive_path_schemes() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let seed = mnemonic_to_seed(mnemonic, None).unwrap();
+
+        let bip44 = derive_path(&seed, "m/44'/60'/0'/0/0").unwrap();
+        assert_eq!(bip44.address(), derive_account(&seed, 0).unwrap().address());
+
+        let ledger_live_1 = derive_path(&seed, "m/44'/60'/1'/0/0").unwrap();
+        let legacy_mew_0 = derive_path(&seed, "m/44'/60'/0'/0").unwrap();
+        assert_ne!(ledger_live_1.address(), bip44.address());
+        assert_ne!(legacy_mew_0.address(), bip44.address());
+
+        assert!(derive_path(&seed, "44'/60'/0'/0/0").is_err());
+        assert!(derive_path(&seed, "m/44'/sixty/0").is_err());
+    }
 }
@@ -1,15 +1,16 @@
 //! Keyring Service Module
 //!
-//! Provides secure key storage using the OS keychain:
-//! - **Windows**: Credential Manager
-//! - **macOS**: Keychain
-//! - **Linux**: Secret Service API (libsecret)
+//! Provides password-encrypted key storage on top of a `SecretStore`
+//! (see `secret_store`):
+//! - **OS keychain**: Windows Credential Manager, macOS Keychain, Linux
+//!   Secret Service API (libsecret)
+//! - **Encrypted file vault**: when no keychain is available (headless Linux, CI)
+//! - **In-memory**: tests
 //!
 //! ## Security Properties
 //!
-//! - Keys stored in OS-provided secure storage
-//! - Keys encrypted at rest by the OS
-//! - Keys protected by user authentication
+//! - Keys encrypted with the wallet password before they reach any store
+//! - Keys additionally encrypted at rest by the OS when using the keychain
 //! - Keys never written to disk in plaintext
 //!
 //! ## Usage
@@ -17,7 +18,7 @@
 //! ```rust,ignore
 //! use vaughan_lib::security::KeyringService;
 //!
-//! // Create service
+//! // Create service (backend selected automatically)
 //! let keyring = KeyringService::new("vaughan-wallet")?;
 //!
 //! // Store a key
@@ -31,34 +32,249 @@
 //! ```
 
 use crate::error::WalletError;
-use crate::security::encryption::{decrypt_data, encrypt_data};
-use keyring::Entry;
+use crate::security::encryption::{decrypt_data, encrypt_data, needs_upgrade};
+use crate::security::secret_store::{self, OsKeyringStore, SecretBackend, SecretStore};
+use secrecy::zeroize::Zeroizing;
 use secrecy::Secret;
+use serde::{Deserialize, Serialize};
+use std::sync::{Arc, RwLock};
+use tracing::{info, warn};
 
 #[cfg(test)]
 use secrecy::ExposeSecret;
 
-/// KeyringService provides secure key storage using the OS keychain
+/// Entry recording an unfinished password change
+const PASSWORD_CHANGE_JOURNAL: &str = "vaughan_password_change";
+
+/// Keys being re-encrypted, and whether the change is past the point of
+/// no return (`committed`: finish it; otherwise: discard it)
+#[derive(Debug, Serialize, Deserialize)]
+struct PasswordChangeJournal {
+    committed: bool,
+    key_ids: Vec<String>,
+}
+
+/// Where the re-encrypted copy of a key waits during a password change
+fn staged_key_id(key_id: &str) -> String {
+    format!("{}.pending", key_id)
+}
+
+/// KeyringService provides secure key storage on a pluggable secret store
 ///
-/// Keys are encrypted with a password before being stored in the keychain,
+/// Keys are encrypted with a password before being stored,
 /// providing defense-in-depth security.
 pub struct KeyringService {
     service_name: String,
+    store: RwLock<Arc<dyn SecretStore>>,
 }
 
 impl KeyringService {
-    /// Create a new KeyringService
+    /// Create a new KeyringService with an automatically selected store
     ///
     /// # Arguments
     ///
     /// * `service_name` - The service name for keychain entries (e.g., "vaughan-wallet")
+    ///
+    /// # Errors
+    ///
+    /// * `WalletError::KeyringError` - No store can safely be used (see
+    ///   `secret_store::select_store`)
     pub fn new(service_name: impl Into<String>) -> Result<Self, WalletError> {
-        Ok(Self {
+        let service_name = service_name.into();
+        let store = secret_store::select_store(&service_name)?;
+        let keyring = Self::with_store(service_name, store);
+        if let Err(e) = keyring.recover_password_change() {
+            warn!("[Keyring] Could not recover an interrupted password change: {}", e);
+        }
+        Ok(keyring)
+    }
+
+    /// Create a new KeyringService on a specific store
+    pub fn with_store(service_name: impl Into<String>, store: Arc<dyn SecretStore>) -> Self {
+        Self {
             service_name: service_name.into(),
-        })
+            store: RwLock::new(store),
+        }
+    }
+
+    fn store(&self) -> Arc<dyn SecretStore> {
+        match self.store.read() {
+            Ok(store) => store.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    /// Backend the keys are currently stored in
+    pub fn backend(&self) -> SecretBackend {
+        self.store().backend()
+    }
+
+    /// Whether the OS keychain works on this system
+    pub fn os_keyring_available(&self) -> bool {
+        OsKeyringStore::new(self.service_name.as_str()).is_available()
+    }
+
+    /// Move keys to another backend and switch to it
+    ///
+    /// Keys are copied and verified before they are removed from the
+    /// current backend; on error nothing is switched.
+    ///
+    /// # Arguments
+    ///
+    /// * `backend` - Target backend
+    /// * `key_ids` - Keys to move (missing keys are skipped)
+    ///
+    /// # Returns
+    ///
+    /// Number of keys moved
+    pub fn migrate_to(&self, backend: SecretBackend, key_ids: &[String]) -> Result<usize, WalletError> {
+        let current = self.store();
+        if current.backend() == backend {
+            return Ok(0);
+        }
+
+        let target = secret_store::open_store(backend, &self.service_name)?;
+        let moved = secret_store::migrate(current.as_ref(), target.as_ref(), key_ids)?;
+
+        match self.store.write() {
+            Ok(mut store) => *store = target,
+            Err(poisoned) => *poisoned.into_inner() = target,
+        }
+        info!("[Keyring] Moved {} keys from {:?} to {:?}", moved, current.backend(), backend);
+        Ok(moved)
+    }
+
+    /// Re-encrypt keys under a new password, all or nothing
+    ///
+    /// Every key is decrypted and re-encrypted in memory first, so a wrong
+    /// password or a corrupt entry changes nothing. The new entries are then
+    /// staged next to the live ones and swapped in under a journal: a failed
+    /// write puts the old entries back, and a change interrupted by a crash
+    /// is finished or discarded by `recover_password_change`. Either way
+    /// all keys end up under the same password.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_ids` - Keys to re-encrypt (missing keys are skipped)
+    ///
+    /// # Returns
+    ///
+    /// Number of keys re-encrypted
+    pub fn change_password(&self, key_ids: &[String], old_password: &str, new_password: &str) -> Result<usize, WalletError> {
+        self.recover_password_change()?;
+        let store = self.store();
+
+        // 1. Re-encrypt in memory
+        let mut staged = Vec::new();
+        for key_id in key_ids {
+            let Some(previous) = store.get(key_id)? else { continue };
+            let encrypted = base64::decode(&previous)
+                .map_err(|e| WalletError::KeyringError(format!("Invalid base64 encoding: {}", e)))?;
+            let plaintext = Zeroizing::new(decrypt_data(&encrypted, old_password)?);
+            let updated = base64::encode(&encrypt_data(&plaintext, new_password)?);
+            staged.push((key_id.clone(), previous, updated));
+        }
+        let staged_ids: Vec<String> = staged.iter().map(|(key_id, _, _)| key_id.clone()).collect();
+
+        // 2. Stage the new entries; the live ones are untouched
+        let mut journal = PasswordChangeJournal { committed: false, key_ids: staged_ids };
+        let written = write_journal(store.as_ref(), &journal).and_then(|_| {
+            staged
+                .iter()
+                .try_for_each(|(key_id, _, updated)| store.set(&staged_key_id(key_id), updated))
+        });
+        if let Err(e) = written {
+            discard_staged(store.as_ref(), &journal.key_ids);
+            return Err(e);
+        }
+
+        // 3. Commit: from here an interrupted change is finished on restart
+        journal.committed = true;
+        if let Err(e) = write_journal(store.as_ref(), &journal) {
+            discard_staged(store.as_ref(), &journal.key_ids);
+            return Err(e);
+        }
+
+        // 4. Swap the new entries in
+        for (i, (key_id, _, updated)) in staged.iter().enumerate() {
+            if let Err(e) = store.set(key_id, updated) {
+                // Entry `i` was not written
+                let restored = staged[..i]
+                    .iter()
+                    .all(|(key_id, previous, _)| store.set(key_id, previous).is_ok());
+                journal.committed = false;
+                if restored && write_journal(store.as_ref(), &journal).is_ok() {
+                    discard_staged(store.as_ref(), &journal.key_ids);
+                } else {
+                    warn!("[Keyring] Password change failed and could not be undone; it will be finished on restart");
+                }
+                return Err(e);
+            }
+        }
+        discard_staged(store.as_ref(), &journal.key_ids);
+
+        info!("[Keyring] Re-encrypted {} keys under the new password", staged.len());
+        Ok(staged.len())
     }
 
-    /// Store a key in the OS keychain
+    /// Finish (if committed) or discard a password change that was
+    /// interrupted, so no key is left under the other password
+    pub fn recover_password_change(&self) -> Result<(), WalletError> {
+        let store = self.store();
+        let Some(journal) = store.get(PASSWORD_CHANGE_JOURNAL)? else {
+            return Ok(());
+        };
+        let journal: PasswordChangeJournal = serde_json::from_str(&journal)
+            .map_err(|e| WalletError::KeyringError(format!("Invalid password change journal: {}", e)))?;
+
+        if journal.committed {
+            for key_id in &journal.key_ids {
+                if let Some(updated) = store.get(&staged_key_id(key_id))? {
+                    store.set(key_id, &updated)?;
+                }
+            }
+            info!("[Keyring] Finished an interrupted password change");
+        } else {
+            info!("[Keyring] Discarded an interrupted password change");
+        }
+        discard_staged(store.as_ref(), &journal.key_ids);
+        Ok(())
+    }
+
+    /// Re-encrypt keys written with an older encryption envelope or KDF
+    /// parameters (see `encryption::needs_upgrade`) with the current ones
+    ///
+    /// The password stays the same, so a key is readable whether or not it
+    /// was upgraded yet and no journal is needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `key_ids` - Keys to check (missing keys are skipped)
+    ///
+    /// # Returns
+    ///
+    /// Number of keys re-encrypted
+    pub fn upgrade_encryption(&self, key_ids: &[String], password: &str) -> Result<usize, WalletError> {
+        let store = self.store();
+        let mut upgraded = 0;
+        for key_id in key_ids {
+            let Some(encoded) = store.get(key_id)? else { continue };
+            let encrypted = base64::decode(&encoded)
+                .map_err(|e| WalletError::KeyringError(format!("Invalid base64 encoding: {}", e)))?;
+            if !needs_upgrade(&encrypted) {
+                continue;
+            }
+            let plaintext = Zeroizing::new(decrypt_data(&encrypted, password)?);
+            store.set(key_id, &base64::encode(&encrypt_data(&plaintext, password)?))?;
+            upgraded += 1;
+        }
+        if upgraded > 0 {
+            info!("[Keyring] Re-encrypted {} keys with the current encryption parameters", upgraded);
+        }
+        Ok(upgraded)
+    }
+
+    /// Store a key in the secret store
     ///
     /// The key is encrypted with the password before storage.
     ///
@@ -79,19 +295,11 @@ impl KeyringService {
         // Encode as base64 for storage
         let encoded = base64::encode(&encrypted);
 
-        // Store in keychain
-        let entry = Entry::new(&self.service_name, key_id).map_err(|e| {
-            WalletError::KeyringError(format!("Keyring entry creation failed: {}", e))
-        })?;
-
-        entry
-            .set_password(&encoded)
-            .map_err(|e| WalletError::KeyringError(format!("Failed to store key: {}", e)))?;
-
-        Ok(())
+        // Store in the secret store
+        self.store().set(key_id, &encoded)
     }
 
-    /// Retrieve a key from the OS keychain
+    /// Retrieve a key from the secret store
     ///
     /// The key is decrypted with the password after retrieval.
     ///
@@ -108,15 +316,11 @@ impl KeyringService {
         key_id: &str,
         password: &str,
     ) -> Result<Secret<String>, WalletError> {
-        // Retrieve from keychain
-        let entry = Entry::new(&self.service_name, key_id).map_err(|e| {
-            WalletError::KeyringError(format!("Keyring entry creation failed: {}", e))
+        // Retrieve from the secret store
+        let encoded = self.store().get(key_id)?.ok_or_else(|| {
+            WalletError::KeyringError(format!("Failed to retrieve key: no entry {}", key_id))
         })?;
 
-        let encoded = entry
-            .get_password()
-            .map_err(|e| WalletError::KeyringError(format!("Failed to retrieve key: {}", e)))?;
-
         // Decode from base64
         let encrypted = base64::decode(&encoded)
             .map_err(|e| WalletError::KeyringError(format!("Invalid base64 encoding: {}", e)))?;
@@ -131,35 +335,28 @@ impl KeyringService {
         Ok(Secret::new(private_key))
     }
 
-    /// Delete a key from the OS keychain
+    /// Delete a key from the secret store
     ///
     /// # Arguments
     ///
     /// * `key_id` - Unique identifier for the key
     pub fn delete_key(&self, key_id: &str) -> Result<(), WalletError> {
-        let entry = Entry::new(&self.service_name, key_id).map_err(|e| {
-            WalletError::KeyringError(format!("Keyring entry creation failed: {}", e))
-        })?;
-
-        entry
-            .delete_password()
-            .map_err(|e| WalletError::KeyringError(format!("Failed to delete key: {}", e)))?;
-
-        Ok(())
+        self.store().delete(key_id)
     }
 
-    /// Check if a key exists in the keychain
+    /// Check if a key exists in the secret store
     ///
     /// # Arguments
     ///
     /// * `key_id` - Unique identifier for the key
     pub fn key_exists(&self, key_id: &str) -> bool {
-        let entry = match Entry::new(&self.service_name, key_id) {
-            Ok(e) => e,
-            Err(_) => return false,
-        };
+        matches!(self.store().get(key_id), Ok(Some(_)))
+    }
 
-        entry.get_password().is_ok()
+    /// Like `key_exists`, but a store that can't be read is an error
+    /// rather than a missing key
+    pub fn has_key(&self, key_id: &str) -> Result<bool, WalletError> {
+        Ok(self.store().get(key_id)?.is_some())
     }
 
     /// List all key IDs stored in the keychain
@@ -173,6 +370,23 @@ impl KeyringService {
     }
 }
 
+fn write_journal(store: &dyn SecretStore, journal: &PasswordChangeJournal) -> Result<(), WalletError> {
+    let json = serde_json::to_string(journal)
+        .map_err(|e| WalletError::InternalError(format!("Failed to encode journal: {}", e)))?;
+    store.set(PASSWORD_CHANGE_JOURNAL, &json)
+}
+
+/// Remove staged entries, then the journal
+///
+/// Only safe once the journal is uncommitted or every live entry is the
+/// new one: a committed journal left behind re-applies what is staged.
+fn discard_staged(store: &dyn SecretStore, key_ids: &[String]) {
+    for key_id in key_ids {
+        let _ = store.delete(&staged_key_id(key_id));
+    }
+    let _ = store.delete(PASSWORD_CHANGE_JOURNAL);
+}
+
 // Add base64 encoding/decoding
 mod base64 {
     pub fn encode(data: &[u8]) -> String {
@@ -189,10 +403,15 @@ mod base64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::security::secret_store::MemoryStore;
+
+    fn memory_keyring(service_name: &str) -> KeyringService {
+        KeyringService::with_store(service_name, Arc::new(MemoryStore::new()))
+    }
 
     #[test]
     fn test_keyring_service_creation() {
-        let keyring = KeyringService::new("test-service").unwrap();
+        let keyring = memory_keyring("test-service");
         assert_eq!(keyring.service_name, "test-service");
 
         println!("✅ KeyringService creation works");
@@ -200,7 +419,7 @@ mod tests {
 
     #[test]
     fn test_store_and_retrieve_key() {
-        let keyring = KeyringService::new("test-vaughan-wallet").unwrap();
+        let keyring = memory_keyring("test-vaughan-wallet");
         let key_id = "test_account_0";
         let private_key = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
         let password = "test_password_123";
@@ -220,7 +439,7 @@ mod tests {
 
     #[test]
     fn test_retrieve_with_wrong_password() {
-        let keyring = KeyringService::new("test-vaughan-wallet-2").unwrap();
+        let keyring = memory_keyring("test-vaughan-wallet-2");
         let key_id = "test_account_1";
         let private_key = "fedcba9876543210fedcba9876543210fedcba9876543210fedcba9876543210";
         let password = "correct_password";
@@ -240,7 +459,7 @@ mod tests {
 
     #[test]
     fn test_key_exists() {
-        let keyring = KeyringService::new("test-vaughan-wallet-3").unwrap();
+        let keyring = memory_keyring("test-vaughan-wallet-3");
         let key_id = "test_account_2";
         let private_key = "1111111111111111111111111111111111111111111111111111111111111111";
         let password = "password";
@@ -268,7 +487,7 @@ mod tests {
 
     #[test]
     fn test_delete_nonexistent_key() {
-        let keyring = KeyringService::new("test-vaughan-wallet-4").unwrap();
+        let keyring = memory_keyring("test-vaughan-wallet-4");
         let key_id = "nonexistent_key";
 
         // Deleting nonexistent key should fail gracefully
@@ -277,4 +496,138 @@ mod tests {
 
         println!("✅ Deleting nonexistent key fails gracefully");
     }
+
+    /// Memory store whose writes to one key fail
+    struct FailingStore {
+        inner: Arc<MemoryStore>,
+        fail_on: String,
+    }
+
+    impl SecretStore for FailingStore {
+        fn backend(&self) -> SecretBackend {
+            SecretBackend::Memory
+        }
+
+        fn get(&self, key_id: &str) -> Result<Option<String>, WalletError> {
+            self.inner.get(key_id)
+        }
+
+        fn set(&self, key_id: &str, value: &str) -> Result<(), WalletError> {
+            if key_id == self.fail_on {
+                return Err(WalletError::KeyringError("write failed".to_string()));
+            }
+            self.inner.set(key_id, value)
+        }
+
+        fn delete(&self, key_id: &str) -> Result<(), WalletError> {
+            self.inner.delete(key_id)
+        }
+    }
+
+    fn key_ids() -> Vec<String> {
+        vec!["vaughan_seed".to_string(), "account_a".to_string(), "account_b".to_string()]
+    }
+
+    fn assert_all_under(keyring: &KeyringService, password: &str) {
+        for key_id in key_ids() {
+            assert_eq!(keyring.retrieve_key(&key_id, password).unwrap().expose_secret(), &key_id);
+        }
+        assert!(!keyring.key_exists(PASSWORD_CHANGE_JOURNAL));
+        assert!(!keyring.key_exists(&staged_key_id("vaughan_seed")));
+    }
+
+    #[test]
+    fn test_change_password() {
+        let keyring = memory_keyring("test-change-password");
+        for key_id in key_ids() {
+            keyring.store_key(&key_id, &key_id, "old").unwrap();
+        }
+
+        // Wrong old password changes nothing
+        assert!(keyring.change_password(&key_ids(), "wrong", "new").is_err());
+        assert_all_under(&keyring, "old");
+
+        let mut ids = key_ids();
+        ids.push("account_missing".to_string());
+        assert_eq!(keyring.change_password(&ids, "old", "new").unwrap(), 3);
+        assert_all_under(&keyring, "new");
+        assert!(keyring.retrieve_key("vaughan_seed", "old").is_err());
+    }
+
+    #[test]
+    fn test_change_password_rolls_back_failed_write() {
+        let inner = Arc::new(MemoryStore::new());
+        let plain = KeyringService::with_store("test", inner.clone());
+        for key_id in key_ids() {
+            plain.store_key(&key_id, &key_id, "old").unwrap();
+        }
+
+        // Fails after the seed has been swapped in
+        let failing = KeyringService::with_store(
+            "test",
+            Arc::new(FailingStore { inner: inner.clone(), fail_on: "account_b".to_string() }),
+        );
+        assert!(failing.change_password(&key_ids(), "old", "new").is_err());
+        assert_all_under(&plain, "old");
+
+        // Fails while staging
+        let failing = KeyringService::with_store(
+            "test",
+            Arc::new(FailingStore { inner, fail_on: staged_key_id("account_a") }),
+        );
+        assert!(failing.change_password(&key_ids(), "old", "new").is_err());
+        assert_all_under(&plain, "old");
+    }
+
+    #[test]
+    fn test_recover_interrupted_change() {
+        let keyring = memory_keyring("test-recover");
+        for key_id in key_ids() {
+            keyring.store_key(&key_id, &key_id, "old").unwrap();
+        }
+        let store = keyring.store();
+        let stage = |committed: bool| {
+            for key_id in key_ids() {
+                let encrypted = encrypt_data(key_id.as_bytes(), "new").unwrap();
+                store.set(&staged_key_id(&key_id), &base64::encode(&encrypted)).unwrap();
+            }
+            write_journal(store.as_ref(), &PasswordChangeJournal { committed, key_ids: key_ids() }).unwrap();
+        };
+
+        // Crash before the commit: discarded
+        stage(false);
+        keyring.recover_password_change().unwrap();
+        assert_all_under(&keyring, "old");
+
+        // Crash after the commit, seed already swapped: finished
+        stage(true);
+        let seed = store.get(&staged_key_id("vaughan_seed")).unwrap().unwrap();
+        store.set("vaughan_seed", &seed).unwrap();
+        keyring.recover_password_change().unwrap();
+        assert_all_under(&keyring, "new");
+    }
+
+    #[test]
+    fn test_upgrade_encryption() {
+        use crate::security::encryption::{encrypt_with, LEGACY_KDF};
+
+        let keyring = memory_keyring("test-upgrade");
+        let store = keyring.store();
+        for key_id in key_ids() {
+            let encrypted = encrypt_with(key_id.as_bytes(), "pw", &LEGACY_KDF).unwrap();
+            store.set(&key_id, &base64::encode(&encrypted)).unwrap();
+        }
+        keyring.store_key("account_current", "account_current", "pw").unwrap();
+
+        let mut ids = key_ids();
+        ids.push("account_current".to_string());
+        assert!(keyring.upgrade_encryption(&ids, "wrong").is_err());
+        assert_eq!(keyring.upgrade_encryption(&ids, "pw").unwrap(), 3);
+        assert_all_under(&keyring, "pw");
+        for key_id in &ids {
+            let encrypted = base64::decode(&store.get(key_id).unwrap().unwrap()).unwrap();
+            assert!(!needs_upgrade(&encrypted));
+        }
+        assert_eq!(keyring.upgrade_encryption(&ids, "pw").unwrap(), 0);
+    }
 }
@@ -0,0 +1,206 @@
+//! Keystore Module
+//!
+//! Web3 Secret Storage (V3 keystore JSON) import and export, as written by
+//! geth, Foundry `cast wallet` and MyEtherWallet.
+//!
+//! ## Supported KDFs
+//!
+//! - **scrypt** (geth, Foundry, and the export format)
+//! - **pbkdf2** (hmac-sha256)
+//!
+//! Encryption and decryption are done by `alloy`'s keystore support
+//! (`eth-keystore`); this module only adapts JSON strings to its file API
+//! and checks the result.
+//!
+//! ## Usage
+//!
+//! ```rust,ignore
+//! use vaughan_lib::security::keystore::{decrypt_keystore, encrypt_keystore};
+//!
+//! let signer = decrypt_keystore(&json, "keystore password")?;
+//! let json = encrypt_keystore(&signer, "new password")?;
+//! ```
+
+use crate::error::WalletError;
+use alloy::primitives::Address;
+use alloy::signers::local::PrivateKeySigner;
+use secrecy::zeroize::Zeroizing;
+use std::fs;
+use std::path::PathBuf;
+
+/// Scratch directory, removed when dropped
+struct ScratchDir(PathBuf);
+
+impl ScratchDir {
+    fn new() -> Result<Self, WalletError> {
+        let path = std::env::temp_dir().join(format!("vaughan-keystore-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&path)
+            .map_err(|e| WalletError::InternalError(format!("Failed to create scratch directory: {}", e)))?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let _ = fs::set_permissions(&path, fs::Permissions::from_mode(0o700));
+        }
+
+        Ok(Self(path))
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Decrypt a V3 keystore
+///
+/// Accepts the `Crypto` spelling used by MyEtherWallet. If the keystore
+/// names an address, the decrypted key must match it.
+///
+/// # Errors
+///
+/// * `WalletError::InvalidPrivateKey` - Not a V3 keystore
+/// * `WalletError::DecryptionFailed` - Wrong password or corrupt keystore
+pub fn decrypt_keystore(json: &str, password: &str) -> Result<PrivateKeySigner, WalletError> {
+    let mut keystore: serde_json::Value = serde_json::from_str(json.trim())
+        .map_err(|e| WalletError::InvalidPrivateKey(format!("Keystore is not valid JSON: {}", e)))?;
+    let object = keystore
+        .as_object_mut()
+        .ok_or_else(|| WalletError::InvalidPrivateKey("Keystore is not a JSON object".to_string()))?;
+
+    if object.get("version").and_then(|v| v.as_u64()) != Some(3) {
+        return Err(WalletError::InvalidPrivateKey("Only version 3 keystores are supported".to_string()));
+    }
+    if !object.contains_key("crypto") {
+        if let Some(crypto) = object.remove("Crypto") {
+            object.insert("crypto".to_string(), crypto);
+        }
+    }
+    let kdf = object
+        .get("crypto")
+        .and_then(|c| c.get("kdf"))
+        .and_then(|k| k.as_str())
+        .ok_or_else(|| WalletError::InvalidPrivateKey("Keystore has no KDF".to_string()))?;
+    if kdf != "scrypt" && kdf != "pbkdf2" {
+        return Err(WalletError::InvalidPrivateKey(format!("Unsupported keystore KDF: {}", kdf)));
+    }
+    let expected: Option<Address> = object
+        .get("address")
+        .and_then(|a| a.as_str())
+        .and_then(|a| a.trim_start_matches("0x").parse().ok());
+
+    let scratch = ScratchDir::new()?;
+    let path = scratch.0.join("keystore.json");
+    fs::write(&path, keystore.to_string())
+        .map_err(|e| WalletError::InternalError(format!("Failed to stage keystore: {}", e)))?;
+
+    let signer = PrivateKeySigner::decrypt_keystore(&path, password)
+        .map_err(|e| WalletError::DecryptionFailed(format!("Wrong password or corrupt keystore: {}", e)))?;
+
+    if let Some(expected) = expected {
+        if signer.address() != expected {
+            return Err(WalletError::DecryptionFailed(format!(
+                "Keystore key belongs to {}, not {}",
+                signer.address(),
+                expected
+            )));
+        }
+    }
+    Ok(signer)
+}
+
+/// Encrypt a key as a V3 keystore (scrypt)
+///
+/// The JSON includes the account address, as geth writes it.
+pub fn encrypt_keystore(signer: &PrivateKeySigner, password: &str) -> Result<String, WalletError> {
+    let scratch = ScratchDir::new()?;
+    let mut rng = rand::thread_rng();
+    let key = Zeroizing::new(signer.to_bytes().0);
+    PrivateKeySigner::encrypt_keystore(&scratch.0, &mut rng, key.as_slice(), password, Some("keystore.json"))
+        .map_err(|e| WalletError::EncryptionFailed(format!("Keystore encryption failed: {}", e)))?;
+
+    let json = fs::read_to_string(scratch.0.join("keystore.json"))
+        .map_err(|e| WalletError::InternalError(format!("Failed to read keystore: {}", e)))?;
+    let mut keystore: serde_json::Value = serde_json::from_str(&json)
+        .map_err(|e| WalletError::InternalError(format!("Invalid keystore output: {}", e)))?;
+    if let Some(object) = keystore.as_object_mut() {
+        let address = hex::encode(signer.address().as_slice());
+        object.insert("address".to_string(), serde_json::Value::String(address));
+    }
+    serde_json::to_string_pretty(&keystore)
+        .map_err(|e| WalletError::InternalError(format!("Failed to serialize keystore: {}", e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Web3 Secret Storage test vector (pbkdf2)
+    const PBKDF2_VECTOR: &str = r#"{
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": { "iv": "6087dab2f9fdbbfaddc31a909735c1e6" },
+            "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+            "kdf": "pbkdf2",
+            "kdfparams": {
+                "c": 262144,
+                "dklen": 32,
+                "prf": "hmac-sha256",
+                "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+            },
+            "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+        },
+        "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+        "version": 3
+    }"#;
+
+    const VECTOR_KEY: &str = "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d";
+
+    #[test]
+    fn test_decrypt_pbkdf2_vector() {
+        let signer = decrypt_keystore(PBKDF2_VECTOR, "testpassword").unwrap();
+        assert_eq!(hex::encode(signer.to_bytes()), VECTOR_KEY);
+
+        assert!(matches!(
+            decrypt_keystore(PBKDF2_VECTOR, "wrong"),
+            Err(WalletError::DecryptionFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_decrypt_accepts_mew_capitalization() {
+        let json = PBKDF2_VECTOR.replace("\"crypto\"", "\"Crypto\"");
+        let signer = decrypt_keystore(&json, "testpassword").unwrap();
+        assert_eq!(hex::encode(signer.to_bytes()), VECTOR_KEY);
+    }
+
+    #[test]
+    fn test_encrypt_roundtrip_scrypt() {
+        let signer = PrivateKeySigner::random();
+        let json = encrypt_keystore(&signer, "export pw").unwrap();
+
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["crypto"]["kdf"], "scrypt");
+        assert_eq!(value["address"], hex::encode(signer.address().as_slice()));
+
+        let decrypted = decrypt_keystore(&json, "export pw").unwrap();
+        assert_eq!(decrypted.address(), signer.address());
+    }
+
+    #[test]
+    fn test_rejects_wrong_address_and_version() {
+        let signer = PrivateKeySigner::random();
+        let json = encrypt_keystore(&signer, "pw").unwrap();
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+
+        value["address"] = serde_json::Value::String(hex::encode(Address::repeat_byte(0x11).as_slice()));
+        assert!(decrypt_keystore(&value.to_string(), "pw").is_err());
+
+        value["version"] = serde_json::json!(1);
+        assert!(matches!(
+            decrypt_keystore(&value.to_string(), "pw"),
+            Err(WalletError::InvalidPrivateKey(_))
+        ));
+    }
+}
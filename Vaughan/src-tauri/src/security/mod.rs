@@ -2,6 +2,10 @@
 //!
 //! This module contains all security-critical functionality for the Vaughan Wallet:
 //! - OS keychain integration (secure key storage)
+//! - Pluggable secret stores (OS keychain, encrypted file vault, in-memory)
+//! - V3 keystore JSON import/export (Web3 Secret Storage)
+//! - SLIP-39 Shamir backup of the seed
+//! - Throttling of wrong password attempts
 //! - Password-based encryption (AES-GCM + Argon2)
 //! - HD wallet support (BIP-39 mnemonics, BIP-32 derivation)
 //! - Account management (create, import, export)
@@ -18,7 +22,10 @@
 //! ## Dependencies
 //!
 //! - `keyring` - OS keychain integration
+//! - `alloy` keystore (`eth-keystore`) - V3 keystore JSON
 //! - `bip39` - BIP-39 mnemonic generation
+//! - `hmac` / `sha2` - HMAC-SHA256 for SLIP-39 (`slip39` implements the
+//!   SLIP-39 scheme itself, checked against its reference test vectors)
 //! - `coins-bip32` - BIP-32 HD wallet derivation
 //! - `aes-gcm` - AES-GCM encryption
 //! - `argon2` - Argon2 key derivation
@@ -28,11 +35,16 @@
 pub mod encryption;
 pub mod hd_wallet;
 pub mod keyring_service;
+pub mod keystore;
+pub mod secret_store;
+pub mod slip39;
+pub mod throttle;
 
 // Re-export main types
 pub use encryption::{decrypt_data, encrypt_data, hash_password, verify_password};
-pub use hd_wallet::{derive_account, generate_mnemonic, mnemonic_to_seed, validate_mnemonic};
+pub use hd_wallet::{derive_account, derive_path, generate_mnemonic, mnemonic_to_seed, validate_mnemonic};
 pub use keyring_service::KeyringService;
+pub use secret_store::{SecretBackend, SecretStore};
 
 #[cfg(test)]
 mod tests {
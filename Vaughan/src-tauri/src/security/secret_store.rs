@@ -0,0 +1,560 @@
+//! Secret Store Module
+//!
+//! Storage backends for the wallet's encrypted secrets. `KeyringService`
+//! encrypts every secret with the wallet password (`security::encryption`)
+//! before handing it to a store, so stores only ever see ciphertext.
+//!
+//! ## Backends
+//!
+//! - **OS keyring**: Windows Credential Manager, macOS Keychain, Linux
+//!   Secret Service
+//! - **Encrypted file vault**: `<data_dir>/vaughan/secrets.vault`, for
+//!   systems without a keyring (headless Linux, CI)
+//! - **In-memory**: tests, or when forced with `VAUGHAN_SECRET_STORE`
+//!
+//! ## Selection
+//!
+//! `select_store` picks, in order:
+//!
+//! 1. `VAUGHAN_SECRET_STORE` (`keyring`, `file` or `memory`) if set
+//! 2. The file vault if it holds the wallet seed
+//! 3. The OS keyring if it works (probed with a throwaway entry)
+//! 4. The file vault, unless the OS keyring holds the wallet
+//!
+//! The store holding the seed always wins: rule 2 keeps a wallet created
+//! while the keyring was unavailable from disappearing once the keyring
+//! comes back, and a marker file written while the keyring holds the seed
+//! makes rule 4 fail instead of switching to an empty vault while the
+//! keyring is down. Nothing falls back to memory on its own, so a wallet
+//! can't be created that is lost on restart.
+
+use crate::error::WalletError;
+use keyring::Entry;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+/// Vault file name in the data directory
+const VAULT_FILE: &str = "secrets.vault";
+
+/// Extension of the marker file (`<service>.keyring` in the data
+/// directory) present while the OS keyring holds the wallet seed
+const KEYRING_MARKER_EXTENSION: &str = "keyring";
+
+/// Entry holding the wallet seed; the store that has it holds the wallet
+pub const SEED_KEY: &str = "vaughan_seed";
+
+/// Entry used to probe whether the OS keyring works
+const PROBE_KEY: &str = "__vaughan_probe";
+
+/// Environment variable forcing a backend
+pub const BACKEND_ENV: &str = "VAUGHAN_SECRET_STORE";
+
+/// Secret storage backend kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub enum SecretBackend {
+    OsKeyring,
+    EncryptedFile,
+    Memory,
+}
+
+impl SecretBackend {
+    fn from_env(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "keyring" | "os" => Some(Self::OsKeyring),
+            "file" | "vault" => Some(Self::EncryptedFile),
+            "memory" => Some(Self::Memory),
+            _ => None,
+        }
+    }
+}
+
+/// Storage for encrypted secrets, keyed by ID (e.g. `vaughan_seed`)
+pub trait SecretStore: Send + Sync {
+    /// Backend kind
+    fn backend(&self) -> SecretBackend;
+
+    /// Stored value, `None` if there is no entry
+    fn get(&self, key_id: &str) -> Result<Option<String>, WalletError>;
+
+    /// Store a value, replacing any existing one
+    fn set(&self, key_id: &str, value: &str) -> Result<(), WalletError>;
+
+    /// Delete an entry (error if there is none)
+    fn delete(&self, key_id: &str) -> Result<(), WalletError>;
+}
+
+// ============================================================================
+// OS Keyring
+// ============================================================================
+
+/// OS keyring backend
+pub struct OsKeyringStore {
+    service_name: String,
+}
+
+impl OsKeyringStore {
+    pub fn new(service_name: impl Into<String>) -> Self {
+        Self {
+            service_name: service_name.into(),
+        }
+    }
+
+    fn entry(&self, key_id: &str) -> Result<Entry, WalletError> {
+        Entry::new(&self.service_name, key_id)
+            .map_err(|e| WalletError::KeyringError(format!("Keyring entry creation failed: {}", e)))
+    }
+
+    /// Whether the keyring can store and read back an entry
+    pub fn is_available(&self) -> bool {
+        let works = self.set(PROBE_KEY, "probe").is_ok()
+            && matches!(self.get(PROBE_KEY), Ok(Some(ref v)) if v == "probe");
+        let _ = self.delete(PROBE_KEY);
+        works
+    }
+
+    fn marker_path(&self) -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| {
+            dir.join("vaughan")
+                .join(&self.service_name)
+                .with_extension(KEYRING_MARKER_EXTENSION)
+        })
+    }
+
+    /// Whether the keyring held the wallet seed when last seen
+    pub fn holds_wallet(&self) -> bool {
+        self.marker_path().is_some_and(|path| path.exists())
+    }
+
+    /// Record whether the keyring holds the wallet seed
+    fn mark_wallet(&self, present: bool) {
+        let Some(path) = self.marker_path() else { return };
+        let result = if present {
+            path.parent()
+                .map_or(Ok(()), fs::create_dir_all)
+                .and_then(|_| fs::write(&path, b""))
+        } else {
+            match fs::remove_file(&path) {
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+                other => other,
+            }
+        };
+        if let Err(e) = result {
+            warn!("[SecretStore] Could not update the keyring marker {:?}: {}", path, e);
+        }
+    }
+}
+
+impl SecretStore for OsKeyringStore {
+    fn backend(&self) -> SecretBackend {
+        SecretBackend::OsKeyring
+    }
+
+    fn get(&self, key_id: &str) -> Result<Option<String>, WalletError> {
+        match self.entry(key_id)?.get_password() {
+            Ok(value) => Ok(Some(value)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(WalletError::KeyringError(format!("Failed to retrieve key: {}", e))),
+        }
+    }
+
+    fn set(&self, key_id: &str, value: &str) -> Result<(), WalletError> {
+        self.entry(key_id)?
+            .set_password(value)
+            .map_err(|e| WalletError::KeyringError(format!("Failed to store key: {}", e)))?;
+        if key_id == SEED_KEY {
+            self.mark_wallet(true);
+        }
+        Ok(())
+    }
+
+    fn delete(&self, key_id: &str) -> Result<(), WalletError> {
+        self.entry(key_id)?
+            .delete_password()
+            .map_err(|e| WalletError::KeyringError(format!("Failed to delete key: {}", e)))?;
+        if key_id == SEED_KEY {
+            self.mark_wallet(false);
+        }
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Encrypted File Vault
+// ============================================================================
+
+/// File vault backend
+///
+/// A JSON map of key ID to (already encrypted) value, written atomically
+/// and readable only by the user on Unix.
+pub struct FileVaultStore {
+    path: PathBuf,
+    lock: Mutex<()>,
+}
+
+impl FileVaultStore {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Vault in the app data directory
+    pub fn in_data_dir() -> Result<Self, WalletError> {
+        let data_dir = dirs::data_dir()
+            .ok_or_else(|| WalletError::InternalError("Failed to resolve app data directory".to_string()))?
+            .join("vaughan");
+        fs::create_dir_all(&data_dir)
+            .map_err(|e| WalletError::InternalError(format!("Failed to create data directory: {}", e)))?;
+        Ok(Self::new(data_dir.join(VAULT_FILE)))
+    }
+
+    /// Whether the vault holds any secrets
+    pub fn has_entries(&self) -> bool {
+        self.read().map(|entries| !entries.is_empty()).unwrap_or(false)
+    }
+
+    fn read(&self) -> Result<BTreeMap<String, String>, WalletError> {
+        match fs::read_to_string(&self.path) {
+            Ok(json) => serde_json::from_str(&json)
+                .map_err(|e| WalletError::KeyringError(format!("Corrupt secret vault: {}", e))),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+            Err(e) => Err(WalletError::KeyringError(format!("Failed to read secret vault: {}", e))),
+        }
+    }
+
+    fn write(&self, entries: &BTreeMap<String, String>) -> Result<(), WalletError> {
+        if entries.is_empty() {
+            return match fs::remove_file(&self.path) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                    Err(WalletError::KeyringError(format!("Failed to remove secret vault: {}", e)))
+                }
+                _ => Ok(()),
+            };
+        }
+
+        let json = serde_json::to_string_pretty(entries)
+            .map_err(|e| WalletError::InternalError(format!("Failed to serialize vault: {}", e)))?;
+        let tmp_path = self.path.with_extension("vault.tmp");
+        fs::write(&tmp_path, json)
+            .map_err(|e| WalletError::KeyringError(format!("Failed to write secret vault: {}", e)))?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let _ = fs::set_permissions(&tmp_path, fs::Permissions::from_mode(0o600));
+        }
+
+        fs::rename(&tmp_path, &self.path)
+            .map_err(|e| WalletError::KeyringError(format!("Failed to write secret vault: {}", e)))
+    }
+}
+
+impl SecretStore for FileVaultStore {
+    fn backend(&self) -> SecretBackend {
+        SecretBackend::EncryptedFile
+    }
+
+    fn get(&self, key_id: &str) -> Result<Option<String>, WalletError> {
+        let _guard = self.lock.lock().map_err(|_| WalletError::InternalError("Vault lock poisoned".to_string()))?;
+        Ok(self.read()?.remove(key_id))
+    }
+
+    fn set(&self, key_id: &str, value: &str) -> Result<(), WalletError> {
+        let _guard = self.lock.lock().map_err(|_| WalletError::InternalError("Vault lock poisoned".to_string()))?;
+        let mut entries = self.read()?;
+        entries.insert(key_id.to_string(), value.to_string());
+        self.write(&entries)
+    }
+
+    fn delete(&self, key_id: &str) -> Result<(), WalletError> {
+        let _guard = self.lock.lock().map_err(|_| WalletError::InternalError("Vault lock poisoned".to_string()))?;
+        let mut entries = self.read()?;
+        if entries.remove(key_id).is_none() {
+            return Err(WalletError::KeyringError(format!("Failed to delete key: no entry {}", key_id)));
+        }
+        self.write(&entries)
+    }
+}
+
+// ============================================================================
+// In-Memory
+// ============================================================================
+
+/// In-memory backend (tests, or forced; nothing survives a restart)
+#[derive(Default)]
+pub struct MemoryStore {
+    entries: Mutex<BTreeMap<String, String>>,
+}
+
+impl MemoryStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SecretStore for MemoryStore {
+    fn backend(&self) -> SecretBackend {
+        SecretBackend::Memory
+    }
+
+    fn get(&self, key_id: &str) -> Result<Option<String>, WalletError> {
+        Ok(self.entries.lock().map_err(|_| WalletError::InternalError("Store lock poisoned".to_string()))?.get(key_id).cloned())
+    }
+
+    fn set(&self, key_id: &str, value: &str) -> Result<(), WalletError> {
+        self.entries
+            .lock()
+            .map_err(|_| WalletError::InternalError("Store lock poisoned".to_string()))?
+            .insert(key_id.to_string(), value.to_string());
+        Ok(())
+    }
+
+    fn delete(&self, key_id: &str) -> Result<(), WalletError> {
+        self.entries
+            .lock()
+            .map_err(|_| WalletError::InternalError("Store lock poisoned".to_string()))?
+            .remove(key_id)
+            .map(|_| ())
+            .ok_or_else(|| WalletError::KeyringError(format!("Failed to delete key: no entry {}", key_id)))
+    }
+}
+
+// ============================================================================
+// Selection
+// ============================================================================
+
+/// Open a specific backend
+pub fn open_store(backend: SecretBackend, service_name: &str) -> Result<Arc<dyn SecretStore>, WalletError> {
+    match backend {
+        SecretBackend::OsKeyring => {
+            let store = OsKeyringStore::new(service_name);
+            if !store.is_available() {
+                return Err(WalletError::KeyringError("OS keyring is not available".to_string()));
+            }
+            Ok(Arc::new(store))
+        }
+        SecretBackend::EncryptedFile => Ok(Arc::new(FileVaultStore::in_data_dir()?)),
+        SecretBackend::Memory => Ok(Arc::new(MemoryStore::new())),
+    }
+}
+
+/// Pick the secret store for this system (see module docs)
+///
+/// # Errors
+///
+/// * `WalletError::KeyringError` - The forced backend is unknown or can't be
+///   opened, the vault can't be read, the OS keyring holds the wallet but
+///   is unavailable, or there is no persistent store at all
+pub fn select_store(service_name: &str) -> Result<Arc<dyn SecretStore>, WalletError> {
+    if let Ok(value) = std::env::var(BACKEND_ENV) {
+        let backend = SecretBackend::from_env(&value).ok_or_else(|| {
+            WalletError::KeyringError(format!("Unknown {} value: {}", BACKEND_ENV, value))
+        })?;
+        let store = open_store(backend, service_name)?;
+        info!("[SecretStore] Using {:?} ({} is set)", backend, BACKEND_ENV);
+        return Ok(store);
+    }
+
+    // A vault that can't be read may hold the wallet, so that is an error
+    let vault = FileVaultStore::in_data_dir().ok();
+    if let Some(vault) = vault.as_ref() {
+        if vault.get(SEED_KEY)?.is_some() {
+            info!("[SecretStore] Using encrypted file vault {:?}", vault.path);
+            return Ok(Arc::new(FileVaultStore::new(vault.path.clone())));
+        }
+    }
+
+    let keyring = OsKeyringStore::new(service_name);
+    if keyring.is_available() {
+        // Wallets created before the marker existed
+        if matches!(keyring.get(SEED_KEY), Ok(Some(_))) && !keyring.holds_wallet() {
+            keyring.mark_wallet(true);
+        }
+        info!("[SecretStore] Using OS keyring");
+        return Ok(Arc::new(keyring));
+    }
+    if keyring.holds_wallet() {
+        return Err(WalletError::KeyringError(
+            "The wallet's keys are in the OS keyring, which is not available; unlock or start it and restart Vaughan"
+                .to_string(),
+        ));
+    }
+
+    match vault {
+        Some(vault) => {
+            warn!("[SecretStore] OS keyring unavailable, using encrypted file vault {:?}", vault.path);
+            Ok(Arc::new(vault))
+        }
+        None => Err(WalletError::KeyringError(format!(
+            "No OS keyring and no data directory for the secret vault; set {}=memory to run without saving keys",
+            BACKEND_ENV
+        ))),
+    }
+}
+
+/// Copy secrets to another store, then remove them from the source
+///
+/// Every copy is read back before anything is deleted. If a copy or a
+/// removal from the source fails, the migration is undone: removed
+/// secrets are written back to the source and the copies are deleted, so
+/// the wallet never ends up in two stores (a stale vault would be picked
+/// again on the next start). Missing keys are skipped.
+///
+/// # Returns
+///
+/// Number of secrets moved
+///
+/// # Errors
+///
+/// * `WalletError::KeyringError` - A secret could not be copied, verified or
+///   removed from the source; nothing was moved
+pub fn migrate(from: &dyn SecretStore, to: &dyn SecretStore, key_ids: &[String]) -> Result<usize, WalletError> {
+    let mut copied: Vec<(&String, String)> = Vec::new();
+    for key_id in key_ids {
+        let Some(value) = from.get(key_id)? else { continue };
+        let copy = to.set(key_id, &value).and_then(|_| match to.get(key_id)? {
+            Some(read) if read == value => Ok(()),
+            _ => Err(WalletError::KeyringError(format!("Verification failed for {}", key_id))),
+        });
+        if let Err(e) = copy {
+            remove_copies(to, &copied);
+            let _ = to.delete(key_id);
+            return Err(e);
+        }
+        copied.push((key_id, value));
+    }
+
+    for (removed, (key_id, _)) in copied.iter().enumerate() {
+        if let Err(e) = from.delete(key_id) {
+            for (restored_id, value) in &copied[..removed] {
+                if let Err(e) = from.set(restored_id, value) {
+                    warn!("[SecretStore] Could not put {} back in the old store: {}", restored_id, e);
+                }
+            }
+            remove_copies(to, &copied);
+            return Err(WalletError::KeyringError(format!(
+                "Could not remove {} from the old store, nothing was moved: {}",
+                key_id, e
+            )));
+        }
+    }
+    Ok(copied.len())
+}
+
+/// Delete the copies made by a failed `migrate`
+fn remove_copies(to: &dyn SecretStore, copied: &[(&String, String)]) {
+    for (key_id, _) in copied {
+        if let Err(e) = to.delete(key_id) {
+            warn!("[SecretStore] Could not remove copy of {} from the new store: {}", key_id, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_vault() -> FileVaultStore {
+        let path = std::env::temp_dir().join(format!("vaughan_vault_{}.vault", uuid::Uuid::new_v4()));
+        FileVaultStore::new(path)
+    }
+
+    fn exercise(store: &dyn SecretStore) {
+        assert_eq!(store.get("a").unwrap(), None);
+        store.set("a", "one").unwrap();
+        store.set("a", "two").unwrap();
+        assert_eq!(store.get("a").unwrap().as_deref(), Some("two"));
+        store.delete("a").unwrap();
+        assert_eq!(store.get("a").unwrap(), None);
+        assert!(store.delete("a").is_err());
+    }
+
+    #[test]
+    fn test_memory_store() {
+        exercise(&MemoryStore::new());
+    }
+
+    #[test]
+    fn test_file_vault() {
+        let vault = temp_vault();
+        exercise(&vault);
+        assert!(!vault.path.exists());
+
+        vault.set("seed", "ciphertext").unwrap();
+        assert!(vault.has_entries());
+        // Survives reopening
+        assert_eq!(FileVaultStore::new(vault.path.clone()).get("seed").unwrap().as_deref(), Some("ciphertext"));
+        vault.delete("seed").unwrap();
+    }
+
+    #[test]
+    fn test_migrate_moves_and_skips_missing() {
+        let from = MemoryStore::new();
+        let to = temp_vault();
+        from.set("vaughan_seed", "s").unwrap();
+        from.set("account_1", "k").unwrap();
+
+        let keys = vec!["vaughan_seed".to_string(), "account_1".to_string(), "account_2".to_string()];
+        assert_eq!(migrate(&from, &to, &keys).unwrap(), 2);
+        assert_eq!(from.get("vaughan_seed").unwrap(), None);
+        assert_eq!(to.get("account_1").unwrap().as_deref(), Some("k"));
+
+        to.delete("vaughan_seed").unwrap();
+        to.delete("account_1").unwrap();
+    }
+
+    /// Memory store whose `delete` fails for one key
+    struct UndeletableStore {
+        inner: MemoryStore,
+        fail_on: &'static str,
+    }
+
+    impl SecretStore for UndeletableStore {
+        fn backend(&self) -> SecretBackend {
+            SecretBackend::Memory
+        }
+        fn get(&self, key_id: &str) -> Result<Option<String>, WalletError> {
+            self.inner.get(key_id)
+        }
+        fn set(&self, key_id: &str, value: &str) -> Result<(), WalletError> {
+            self.inner.set(key_id, value)
+        }
+        fn delete(&self, key_id: &str) -> Result<(), WalletError> {
+            if key_id == self.fail_on {
+                return Err(WalletError::KeyringError("locked".to_string()));
+            }
+            self.inner.delete(key_id)
+        }
+    }
+
+    #[test]
+    fn test_migrate_undone_when_source_cleanup_fails() {
+        let from = UndeletableStore { inner: MemoryStore::new(), fail_on: "account_1" };
+        let to = temp_vault();
+        from.set("vaughan_seed", "s").unwrap();
+        from.set("account_1", "k").unwrap();
+
+        let keys = vec!["vaughan_seed".to_string(), "account_1".to_string()];
+        assert!(matches!(migrate(&from, &to, &keys), Err(WalletError::KeyringError(_))));
+
+        // Source is whole again and the new store holds nothing
+        assert_eq!(from.get("vaughan_seed").unwrap().as_deref(), Some("s"));
+        assert_eq!(from.get("account_1").unwrap().as_deref(), Some("k"));
+        assert_eq!(to.get(SEED_KEY).unwrap(), None);
+        assert_eq!(to.get("account_1").unwrap(), None);
+    }
+
+    #[test]
+    fn test_backend_from_env() {
+        assert_eq!(SecretBackend::from_env("File"), Some(SecretBackend::EncryptedFile));
+        assert_eq!(SecretBackend::from_env("keyring"), Some(SecretBackend::OsKeyring));
+        assert_eq!(SecretBackend::from_env("cloud"), None);
+    }
+}
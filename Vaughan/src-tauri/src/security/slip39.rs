@@ -0,0 +1,682 @@
+//! SLIP-39 Module
+//!
+//! Shamir backup of the wallet seed (SLIP-39,
+//! https://github.com/satoshilabs/slips/blob/master/slip-0039.md).
+//!
+//! The wordlist and the test vectors are those of the SLIP-39 reference
+//! implementation. Shares are written without the extendable flag, so any
+//! SLIP-39 wallet can read them.
+//!
+//! ## What Is Split
+//!
+//! The master secret is the BIP-39 entropy of the wallet mnemonic (16-32
+//! bytes), so combining shares gives back the same mnemonic and the wallet
+//! derives the same accounts. A BIP-39 passphrase is not part of the
+//! shares and must be kept separately.
+//!
+//! ## Groups
+//!
+//! Shares are organised in groups: each group needs `threshold` of its
+//! `count` member shares, and `group_threshold` groups are needed to
+//! restore. Splits where a single share could restore the wallet are
+//! rejected.
+
+use crate::error::WalletError;
+use bip39::{Language, Mnemonic};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use secrecy::zeroize::Zeroizing;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use specta::Type;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+/// Most members per group (SLIP-39 limit)
+pub const MAX_SHARES: u8 = 16;
+
+/// PBKDF2 iteration exponent for the share encryption (SLIP-39 default)
+const ITERATION_EXPONENT: u8 = 1;
+
+/// SLIP-39 English wordlist (1024 words, sorted)
+const WORDLIST: &str = include_str!("slip39_wordlist.txt");
+
+/// Bits per word
+const RADIX_BITS: usize = 10;
+
+/// Identifier, extendable flag and iteration exponent words
+const ID_EXP_WORDS: usize = 2;
+
+/// Group and member parameter words
+const PARAMS_WORDS: usize = 2;
+
+/// RS1024 checksum words
+const CHECKSUM_WORDS: usize = 3;
+
+/// Shortest share (128-bit secret)
+const MIN_SHARE_WORDS: usize = 20;
+
+/// Checksum customization of shares without the extendable flag
+const CUSTOMIZATION: &[u8] = b"shamir";
+
+/// PBKDF2 iterations per Feistel round at exponent 0, times the rounds
+const BASE_ITERATION_COUNT: u32 = 10_000;
+
+/// Feistel rounds of the share encryption
+const ROUND_COUNT: u8 = 4;
+
+/// x coordinate of the digest share
+const DIGEST_INDEX: u8 = 254;
+
+/// x coordinate of the shared secret
+const SECRET_INDEX: u8 = 255;
+
+/// Bytes of the digest guarding the shared secret
+const DIGEST_LENGTH: usize = 4;
+
+/// A group of shares
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct ShareGroup {
+    /// Member shares needed from this group
+    pub threshold: u8,
+    /// Member shares in this group
+    pub count: u8,
+}
+
+/// Check a share configuration
+///
+/// # Errors
+///
+/// * `WalletError::Custom` - Invalid thresholds, or one share could restore the wallet
+pub fn validate_groups(group_threshold: u8, groups: &[ShareGroup]) -> Result<(), WalletError> {
+    if groups.is_empty() || groups.len() > MAX_SHARES as usize {
+        return Err(WalletError::Custom(format!("Use 1 to {} groups", MAX_SHARES)));
+    }
+    if group_threshold == 0 || group_threshold as usize > groups.len() {
+        return Err(WalletError::Custom(format!(
+            "Group threshold must be between 1 and {}",
+            groups.len()
+        )));
+    }
+    for group in groups {
+        if group.count == 0 || group.count > MAX_SHARES || group.threshold == 0 || group.threshold > group.count {
+            return Err(WalletError::Custom(format!(
+                "Invalid group {} of {}: need 1 <= threshold <= count <= {}",
+                group.threshold, group.count, MAX_SHARES
+            )));
+        }
+        // SLIP-39: a 1-of-n group must be 1-of-1
+        if group.threshold == 1 && group.count > 1 {
+            return Err(WalletError::Custom(
+                "A group with threshold 1 must have a single share".to_string(),
+            ));
+        }
+    }
+    if group_threshold == 1 && groups.iter().any(|g| g.threshold == 1) {
+        return Err(WalletError::Custom(
+            "A single share would restore the wallet; raise a threshold".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Split a BIP-39 mnemonic into SLIP-39 shares
+///
+/// # Returns
+///
+/// For each group, its member shares (each a space-separated word list)
+pub fn split_mnemonic(mnemonic: &str, group_threshold: u8, groups: &[ShareGroup]) -> Result<Vec<Vec<String>>, WalletError> {
+    validate_groups(group_threshold, groups)?;
+
+    let mnemonic = Mnemonic::from_str(mnemonic)
+        .map_err(|e| WalletError::InvalidMnemonic(format!("Invalid mnemonic: {}", e)))?;
+    let entropy = Zeroizing::new(mnemonic.to_entropy());
+
+    let mut id_bytes = [0u8; 2];
+    rand::thread_rng().fill_bytes(&mut id_bytes);
+    let identifier = u16::from_be_bytes(id_bytes) & 0x7fff;
+    let encrypted = encrypt(&entropy, b"", ITERATION_EXPONENT, identifier);
+
+    let group_count = groups.len() as u8;
+    let group_secrets = split_secret(group_threshold, group_count, &encrypted)?;
+    groups
+        .iter()
+        .zip(group_secrets.iter())
+        .map(|(group, (group_index, group_secret))| {
+            let members = split_secret(group.threshold, group.count, group_secret)?;
+            Ok(members
+                .iter()
+                .map(|(member_index, value)| {
+                    Share {
+                        identifier,
+                        iteration_exponent: ITERATION_EXPONENT,
+                        group_index: *group_index,
+                        group_threshold,
+                        group_count,
+                        member_index: *member_index,
+                        member_threshold: group.threshold,
+                        value: value.clone(),
+                    }
+                    .to_mnemonic()
+                })
+                .collect())
+        })
+        .collect()
+}
+
+/// Combine SLIP-39 shares back into the BIP-39 mnemonic
+///
+/// Shares beyond a quorum are ignored, as are groups short of their
+/// threshold when enough other groups are complete.
+///
+/// # Errors
+///
+/// * `WalletError::InvalidMnemonic` - Bad share, mixed sets or not enough shares
+pub fn combine_shares(shares: &[String]) -> Result<String, WalletError> {
+    let entropy = combine(shares, b"")?;
+    let mnemonic = Mnemonic::from_entropy_in(Language::English, &entropy)
+        .map_err(|e| WalletError::InvalidMnemonic(format!("Shares do not hold a BIP-39 seed: {}", e)))?;
+    Ok(mnemonic.to_string())
+}
+
+/// Recover the master secret from shares
+fn combine(shares: &[String], passphrase: &[u8]) -> Result<Zeroizing<Vec<u8>>, WalletError> {
+    let mut parsed = Vec::new();
+    for share in shares.iter().filter(|s| !s.trim().is_empty()) {
+        let share = Share::from_mnemonic(share)?;
+        if !parsed.contains(&share) {
+            parsed.push(share);
+        }
+    }
+    let Some(first) = parsed.first() else {
+        return Err(invalid("No shares given"));
+    };
+    let common = first.common_parameters();
+    if parsed.iter().any(|s| s.common_parameters() != common) {
+        return Err(invalid(
+            "Shares are from different sets (identifier, iteration exponent or group parameters differ)",
+        ));
+    }
+    let (identifier, iteration_exponent, group_threshold, _) = common;
+
+    let mut groups: BTreeMap<u8, Vec<&Share>> = BTreeMap::new();
+    for share in &parsed {
+        groups.entry(share.group_index).or_default().push(share);
+    }
+    let mut group_secrets = Vec::new();
+    for members in groups.values() {
+        let member_threshold = members[0].member_threshold;
+        if members.iter().any(|s| s.member_threshold != member_threshold) {
+            return Err(invalid("Shares of one group have different member thresholds"));
+        }
+        let mut indices: Vec<u8> = members.iter().map(|s| s.member_index).collect();
+        indices.sort_unstable();
+        indices.dedup();
+        if indices.len() != members.len() {
+            return Err(invalid("Two different shares have the same member index"));
+        }
+        if members.len() < member_threshold as usize {
+            continue;
+        }
+        let points: Vec<(u8, &[u8])> = members
+            .iter()
+            .take(member_threshold as usize)
+            .map(|s| (s.member_index, s.value.as_slice()))
+            .collect();
+        group_secrets.push((members[0].group_index, recover_secret(member_threshold, &points)?));
+    }
+    if group_secrets.len() < group_threshold as usize {
+        return Err(invalid(&format!(
+            "Not enough shares: {} of {} groups are complete",
+            group_secrets.len(),
+            group_threshold
+        )));
+    }
+
+    let points: Vec<(u8, &[u8])> = group_secrets
+        .iter()
+        .take(group_threshold as usize)
+        .map(|(index, secret)| (*index, secret.as_slice()))
+        .collect();
+    let encrypted = recover_secret(group_threshold, &points)?;
+    Ok(decrypt(&encrypted, passphrase, iteration_exponent, identifier))
+}
+
+fn invalid(message: &str) -> WalletError {
+    WalletError::InvalidMnemonic(message.to_string())
+}
+
+// ============================================================================
+// Shares
+// ============================================================================
+
+/// One decoded share
+#[derive(PartialEq)]
+struct Share {
+    identifier: u16,
+    iteration_exponent: u8,
+    group_index: u8,
+    group_threshold: u8,
+    group_count: u8,
+    member_index: u8,
+    member_threshold: u8,
+    value: Zeroizing<Vec<u8>>,
+}
+
+impl Share {
+    /// Parameters every share of a set has in common
+    fn common_parameters(&self) -> (u16, u8, u8, u8) {
+        (self.identifier, self.iteration_exponent, self.group_threshold, self.group_count)
+    }
+
+    fn to_mnemonic(&self) -> String {
+        let mut packer = WordPacker::default();
+        // Extendable flag 0
+        packer.push(u32::from(self.identifier) << 1, 16);
+        packer.push(u32::from(self.iteration_exponent), 4);
+        packer.push(u32::from(self.group_index), 4);
+        packer.push(u32::from(self.group_threshold - 1), 4);
+        packer.push(u32::from(self.group_count - 1), 4);
+        packer.push(u32::from(self.member_index), 4);
+        packer.push(u32::from(self.member_threshold - 1), 4);
+        let value_bits = self.value.len() * 8;
+        packer.push(0, (RADIX_BITS - value_bits % RADIX_BITS) % RADIX_BITS);
+        for byte in self.value.iter() {
+            packer.push(u32::from(*byte), 8);
+        }
+        let mut words = packer.words;
+        let checksum = rs1024_create_checksum(&words);
+        words.extend_from_slice(&checksum);
+
+        let wordlist = wordlist();
+        words
+            .iter()
+            .map(|&index| wordlist[index as usize])
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    fn from_mnemonic(mnemonic: &str) -> Result<Self, WalletError> {
+        let wordlist = wordlist();
+        let words = mnemonic
+            .split_whitespace()
+            .map(|word| {
+                let word = word.to_lowercase();
+                wordlist
+                    .binary_search(&word.as_str())
+                    .map(|index| index as u16)
+                    .map_err(|_| WalletError::InvalidMnemonic(format!("Not a SLIP-39 word: {}", word)))
+            })
+            .collect::<Result<Vec<u16>, _>>()?;
+        if words.len() < MIN_SHARE_WORDS {
+            return Err(invalid("Share is too short"));
+        }
+        let value_words = words.len() - ID_EXP_WORDS - PARAMS_WORDS - CHECKSUM_WORDS;
+        let padding = (RADIX_BITS * value_words) % 16;
+        if padding > 8 {
+            return Err(invalid("Share has an invalid length"));
+        }
+
+        let mut reader = WordReader::new(&words);
+        let identifier = reader.read(15) as u16;
+        let extendable = reader.read(1) == 1;
+        let iteration_exponent = reader.read(4) as u8;
+        if extendable {
+            return Err(invalid("Extendable SLIP-39 shares are not supported"));
+        }
+        if !rs1024_verify_checksum(&words) {
+            return Err(invalid("Share checksum is invalid (a word is wrong)"));
+        }
+        let group_index = reader.read(4) as u8;
+        let group_threshold = reader.read(4) as u8 + 1;
+        let group_count = reader.read(4) as u8 + 1;
+        let member_index = reader.read(4) as u8;
+        let member_threshold = reader.read(4) as u8 + 1;
+        if group_threshold > group_count {
+            return Err(invalid("Share group threshold is greater than the group count"));
+        }
+        if reader.read(padding) != 0 {
+            return Err(invalid("Share has invalid padding"));
+        }
+        let value_len = (RADIX_BITS * value_words - padding) / 8;
+        let value = Zeroizing::new((0..value_len).map(|_| reader.read(8) as u8).collect());
+
+        Ok(Self {
+            identifier,
+            iteration_exponent,
+            group_index,
+            group_threshold,
+            group_count,
+            member_index,
+            member_threshold,
+            value,
+        })
+    }
+}
+
+fn wordlist() -> &'static [&'static str] {
+    static WORDS: OnceLock<Vec<&'static str>> = OnceLock::new();
+    WORDS.get_or_init(|| WORDLIST.split_whitespace().collect())
+}
+
+/// Packs bit fields into 10-bit word indices
+#[derive(Default)]
+struct WordPacker {
+    words: Vec<u16>,
+    acc: u16,
+    bits: usize,
+}
+
+impl WordPacker {
+    fn push(&mut self, value: u32, bits: usize) {
+        for i in (0..bits).rev() {
+            self.acc = (self.acc << 1) | ((value >> i) & 1) as u16;
+            self.bits += 1;
+            if self.bits == RADIX_BITS {
+                self.words.push(self.acc);
+                self.acc = 0;
+                self.bits = 0;
+            }
+        }
+    }
+}
+
+/// Reads bit fields from 10-bit word indices
+struct WordReader<'a> {
+    words: &'a [u16],
+    position: usize,
+}
+
+impl<'a> WordReader<'a> {
+    fn new(words: &'a [u16]) -> Self {
+        Self { words, position: 0 }
+    }
+
+    fn read(&mut self, bits: usize) -> u32 {
+        let mut value = 0;
+        for _ in 0..bits {
+            let word = self.words[self.position / RADIX_BITS];
+            let bit = (word >> (RADIX_BITS - 1 - self.position % RADIX_BITS)) & 1;
+            value = (value << 1) | u32::from(bit);
+            self.position += 1;
+        }
+        value
+    }
+}
+
+fn rs1024_polymod(values: impl IntoIterator<Item = u32>) -> u32 {
+    const GEN: [u32; 10] = [
+        0xe0e040, 0x1c1c080, 0x3838100, 0x7070200, 0xe0e0009, 0x1c0c2412, 0x38086c24, 0x3090fc48, 0x21b1f890, 0x3f3f120,
+    ];
+    let mut chk = 1u32;
+    for value in values {
+        let b = chk >> 20;
+        chk = ((chk & 0xfffff) << 10) ^ value;
+        for (i, generator) in GEN.iter().enumerate() {
+            if (b >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk
+}
+
+fn rs1024_values(words: &[u16]) -> impl Iterator<Item = u32> + '_ {
+    CUSTOMIZATION
+        .iter()
+        .map(|&c| u32::from(c))
+        .chain(words.iter().map(|&w| u32::from(w)))
+}
+
+fn rs1024_create_checksum(words: &[u16]) -> [u16; CHECKSUM_WORDS] {
+    let polymod = rs1024_polymod(rs1024_values(words).chain([0, 0, 0])) ^ 1;
+    [
+        ((polymod >> 20) & 1023) as u16,
+        ((polymod >> 10) & 1023) as u16,
+        (polymod & 1023) as u16,
+    ]
+}
+
+fn rs1024_verify_checksum(words: &[u16]) -> bool {
+    rs1024_polymod(rs1024_values(words)) == 1
+}
+
+// ============================================================================
+// Shamir Secret Sharing over GF(256)
+// ============================================================================
+
+/// Exponent and logarithm tables of GF(256) (Rijndael polynomial,
+/// generator x + 1)
+fn gf256_tables() -> &'static ([u8; 255], [u8; 256]) {
+    static TABLES: OnceLock<([u8; 255], [u8; 256])> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let mut exp = [0u8; 255];
+        let mut log = [0u8; 256];
+        let mut poly: u16 = 1;
+        for (i, slot) in exp.iter_mut().enumerate() {
+            *slot = poly as u8;
+            log[poly as usize] = i as u8;
+            poly = (poly << 1) ^ poly;
+            if poly & 0x100 != 0 {
+                poly ^= 0x11b;
+            }
+        }
+        (exp, log)
+    })
+}
+
+/// Value at `x` of the polynomial through `points` (distinct x)
+fn interpolate(points: &[(u8, &[u8])], x: u8) -> Result<Zeroizing<Vec<u8>>, WalletError> {
+    let len = points[0].1.len();
+    if points.iter().any(|(_, value)| value.len() != len) {
+        return Err(invalid("Shares have different lengths"));
+    }
+    if let Some((_, value)) = points.iter().find(|(xi, _)| *xi == x) {
+        return Ok(Zeroizing::new(value.to_vec()));
+    }
+
+    let (exp, log) = gf256_tables();
+    let log_prod: u32 = points.iter().map(|(xi, _)| u32::from(log[(xi ^ x) as usize])).sum();
+    let mut result = Zeroizing::new(vec![0u8; len]);
+    for (xi, value) in points {
+        let log_others: u32 = points
+            .iter()
+            .filter(|(xj, _)| xj != xi)
+            .map(|(xj, _)| u32::from(log[(xi ^ xj) as usize]))
+            .sum();
+        // Logarithm of the Lagrange basis polynomial of `xi` at `x`
+        let log_basis = (log_prod + 255 * 16 - u32::from(log[(xi ^ x) as usize]) - log_others) % 255;
+        for (out, &byte) in result.iter_mut().zip(value.iter()) {
+            if byte != 0 {
+                *out ^= exp[((u32::from(log[byte as usize]) + log_basis) % 255) as usize];
+            }
+        }
+    }
+    Ok(result)
+}
+
+/// Share of a secret: (x, value)
+type RawShare = (u8, Zeroizing<Vec<u8>>);
+
+/// Split `secret` into `count` shares needing `threshold`
+fn split_secret(threshold: u8, count: u8, secret: &[u8]) -> Result<Vec<RawShare>, WalletError> {
+    if threshold == 0 || threshold > count || count > MAX_SHARES {
+        return Err(WalletError::EncryptionFailed(format!(
+            "Cannot split into {} of {} shares",
+            threshold, count
+        )));
+    }
+    if threshold == 1 {
+        return Ok((0..count).map(|i| (i, Zeroizing::new(secret.to_vec()))).collect());
+    }
+
+    let mut rng = rand::thread_rng();
+    let random_count = threshold - 2;
+    let mut shares: Vec<RawShare> = (0..random_count)
+        .map(|i| {
+            let mut value = Zeroizing::new(vec![0u8; secret.len()]);
+            rng.fill_bytes(&mut value);
+            (i, value)
+        })
+        .collect();
+
+    // The digest share lets a wrong combination be detected
+    let mut digest_share = Zeroizing::new(vec![0u8; secret.len()]);
+    rng.fill_bytes(&mut digest_share[DIGEST_LENGTH..]);
+    let digest = create_digest(&digest_share[DIGEST_LENGTH..], secret);
+    digest_share[..DIGEST_LENGTH].copy_from_slice(&digest);
+
+    let mut base: Vec<(u8, &[u8])> = shares.iter().map(|(x, v)| (*x, v.as_slice())).collect();
+    base.push((DIGEST_INDEX, digest_share.as_slice()));
+    base.push((SECRET_INDEX, secret));
+    let rest = (random_count..count)
+        .map(|i| interpolate(&base, i).map(|value| (i, value)))
+        .collect::<Result<Vec<_>, _>>()?;
+    shares.extend(rest);
+    Ok(shares)
+}
+
+/// Recover a secret from `threshold` shares and check its digest
+fn recover_secret(threshold: u8, points: &[(u8, &[u8])]) -> Result<Zeroizing<Vec<u8>>, WalletError> {
+    if threshold == 1 {
+        return Ok(Zeroizing::new(points[0].1.to_vec()));
+    }
+    let secret = interpolate(points, SECRET_INDEX)?;
+    let digest_share = interpolate(points, DIGEST_INDEX)?;
+    if digest_share[..DIGEST_LENGTH] != create_digest(&digest_share[DIGEST_LENGTH..], &secret)[..] {
+        return Err(invalid("Shares do not combine (digest mismatch)"));
+    }
+    Ok(secret)
+}
+
+fn create_digest(random: &[u8], secret: &[u8]) -> [u8; DIGEST_LENGTH] {
+    let mut digest = [0u8; DIGEST_LENGTH];
+    digest.copy_from_slice(&hmac_sha256(random, &[secret])[..DIGEST_LENGTH]);
+    digest
+}
+
+// ============================================================================
+// Master Secret Encryption (4-round Feistel, PBKDF2-HMAC-SHA256)
+// ============================================================================
+
+fn hmac_sha256(key: &[u8], parts: &[&[u8]]) -> [u8; 32] {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(key).unwrap_or_else(|_| unreachable!("HMAC takes keys of any length"));
+    for part in parts {
+        mac.update(part);
+    }
+    let mut out = [0u8; 32];
+    out.copy_from_slice(&mac.finalize().into_bytes());
+    out
+}
+
+fn round_function(round: u8, passphrase: &[u8], iteration_exponent: u8, identifier: u16, r: &[u8]) -> Zeroizing<Vec<u8>> {
+    let mut password = Zeroizing::new(vec![round]);
+    password.extend_from_slice(passphrase);
+    let mut salt = CUSTOMIZATION.to_vec();
+    salt.extend_from_slice(&identifier.to_be_bytes());
+    salt.extend_from_slice(r);
+    let iterations = (BASE_ITERATION_COUNT << iteration_exponent) / u32::from(ROUND_COUNT);
+
+    // PBKDF2 with one output block: `r` is at most 16 bytes
+    let mut u = Zeroizing::new(hmac_sha256(&password, &[&salt, &1u32.to_be_bytes()]));
+    let mut t = Zeroizing::new(*u);
+    for _ in 1..iterations {
+        *u = hmac_sha256(&password, &[&u[..]]);
+        t.iter_mut().zip(u.iter()).for_each(|(t, u)| *t ^= u);
+    }
+    Zeroizing::new(t[..r.len()].to_vec())
+}
+
+fn feistel(secret: &[u8], passphrase: &[u8], iteration_exponent: u8, identifier: u16, rounds: &[u8]) -> Zeroizing<Vec<u8>> {
+    let half = secret.len() / 2;
+    let mut l = Zeroizing::new(secret[..half].to_vec());
+    let mut r = Zeroizing::new(secret[half..].to_vec());
+    for &round in rounds {
+        let f = round_function(round, passphrase, iteration_exponent, identifier, &r);
+        l.iter_mut().zip(f.iter()).for_each(|(l, f)| *l ^= f);
+        std::mem::swap(&mut l, &mut r);
+    }
+    let mut out = Zeroizing::new(r.to_vec());
+    out.extend_from_slice(&l);
+    out
+}
+
+fn encrypt(secret: &[u8], passphrase: &[u8], iteration_exponent: u8, identifier: u16) -> Zeroizing<Vec<u8>> {
+    feistel(secret, passphrase, iteration_exponent, identifier, &[0, 1, 2, 3])
+}
+
+fn decrypt(encrypted: &[u8], passphrase: &[u8], iteration_exponent: u8, identifier: u16) -> Zeroizing<Vec<u8>> {
+    feistel(encrypted, passphrase, iteration_exponent, identifier, &[3, 2, 1, 0])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_split_and_combine_quorum() {
+        let groups = [ShareGroup { threshold: 2, count: 3 }];
+        let shares = split_mnemonic(MNEMONIC, 1, &groups).unwrap();
+        assert_eq!(shares.len(), 1);
+        assert_eq!(shares[0].len(), 3);
+
+        // Any two shares restore
+        let quorum = vec![shares[0][0].clone(), shares[0][2].clone()];
+        assert_eq!(combine_shares(&quorum).unwrap(), MNEMONIC);
+
+        // One share doesn't
+        assert!(combine_shares(&shares[0][..1]).is_err());
+    }
+
+    #[test]
+    fn test_two_groups() {
+        let groups = [ShareGroup { threshold: 1, count: 1 }, ShareGroup { threshold: 2, count: 3 }];
+        let shares = split_mnemonic(MNEMONIC, 2, &groups).unwrap();
+
+        let quorum = vec![shares[0][0].clone(), shares[1][0].clone(), shares[1][1].clone()];
+        assert_eq!(combine_shares(&quorum).unwrap(), MNEMONIC);
+    }
+
+    #[test]
+    fn test_reference_vectors() {
+        let vectors: Vec<(String, Vec<String>, String)> =
+            serde_json::from_str(include_str!("slip39_vectors.json")).unwrap();
+        assert_eq!(vectors.len(), 40);
+        for (description, shares, secret) in vectors {
+            let result = combine(&shares, b"TREZOR");
+            if secret.is_empty() {
+                assert!(result.is_err(), "{}", description);
+            } else {
+                assert_eq!(hex::encode(result.unwrap().as_slice()), secret, "{}", description);
+            }
+        }
+    }
+
+    #[test]
+    fn test_extra_shares_and_incomplete_groups_are_ignored() {
+        let groups = [ShareGroup { threshold: 2, count: 3 }, ShareGroup { threshold: 2, count: 3 }, ShareGroup { threshold: 3, count: 5 }];
+        let shares = split_mnemonic(MNEMONIC, 2, &groups).unwrap();
+
+        // Three shares of group 1, one of group 2 (incomplete), two of group 3
+        let mut given = shares[0].clone();
+        given.push(shares[1][0].clone());
+        given.extend(shares[2][..3].iter().cloned());
+        assert_eq!(combine_shares(&given).unwrap(), MNEMONIC);
+
+        // A word changed: caught by the checksum
+        let mut words: Vec<&str> = shares[0][0].split(' ').collect();
+        words[5] = if words[5] == "academic" { "acid" } else { "academic" };
+        assert!(combine_shares(&[words.join(" "), shares[0][1].clone()]).is_err());
+    }
+
+    #[test]
+    fn test_rejects_single_share_restore() {
+        assert!(validate_groups(1, &[ShareGroup { threshold: 1, count: 1 }]).is_err());
+        assert!(validate_groups(1, &[ShareGroup { threshold: 1, count: 3 }]).is_err());
+        assert!(validate_groups(3, &[ShareGroup { threshold: 2, count: 3 }]).is_err());
+        assert!(validate_groups(1, &[ShareGroup { threshold: 3, count: 5 }]).is_ok());
+    }
+}
@@ -0,0 +1,153 @@
+//! Unlock Throttle Module
+//!
+//! Limits how fast the wallet password can be guessed through the app.
+//!
+//! ## Policy
+//!
+//! - The first `FREE_ATTEMPTS` wrong passwords cost nothing
+//! - Every further failure doubles the wait before the next attempt,
+//!   starting at `BASE_DELAY_SECS` and capped at `MAX_DELAY_SECS`
+//! - A correct password clears the count
+//!
+//! The count lives in the persisted state, so restarting the app does not
+//! reset it. A wall clock set backwards only lengthens the wait.
+//!
+//! `WalletService::verify_password` enforces the policy, so every command
+//! that takes the wallet password is throttled. Where the count is kept
+//! (and what happens on a wrong password) is up to its `PasswordAttempts`.
+
+use std::sync::Mutex;
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use crate::error::WalletError;
+
+/// Wrong passwords allowed before delays start
+pub const FREE_ATTEMPTS: u32 = 3;
+
+/// Wait after the first delayed failure
+pub const BASE_DELAY_SECS: u64 = 5;
+
+/// Longest wait between attempts
+pub const MAX_DELAY_SECS: u64 = 60 * 60;
+
+/// Failed password attempts since the last correct one
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Type)]
+pub struct UnlockThrottle {
+    /// Wrong passwords in a row
+    pub failed_attempts: u32,
+
+    /// Unix time (seconds) of the last wrong password
+    pub last_failure: u64,
+}
+
+impl UnlockThrottle {
+    /// Wait required after the current failures
+    pub fn delay_secs(&self) -> u64 {
+        match self.failed_attempts.saturating_sub(FREE_ATTEMPTS) {
+            0 => 0,
+            // Past the cap long before the shift could overflow
+            over if over > 16 => MAX_DELAY_SECS,
+            over => (BASE_DELAY_SECS << (over - 1)).min(MAX_DELAY_SECS),
+        }
+    }
+
+    /// Seconds until the next attempt is allowed (0 = now)
+    pub fn retry_after(&self, now: u64) -> u64 {
+        let delay = self.delay_secs();
+        if delay == 0 {
+            return 0;
+        }
+        // A clock set back before the failure counts from now
+        let elapsed = now.saturating_sub(self.last_failure);
+        delay.saturating_sub(elapsed)
+    }
+
+    /// Record a wrong password at `now`
+    pub fn record_failure(&mut self, now: u64) {
+        self.failed_attempts = self.failed_attempts.saturating_add(1);
+        self.last_failure = now;
+    }
+
+    /// Clear the count after a correct password
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+/// Where `WalletService` keeps the count of wrong passwords
+#[async_trait]
+pub trait PasswordAttempts: Send + Sync {
+    fn load(&self) -> UnlockThrottle;
+
+    fn save(&self, throttle: &UnlockThrottle) -> Result<(), WalletError>;
+
+    /// Called after a wrong password, once the count is saved
+    async fn on_failure(&self, _throttle: &UnlockThrottle, _retry_after_secs: u64) {}
+}
+
+/// Count kept in memory only (tests, services without persisted state)
+#[derive(Debug, Default)]
+pub struct MemoryAttempts(Mutex<UnlockThrottle>);
+
+#[async_trait]
+impl PasswordAttempts for MemoryAttempts {
+    fn load(&self) -> UnlockThrottle {
+        match self.0.lock() {
+            Ok(throttle) => throttle.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    fn save(&self, throttle: &UnlockThrottle) -> Result<(), WalletError> {
+        match self.0.lock() {
+            Ok(mut current) => *current = throttle.clone(),
+            Err(poisoned) => *poisoned.into_inner() = throttle.clone(),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delay_grows_exponentially() {
+        let mut throttle = UnlockThrottle::default();
+        let delays: Vec<u64> = (0..8)
+            .map(|i| {
+                throttle.record_failure(i);
+                throttle.delay_secs()
+            })
+            .collect();
+        assert_eq!(delays, vec![0, 0, 0, 5, 10, 20, 40, 80]);
+
+        throttle.failed_attempts = 100;
+        assert_eq!(throttle.delay_secs(), MAX_DELAY_SECS);
+        throttle.failed_attempts = u32::MAX;
+        assert_eq!(throttle.delay_secs(), MAX_DELAY_SECS);
+
+        throttle.reset();
+        assert_eq!(throttle, UnlockThrottle::default());
+    }
+
+    #[test]
+    fn test_retry_after() {
+        let mut throttle = UnlockThrottle::default();
+        for _ in 0..FREE_ATTEMPTS {
+            throttle.record_failure(1_000);
+        }
+        assert_eq!(throttle.retry_after(1_000), 0);
+
+        throttle.record_failure(1_000);
+        assert_eq!(throttle.retry_after(1_000), 5);
+        assert_eq!(throttle.retry_after(1_003), 2);
+        assert_eq!(throttle.retry_after(1_005), 0);
+
+        // Clock set backwards: the full delay again, never less
+        assert_eq!(throttle.retry_after(500), 5);
+    }
+}
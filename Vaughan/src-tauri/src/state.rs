@@ -49,35 +49,48 @@
 //! # }
 //! ```
 
+use crate::models::contract::ContractAbiEntry;
 use crate::models::token::TrackedToken;
 
 use crate::chains::evm::EvmAdapter;
 use crate::chains::NetworkId;
 use crate::core::persistence::StateManager;
-use crate::core::{NetworkService, PriceService, TransactionService, WalletService};
+use crate::chains::evm::eip7702::{self, SetCodeRequest};
+use crate::chains::evm::erc4337::{self, BundlerClient};
+use crate::chains::evm::safe::{self, SafeState, SafeTx};
+use crate::core::smart_account::SmartAccountCall;
+use crate::core::backup::{self, WalletBackup};
+use crate::chains::evm::disperse;
+use crate::core::discovery::{
+    self, DiscoveredAccount, DiscoveryOptions, DiscoveryResult, GapScan, MAX_SCAN_INDEX,
+};
+use crate::core::{
+    AddressBookService, BatchAssets, BatchJob, BatchMode, BatchRowStatus, BatchService,
+    BundlerConfig, ContactImportResult, ContactInput, NetworkConfig, NetworkOverride,
+    NetworkService, PriceService, SafeProposal, SafeService, SmartAccountService,
+    TransactionService, WalletService,
+};
+use crate::models::contact::Contact;
+use crate::models::wallet::{Account, SafeAccountConfig};
 use crate::dapp::{ApprovalQueue, RateLimiter, SessionManager, WindowRegistry};
 use crate::error::WalletError;
-use alloy::primitives::Address;
-use std::collections::HashMap;
+use alloy::eips::eip7702::SignedAuthorization;
+use alloy::primitives::{Address, Bytes, B256, U256};
+use alloy::rpc::types::TransactionRequest;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use std::time::Instant;
 use tokio::sync::Mutex;
 use tracing::{debug, error, info, warn};
 
 /// Maps chain ID + optional user-defined networks to the adapter’s native currency metadata.
+///
+/// User-defined networks win over the registry so a user's own config is respected.
 fn resolve_chain_native_token_for_adapter(
     chain_id: u64,
     custom_networks: &[crate::core::NetworkConfig],
 ) -> crate::chains::types::TokenInfo {
     use crate::chains::evm::networks::get_network_by_chain_id;
     use crate::chains::types::TokenInfo;
-    if let Some(net) = get_network_by_chain_id(chain_id) {
-        return TokenInfo::native(
-            net.native_symbol.clone(),
-            net.native_name.clone(),
-            net.decimals,
-        );
-    }
     if let Some(cfg) = custom_networks.iter().find(|n| n.chain_id == chain_id) {
         return TokenInfo::native(
             cfg.native_token.symbol.clone(),
@@ -85,6 +98,13 @@ fn resolve_chain_native_token_for_adapter(
             cfg.native_token.decimals,
         );
     }
+    if let Some(net) = get_network_by_chain_id(chain_id) {
+        return TokenInfo::native(
+            net.native_symbol.clone(),
+            net.native_name.clone(),
+            net.decimals,
+        );
+    }
     TokenInfo::native("NATIVE".into(), format!("Chain {}", chain_id), 18)
 }
 
@@ -102,6 +122,21 @@ pub struct VaughanState {
     /// Wallet service (manages accounts, HD wallet, keyring)
     pub wallet_service: WalletService,
 
+    /// Smart account service (ERC-4337 UserOperations, stateless)
+    pub smart_account_service: SmartAccountService,
+
+    /// Safe multisig service (proposals and owner signatures, stateless)
+    pub safe_service: SafeService,
+
+    /// Batch payout service (CSV parsing and validation, stateless)
+    pub batch_service: BatchService,
+
+    /// Address book service (contact validation, lookup and CSV, stateless)
+    pub address_book_service: AddressBookService,
+
+    /// IDs of batch jobs currently being sent
+    running_batches: Mutex<HashSet<String>>,
+
     // ===== PROVIDER-DEPENDENT ADAPTERS (Per-Network, Cached) =====
     /// EVM adapters cached by network ID
     /// Created on-demand when switching networks
@@ -114,6 +149,10 @@ pub struct VaughanState {
     /// Currently active account address
     active_account: Mutex<Option<Address>>,
 
+    /// Resolved network list (`networks()`), dropped whenever custom
+    /// networks or overrides change
+    networks_cache: std::sync::Mutex<Option<Vec<NetworkConfig>>>,
+
     // ===== DAPP STATE =====
     /// Session manager for dApp connections
     pub session_manager: SessionManager,
@@ -140,8 +179,12 @@ pub struct VaughanState {
     /// Used by BalanceWatcher to optimize polling.
     pub focused_asset: Mutex<Option<String>>,
 
-    /// Last user activity (click/key/focus). Balance watcher backs off when idle.
-    pub last_activity: Mutex<Instant>,
+    /// Last user activity (click/key/focus). Balance watcher backs off when
+    /// idle; the auto-lock supervisor locks after the configured idle time.
+    pub activity: crate::monitoring::auto_lock::ActivityTracker,
+
+    /// RPC health samples, updated by the background health monitor
+    pub network_health: crate::monitoring::network_health::NetworkHealthMonitor,
 
     /// State persistence manager
     state_manager: StateManager,
@@ -177,7 +220,12 @@ impl VaughanState {
             transaction_service: TransactionService::new(),
             network_service: NetworkService::new(),
             price_service: PriceService::new(),
-            wallet_service: WalletService::new(),
+            wallet_service: WalletService::new()?,
+            smart_account_service: SmartAccountService::new(),
+            safe_service: SafeService::new(),
+            batch_service: BatchService::new(),
+            address_book_service: AddressBookService::new(),
+            running_batches: Mutex::new(HashSet::new()),
 
             // Provider-dependent adapters (empty, created on-demand)
             evm_adapters: Mutex::new(HashMap::new()),
@@ -185,6 +233,7 @@ impl VaughanState {
             // Application state (restored from persisted state)
             active_network: Mutex::new(None),
             active_account: Mutex::new(active_account),
+            networks_cache: std::sync::Mutex::new(None),
             tracked_tokens: Mutex::new(tracked_tokens_map),
             focused_asset: Mutex::new(None),
 
@@ -199,7 +248,10 @@ impl VaughanState {
             sound_player: crate::audio::SoundPlayer::new(crate::audio::SoundConfig::default()),
 
             // Activity-based polling: start in "active" mode (3s) until idle
-            last_activity: Mutex::new(Instant::now()),
+            activity: crate::monitoring::auto_lock::ActivityTracker::new(),
+
+            // Network health (filled by background monitor)
+            network_health: crate::monitoring::network_health::NetworkHealthMonitor::new(),
 
             // State persistence
             state_manager,
@@ -299,7 +351,7 @@ impl VaughanState {
     }
 
     pub async fn set_active_chain(&self, chain_id: u64) -> Result<(), WalletError> {
-        let config = crate::chains::evm::networks::get_network_by_chain_id(chain_id).ok_or(
+        let config = self.find_network_by_chain_id(chain_id).ok_or(
             WalletError::UnsupportedNetwork(format!("Chain ID {} not supported", chain_id)),
         )?;
         self.switch_network(&config.id, &config.rpc_url, config.chain_id)
@@ -311,8 +363,8 @@ impl VaughanState {
         &self,
         chain_id: u64,
     ) -> Result<Arc<EvmAdapter>, WalletError> {
-        // Find network info in predefined list
-        let config = self.network_service.find_network_by_chain_id(chain_id).ok_or(
+        // Find network info in predefined + custom list
+        let config = self.find_network_by_chain_id(chain_id).ok_or(
             WalletError::UnsupportedNetwork(format!("Chain ID {} not supported", chain_id)),
         )?;
 
@@ -386,6 +438,20 @@ impl VaughanState {
             .ok_or(WalletError::NetworkNotInitialized)
     }
 
+    /// Check the active network's RPC health before sending a transaction
+    ///
+    /// Based on the latest sample from the background health monitor.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(None)` - No known problem
+    /// * `Ok(Some(warning))` - RPC lagging or unresponsive; show a warning
+    /// * `Err(WalletError::RpcBehind)` - RPC too far behind the chain head to send
+    pub async fn check_send_health(&self) -> Result<Option<String>, WalletError> {
+        let network_id = self.current_network_id().await?;
+        self.network_health.check_send(&network_id).await
+    }
+
     /// Clear adapter cache for a network
     ///
     /// Use this when RPC URL changes or adapter needs to be recreated.
@@ -404,6 +470,250 @@ impl VaughanState {
         self.evm_adapters.lock().await.clear();
     }
 
+    // ========================================================================
+    // Custom Network Management
+    // ========================================================================
+
+    /// Get all known networks
+    ///
+    /// Predefined networks (with user RPC/explorer overrides applied)
+    /// followed by user-added custom networks.
+    pub fn networks(&self) -> Vec<NetworkConfig> {
+        // Resolved under the lock, so a list read before a change can't be
+        // cached after `invalidate_networks`
+        let mut cache = match self.networks_cache.lock() {
+            Ok(cache) => cache,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        cache
+            .get_or_insert_with(|| {
+                let persisted = self.state_manager.load();
+                self.network_service
+                    .resolve_networks(&persisted.custom_networks, &persisted.network_overrides)
+            })
+            .clone()
+    }
+
+    /// Drop the cached network list after custom networks or overrides changed
+    fn invalidate_networks(&self) {
+        match self.networks_cache.lock() {
+            Ok(mut cache) => *cache = None,
+            Err(poisoned) => *poisoned.into_inner() = None,
+        }
+    }
+
+    /// Find a known network (predefined or custom) by chain ID
+    pub fn find_network_by_chain_id(&self, chain_id: u64) -> Option<NetworkConfig> {
+        self.networks().into_iter().find(|n| n.chain_id == chain_id)
+    }
+
+    /// Find a known network (predefined or custom) by network ID
+    pub fn find_network_by_id(&self, network_id: &str) -> Option<NetworkConfig> {
+        self.networks().into_iter().find(|n| n.id == network_id)
+    }
+
+    /// Add a user-defined network
+    ///
+    /// Validates the configuration and checks that the RPC's `eth_chainId`
+    /// matches the declared chain ID before persisting it.
+    ///
+    /// # Errors
+    ///
+    /// * `WalletError::InvalidNetwork` - Invalid config, duplicate ID/chain ID,
+    ///   or chain ID mismatch
+    /// * `WalletError::RpcError` / `ConnectionTimeout` - RPC unreachable
+    pub async fn add_custom_network(&self, config: NetworkConfig) -> Result<(), WalletError> {
+        self.network_service.validate_network_config(&config)?;
+
+        let networks = self.networks();
+        if networks.iter().any(|n| n.id == config.id) {
+            return Err(WalletError::InvalidNetwork(format!(
+                "Network ID '{}' already exists",
+                config.id
+            )));
+        }
+        if let Some(existing) = networks.iter().find(|n| n.chain_id == config.chain_id) {
+            return Err(WalletError::InvalidNetwork(format!(
+                "Chain ID {} is already configured as '{}'",
+                config.chain_id, existing.name
+            )));
+        }
+
+        self.network_service
+            .verify_chain_id(&config.rpc_url, config.chain_id)
+            .await?;
+
+        info!("[VaughanState] Adding custom network: {} ({})", config.id, config.chain_id);
+        let mut persisted = self.state_manager.load();
+        persisted.custom_networks.push(config);
+        self.state_manager.save(&persisted)?;
+        self.invalidate_networks();
+        Ok(())
+    }
+
+    /// Add a network from the network registry
+    ///
+    /// The registry entry is added as a custom network (with the same
+    /// validation and chain ID verification as `add_custom_network`).
+    ///
+    /// # Arguments
+    ///
+    /// * `chain_id` - Chain ID of the registry network
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(NetworkConfig)` - The added network
+    /// * `Err(WalletError::UnsupportedNetwork)` - Chain not in the registry
+    pub async fn add_network_from_registry(
+        &self,
+        chain_id: u64,
+    ) -> Result<NetworkConfig, WalletError> {
+        let config = self
+            .network_service
+            .find_registry_network(chain_id)
+            .ok_or_else(|| {
+                WalletError::UnsupportedNetwork(format!(
+                    "Chain ID {} not found in network registry",
+                    chain_id
+                ))
+            })?;
+
+        self.add_custom_network(config.clone()).await?;
+        Ok(config)
+    }
+
+    /// Replace an existing user-defined network
+    ///
+    /// The chain ID is re-verified against the (possibly new) RPC URL. The
+    /// cached adapter is dropped so the next use picks up the new endpoint.
+    pub async fn update_custom_network(&self, config: NetworkConfig) -> Result<(), WalletError> {
+        self.network_service.validate_network_config(&config)?;
+
+        let persisted = self.state_manager.load();
+        if !persisted.custom_networks.iter().any(|n| n.id == config.id) {
+            return Err(WalletError::InvalidNetwork(format!(
+                "Custom network '{}' not found",
+                config.id
+            )));
+        }
+        if let Some(existing) = self
+            .networks()
+            .iter()
+            .find(|n| n.chain_id == config.chain_id && n.id != config.id)
+        {
+            return Err(WalletError::InvalidNetwork(format!(
+                "Chain ID {} is already configured as '{}'",
+                config.chain_id, existing.name
+            )));
+        }
+
+        self.network_service
+            .verify_chain_id(&config.rpc_url, config.chain_id)
+            .await?;
+
+        info!("[VaughanState] Updating custom network: {}", config.id);
+        let network_id = config.id.clone();
+        let mut persisted = self.state_manager.load();
+        if let Some(slot) = persisted.custom_networks.iter_mut().find(|n| n.id == network_id) {
+            *slot = config;
+        }
+        self.state_manager.save(&persisted)?;
+        self.invalidate_networks();
+
+        self.refresh_network(&network_id).await
+    }
+
+    /// Remove a user-defined network
+    ///
+    /// The active network cannot be removed; switch away from it first.
+    pub async fn remove_custom_network(&self, network_id: &str) -> Result<(), WalletError> {
+        if self.current_network_id().await.ok().as_deref() == Some(network_id) {
+            return Err(WalletError::InvalidNetwork(
+                "Cannot remove the active network. Switch to another network first.".to_string(),
+            ));
+        }
+
+        let mut persisted = self.state_manager.load();
+        let before = persisted.custom_networks.len();
+        persisted.custom_networks.retain(|n| n.id != network_id);
+        if persisted.custom_networks.len() == before {
+            return Err(WalletError::InvalidNetwork(format!(
+                "Custom network '{}' not found",
+                network_id
+            )));
+        }
+
+        info!("[VaughanState] Removing custom network: {}", network_id);
+        self.state_manager.save(&persisted)?;
+        self.invalidate_networks();
+        self.clear_adapter_cache(network_id).await;
+        Ok(())
+    }
+
+    /// Override the RPC and/or explorer URL of a predefined network
+    ///
+    /// A new RPC URL is verified against the network's chain ID before it is
+    /// saved. Passing `None` for both URLs removes the override.
+    pub async fn set_network_override(
+        &self,
+        network_id: &str,
+        rpc_url: Option<String>,
+        explorer_url: Option<String>,
+    ) -> Result<(), WalletError> {
+        let predefined = self.network_service.find_network_by_id(network_id).ok_or_else(|| {
+            WalletError::InvalidNetwork(format!("'{}' is not a predefined network", network_id))
+        })?;
+
+        // Validate by applying the override to the predefined config
+        let mut candidate = predefined.clone();
+        if let Some(ref url) = rpc_url {
+            candidate.rpc_url = url.clone();
+        }
+        if let Some(ref url) = explorer_url {
+            candidate.explorer_url = Some(url.clone());
+        }
+        self.network_service.validate_network_config(&candidate)?;
+
+        if let Some(ref url) = rpc_url {
+            self.network_service
+                .verify_chain_id(url, predefined.chain_id)
+                .await?;
+        }
+
+        let mut persisted = self.state_manager.load();
+        persisted.network_overrides.retain(|o| o.network_id != network_id);
+        if rpc_url.is_some() || explorer_url.is_some() {
+            info!("[VaughanState] Setting network override: {}", network_id);
+            persisted.network_overrides.push(NetworkOverride {
+                network_id: network_id.to_string(),
+                rpc_url,
+                explorer_url,
+            });
+        } else {
+            info!("[VaughanState] Clearing network override: {}", network_id);
+        }
+        self.state_manager.save(&persisted)?;
+        self.invalidate_networks();
+
+        self.refresh_network(network_id).await
+    }
+
+    /// Drop the cached adapter for a network whose endpoint changed
+    ///
+    /// If the network is active, it is switched to again so the active
+    /// adapter uses the new RPC URL right away.
+    async fn refresh_network(&self, network_id: &str) -> Result<(), WalletError> {
+        self.clear_adapter_cache(network_id).await;
+
+        if self.current_network_id().await.ok().as_deref() == Some(network_id) {
+            if let Some(config) = self.find_network_by_id(network_id) {
+                self.switch_network(&config.id, &config.rpc_url, config.chain_id)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
     // ========================================================================
     // Account Management
     // ========================================================================
@@ -435,13 +745,1238 @@ impl VaughanState {
             .ok_or(WalletError::NoActiveAccount)
     }
 
+    // ========================================================================
+    // Smart Accounts (ERC-4337)
+    // ========================================================================
+
+    /// Configured bundler endpoints
+    pub fn bundlers(&self) -> Vec<BundlerConfig> {
+        self.state_manager.load().bundlers
+    }
+
+    /// Set or clear (`None`) the bundler URL for a chain
+    ///
+    /// A new URL must answer `eth_supportedEntryPoints` and list the
+    /// EntryPoint v0.7 used by smart accounts.
+    pub async fn set_bundler_url(&self, chain_id: u64, url: Option<String>) -> Result<(), WalletError> {
+        if let Some(ref url) = url {
+            self.smart_account_service.validate_bundler_url(url)?;
+            let entry_points = BundlerClient::new(url).supported_entry_points().await?;
+            if !entry_points.contains(&erc4337::ENTRY_POINT_V07) {
+                return Err(WalletError::ConfigError(format!(
+                    "Bundler does not support EntryPoint {}",
+                    erc4337::ENTRY_POINT_V07
+                )));
+            }
+        }
+
+        let mut persisted = self.state_manager.load();
+        persisted.bundlers.retain(|b| b.chain_id != chain_id);
+        if let Some(url) = url {
+            info!("[VaughanState] Setting bundler for chain {}", chain_id);
+            persisted.bundlers.push(BundlerConfig { chain_id, url });
+        } else {
+            info!("[VaughanState] Clearing bundler for chain {}", chain_id);
+        }
+        self.state_manager.save(&persisted)
+    }
+
+    /// Bundler client for a chain
+    ///
+    /// # Errors
+    ///
+    /// * `WalletError::ConfigError` - No bundler configured for the chain
+    pub fn bundler_for_chain(&self, chain_id: u64) -> Result<BundlerClient, WalletError> {
+        self.bundlers()
+            .into_iter()
+            .find(|b| b.chain_id == chain_id)
+            .map(|b| BundlerClient::new(&b.url))
+            .ok_or_else(|| {
+                WalletError::ConfigError(format!("No bundler configured for chain {}", chain_id))
+            })
+    }
+
+    /// Whether an address is one of the wallet's smart accounts
+    pub async fn smart_account(&self, address: &Address) -> Option<Account> {
+        self.wallet_service
+            .get_account(address)
+            .await
+            .filter(|a| a.smart_account.is_some())
+    }
+
+    /// Add a smart account owned by an existing wallet account
+    ///
+    /// The address is the counterfactual address reported by the factory on
+    /// the active network; the contract is deployed with the first
+    /// UserOperation. Each additional smart account of the same owner uses
+    /// the next salt.
+    pub async fn create_smart_account(
+        &self,
+        owner: Address,
+        name: Option<String>,
+        password: &str,
+    ) -> Result<Account, WalletError> {
+        let accounts = self.wallet_service.get_accounts().await?;
+        let salt = accounts
+            .iter()
+            .filter_map(|a| a.smart_account.as_ref())
+            .filter(|c| c.owner == owner)
+            .count() as u64;
+
+        let config = self.smart_account_service.default_config(owner, salt);
+        let adapter = self.current_adapter().await?;
+        let address = self.smart_account_service.compute_address(&adapter, &config).await?;
+        if accounts.iter().any(|a| a.address == address) {
+            return Err(WalletError::Custom(format!("Account {} already exists", address)));
+        }
+
+        let name = name.unwrap_or_else(|| {
+            let count = accounts.iter().filter(|a| a.smart_account.is_some()).count();
+            format!("Smart Account {}", count + 1)
+        });
+        let account = self
+            .wallet_service
+            .add_smart_account(address, name, config, password)
+            .await?;
+        info!("[VaughanState] Added smart account {} (owner {})", address, owner);
+
+        let _ = self.save_state().await;
+        Ok(account)
+    }
+
+    /// Send a call from a smart account as a UserOperation
+    ///
+    /// Builds the operation on the active network, has the bundler estimate
+    /// its gas, signs it with the owner and submits it.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(B256)` - UserOperation hash
+    pub async fn send_user_operation(
+        &self,
+        from: Address,
+        call: SmartAccountCall,
+        password: &str,
+    ) -> Result<B256, WalletError> {
+        self.wallet_service.verify_password(password).await?;
+        let account = self
+            .smart_account(&from)
+            .await
+            .ok_or_else(|| WalletError::AccountNotFound(from.to_string()))?;
+
+        self.check_send_health().await?;
+        let adapter = self.current_adapter().await?;
+        let bundler = self.bundler_for_chain(adapter.chain_id())?;
+
+        let mut op = self
+            .smart_account_service
+            .prepare_user_operation(&adapter, &bundler, &account, call)
+            .await?;
+        self.smart_account_service
+            .sign_user_operation(&self.wallet_service, &account, &mut op, adapter.chain_id(), password)
+            .await?;
+
+        let entry_point = account
+            .smart_account
+            .as_ref()
+            .map(|c| c.entry_point)
+            .unwrap_or(erc4337::ENTRY_POINT_V07);
+        let hash = bundler.send_user_operation(&op, entry_point).await?;
+        info!("[VaughanState] UserOperation sent: {:?}", hash);
+        Ok(hash)
+    }
+
+    // ========================================================================
+    // EIP-7702 Delegation
+    // ========================================================================
+
+    /// Sign an EIP-7702 authorization for one of the wallet's EOAs
+    ///
+    /// `chain_id` defaults to the active chain and `nonce` to the account's
+    /// current nonce. When the authority also sends the set-code transaction,
+    /// that transaction uses the current nonce first, so pass
+    /// `for_own_transaction` to sign for the next one.
+    pub async fn sign_authorization(
+        &self,
+        authority: Address,
+        delegate: Address,
+        chain_id: Option<u64>,
+        nonce: Option<u64>,
+        for_own_transaction: bool,
+        password: &str,
+    ) -> Result<SignedAuthorization, WalletError> {
+        self.wallet_service.ensure_can_sign_hash(&authority).await?;
+        let adapter = self.current_adapter().await?;
+        let chain_id = chain_id.unwrap_or_else(|| adapter.chain_id());
+        let nonce = match nonce {
+            Some(nonce) => nonce,
+            None => {
+                let current = adapter.get_transaction_count(authority).await?;
+                if for_own_transaction { current + 1 } else { current }
+            },
+        };
+
+        info!("[VaughanState] Signing EIP-7702 authorization: {} -> {}", authority, delegate);
+        self.wallet_service
+            .sign_authorization(&authority, eip7702::authorization(chain_id, delegate, nonce), password)
+            .await
+    }
+
+    /// Sign and send a type-4 (set-code) transaction on the active network
+    ///
+    /// The transaction is signed locally with `request.from`'s key and
+    /// carries the given signed authorizations.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(B256)` - Transaction hash
+    pub async fn send_set_code_transaction(
+        &self,
+        request: SetCodeRequest,
+        password: &str,
+    ) -> Result<B256, WalletError> {
+        let SetCodeRequest { from, to, value, data, gas_limit, authorizations } = request;
+
+        if authorizations.is_empty() {
+            return Err(WalletError::InvalidTransaction(
+                "A set-code transaction needs at least one authorization".to_string(),
+            ));
+        }
+
+        self.wallet_service.verify_password(password).await?;
+
+        let mut tx = TransactionRequest::default();
+        tx.to = Some(to.into());
+        tx.value = Some(value);
+        tx.input.input = Some(data);
+        tx.authorization_list = Some(authorizations);
+        tx.gas = gas_limit;
+
+        let hash = self.send_locally_signed(from, tx).await?;
+        info!("[VaughanState] Set-code transaction sent: {:?}", hash);
+        Ok(hash)
+    }
+
+    /// Sign a transaction with the account's signer (local key or external
+    /// signer) and broadcast it raw
+    ///
+    /// Fills in chain ID, nonce, EIP-1559 fees and (if unset) the gas limit
+    /// on the active network. Callers verify the password first.
+    pub(crate) async fn send_locally_signed(
+        &self,
+        from: Address,
+        mut tx: TransactionRequest,
+    ) -> Result<B256, WalletError> {
+        use alloy::eips::eip2718::Encodable2718;
+        use alloy::providers::Provider;
+
+        self.check_send_health().await?;
+        let signer = self.wallet_service.get_signer(&from).await?;
+        let adapter = self.current_adapter().await?;
+        let provider = adapter.provider();
+
+        let fees = provider
+            .estimate_eip1559_fees()
+            .await
+            .map_err(|e| WalletError::GasEstimationFailed(e.to_string()))?;
+
+        tx.from = Some(from);
+        tx.chain_id = Some(adapter.chain_id());
+        if tx.nonce.is_none() {
+            tx.nonce = Some(adapter.get_transaction_count(from).await?);
+        }
+        tx.max_fee_per_gas = Some(fees.max_fee_per_gas);
+        tx.max_priority_fee_per_gas = Some(fees.max_priority_fee_per_gas);
+        if tx.gas.is_none() {
+            tx.gas = Some(
+                provider
+                    .estimate_gas(tx.clone())
+                    .await
+                    .map_err(|e| WalletError::GasEstimationFailed(e.to_string()))?,
+            );
+        }
+
+        let envelope = signer.sign_transaction(tx).await?;
+        let pending = provider
+            .send_raw_transaction(&envelope.encoded_2718())
+            .await
+            .map_err(|e| WalletError::TransactionFailed(e.to_string()))?;
+
+        Ok(*pending.tx_hash())
+    }
+
+    // ========================================================================
+    // Safe Multisig
+    // ========================================================================
+
+    /// Whether an address is one of the wallet's Safe accounts
+    pub async fn safe_account(&self, address: &Address) -> Option<Account> {
+        self.wallet_service
+            .get_account(address)
+            .await
+            .filter(|a| a.safe.is_some())
+    }
+
+    /// Adapter for a Safe's chain (must be the active network)
+    async fn safe_adapter(&self, safe_address: Address) -> Result<Arc<EvmAdapter>, WalletError> {
+        let account = self
+            .safe_account(&safe_address)
+            .await
+            .ok_or_else(|| WalletError::AccountNotFound(safe_address.to_string()))?;
+        let chain_id = account.safe.map(|c| c.chain_id).unwrap_or_default();
+
+        let adapter = self.current_adapter().await?;
+        if adapter.chain_id() != chain_id {
+            return Err(WalletError::InvalidNetwork(format!(
+                "Safe {} is on chain {}; switch to that network first",
+                safe_address, chain_id
+            )));
+        }
+        Ok(adapter)
+    }
+
+    /// Add an existing Safe on the active network as an account
+    ///
+    /// The address must answer `getOwners`/`getThreshold`. None of the
+    /// owners has to be in this wallet; signatures can be imported.
+    pub async fn add_safe_account(
+        &self,
+        address: Address,
+        name: Option<String>,
+        password: &str,
+    ) -> Result<(Account, SafeState), WalletError> {
+        let accounts = self.wallet_service.get_accounts().await?;
+        if accounts.iter().any(|a| a.address == address) {
+            return Err(WalletError::Custom(format!("Account {} already exists", address)));
+        }
+
+        let adapter = self.current_adapter().await?;
+        let safe_state = safe::fetch_state(&adapter.provider(), address).await?;
+
+        let name = name.unwrap_or_else(|| {
+            let count = accounts.iter().filter(|a| a.safe.is_some()).count();
+            format!("Safe {}", count + 1)
+        });
+        let config = SafeAccountConfig { chain_id: adapter.chain_id() };
+        let account = self
+            .wallet_service
+            .add_safe_account(address, name, config, password)
+            .await?;
+        info!(
+            "[VaughanState] Added Safe {} ({}/{} owners)",
+            address,
+            safe_state.threshold,
+            safe_state.owners.len()
+        );
+
+        let _ = self.save_state().await;
+        Ok((account, safe_state))
+    }
+
+    /// Current owners, threshold and nonce of a Safe account
+    pub async fn safe_state(&self, safe_address: Address) -> Result<SafeState, WalletError> {
+        let adapter = self.safe_adapter(safe_address).await?;
+        safe::fetch_state(&adapter.provider(), safe_address).await
+    }
+
+    /// Stored proposals, optionally only those of one Safe
+    pub fn safe_proposals(&self, safe_address: Option<Address>) -> Vec<SafeProposal> {
+        let safe_filter = safe_address.map(|a| format!("{:?}", a));
+        self.state_manager
+            .load()
+            .safe_proposals
+            .into_iter()
+            .filter(|p| {
+                safe_filter
+                    .as_ref()
+                    .map(|s| p.safe.eq_ignore_ascii_case(s))
+                    .unwrap_or(true)
+            })
+            .collect()
+    }
+
+    /// Stored proposal by SafeTx hash
+    pub fn safe_proposal(&self, safe_tx_hash: &str) -> Result<SafeProposal, WalletError> {
+        self.safe_proposals(None)
+            .into_iter()
+            .find(|p| p.safe_tx_hash.eq_ignore_ascii_case(safe_tx_hash))
+            .ok_or_else(|| WalletError::Custom(format!("Safe proposal {} not found", safe_tx_hash)))
+    }
+
+    /// Insert or replace a proposal (matched by SafeTx hash)
+    fn store_safe_proposal(&self, proposal: &SafeProposal) -> Result<(), WalletError> {
+        let mut persisted = self.state_manager.load();
+        persisted
+            .safe_proposals
+            .retain(|p| !p.safe_tx_hash.eq_ignore_ascii_case(&proposal.safe_tx_hash));
+        persisted.safe_proposals.push(proposal.clone());
+        self.state_manager.save(&persisted)
+    }
+
+    /// Remove a proposal
+    pub fn delete_safe_proposal(&self, safe_tx_hash: &str) -> Result<(), WalletError> {
+        let mut persisted = self.state_manager.load();
+        persisted
+            .safe_proposals
+            .retain(|p| !p.safe_tx_hash.eq_ignore_ascii_case(safe_tx_hash));
+        self.state_manager.save(&persisted)
+    }
+
+    /// Create a proposal for a call from a Safe
+    ///
+    /// Uses the next free Safe nonce: the on-chain nonce, or one past the
+    /// highest pending proposal. The hash is checked against the Safe's own
+    /// `getTransactionHash` so owners never sign something it won't accept.
+    pub async fn propose_safe_transaction(
+        &self,
+        safe_address: Address,
+        to: Address,
+        value: U256,
+        data: Bytes,
+        operation: u8,
+        origin: Option<String>,
+    ) -> Result<SafeProposal, WalletError> {
+        let adapter = self.safe_adapter(safe_address).await?;
+        let provider = adapter.provider();
+        let safe_state = safe::fetch_state(&provider, safe_address).await?;
+
+        let on_chain: u64 = safe_state.nonce.saturating_to();
+        let nonce = self
+            .safe_proposals(Some(safe_address))
+            .iter()
+            .filter(|p| p.executed_tx_hash.is_none() && p.nonce >= on_chain)
+            .map(|p| p.nonce + 1)
+            .max()
+            .unwrap_or(on_chain)
+            .max(on_chain);
+
+        let mut tx = SafeTx::call(to, value, data, U256::from(nonce));
+        tx.operation = operation;
+        let proposal = self
+            .safe_service
+            .create_proposal(adapter.chain_id(), safe_address, &tx, origin);
+
+        let expected = safe::fetch_tx_hash(&provider, safe_address, &tx).await?;
+        if format!("{:?}", expected) != proposal.safe_tx_hash {
+            return Err(WalletError::InvalidTransaction(
+                "Safe reports a different transaction hash (unsupported Safe version?)".to_string(),
+            ));
+        }
+
+        self.store_safe_proposal(&proposal)?;
+        info!("[VaughanState] Safe proposal {} (nonce {})", proposal.safe_tx_hash, nonce);
+        Ok(proposal)
+    }
+
+    /// Sign a stored proposal with a local owner key
+    pub async fn sign_safe_proposal(
+        &self,
+        safe_tx_hash: &str,
+        owner: Address,
+        password: &str,
+    ) -> Result<SafeProposal, WalletError> {
+        let mut proposal = self.safe_proposal(safe_tx_hash)?;
+        let safe_state = self.safe_state(self.safe_service.safe_address(&proposal)?).await?;
+        if !safe_state.owners.contains(&owner) {
+            return Err(WalletError::PermissionDenied(format!("{} is not an owner of this Safe", owner)));
+        }
+
+        self.safe_service
+            .sign(&self.wallet_service, &mut proposal, owner, password)
+            .await?;
+        self.store_safe_proposal(&proposal)?;
+        info!("[VaughanState] Signed Safe proposal {} as {}", proposal.safe_tx_hash, owner);
+        Ok(proposal)
+    }
+
+    /// Import a proposal (with signatures) exported by another owner
+    ///
+    /// Merges signatures into a matching stored proposal, or stores it as
+    /// new. Signatures that don't verify or aren't from a current owner are
+    /// dropped.
+    pub async fn import_safe_proposal(&self, json: &str) -> Result<SafeProposal, WalletError> {
+        let imported: SafeProposal = serde_json::from_str(json)
+            .map_err(|e| WalletError::ParseError(format!("Invalid Safe proposal: {}", e)))?;
+        let safe_address = self.safe_service.safe_address(&imported)?;
+        self.safe_service.verify_hash(&imported)?;
+
+        let adapter = self.safe_adapter(safe_address).await?;
+        if imported.chain_id != adapter.chain_id() {
+            return Err(WalletError::InvalidNetwork(format!(
+                "Proposal is for chain {}",
+                imported.chain_id
+            )));
+        }
+        let safe_state = safe::fetch_state(&adapter.provider(), safe_address).await?;
+
+        let mut proposal = match self.safe_proposal(&imported.safe_tx_hash) {
+            Ok(existing) => existing,
+            Err(_) => SafeProposal {
+                signatures: Vec::new(),
+                executed_tx_hash: None,
+                ..imported.clone()
+            },
+        };
+        let added = self
+            .safe_service
+            .merge_signatures(&mut proposal, &imported, &safe_state.owners)?;
+
+        self.store_safe_proposal(&proposal)?;
+        info!(
+            "[VaughanState] Imported Safe proposal {} ({} new signatures)",
+            proposal.safe_tx_hash, added
+        );
+        Ok(proposal)
+    }
+
+    /// Execute a proposal that has enough owner signatures
+    ///
+    /// `executor` (any wallet account) sends `execTransaction` and pays gas.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(B256)` - Transaction hash
+    pub async fn execute_safe_proposal(
+        &self,
+        safe_tx_hash: &str,
+        executor: Address,
+        password: &str,
+    ) -> Result<B256, WalletError> {
+        self.wallet_service.verify_password(password).await?;
+        let mut proposal = self.safe_proposal(safe_tx_hash)?;
+        if proposal.executed_tx_hash.is_some() {
+            return Err(WalletError::InvalidTransaction("Proposal already executed".to_string()));
+        }
+
+        let safe_address = self.safe_service.safe_address(&proposal)?;
+        let safe_state = self.safe_state(safe_address).await?;
+        if U256::from(proposal.nonce) != safe_state.nonce {
+            return Err(WalletError::InvalidTransaction(format!(
+                "Proposal nonce {} does not match Safe nonce {}",
+                proposal.nonce, safe_state.nonce
+            )));
+        }
+
+        let signatures = self
+            .safe_service
+            .valid_signatures(&proposal, &safe_state.owners)?;
+        if (signatures.len() as u64) < safe_state.threshold {
+            return Err(WalletError::InvalidTransaction(format!(
+                "{} of {} required signatures",
+                signatures.len(),
+                safe_state.threshold
+            )));
+        }
+
+        let tx = self.safe_service.safe_tx(&proposal)?;
+        let mut request = TransactionRequest::default();
+        request.to = Some(safe_address.into());
+        request.input.input = Some(safe::exec_transaction_call_data(
+            &tx,
+            safe::encode_signatures(&signatures),
+        ));
+
+        let hash = self.send_locally_signed(executor, request).await?;
+        proposal.executed_tx_hash = Some(format!("{:?}", hash));
+        self.store_safe_proposal(&proposal)?;
+        info!("[VaughanState] Safe proposal {} executed: {:?}", proposal.safe_tx_hash, hash);
+        Ok(hash)
+    }
+
+    // ========================================================================
+    // Contract ABIs
+    // ========================================================================
+
+    /// Imported contract ABIs, optionally only those of one chain
+    pub fn contract_abis(&self, chain_id: Option<u64>) -> Vec<ContractAbiEntry> {
+        self.state_manager
+            .load()
+            .contract_abis
+            .into_iter()
+            .filter(|e| chain_id.map(|id| e.chain_id == id).unwrap_or(true))
+            .collect()
+    }
+
+    /// Imported ABI of a contract
+    ///
+    /// # Errors
+    ///
+    /// * `WalletError::Custom` - No ABI imported for the contract
+    pub fn contract_abi(&self, chain_id: u64, address: Address) -> Result<alloy::json_abi::JsonAbi, WalletError> {
+        let address = format!("{:?}", address);
+        let entry = self
+            .contract_abis(Some(chain_id))
+            .into_iter()
+            .find(|e| e.address.eq_ignore_ascii_case(&address))
+            .ok_or_else(|| {
+                WalletError::Custom(format!("No ABI imported for {} on chain {}", address, chain_id))
+            })?;
+        crate::chains::evm::abi::parse_abi(&entry.abi)
+    }
+
+    /// Save an imported ABI, replacing any earlier one for the same contract
+    pub fn save_contract_abi(&self, entry: ContractAbiEntry) -> Result<(), WalletError> {
+        let mut persisted = self.state_manager.load();
+        persisted
+            .contract_abis
+            .retain(|e| !(e.chain_id == entry.chain_id && e.address.eq_ignore_ascii_case(&entry.address)));
+        info!("[VaughanState] Saving ABI for {} on chain {}", entry.address, entry.chain_id);
+        persisted.contract_abis.push(entry);
+        self.state_manager.save(&persisted)
+    }
+
+    /// Remove an imported ABI
+    pub fn remove_contract_abi(&self, chain_id: u64, address: Address) -> Result<(), WalletError> {
+        let address = format!("{:?}", address);
+        let mut persisted = self.state_manager.load();
+        persisted
+            .contract_abis
+            .retain(|e| !(e.chain_id == chain_id && e.address.eq_ignore_ascii_case(&address)));
+        self.state_manager.save(&persisted)
+    }
+
+    // ========================================================================
+    // Address Book
+    // ========================================================================
+
+    /// Contacts sorted by name, optionally only those that apply to a chain
+    pub fn contacts(&self, chain_id: Option<u64>) -> Vec<Contact> {
+        let mut contacts: Vec<Contact> = self
+            .state_manager
+            .load()
+            .contacts
+            .into_iter()
+            .filter(|c| match (chain_id, c.chain_id) {
+                (Some(chain), Some(scope)) => chain == scope,
+                _ => true,
+            })
+            .collect();
+        contacts.sort_by_key(|c| c.name.to_lowercase());
+        contacts
+    }
+
+    /// Contact for an address on a chain (chain-scoped contacts win)
+    pub fn contact_for(&self, chain_id: u64, address: &str) -> Option<Contact> {
+        let contacts = self.state_manager.load().contacts;
+        self.address_book_service
+            .lookup(&contacts, chain_id, address)
+            .cloned()
+    }
+
+    /// Add a contact
+    pub fn add_contact(&self, input: ContactInput) -> Result<Contact, WalletError> {
+        let mut persisted = self.state_manager.load();
+        let contact = self.address_book_service.add(&mut persisted.contacts, input)?;
+        self.state_manager.save(&persisted)?;
+        info!("[VaughanState] Contact added: {} ({})", contact.name, contact.address);
+        Ok(contact)
+    }
+
+    /// Update a contact
+    pub fn update_contact(&self, id: &str, input: ContactInput) -> Result<Contact, WalletError> {
+        let mut persisted = self.state_manager.load();
+        let contact = self.address_book_service.update(&mut persisted.contacts, id, input)?;
+        self.state_manager.save(&persisted)?;
+        Ok(contact)
+    }
+
+    /// Delete a contact
+    pub fn delete_contact(&self, id: &str) -> Result<(), WalletError> {
+        let mut persisted = self.state_manager.load();
+        let before = persisted.contacts.len();
+        persisted.contacts.retain(|c| c.id != id);
+        if persisted.contacts.len() == before {
+            return Err(WalletError::Custom(format!("Contact not found: {}", id)));
+        }
+        self.state_manager.save(&persisted)
+    }
+
+    /// Import contacts from CSV (existing contacts are updated)
+    pub fn import_contacts_csv(&self, csv: &str) -> Result<ContactImportResult, WalletError> {
+        let mut persisted = self.state_manager.load();
+        let result = self.address_book_service.import_csv(&mut persisted.contacts, csv)?;
+        self.state_manager.save(&persisted)?;
+        info!(
+            "[VaughanState] Contacts imported: {} added, {} updated, {} rejected",
+            result.added,
+            result.updated,
+            result.errors.len()
+        );
+        Ok(result)
+    }
+
+    /// Export all contacts as CSV
+    pub fn export_contacts_csv(&self) -> String {
+        self.address_book_service.export_csv(&self.contacts(None))
+    }
+
+    // ========================================================================
+    // Batch Payouts
+    // ========================================================================
+
+    /// All batch jobs, newest first
+    pub fn batch_jobs(&self) -> Vec<BatchJob> {
+        let mut jobs = self.state_manager.load().batch_jobs;
+        jobs.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        jobs
+    }
+
+    /// Batch job by ID
+    pub fn batch_job(&self, id: &str) -> Result<BatchJob, WalletError> {
+        self.state_manager
+            .load()
+            .batch_jobs
+            .into_iter()
+            .find(|j| j.id == id)
+            .ok_or_else(|| WalletError::Custom(format!("Batch job not found: {}", id)))
+    }
+
+    fn store_batch_job(&self, job: &BatchJob) -> Result<(), WalletError> {
+        let mut persisted = self.state_manager.load();
+        match persisted.batch_jobs.iter_mut().find(|j| j.id == job.id) {
+            Some(existing) => *existing = job.clone(),
+            None => persisted.batch_jobs.push(job.clone()),
+        }
+        self.state_manager.save(&persisted)
+    }
+
+    /// Delete a batch job (its sent transactions are not affected)
+    pub async fn delete_batch_job(&self, id: &str) -> Result<(), WalletError> {
+        if self.running_batches.lock().await.contains(id) {
+            return Err(WalletError::InvalidTransaction("Batch is being sent".to_string()));
+        }
+        let mut persisted = self.state_manager.load();
+        persisted.batch_jobs.retain(|j| j.id != id);
+        self.state_manager.save(&persisted)
+    }
+
+    /// Adapter for a batch (its chain must be the active network)
+    async fn batch_adapter(&self, job: &BatchJob) -> Result<Arc<EvmAdapter>, WalletError> {
+        let adapter = self.current_adapter().await?;
+        if adapter.chain_id() != job.chain_id {
+            return Err(WalletError::InvalidNetwork(format!(
+                "Batch is for chain {}; switch to it first",
+                job.chain_id
+            )));
+        }
+        Ok(adapter)
+    }
+
+    /// Check the paying account can cover the pending rows plus gas
+    ///
+    /// Also stores the fee estimate on the job.
+    async fn check_batch_balance(&self, adapter: &EvmAdapter, job: &mut BatchJob) -> Result<(), WalletError> {
+        use crate::chains::ChainAdapter;
+        use crate::chains::evm::utils::format_wei_to_eth;
+
+        let fee = U256::from(adapter.get_gas_price().await?) * U256::from(job.estimated_gas());
+        job.estimated_fee = fee.to_string();
+
+        for (token, indices) in job.pending_groups() {
+            let mut need = job.sum(&indices);
+            let (balance, decimals) = match token.as_deref() {
+                Some(token) => {
+                    let balance = adapter.get_token_balance(token, &job.from).await?;
+                    (balance.raw, balance.token.decimals)
+                }
+                None => {
+                    need += fee;
+                    (adapter.get_balance(&job.from).await?.raw, adapter.native_decimals())
+                }
+            };
+            let have = balance.parse::<U256>().unwrap_or_default();
+            if have < need {
+                return Err(WalletError::InsufficientBalance {
+                    need: format_wei_to_eth(need, decimals),
+                    have: format_wei_to_eth(have, decimals),
+                });
+            }
+        }
+
+        // Fee alone when only tokens are paid out
+        if job.pending_groups().iter().all(|(token, _)| token.is_some()) {
+            let have = adapter.get_balance(&job.from).await?.raw.parse::<U256>().unwrap_or_default();
+            if have < fee {
+                return Err(WalletError::InsufficientBalance {
+                    need: format_wei_to_eth(fee, adapter.native_decimals()),
+                    have: format_wei_to_eth(have, adapter.native_decimals()),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Create a batch payout job from CSV on the active network
+    ///
+    /// Every row is validated; invalid rows are reported on the job (which
+    /// can't run until they are fixed). The valid rows' totals plus
+    /// estimated gas are checked against the account's balances.
+    pub async fn create_batch_job(&self, from: Address, csv: &str, mode: BatchMode) -> Result<BatchJob, WalletError> {
+        use crate::chains::ChainAdapter;
+
+        if self.wallet_service.get_account(&from).await.is_none() {
+            return Err(WalletError::AccountNotFound(format!("{:?}", from)));
+        }
+        let adapter = self.current_adapter().await?;
+        let rows = self.batch_service.parse_csv(csv)?;
+
+        let mut assets = BatchAssets::new();
+        let native = adapter.get_balance(&format!("{:?}", from)).await?.token;
+        assets.insert(None, (native.symbol, adapter.native_decimals()));
+        for token in rows.iter().filter_map(|r| r.token.as_deref()) {
+            let Ok(address) = token.parse::<Address>() else { continue };
+            if assets.contains_key(&Some(address)) {
+                continue;
+            }
+            // Unreadable tokens are left out; their rows become invalid
+            if let Ok(info) = adapter.get_token_metadata(token).await {
+                assets.insert(Some(address), (info.symbol, info.decimals));
+            }
+        }
+
+        if mode == BatchMode::Disperse && !disperse::is_deployed(&adapter.provider()).await? {
+            return Err(WalletError::UnsupportedNetwork(format!(
+                "Disperse is not deployed on chain {}",
+                adapter.chain_id()
+            )));
+        }
+
+        let mut job = self.batch_service.create_job(
+            &self.transaction_service,
+            from,
+            adapter.chain_id(),
+            mode,
+            rows,
+            &assets,
+        );
+        self.check_batch_balance(&adapter, &mut job).await?;
+        self.store_batch_job(&job)?;
+        info!(
+            "[VaughanState] Batch job {} created: {} rows ({} invalid)",
+            job.id,
+            job.rows.len(),
+            job.rows.iter().filter(|r| r.status == BatchRowStatus::Invalid).count()
+        );
+        Ok(job)
+    }
+
+    /// Update sent rows from their receipts
+    pub async fn refresh_batch_job(&self, id: &str) -> Result<BatchJob, WalletError> {
+        let mut job = self.batch_job(id)?;
+        let adapter = self.batch_adapter(&job).await?;
+        self.refresh_batch_rows(&adapter, &mut job).await?;
+        self.store_batch_job(&job)?;
+        Ok(job)
+    }
+
+    async fn refresh_batch_rows(&self, adapter: &EvmAdapter, job: &mut BatchJob) -> Result<(), WalletError> {
+        use crate::chains::ChainAdapter;
+
+        let mut receipts: HashMap<B256, bool> = HashMap::new();
+        for row in job.rows.iter_mut().filter(|r| r.status == BatchRowStatus::Sent) {
+            let Some(hash) = row.tx_hash.as_deref().and_then(|h| h.parse::<B256>().ok()) else {
+                continue;
+            };
+            // Disperse rows share one transaction
+            let success = match receipts.get(&hash) {
+                Some(success) => Some(*success),
+                None => adapter.get_transaction_receipt(hash).await?.map(|r| r.status()),
+            };
+            if let Some(success) = success {
+                receipts.insert(hash, success);
+                row.status = if success { BatchRowStatus::Confirmed } else { BatchRowStatus::Reverted };
+            }
+        }
+        job.touch();
+        Ok(())
+    }
+
+    /// Send (or resume) a batch job
+    ///
+    /// Sends the pending rows, retrying rows that previously failed to
+    /// send if the nonce they were tried with is still unused. Rows already
+    /// sent are never sent again. Stops at the first send error, which is
+    /// recorded on the row; run again to resume.
+    /// `on_progress` is called with the job after every change.
+    pub async fn run_batch_job(
+        &self,
+        id: &str,
+        password: &str,
+        on_progress: impl Fn(&BatchJob),
+    ) -> Result<BatchJob, WalletError> {
+        self.wallet_service.verify_password(password).await?;
+        if !self.running_batches.lock().await.insert(id.to_string()) {
+            return Err(WalletError::InvalidTransaction("Batch is already being sent".to_string()));
+        }
+        let result = self.send_batch_rows(id, &on_progress).await;
+        self.running_batches.lock().await.remove(id);
+        result
+    }
+
+    async fn send_batch_rows(&self, id: &str, on_progress: &impl Fn(&BatchJob)) -> Result<BatchJob, WalletError> {
+        use alloy::sol_types::SolCall;
+
+        let mut job = self.batch_job(id)?;
+        if job.has_invalid_rows() {
+            return Err(WalletError::InvalidTransaction(
+                "Batch has invalid rows; fix the CSV and create a new batch".to_string(),
+            ));
+        }
+        let from: Address = job
+            .from
+            .parse()
+            .map_err(|_| WalletError::InvalidAddress(job.from.clone()))?;
+        let adapter = self.batch_adapter(&job).await?;
+
+        self.refresh_batch_rows(&adapter, &mut job).await?;
+        job.reset_unsent_rows(adapter.get_pending_transaction_count(from).await?);
+        self.check_batch_balance(&adapter, &mut job).await?;
+        self.store_batch_job(&job)?;
+        on_progress(&job);
+
+        let mut nonce = job.next_nonce(adapter.get_transaction_count(from).await?);
+        info!("[VaughanState] Sending batch {} from nonce {}", job.id, nonce);
+
+        // Each unit covers some rows with one payout transaction, preceded
+        // by an approval for Disperse token payouts
+        let mut units: Vec<(Vec<usize>, Vec<TransactionRequest>)> = Vec::new();
+        for (token, indices) in job.pending_groups() {
+            let token = token
+                .as_deref()
+                .map(|t| t.parse::<Address>().map_err(|_| WalletError::InvalidAddress(t.to_string())))
+                .transpose()?;
+            let recipients = indices
+                .iter()
+                .map(|&i| {
+                    let recipient = &job.rows[i].recipient;
+                    recipient
+                        .parse::<Address>()
+                        .map_err(|_| WalletError::InvalidAddress(recipient.clone()))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            let amounts: Vec<U256> = indices.iter().map(|&i| job.sum(&[i])).collect();
+
+            match job.mode {
+                BatchMode::Sequential => {
+                    for ((&index, recipient), amount) in indices.iter().zip(recipients).zip(amounts) {
+                        let mut tx = TransactionRequest::default();
+                        match token {
+                            Some(token) => {
+                                tx.to = Some(token.into());
+                                tx.input.input = Some(
+                                    crate::models::erc20::IERC20::transferCall { to: recipient, amount }
+                                        .abi_encode()
+                                        .into(),
+                                );
+                            }
+                            None => {
+                                tx.to = Some(recipient.into());
+                                tx.value = Some(amount);
+                                tx.gas = Some(crate::core::batch::NATIVE_TRANSFER_GAS);
+                            }
+                        }
+                        units.push((vec![index], vec![tx]));
+                    }
+                }
+                BatchMode::Disperse => {
+                    let total = job.sum(&indices);
+                    let mut txs = Vec::new();
+                    let mut tx = TransactionRequest::default();
+                    tx.to = Some(disperse::DISPERSE_ADDRESS.into());
+                    // Explicit gas: estimating `disperseToken` fails until
+                    // the approval is mined
+                    tx.gas = Some(disperse::disperse_gas(indices.len()));
+                    match token {
+                        Some(token) => {
+                            let mut approve = TransactionRequest::default();
+                            approve.to = Some(token.into());
+                            approve.input.input =
+                                Some(disperse::approve_call_data(disperse::DISPERSE_ADDRESS, total));
+                            approve.gas = Some(disperse::APPROVE_GAS);
+                            txs.push(approve);
+                            tx.input.input =
+                                Some(disperse::disperse_token_call_data(token, recipients, amounts));
+                        }
+                        None => {
+                            tx.value = Some(total);
+                            tx.input.input = Some(disperse::disperse_ether_call_data(recipients, amounts));
+                        }
+                    }
+                    txs.push(tx);
+                    units.push((indices, txs));
+                }
+            }
+        }
+
+        for (indices, txs) in units {
+            // Recorded before sending, so a send that fails (or a crash)
+            // after the payout went out is never paid twice
+            let payout_nonce = nonce + txs.len() as u64 - 1;
+            for &i in &indices {
+                job.rows[i].nonce = Some(payout_nonce);
+            }
+            self.store_batch_job(&job)?;
+
+            let mut sent = None;
+            for mut tx in txs {
+                tx.nonce = Some(nonce);
+                match self.send_locally_signed(from, tx).await {
+                    Ok(hash) => {
+                        debug!("[VaughanState] Batch {} nonce {}: {:?}", job.id, nonce, hash);
+                        sent = Some((hash, nonce));
+                        nonce += 1;
+                    }
+                    Err(e) => {
+                        warn!("[VaughanState] Batch {} stopped at nonce {}: {}", job.id, nonce, e);
+                        for &i in &indices {
+                            job.rows[i].status = BatchRowStatus::Failed;
+                            job.rows[i].error = Some(e.user_message());
+                        }
+                        job.touch();
+                        self.store_batch_job(&job)?;
+                        on_progress(&job);
+                        return Ok(job);
+                    }
+                }
+            }
+
+            // The last transaction of the unit pays the rows
+            if let Some((hash, nonce)) = sent {
+                for &i in &indices {
+                    let row = &mut job.rows[i];
+                    row.status = BatchRowStatus::Sent;
+                    row.tx_hash = Some(format!("{:?}", hash));
+                    row.nonce = Some(nonce);
+                }
+            }
+            job.touch();
+            self.store_batch_job(&job)?;
+            on_progress(&job);
+        }
+
+        info!("[VaughanState] Batch {} sent", job.id);
+        Ok(job)
+    }
+
+    // ========================================================================
+    // Account Discovery
+    // ========================================================================
+
+    /// Find and add the used HD accounts of the wallet seed
+    ///
+    /// Scans each derivation scheme until `gap_limit` addresses in a row
+    /// have no nonce and no balance on any scanned network. Used accounts
+    /// not yet in the wallet are added (the caller persists them).
+    ///
+    /// # Errors
+    ///
+    /// * `WalletError::NetworkNotInitialized` - No scanned network is reachable
+    pub async fn discover_accounts(&self, password: &str, options: DiscoveryOptions) -> Result<DiscoveryResult, WalletError> {
+        let gap_limit = options.gap_limit();
+        let networks: Vec<NetworkConfig> = match &options.chain_ids {
+            Some(chain_ids) => chain_ids
+                .iter()
+                .filter_map(|chain_id| self.find_network_by_chain_id(*chain_id))
+                .collect(),
+            None => self.networks().into_iter().filter(|n| !n.is_testnet).collect(),
+        };
+
+        let mut result = DiscoveryResult::default();
+        let mut adapters = Vec::new();
+        for network in networks {
+            match self.get_or_create_adapter_by_chain_id(network.chain_id).await {
+                Ok(adapter) => adapters.push(adapter),
+                Err(e) => {
+                    warn!("[Discovery] Skipping {}: {}", network.name, e);
+                    result.unreachable_chain_ids.push(network.chain_id);
+                }
+            }
+        }
+        if adapters.is_empty() {
+            return Err(WalletError::NetworkNotInitialized);
+        }
+
+        let passphrase_id = options.passphrase_id.as_deref();
+        for scheme in &options.schemes {
+            let mut scan = GapScan::new(gap_limit);
+            let mut next = 0;
+            'scan: while next < MAX_SCAN_INDEX {
+                let batch = self
+                    .wallet_service
+                    .derive_addresses(password, passphrase_id, scheme, next, gap_limit)
+                    .await?;
+                next += gap_limit;
+
+                for (index, path, address) in batch {
+                    let chain_ids = self
+                        .chains_with_activity(&adapters, address, &mut result.unreachable_chain_ids)
+                        .await;
+                    let used = !chain_ids.is_empty();
+                    scan.record(used);
+
+                    if used && !result.accounts.iter().any(|a| a.derivation_path == path) {
+                        let added = if self.wallet_service.get_account(&address).await.is_some() {
+                            false
+                        } else {
+                            let name = discovery::account_name(scheme, index);
+                            self.wallet_service
+                                .add_hd_account(password, passphrase_id, index, &path, name)
+                                .await?;
+                            true
+                        };
+                        result.accounts.push(DiscoveredAccount {
+                            address: format!("{:?}", address),
+                            derivation_path: path,
+                            index,
+                            chain_ids,
+                            added,
+                        });
+                    }
+
+                    if scan.is_done() || !scheme.is_indexed() {
+                        break 'scan;
+                    }
+                }
+            }
+        }
+
+        result.scanned_chain_ids = adapters
+            .iter()
+            .map(|a| a.chain_id())
+            .filter(|id| !result.unreachable_chain_ids.contains(id))
+            .collect();
+        info!(
+            "[Discovery] Found {} used accounts ({} new)",
+            result.accounts.len(),
+            result.accounts.iter().filter(|a| a.added).count()
+        );
+        Ok(result)
+    }
+
+    /// Chains where `address` has a nonce or balance
+    ///
+    /// A chain whose RPC fails is added to `unreachable` and not asked again.
+    async fn chains_with_activity(
+        &self,
+        adapters: &[Arc<EvmAdapter>],
+        address: Address,
+        unreachable: &mut Vec<u64>,
+    ) -> Vec<u64> {
+        use alloy::providers::Provider;
+
+        let mut chain_ids = Vec::new();
+        for adapter in adapters {
+            let chain_id = adapter.chain_id();
+            if unreachable.contains(&chain_id) {
+                continue;
+            }
+            let used = match adapter.get_transaction_count(address).await {
+                Ok(nonce) if nonce > 0 => Ok(true),
+                Ok(_) => adapter
+                    .provider()
+                    .get_balance(address)
+                    .await
+                    .map(|balance| !balance.is_zero())
+                    .map_err(|e| WalletError::RpcError(e.to_string())),
+                Err(e) => Err(e),
+            };
+            match used {
+                Ok(true) => chain_ids.push(chain_id),
+                Ok(false) => {}
+                Err(e) => {
+                    warn!("[Discovery] Chain {} unreachable: {}", chain_id, e);
+                    unreachable.push(chain_id);
+                }
+            }
+        }
+        chain_ids
+    }
+
+    // ========================================================================
+    // Encrypted Backup
+    // ========================================================================
+
+    /// Full wallet backup sealed under `backup_password`
+    ///
+    /// Holds the seed, BIP-39 passphrases, imported keys, accounts, custom
+    /// networks, tracked tokens, contacts and preferences. Wallet must be
+    /// unlocked.
+    ///
+    /// # Returns
+    ///
+    /// The backup file contents (see `core::backup`)
+    pub async fn export_backup(&self, password: &str, backup_password: &str) -> Result<String, WalletError> {
+        let secrets = self.wallet_service.backup_secrets(password).await?;
+        let accounts = self.wallet_service.get_accounts().await?;
+        let tracked_tokens: Vec<TrackedToken> = self
+            .tracked_tokens
+            .lock()
+            .await
+            .values()
+            .flat_map(|v| v.clone())
+            .collect();
+        let backup = WalletBackup::new(secrets, accounts, tracked_tokens, self.state_manager.load());
+        backup::seal(&backup, backup_password)
+    }
+
+    /// Restore a wallet from a backup file on a device without a wallet
+    ///
+    /// Keys are stored under `password`; the backup's networks, tokens,
+    /// contacts and preferences replace the current ones. The wallet is
+    /// left unlocked.
+    ///
+    /// # Returns
+    ///
+    /// The accounts restored
+    pub async fn restore_backup(&self, contents: &str, backup_password: &str, password: &str) -> Result<Vec<Account>, WalletError> {
+        let WalletBackup {
+            secrets,
+            accounts,
+            custom_networks,
+            network_overrides,
+            tracked_tokens,
+            contacts,
+            preferences,
+            ..
+        } = backup::open(contents, backup_password)?;
+        let first_account = accounts.first().map(|a| a.address);
+        let restored = self
+            .wallet_service
+            .restore_backup(&secrets, accounts, password)
+            .await?;
+        info!("[VaughanState] Restored {} accounts from backup", restored.len());
+
+        let mut persisted = self.state_manager.load();
+        persisted.custom_networks = custom_networks;
+        persisted.network_overrides = network_overrides;
+        persisted.contacts = contacts;
+        persisted.preferences = preferences;
+        self.state_manager.save(&persisted)?;
+        self.invalidate_networks();
+
+        let mut tracked_tokens_map: HashMap<u64, Vec<TrackedToken>> = HashMap::new();
+        for token in tracked_tokens {
+            tracked_tokens_map.entry(token.chain_id).or_default().push(token);
+        }
+        *self.tracked_tokens.lock().await = tracked_tokens_map;
+        *self.active_account.lock().await = first_account.filter(|a| restored.iter().any(|r| r.address == *a));
+        self.activity.touch();
+
+        self.save_state().await?;
+        Ok(restored)
+    }
+
     // ========================================================================
     // Wallet Lock State (Delegated to WalletService)
     // ========================================================================
 
     /// Lock the wallet
+    ///
+    /// Drops the unlocked keys and rejects all pending dApp approvals. Use
+    /// `monitoring::auto_lock::lock_wallet` to also notify the UI and dApps.
     pub async fn lock_wallet(&self) {
         self.wallet_service.lock().await;
+        self.approval_queue.clear_all().await;
     }
 
     /// Unlock the wallet with password
@@ -457,7 +1992,23 @@ impl VaughanState {
     pub async fn unlock_wallet(&self, password: &str) -> Result<(), WalletError> {
         let persisted = self.state_manager.load();
         let accounts_to_restore = persisted.accounts.clone();
-        self.wallet_service.unlock(password, accounts_to_restore).await
+        self.wallet_service.unlock(password, accounts_to_restore).await?;
+        // Don't auto-lock straight away on an old idle timestamp
+        self.activity.touch();
+        Ok(())
+    }
+
+    /// Delete the wallet keys and the persisted state
+    ///
+    /// Used by `reset_state` and when too many wrong passwords were
+    /// entered (see `monitoring::unlock_throttle`).
+    pub async fn wipe_wallet(&self) -> Result<(), WalletError> {
+        let persisted = self.state_manager.load();
+        self.wallet_service.wipe(&persisted.accounts).await?;
+        self.approval_queue.clear_all().await;
+        self.state_manager.reset()?;
+        self.invalidate_networks();
+        Ok(())
     }
 
     /// Check if wallet is locked
@@ -579,7 +2130,7 @@ mod tests {
         // Create wallet first
         let _mnemonic = state
             .wallet_service
-            .create_wallet("test_password", 12)
+            .create_wallet("test_password", 12, None)
             .await
             .unwrap();
 
@@ -30,4 +30,19 @@ impl MockRpcServer {
             .mount(&self.server)
             .await;
     }
+
+    /// Mock any JSON-RPC method with a fixed result
+    pub async fn mock_method(&self, rpc_method: &str, result: serde_json::Value) {
+        let response = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "result": result
+        });
+
+        Mock::given(method("POST"))
+            .and(body_string_contains(rpc_method))
+            .respond_with(ResponseTemplate::new(200).set_body_json(response))
+            .mount(&self.server)
+            .await;
+    }
 }
@@ -1,6 +1,7 @@
 mod common;
 
 use common::mock_rpc::MockRpcServer;
+use vaughan_lib::core::NetworkService;
 use vaughan_lib::state::VaughanState;
 
 #[tokio::test]
@@ -30,3 +31,92 @@ async fn test_network_switch_with_mock() {
     let adapter = state.current_adapter().await.unwrap();
     assert_eq!(adapter.chain_id(), 943);
 }
+
+#[tokio::test]
+async fn test_verify_chain_id_with_mock() {
+    let server = MockRpcServer::start().await;
+    server.mock_chain_id(943).await;
+
+    let service = NetworkService::new();
+
+    // Matching chain ID is accepted
+    service.verify_chain_id(&server.uri(), 943).await.unwrap();
+
+    // Mismatched chain ID is rejected
+    let result = service.verify_chain_id(&server.uri(), 1).await;
+    assert!(result.is_err());
+}
+
+#[tokio::test]
+async fn test_bundler_client_with_mock() {
+    use alloy::primitives::{Address, Bytes, U256};
+    use vaughan_lib::chains::evm::erc4337::{BundlerClient, UserOperation, ENTRY_POINT_V07};
+
+    // Local stand-in for an ERC-4337 bundler
+    let server = MockRpcServer::start().await;
+    server
+        .mock_method("eth_supportedEntryPoints", serde_json::json!([format!("{:?}", ENTRY_POINT_V07)]))
+        .await;
+    server
+        .mock_method(
+            "eth_estimateUserOperationGas",
+            serde_json::json!({
+                "preVerificationGas": "0xc350",
+                "verificationGasLimit": "0x30d40",
+                "callGasLimit": "0x186a0"
+            }),
+        )
+        .await;
+    let user_op_hash = format!("0x{}", "ab".repeat(32));
+    server
+        .mock_method("eth_sendUserOperation", serde_json::json!(user_op_hash))
+        .await;
+    server
+        .mock_method("eth_getUserOperationReceipt", serde_json::Value::Null)
+        .await;
+
+    let bundler = BundlerClient::new(&server.uri());
+    assert_eq!(bundler.supported_entry_points().await.unwrap(), vec![ENTRY_POINT_V07]);
+
+    let mut op = UserOperation {
+        sender: Address::repeat_byte(0x11),
+        call_data: Bytes::from(vec![0xb6, 0x1d, 0x27, 0xf6]),
+        ..Default::default()
+    };
+    let estimate = bundler.estimate_user_operation_gas(&op, ENTRY_POINT_V07).await.unwrap();
+    op.apply_gas_estimate(&estimate);
+    assert_eq!(op.call_gas_limit, U256::from(100_000u64));
+    assert_eq!(op.verification_gas_limit, U256::from(200_000u64));
+    assert_eq!(op.pre_verification_gas, U256::from(50_000u64));
+
+    let hash = bundler.send_user_operation(&op, ENTRY_POINT_V07).await.unwrap();
+    assert_eq!(format!("{:?}", hash), user_op_hash);
+
+    // Pending operations have no receipt yet
+    assert!(bundler.get_user_operation_receipt(hash).await.unwrap().is_none());
+}
+
+#[tokio::test]
+async fn test_eip1271_verification_with_mock() {
+    use alloy::primitives::{Address, B256};
+    use alloy::providers::RootProvider;
+    use alloy::rpc::client::RpcClient;
+    use vaughan_lib::chains::evm::signature::{verify_hash, VerificationMethod};
+
+    // Contract wallet that accepts any signature
+    let server = MockRpcServer::start().await;
+    server.mock_method("eth_getCode", serde_json::json!("0x6080604052")).await;
+    server
+        .mock_method(
+            "eth_call",
+            serde_json::json!(format!("0x1626ba7e{}", "00".repeat(28))),
+        )
+        .await;
+
+    let provider = RootProvider::<alloy::network::Ethereum>::new(RpcClient::new_http(server.uri().parse().unwrap()));
+    let result = verify_hash(Some(&provider), Address::repeat_byte(0x11), B256::repeat_byte(0xab), &[0u8; 65])
+        .await
+        .unwrap();
+    assert!(result.valid);
+    assert_eq!(result.method, VerificationMethod::Eip1271);
+}